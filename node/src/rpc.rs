@@ -46,6 +46,7 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: xyk_rpc::XykRuntimeApi<Block, Balance, TokenId, AccountId>,
+	C::Api: issuance_rpc::IssuanceRuntimeApi<Block, Balance>,
 	C::Api: pallet_vesting_mangata_rpc::VestingMangataRuntimeApi<
 		Block,
 		AccountId,
@@ -57,6 +58,7 @@ where
 	C::Api: VerApi<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use issuance_rpc::{Issuance, IssuanceApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use pallet_vesting_mangata_rpc::{VestingMangata, VestingMangataApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -68,6 +70,7 @@ where
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 	module.merge(Xyk::new(client.clone()).into_rpc())?;
+	module.merge(Issuance::new(client.clone()).into_rpc())?;
 	module.merge(VestingMangata::new(client.clone()).into_rpc())?;
 
 	Ok(module)
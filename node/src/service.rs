@@ -325,6 +325,7 @@ where
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
 		+ ver_api::VerApi<Block>
 		+ xyk_rpc::XykRuntimeApi<Block, Balance, TokenId, AccountId>
+		+ issuance_rpc::IssuanceRuntimeApi<Block, Balance>
 		+ pallet_vesting_mangata_rpc::VestingMangataRuntimeApi<
 			Block,
 			AccountId,
@@ -527,6 +528,7 @@ where
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>
 		+ ver_api::VerApi<Block>
 		+ xyk_rpc::XykRuntimeApi<Block, Balance, TokenId, AccountId>
+		+ issuance_rpc::IssuanceRuntimeApi<Block, Balance>
 		+ pallet_vesting_mangata_rpc::VestingMangataRuntimeApi<
 			Block,
 			AccountId,
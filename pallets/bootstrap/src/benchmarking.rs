@@ -34,13 +34,14 @@ benchmarks! {
 
 	schedule_bootstrap {
 		frame_system::Pallet::<T>::set_block_number(1_u32.into());
-		assert!(crate::BootstrapSchedule::<T>::get().is_none());
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		assert!(crate::BootstrapSchedule::<T>::get(bootstrap_id).is_none());
 		let caller: T::AccountId = whitelisted_caller();
 		let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
 		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
-	}: schedule_bootstrap(RawOrigin::Root, first_token_id, second_token_id, 123_456_789_u32.into(), Some(100_000_u32), 100_000_u32, Some(DEFAULT_RATIO), true)
+	}: schedule_bootstrap(RawOrigin::Root, first_token_id, second_token_id, 123_456_789_u32.into(), Some(100_000_u32), 100_000_u32, Some(DEFAULT_RATIO), true, None)
 	verify {
-		assert!(crate::BootstrapSchedule::<T>::get().is_some());
+		assert!(crate::BootstrapSchedule::<T>::get(bootstrap_id).is_some());
 	}
 
 	provision {
@@ -50,42 +51,44 @@ benchmarks! {
 		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
 		let ksm_provision_amount = 100_000_u128;
 		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
 
-		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false).unwrap();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, None).unwrap();
 		// jump to public phase
 		BootstrapPallet::<T>::on_initialize(20_u32.into());
-		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
 
-	}: provision(RawOrigin::Signed(caller.clone().into()), first_token_id, ksm_provision_amount)
+	}: provision(RawOrigin::Signed(caller.clone().into()), bootstrap_id, first_token_id, ksm_provision_amount)
 	verify {
-		assert_eq!(BootstrapPallet::<T>::provisions(caller, first_token_id), ksm_provision_amount);
+		assert_eq!(BootstrapPallet::<T>::provisions(bootstrap_id, (caller, first_token_id)), ksm_provision_amount);
 	}
 
-	// provision_vested {
-	// 	frame_system::Pallet::<T>::set_block_number(1_u32.into());
-	// 	let caller: T::AccountId = whitelisted_caller();
-	// 	let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
-	// 	let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+	provision_vested {
+		frame_system::Pallet::<T>::set_block_number(1_u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
 
-	// 	let ksm_provision_amount = 100_000_u128;
-	// 	let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let ksm_provision_amount = 100_000_u128;
+		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
 
-	// 	let lock = 100_000_000_u128;
+		let lock = 100_000_000_u128;
 
-	// 	frame_system::Pallet::<T>::set_block_number(1_u32.into());
-	// 	<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), (ksm_provision_amount*2).into(), None, lock.into()).unwrap();
-	// 	frame_system::Pallet::<T>::set_block_number(2_u32.into());
+		frame_system::Pallet::<T>::set_block_number(1_u32.into());
+		<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), (ksm_provision_amount*2).into(), None, lock.into()).unwrap();
+		frame_system::Pallet::<T>::set_block_number(2_u32.into());
 
-	// 	BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, Some(10_u32.into()), 10_u32, 10_u32, Some(DEFAULT_RATIO), false).unwrap();
-	// 	// jump to public phase
-	// 	BootstrapPallet::<T>::on_initialize(20_u32.into());
-	// 	BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_provision_amount).unwrap();
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, None).unwrap();
+		// jump to public phase
+		BootstrapPallet::<T>::on_initialize(20_u32.into());
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
 
-	// 	assert_eq!(BootstrapPallet::<T>::vested_provisions(caller.clone(), first_token_id), (0, 0, 0));
-	// }: provision_vested(RawOrigin::Signed(caller.clone().into()), first_token_id, ksm_provision_amount)
-	// verify {
-	// 	assert_eq!(BootstrapPallet::<T>::vested_provisions(caller, first_token_id).0, ksm_provision_amount);
-	// }
+		assert_eq!(BootstrapPallet::<T>::vested_provisions(bootstrap_id, (caller.clone(), first_token_id)), (0, 0, 0));
+	}: provision_vested(RawOrigin::Signed(caller.clone().into()), bootstrap_id, first_token_id, ksm_provision_amount)
+	verify {
+		assert_eq!(BootstrapPallet::<T>::vested_provisions(bootstrap_id, (caller, first_token_id)).0, ksm_provision_amount);
+	}
 
 	claim_and_activate_liquidity_tokens {
 		frame_system::Pallet::<T>::set_block_number(1_u32.into());
@@ -95,8 +98,7 @@ benchmarks! {
 		let liquidity_asset_id = second_token_id + 1;
 
 		let ksm_provision_amount = 100_000_u128;
-		// let ksm_vested_provision_amount = 300_000_u128;
-		let ksm_vested_provision_amount = 0_u128;
+		let ksm_vested_provision_amount = 300_000_u128;
 		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
 		let mga_vested_provision_amount = ksm_vested_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
 		let total_ksm_provision = ksm_provision_amount + ksm_vested_provision_amount;
@@ -107,36 +109,37 @@ benchmarks! {
 		<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), (ksm_provision_amount + ksm_vested_provision_amount).into(), None, lock.into()).unwrap();
 		<T as Config>::VestingProvider::lock_tokens(&caller, second_token_id.into(), (mga_provision_amount + mga_vested_provision_amount).into(), None, lock.into()).unwrap();
 
-		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false).unwrap();
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, None).unwrap();
 		BootstrapPallet::<T>::on_initialize(20_u32.into());
-		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_provision_amount).unwrap();
-		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), first_token_id, ksm_provision_amount).unwrap();
-		// BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_vested_provision_amount).unwrap();
-		// BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), first_token_id, ksm_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_vested_provision_amount).unwrap();
 		BootstrapPallet::<T>::on_initialize(30_u32.into());
 
-		assert_eq!(BootstrapPallet::<T>::phase(), BootstrapPhase::Finished);
-		assert_eq!(BootstrapPallet::<T>::claimed_rewards(caller.clone(), first_token_id), 0_u128);
-		assert_eq!(BootstrapPallet::<T>::claimed_rewards(caller.clone(), second_token_id), 0_u128);
-		assert_eq!(BootstrapPallet::<T>::valuations(), (total_mga_provision, total_ksm_provision));
-		assert_eq!(BootstrapPallet::<T>::provisions(caller.clone(), first_token_id), (ksm_provision_amount));
-		assert_eq!(BootstrapPallet::<T>::provisions(caller.clone(), second_token_id), (mga_provision_amount));
-		// assert_eq!(BootstrapPallet::<T>::vested_provisions(caller.clone(), first_token_id), (ksm_vested_provision_amount, 1, lock + 1));
-		// assert_eq!(BootstrapPallet::<T>::vested_provisions(caller.clone(), second_token_id), (mga_vested_provision_amount, 1, lock + 1));
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::Finished);
+		assert_eq!(BootstrapPallet::<T>::claimed_rewards(bootstrap_id, (caller.clone(), first_token_id)), 0_u128);
+		assert_eq!(BootstrapPallet::<T>::claimed_rewards(bootstrap_id, (caller.clone(), second_token_id)), 0_u128);
+		assert_eq!(BootstrapPallet::<T>::valuations(bootstrap_id), (total_mga_provision, total_ksm_provision));
+		assert_eq!(BootstrapPallet::<T>::provisions(bootstrap_id, (caller.clone(), first_token_id)), (ksm_provision_amount));
+		assert_eq!(BootstrapPallet::<T>::provisions(bootstrap_id, (caller.clone(), second_token_id)), (mga_provision_amount));
+		assert_eq!(BootstrapPallet::<T>::vested_provisions(bootstrap_id, (caller.clone(), first_token_id)), (ksm_vested_provision_amount, 1, lock + 1));
+		assert_eq!(BootstrapPallet::<T>::vested_provisions(bootstrap_id, (caller.clone(), second_token_id)), (mga_vested_provision_amount, 1, lock + 1));
 
 		// promote pool
-		pallet_issuance::PromotedPoolsRewards::<T>::insert(liquidity_asset_id, 0_u128);
+		pallet_issuance::PromotedPoolsRewardsV2::<T>::insert(liquidity_asset_id, U256::from(0_u128));
 
-	}: claim_and_activate_liquidity_tokens(RawOrigin::Signed(caller.clone().into()))
+	}: claim_and_activate_liquidity_tokens(RawOrigin::Signed(caller.clone().into()), bootstrap_id)
 	verify {
-		let (total_mga_provision, total_ksm_provision) = BootstrapPallet::<T>::valuations();
+		let (total_mga_provision, total_ksm_provision) = BootstrapPallet::<T>::valuations(bootstrap_id);
 		let ksm_non_vested_rewards = total_provision / 2 / 2 * ksm_provision_amount / total_ksm_provision;
 		let ksm_vested_rewards = total_provision / 2 / 2 * ksm_vested_provision_amount / total_ksm_provision;
 		let mga_non_vested_rewards = total_provision / 2 / 2 * mga_provision_amount / total_mga_provision;
 		let mga_vested_rewards = total_provision / 2 / 2 * mga_vested_provision_amount / total_mga_provision;
 
-		assert_eq!(BootstrapPallet::<T>::claimed_rewards(caller.clone(), first_token_id), ksm_vested_rewards + ksm_non_vested_rewards);
-		assert_eq!(BootstrapPallet::<T>::claimed_rewards(caller.clone(), second_token_id), mga_vested_rewards + mga_non_vested_rewards);
+		assert_eq!(BootstrapPallet::<T>::claimed_rewards(bootstrap_id, (caller.clone(), first_token_id)), ksm_vested_rewards + ksm_non_vested_rewards);
+		assert_eq!(BootstrapPallet::<T>::claimed_rewards(bootstrap_id, (caller.clone(), second_token_id)), mga_vested_rewards + mga_non_vested_rewards);
 	}
 
 	finalize {
@@ -146,8 +149,7 @@ benchmarks! {
 		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
 
 		let ksm_provision_amount = 100_000_u128;
-		// let ksm_vested_provision_amount = 300_000_u128;
-		let ksm_vested_provision_amount = 0_u128;
+		let ksm_vested_provision_amount = 300_000_u128;
 		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
 		let mga_vested_provision_amount = ksm_vested_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
 		let total_ksm_provision = ksm_provision_amount + ksm_vested_provision_amount;
@@ -158,20 +160,106 @@ benchmarks! {
 		<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), (ksm_provision_amount + ksm_vested_provision_amount).into(), None, lock.into()).unwrap();
 		<T as Config>::VestingProvider::lock_tokens(&caller, second_token_id.into(), (mga_provision_amount + mga_vested_provision_amount).into(), None, lock.into()).unwrap();
 
-		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false).unwrap();
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, None).unwrap();
+		BootstrapPallet::<T>::on_initialize(20_u32.into());
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::on_initialize(30_u32.into());
+
+		BootstrapPallet::<T>::claim_liquidity_tokens(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id).unwrap();
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::Finished);
+
+	}: finalize(RawOrigin::Root, bootstrap_id, 200)
+	verify {
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::BeforeStart);
+	}
+
+	finish_bootstrap {
+		frame_system::Pallet::<T>::set_block_number(1_u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+		let liquidity_asset_id = second_token_id + 1;
+
+		let ksm_provision_amount = 100_000_u128;
+		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), true, None).unwrap();
+		BootstrapPallet::<T>::on_initialize(20_u32.into());
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_provision_amount).unwrap();
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::Public);
+
+	}: { BootstrapPallet::<T>::on_initialize(30_u32.into()); }
+	verify {
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::Finished);
+		assert_eq!(BootstrapPallet::<T>::minted_liquidity(bootstrap_id).0, liquidity_asset_id);
+	}
+
+	withdraw_provisions {
+		frame_system::Pallet::<T>::set_block_number(1_u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+
+		let ksm_provision_amount = 100_000_u128;
+		let ksm_vested_provision_amount = 300_000_u128;
+		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let mga_vested_provision_amount = ksm_vested_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let lock = 150_u128;
+
+		<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), ksm_vested_provision_amount.into(), None, lock.into()).unwrap();
+		<T as Config>::VestingProvider::lock_tokens(&caller, second_token_id.into(), mga_vested_provision_amount.into(), None, lock.into()).unwrap();
+
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, Some((MILION, MILION))).unwrap();
 		BootstrapPallet::<T>::on_initialize(20_u32.into());
-		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_provision_amount).unwrap();
-		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), first_token_id, ksm_provision_amount).unwrap();
-		// BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), second_token_id, mga_vested_provision_amount).unwrap();
-		// BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), first_token_id, ksm_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_vested_provision_amount).unwrap();
 		BootstrapPallet::<T>::on_initialize(30_u32.into());
+		assert_eq!(BootstrapPallet::<T>::phase(bootstrap_id), BootstrapPhase::Failed);
 
-		BootstrapPallet::<T>::claim_liquidity_tokens(RawOrigin::Signed(caller.clone().into()).into()).unwrap();
-		assert_eq!(BootstrapPallet::<T>::phase(), BootstrapPhase::Finished);
+	}: withdraw_provisions(RawOrigin::Signed(caller.clone().into()), bootstrap_id)
+	verify {
+		assert!(BootstrapPallet::<T>::provision_accounts(bootstrap_id, caller.clone()).is_none());
+		assert_eq!(BootstrapPallet::<T>::vested_provisions(bootstrap_id, (caller.clone(), first_token_id)), (0, 0, 0));
+		assert_eq!(BootstrapPallet::<T>::provisions(bootstrap_id, (caller, second_token_id)), 0);
+	}
+
+	lock_up_provisions {
+		frame_system::Pallet::<T>::set_block_number(1_u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		let first_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+		let second_token_id = <T as Config>::Currency::create(&caller, MILION.into()).expect("Token creation failed").into();
+
+		let ksm_provision_amount = 100_000_u128;
+		let ksm_vested_provision_amount = 300_000_u128;
+		let mga_provision_amount = ksm_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let mga_vested_provision_amount = ksm_vested_provision_amount * DEFAULT_RATIO.1 / DEFAULT_RATIO.0;
+		let lock = 150_u128;
+
+		<T as Config>::VestingProvider::lock_tokens(&caller, first_token_id.into(), ksm_vested_provision_amount.into(), None, lock.into()).unwrap();
+		<T as Config>::VestingProvider::lock_tokens(&caller, second_token_id.into(), mga_vested_provision_amount.into(), None, lock.into()).unwrap();
+
+		let bootstrap_id = BootstrapPallet::<T>::next_bootstrap_id();
+		BootstrapPallet::<T>::schedule_bootstrap(RawOrigin::Root.into(), first_token_id, second_token_id, 10_u32.into(), Some(10_u32), 10_u32, Some(DEFAULT_RATIO), false, None).unwrap();
+		BootstrapPallet::<T>::update_lockup_bonus(RawOrigin::Root.into(), bootstrap_id, Some((100_u32, (3_u128, 2_u128)))).unwrap();
+		BootstrapPallet::<T>::on_initialize(20_u32.into());
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, second_token_id, mga_vested_provision_amount).unwrap();
+		BootstrapPallet::<T>::provision_vested(RawOrigin::Signed(caller.clone().into()).into(), bootstrap_id, first_token_id, ksm_vested_provision_amount).unwrap();
 
-	}: finalize(RawOrigin::Root, 200)
+	}: lock_up_provisions(RawOrigin::Signed(caller.clone().into()), bootstrap_id)
 	verify {
-		assert_eq!(BootstrapPallet::<T>::phase(), BootstrapPhase::BeforeStart);
+		assert!(BootstrapPallet::<T>::locked_up_accounts(bootstrap_id, caller.clone()).is_some());
+		assert_eq!(BootstrapPallet::<T>::lockup_bonus_provisions(bootstrap_id, (caller, first_token_id)), (ksm_provision_amount + ksm_vested_provision_amount) / 2);
 	}
 
 	impl_benchmark_test_suite!(BootstrapPallet, crate::mock::new_test_ext(), crate::mock::Test)
@@ -13,6 +13,8 @@
 //! ### Features
 //!
 //! * Bootstrap pallet is reusable** - after bootstrap between tokens `X` and `Y` is finished the following one can be scheduled (with different pair of tokens).
+//! * Multiple bootstraps can be run at the same time - every bootstrap is identified by its
+//! [`BootstrapId`] and has its own phases, provisions, whitelist and claims.
 //! * After bootstrap is finished new liquidity token (`Z`) is created and [`pallet_xyk`] can be used to:
 //!		* exchange/trade `X` and `Y` tokens
 //!		* mint/burn `Z` tokens
@@ -22,23 +24,47 @@
 //! cleaning up storage is complex operation and might not fit in a single block.(as it needs to
 //! remove a lot of keys/value pair from the runtime storage)**
 //!
+//! * All active bootstraps are driven from `on_initialize`. Finishing a bootstrap (pool creation)
+//! is the expensive part, so bootstraps are only finished as long as they fit into
+//! [`Config::InitializeWeightBudget`], the remaining ones are finished in following blocks.
+//!
 //! # How to bootstrap
-//! 1. Entity with sudo privileges needs to use [`Pallet::schedule_bootstrap`] to initiate new bootstrap
+//! 1. Entity with sudo privileges needs to use [`Pallet::schedule_bootstrap`] to initiate new bootstrap,
+//!    [`Event::BootstrapScheduled`] carries [`BootstrapId`] that identifies bootstrap in all other extrinsics
 //!
 //! 1.1 [**optional**] depending on fact if [`BootstrapPhase::Whitelist`] is enabled entity
-//!   with sudo privileges can whitelist particular users using [`Pallet::whitelist_accounts`]
+//!   with sudo privileges can whitelist particular users using [`Pallet::whitelist_accounts`],
+//!   optionally limiting how much they can provision in [`BootstrapPhase::Whitelist`] phase
 //!
 //! 1.2 [**optional**] [`Pallet::update_promote_bootstrap_pool`] can be used to enable or disable
 //!   automatic pool promotion of liquidity pool.
 //!
 //! 1.3 [**optional**] [`Pallet::cancel_bootstrap`] can be used to cancel bootstrap event
 //!
+//! 1.4 [**optional**] [`Pallet::update_lockup_bonus`] can be used to reward participants that agree
+//!   to keep their liquidity tokens locked for some time after the bootstrap is finished.
+//!
+//! 1.5 [**optional**] [`Pallet::update_provision_caps`] can be used to limit how much single
+//!   account can provision in bootstrap.
+//!
 //! 2. When blockchain reaches block that is scheduled as start od the bootstrap participation is
 //!    automatically enabled:
 //!    * in [`BootstrapPhase::Whitelist`] phase only whitelisted accounts [`Pallet::whitelist_accounts`]
 //!    can participate
 //!    * in [`BootstrapPhase::Public`] phase everyone can participate
 //!
+//!    Provisions of single account (regular and vested together) cannot exceed its whitelist
+//!    allowance in [`BootstrapPhase::Whitelist`] phase, all other provisions cannot exceed
+//!    provision caps.
+//!
+//!    Tokens locked in vesting schedule can be provisioned using [`Pallet::provision_vested`],
+//!    liquidity tokens claimed for such provision are locked with the same schedule.
+//!
+//!    If lockup bonus is set for the bootstrap participants can use [`Pallet::lock_up_provisions`]
+//!    so all their provisions (also the following ones) count with the bonus multiplier when liquidity
+//!    tokens are shared. In exchange liquidity tokens they claim are locked (using vesting provider)
+//!    for the lockup length since the end of the bootstrap and unlocked all at once when it is over.
+//!
 //! 3. When blockchain reaches block:
 //! ```ignore
 //!  current_block_nr > bootstrap_start_block + whitelist_phase_length + public_phase_length
@@ -50,17 +76,23 @@
 //! storage item. All the liquidity token minted as a result of pool creation are now stored in
 //! bootstrap pallet account.
 //!
+//! If minimal valuations were set for the bootstrap (see [`MinValuations`]) and any of them is not
+//! reached, or the pool cannot be created, bootstrap goes to [`BootstrapPhase::Failed`] instead.
+//! Participants can then get their provisions back using [`Pallet::withdraw_provisions`] (vested
+//! provisions are locked again with their original schedule) and go straight to the 5th point.
+//!
 //! 4. Accounts that participated in bootstrap can claim their liquidity pool share. Share is
 //!    calculated proportionally based on provisioned amount. One can use one of below extrinsics to
 //!    claim rewards:
 //!    * [`Pallet::claim_liquidity_tokens`]
 //!    * [`Pallet::claim_and_activate_liquidity_tokens`]
 //!
-//! 5. When every participant of the bootstrap has claimed their liquidity tokens entity with sudo
+//! 5. When every participant of the bootstrap has claimed their liquidity tokens (or withdrawn
+//!    provisions of failed bootstrap) entity with sudo
 //!    rights can [`Pallet::finalize`] whole bootstrap event. If there are some accounts that still
 //!    hasnt claim their tokens [`Pallet::claim_liquidity_tokens_for_account`] can be used to do
 //!    that in behalf of these accounts. When [`Pallet::finalize`] results with [`Event::BootstrapFinalized`]
-//!    Bootstrap is finalized and another bootstrap can be scheduled (as described in 1st point).
+//!    Bootstrap is finalized and another bootstrap of the same pair can be scheduled (as described in 1st point).
 //!
 //! Bootstrap has specific lifecycle as presented below:
 //! ```plantuml
@@ -70,7 +102,9 @@
 //! BeforeStart --> Public
 //! Whitelist --> Public
 //! Public --> Finished
+//! Public --> Failed
 //! Finished --> BeforeStart
+//! Failed --> BeforeStart
 //! @enduml
 //! ```
 //!
@@ -78,6 +112,10 @@
 //!
 //! ## Runtime Storage Entries
 //!
+//! All storage entries except [`NextBootstrapId`] and [`ArchivedBootstrap`] are keyed by [`BootstrapId`]
+//!
+//! - [`NextBootstrapId`] - id that will be assigned to the next scheduled bootstrap
+//!
 //! - [`Provisions`] - stores information about who provisioned what (non vested tokens)
 //!
 //! - [`VestedProvisions`] - stores information about who provisioned what (vested tokens)
@@ -86,9 +124,9 @@
 //!
 //! - [`Phase`] - current state of bootstrap
 //!
-//! - [`Valuations`] - sum of all provisions in active bootstrap
+//! - [`Valuations`] - sum of all provisions in bootstrap
 //!
-//! - [`BootstrapSchedule`] - parameters of active bootstrap stored as for more details check [`Pallet::schedule_bootstrap`]
+//! - [`BootstrapSchedule`] - parameters of bootstrap stored as for more details check [`Pallet::schedule_bootstrap`]
 //!
 //!  ```ignore
 //!  [
@@ -109,16 +147,38 @@
 //!
 //! - [`ActivePair`] - bootstraped pair of tokens
 //!
+//! - [`MinValuations`] - minimal valuations of bootstraped tokens required to create the pool
+//!
+//! - [`LockupBonus`] - lockup length and bonus multiplier for participants that lock up their liquidity tokens
+//!
+//! - [`LockedUpAccounts`] - participants that agreed to lock up their liquidity tokens
+//!
+//! - [`LockupBonusProvisions`] - bonus part of provisions of participants that locked up their liquidity tokens
+//!
+//! - [`LockupBonusVestedProvisions`] - part of [`LockupBonusProvisions`] that is bonus of vested provisions
+//!
+//! - [`LockupBonusValuations`] - sum of all bonus parts of provisions in bootstrap
+//!
+//! - [`WhitelistAllowances`] - maximal provisions of whitelisted accounts in [`BootstrapPhase::Whitelist`]
+//!
+//! - [`ProvisionCaps`] - maximal provisions of single account in bootstrap
+//!
 //! ## Extrinsics
 //!
 //! * [`Pallet::schedule_bootstrap`]
 //! * [`Pallet::whitelist_accounts`]
 //! * [`Pallet::update_promote_bootstrap_pool`]
 //! * [`Pallet::cancel_bootstrap`]
+//! * [`Pallet::update_lockup_bonus`]
+//! * [`Pallet::update_provision_caps`]
 //! * [`Pallet::provision`]
+//! * [`Pallet::provision_vested`]
+//! * [`Pallet::lock_up_provisions`]
 //! * [`Pallet::claim_liquidity_tokens`]
 //! * [`Pallet::claim_liquidity_tokens_for_account`]
 //! * [`Pallet::claim_and_activate_liquidity_tokens`]
+//! * [`Pallet::withdraw_provisions`]
+//! * [`Pallet::withdraw_provisions_for_account`]
 //! * [`Pallet::finalize`]
 //!
 //! for more details see [click](#how-to-bootstrap)
@@ -128,6 +188,7 @@ use frame_support::pallet_prelude::*;
 
 use frame_support::{
 	codec::{Decode, Encode},
+	storage::{with_transaction, TransactionOutcome},
 	traits::{
 		tokens::currency::MultiTokenCurrency, Contains, ExistenceRequirement, Get, StorageVersion,
 	},
@@ -176,6 +237,9 @@ macro_rules! log {
 
 pub type BlockNrAsBalance = Balance;
 
+/// Identifies single bootstrap event, assigned by [`Pallet::schedule_bootstrap`]
+pub type BootstrapId = u32;
+
 pub enum ProvisionKind {
 	Regular,
 	Vested(BlockNrAsBalance, BlockNrAsBalance),
@@ -186,7 +250,7 @@ pub mod pallet {
 
 	use super::*;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
@@ -197,67 +261,70 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		fn on_initialize(n: T::BlockNumber) -> Weight {
-			let phase = Phase::<T>::get(); // R:1
-			if phase == BootstrapPhase::Finished {
-				return T::DbWeight::get().reads(1)
-			}
-
-			if let Some((start, whitelist_length, public_length, _)) = BootstrapSchedule::<T>::get()
+			let budget = T::InitializeWeightBudget::get();
+			let finish_weight = <T as Config>::WeightInfo::finish_bootstrap();
+			let mut consumed_weight = T::DbWeight::get().reads(1);
+			let mut any_finished = false;
+
+			// NOTE: number of iterations is bounded by Config::MaxActiveBootstraps
+			for (bootstrap_id, (start, whitelist_length, public_length, _)) in
+				BootstrapSchedule::<T>::iter()
 			{
-				// R:1
+				let phase = Phase::<T>::get(bootstrap_id);
+				// R:2
+				consumed_weight = consumed_weight.saturating_add(T::DbWeight::get().reads(2));
+				if phase == BootstrapPhase::Finished || phase == BootstrapPhase::Failed {
+					continue
+				}
+
 				// NOTE: arythmetics protected by invariant check in Bootstrap::start_ido
 				let whitelist_start = start;
 				let public_start = start + whitelist_length.into();
 				let finished = start + whitelist_length.into() + public_length.into();
 
 				if n >= finished {
-					Phase::<T>::put(BootstrapPhase::Finished); // 1 WRINTE
-					log!(info, "bootstrap event finished");
-					let (second_token_valuation, first_token_valuation) = Valuations::<T>::get();
-					// XykFunctionsTrait R: 11 W:12
-					// PoolCreateApi::pool_create R:2  +
-					// ---------------------------------
-					// R: 13 W 12
-					if let Some((liq_asset_id, issuance)) = T::PoolCreateApi::pool_create(
-						Self::vault_address(),
-						Self::first_token_id(),
-						first_token_valuation,
-						Self::second_token_id(),
-						second_token_valuation,
-					) {
-						MintedLiquidity::<T>::put((liq_asset_id, issuance)); // W:1
-						if PromoteBootstrapPool::<T>::get() {
-							if !T::RewardsApi::promote_pool(liq_asset_id) {
-								log!(error, "pool already promoted!");
-							}
-						}
-					} else {
-						log!(error, "cannot create pool!");
+					// at least one bootstrap is finished in every block so none of them is starved
+					if any_finished &&
+						consumed_weight.saturating_add(finish_weight).ref_time() >
+							budget.ref_time()
+					{
+						log!(info, "bootstrap {} finish postponed", bootstrap_id);
+						continue
 					}
-					// TODO: include cost of pool_create call
-					T::DbWeight::get().reads_writes(17, 14)
+					Self::finish_bootstrap(bootstrap_id);
+					any_finished = true;
+					consumed_weight = consumed_weight.saturating_add(finish_weight);
 				} else if n >= public_start {
 					if phase != BootstrapPhase::Public {
-						Phase::<T>::put(BootstrapPhase::Public);
-						log!(info, "starting public phase");
-						T::DbWeight::get().reads_writes(2, 1)
-					} else {
-						T::DbWeight::get().reads(2)
+						Phase::<T>::insert(bootstrap_id, BootstrapPhase::Public);
+						log!(info, "starting public phase of bootstrap {}", bootstrap_id);
+						consumed_weight =
+							consumed_weight.saturating_add(T::DbWeight::get().writes(1));
 					}
 				} else if n >= whitelist_start {
 					if phase != BootstrapPhase::Whitelist {
-						log!(info, "starting whitelist phase");
-						Phase::<T>::put(BootstrapPhase::Whitelist);
-						T::DbWeight::get().reads_writes(2, 1)
-					} else {
-						T::DbWeight::get().reads(2)
+						log!(info, "starting whitelist phase of bootstrap {}", bootstrap_id);
+						Phase::<T>::insert(bootstrap_id, BootstrapPhase::Whitelist);
+						consumed_weight =
+							consumed_weight.saturating_add(T::DbWeight::get().writes(1));
 					}
-				} else {
-					T::DbWeight::get().reads(2)
 				}
-			} else {
-				T::DbWeight::get().reads(2)
 			}
+			consumed_weight
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v3::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::v3::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::v3::post_upgrade::<T>()
 		}
 	}
 
@@ -285,6 +352,15 @@ pub mod pallet {
 		#[pallet::constant]
 		type TreasuryPalletId: Get<PalletId>;
 
+		/// Maximum number of bootstraps that are scheduled and not finalized at the same time
+		#[pallet::constant]
+		type MaxActiveBootstraps: Get<u32>;
+
+		/// Weight that `on_initialize` may spend on finishing bootstraps in a single block,
+		/// bootstraps that do not fit are finished in following blocks
+		#[pallet::constant]
+		type InitializeWeightBudget: Get<Weight>;
+
 		type VestingProvider: MultiTokenVestingLocks<Self::AccountId, Self::BlockNumber>;
 
 		type WeightInfo: WeightInfo;
@@ -292,72 +368,183 @@ pub mod pallet {
 		type RewardsApi: RewardsApi<AccountId = Self::AccountId>;
 	}
 
-	/// maps ([`frame_system::Config::AccountId`], [`TokenId`]) -> [`Balance`] - identifies how much tokens did account provisioned in active bootstrap
+	/// Id that will be assigned to the next scheduled bootstrap
+	#[pallet::storage]
+	#[pallet::getter(fn next_bootstrap_id)]
+	pub type NextBootstrapId<T: Config> = StorageValue<_, BootstrapId, ValueQuery>;
+
+	/// maps ([`BootstrapId`], ([`frame_system::Config::AccountId`], [`TokenId`])) -> [`Balance`] - identifies how much tokens did account provisioned in bootstrap
 	#[pallet::storage]
 	#[pallet::getter(fn provisions)]
-	pub type Provisions<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, TokenId, Balance, ValueQuery>;
+	pub type Provisions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		(T::AccountId, TokenId),
+		Balance,
+		ValueQuery,
+	>;
 
-	/// maps ([`frame_system::Config::AccountId`], [`TokenId`]) -> [`Balance`] - identifies how much vested tokens did account provisioned in active bootstrap
+	/// maps ([`BootstrapId`], ([`frame_system::Config::AccountId`], [`TokenId`])) -> [`Balance`] - identifies how much vested tokens did account provisioned in bootstrap
 	#[pallet::storage]
 	#[pallet::getter(fn vested_provisions)]
 	pub type VestedProvisions<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
-		T::AccountId,
+		BootstrapId,
 		Twox64Concat,
-		TokenId,
+		(T::AccountId, TokenId),
 		(Balance, BlockNrAsBalance, BlockNrAsBalance),
 		ValueQuery,
 	>;
 
-	/// list ([`Vec<AccountId>`]) of whitelisted accounts allowed to participate in [`BootstrapPhase::Whitelist`] phase
+	/// list ([`Vec<AccountId>`]) of whitelisted accounts allowed to participate in [`BootstrapPhase::Whitelist`] phase of bootstrap
 	#[pallet::storage]
 	#[pallet::getter(fn whitelisted_accounts)]
 	pub type WhitelistedAccount<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, (), ValueQuery>;
+		StorageDoubleMap<_, Twox64Concat, BootstrapId, Twox64Concat, T::AccountId, (), ValueQuery>;
 
 	/// Current state of bootstrap as [`BootstrapPhase`]
 	#[pallet::storage]
 	#[pallet::getter(fn phase)]
-	pub type Phase<T: Config> = StorageValue<_, BootstrapPhase, ValueQuery>;
+	pub type Phase<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, BootstrapPhase, ValueQuery>;
 
-	/// Total sum of provisions of `first` and `second` token in active bootstrap
+	/// Total sum of provisions of `first` and `second` token in bootstrap
 	#[pallet::storage]
 	#[pallet::getter(fn valuations)]
-	pub type Valuations<T: Config> = StorageValue<_, (Balance, Balance), ValueQuery>;
+	pub type Valuations<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (Balance, Balance), ValueQuery>;
 
-	/// Active bootstrap parameters
+	/// Bootstrap parameters
 	#[pallet::storage]
 	#[pallet::getter(fn config)]
-	pub type BootstrapSchedule<T: Config> =
-		StorageValue<_, (T::BlockNumber, u32, u32, (u128, u128)), OptionQuery>;
+	pub type BootstrapSchedule<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		(T::BlockNumber, u32, u32, (u128, u128)),
+		OptionQuery,
+	>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn minted_liquidity)]
-	pub type MintedLiquidity<T: Config> = StorageValue<_, (TokenId, Balance), ValueQuery>;
+	pub type MintedLiquidity<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (TokenId, Balance), ValueQuery>;
 
-	///  Maps ([`frame_system::Config::AccountId`], [`TokenId`] ) -> [`Balance`] - where [`TokeinId`] is id of the token that user participated with. This storage item is used to identify how much liquidity tokens has been claim by the user. If user participated with 2 tokens there are two entries associated with given account (`Address`, `first_token_id`) and (`Address`, `second_token_id`)
+	///  Maps ([`BootstrapId`], ([`frame_system::Config::AccountId`], [`TokenId`])) -> [`Balance`] - where [`TokeinId`] is id of the token that user participated with. This storage item is used to identify how much liquidity tokens has been claim by the user. If user participated with 2 tokens there are two entries associated with given account (`Address`, `first_token_id`) and (`Address`, `second_token_id`)
 	#[pallet::storage]
 	#[pallet::getter(fn claimed_rewards)]
-	pub type ClaimedRewards<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, TokenId, Balance, ValueQuery>;
+	pub type ClaimedRewards<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		(T::AccountId, TokenId),
+		Balance,
+		ValueQuery,
+	>;
 
 	/// List of accouts that provisioned funds to bootstrap and has not claimed liquidity tokens yet
 	#[pallet::storage]
 	#[pallet::getter(fn provision_accounts)]
 	pub type ProvisionAccounts<T: Config> =
-		StorageMap<_, Twox64Concat, T::AccountId, (), OptionQuery>;
+		StorageDoubleMap<_, Twox64Concat, BootstrapId, Twox64Concat, T::AccountId, (), OptionQuery>;
 
-	/// Currently bootstraped pair of tokens representaed as [ `first_token_id`, `second_token_id`]
+	/// Bootstraped pair of tokens representaed as [ `first_token_id`, `second_token_id`]
 	#[pallet::storage]
 	#[pallet::getter(fn pair)]
-	pub type ActivePair<T: Config> = StorageValue<_, (TokenId, TokenId), OptionQuery>;
+	pub type ActivePair<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (TokenId, TokenId), OptionQuery>;
 
 	/// Wheter to automatically promote the pool after [`BootstrapPhase::PublicPhase`] or not.
 	#[pallet::storage]
 	#[pallet::getter(fn get_promote_bootstrap_pool)]
-	pub type PromoteBootstrapPool<T: Config> = StorageValue<_, bool, ValueQuery>;
+	pub type PromoteBootstrapPool<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, bool, ValueQuery>;
+
+	/// Minimal valuations of (first token, second token) that needs to be reached for the pool to
+	/// be created, otherwise bootstrap goes to [`BootstrapPhase::Failed`]
+	#[pallet::storage]
+	#[pallet::getter(fn min_valuations)]
+	pub type MinValuations<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (Balance, Balance), ValueQuery>;
+
+	/// Lockup length (in blocks since the end of the bootstrap) and (numerator, denominator) of
+	/// bonus multiplier applied to provisions of participants that lock up their liquidity tokens
+	#[pallet::storage]
+	#[pallet::getter(fn lockup_bonus)]
+	pub type LockupBonus<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (u32, (u128, u128)), OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn locked_up_accounts)]
+	pub type LockedUpAccounts<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Bonus part of provisions (regular and vested) of participants that locked up their
+	/// liquidity tokens
+	#[pallet::storage]
+	#[pallet::getter(fn lockup_bonus_provisions)]
+	pub type LockupBonusProvisions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		Balance,
+		ValueQuery,
+	>;
+
+	/// Part of [`LockupBonusProvisions`] that is bonus of vested provisions, liquidity tokens
+	/// rewarded for it are locked together with the rest of vested rewards
+	#[pallet::storage]
+	#[pallet::getter(fn lockup_bonus_vested_provisions)]
+	pub type LockupBonusVestedProvisions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		Balance,
+		ValueQuery,
+	>;
+
+	/// Sum of bonus parts of provisions, stored in the same order as [`Valuations`]
+	#[pallet::storage]
+	#[pallet::getter(fn lockup_bonus_valuations)]
+	pub type LockupBonusValuations<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (Balance, Balance), ValueQuery>;
+
+	/// Maximal provisions of (first token, second token) of whitelisted accounts that can be made
+	/// in [`BootstrapPhase::Whitelist`] phase, accounts without allowance are limited by
+	/// [`ProvisionCaps`]
+	#[pallet::storage]
+	#[pallet::getter(fn whitelist_allowances)]
+	pub type WhitelistAllowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BootstrapId,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		(Balance, Balance),
+		OptionQuery,
+	>;
+
+	/// Maximal provisions of (first token, second token) that single account can make in
+	/// bootstrap, applies to all provisions except the ones limited by [`WhitelistAllowances`]
+	#[pallet::storage]
+	#[pallet::getter(fn provision_caps)]
+	pub type ProvisionCaps<T: Config> =
+		StorageMap<_, Twox64Concat, BootstrapId, (Balance, Balance), OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn archived)]
@@ -366,35 +553,113 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		// /// provisions vested/locked tokens into the boostrstrap
-		// #[pallet::weight(<<T as Config>::WeightInfo>::provision_vested())]
-		// #[transactional]
-		// pub fn provision_vested(
-		// 	origin: OriginFor<T>,
-		// 	token_id: TokenId,
-		// 	amount: Balance,
-		// ) -> DispatchResult {
-		// 	let sender = ensure_signed(origin)?;
-		// 	let (vesting_starting_block, vesting_ending_block_as_balance) =
-		// 		<<T as Config>::VestingProvider>::unlock_tokens(
-		// 			&sender,
-		// 			token_id.into(),
-		// 			amount.into(),
-		// 		)
-		// 		.map_err(|_| Error::<T>::NotEnoughVestedAssets)?;
-		// 	Self::do_provision(
-		// 		&sender,
-		// 		token_id,
-		// 		amount,
-		// 		ProvisionKind::Vested(
-		// 			vesting_starting_block.saturated_into::<BlockNrAsBalance>(),
-		// 			vesting_ending_block_as_balance.into(),
-		// 		),
-		// 	)?;
-		// 	ProvisionAccounts::<T>::insert(&sender, ());
-		// 	Self::deposit_event(Event::Provisioned(token_id, amount));
-		// 	Ok(().into())
-		// }
+		/// Allows for provisioning one of the tokens from currently bootstrapped pair using tokens
+		/// locked in vesting schedule. Provisioned amount is unlocked from the vesting schedule
+		/// and liquidity tokens claimed for it are locked again with the matching schedule. Can
+		/// only be called during:
+		/// - [`BootstrapPhase::Whitelist`]
+		/// - [`BootstrapPhase::Public`]
+		///
+		/// phases.
+		///
+		/// # Args:
+		///  - `bootstrap_id` - id of the bootstrap to provision into
+		///  - `token_id` - id of the token to provision (should be one of the bootstraped pair([`ActivePair`]))
+		///  - `amount` - amount of the vested token to provision
+		#[pallet::weight(<<T as Config>::WeightInfo>::provision_vested())]
+		#[transactional]
+		pub fn provision_vested(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+			token_id: TokenId,
+			amount: Balance,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let (vesting_starting_block, vesting_ending_block_as_balance): (
+				T::BlockNumber,
+				Balance,
+			) = <<T as Config>::VestingProvider>::unlock_tokens(
+				&sender,
+				token_id.into(),
+				amount.into(),
+			)
+			.map(|x| (x.0.into(), x.1.into()))
+			.map_err(|_| Error::<T>::NotEnoughVestedAssets)?;
+			Self::do_provision(
+				bootstrap_id,
+				&sender,
+				token_id,
+				amount,
+				ProvisionKind::Vested(
+					vesting_starting_block.saturated_into::<BlockNrAsBalance>(),
+					vesting_ending_block_as_balance,
+				),
+			)?;
+			ProvisionAccounts::<T>::insert(bootstrap_id, &sender, ());
+			Self::deposit_event(Event::VestedProvisioned(bootstrap_id, token_id, amount));
+			Ok(().into())
+		}
+
+		/// Locks up liquidity tokens that will be claimed for all provisions of the caller in exchange
+		/// for bonus multiplier (see [`LockupBonus`]) applied to these provisions when liquidity
+		/// tokens are shared. Applies to provisions made before and after the call. Claimed liquidity
+		/// tokens are locked using vesting provider for lockup length since the end of the bootstrap,
+		/// all of them are unlocked at once when the lockup is over.
+		/// Can only be called during:
+		/// - [`BootstrapPhase::Whitelist`]
+		/// - [`BootstrapPhase::Public`]
+		///
+		/// phases.
+		///
+		/// # Args:
+		///  - `bootstrap_id` - id of the bootstrap
+		#[pallet::weight(<<T as Config>::WeightInfo>::lock_up_provisions())]
+		#[transactional]
+		pub fn lock_up_provisions(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(LockupBonus::<T>::contains_key(bootstrap_id), Error::<T>::LockupNotAvailable);
+			let phase = Phase::<T>::get(bootstrap_id);
+			ensure!(
+				phase == BootstrapPhase::Whitelist || phase == BootstrapPhase::Public,
+				Error::<T>::Unauthorized
+			);
+			let (start, whitelist_length, public_length, _) =
+				BootstrapSchedule::<T>::get(bootstrap_id).ok_or(Error::<T>::Unauthorized)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <
+					start + whitelist_length.into() + public_length.into(),
+				Error::<T>::Unauthorized
+			);
+			ensure!(
+				!LockedUpAccounts::<T>::contains_key(bootstrap_id, &sender),
+				Error::<T>::AlreadyLockedUp
+			);
+
+			LockedUpAccounts::<T>::insert(bootstrap_id, &sender, ());
+			for token_id in
+				[Self::first_token_id(bootstrap_id), Self::second_token_id(bootstrap_id)]
+			{
+				let provision = Provisions::<T>::get(bootstrap_id, (sender.clone(), token_id));
+				let (vested_provision, _, _) =
+					VestedProvisions::<T>::get(bootstrap_id, (sender.clone(), token_id));
+				let total_provision =
+					provision.checked_add(vested_provision).ok_or(Error::<T>::MathOverflow)?;
+				Self::add_lockup_bonus(
+					bootstrap_id,
+					&sender,
+					token_id,
+					total_provision,
+					vested_provision,
+				)?;
+			}
+
+			Self::deposit_event(Event::ProvisionsLockedUp(bootstrap_id, sender));
+			Ok(().into())
+		}
 
 		/// Allows for provisioning one of the tokens from currently bootstrapped pair. Can only be called during:
 		/// - [`BootstrapPhase::Whitelist`]
@@ -403,39 +668,58 @@ pub mod pallet {
 		/// phases.
 		///
 		/// # Args:
-		///  - `token_id` - id of the token to provision (should be one of the currently bootstraped pair([`ActivePair`]))
+		///  - `bootstrap_id` - id of the bootstrap to provision into
+		///  - `token_id` - id of the token to provision (should be one of the bootstraped pair([`ActivePair`]))
 		///  - `amount` - amount of the token to provision
 		#[pallet::weight(<<T as Config>::WeightInfo>::provision())]
 		#[transactional]
 		pub fn provision(
 			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
 			token_id: TokenId,
 			amount: Balance,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::do_provision(&sender, token_id, amount, ProvisionKind::Regular)?;
-			ProvisionAccounts::<T>::insert(&sender, ());
-			Self::deposit_event(Event::Provisioned(token_id, amount));
+			Self::do_provision(bootstrap_id, &sender, token_id, amount, ProvisionKind::Regular)?;
+			ProvisionAccounts::<T>::insert(bootstrap_id, &sender, ());
+			Self::deposit_event(Event::Provisioned(bootstrap_id, token_id, amount));
 			Ok(().into())
 		}
 
-		/// Allows for whitelisting accounts, so they can participate in during whitelist phase. The list of
-		/// account is extended with every subsequent call
-		#[pallet::weight(T::DbWeight::get().writes(1) * (accounts.len() as u64))]
+		/// Allows for whitelisting accounts, so they can participate in during whitelist phase of
+		/// particular bootstrap. The list of account is extended with every subsequent call
+		///
+		/// # Args:
+		/// - `bootstrap_id` - id of the bootstrap
+		/// - `accounts` - accounts to whitelist
+		/// - `allowance` - maximal provisions of (first token, second token) each of the accounts
+		/// can make in whitelist phase, `None` means accounts are limited by [`ProvisionCaps`] (if
+		/// any) as everyone else. Overrides allowance set by previous calls for the same account.
+		/// Updates [`WhitelistAllowances`]
+		#[pallet::weight(T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().writes(2) * (accounts.len() as u64)))]
 		#[transactional]
 		pub fn whitelist_accounts(
 			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
 			accounts: Vec<T::AccountId>,
+			allowance: Option<(Balance, Balance)>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
+			ensure!(
+				BootstrapSchedule::<T>::contains_key(bootstrap_id),
+				Error::<T>::BootstrapNotSchduled
+			);
 			for account in accounts {
-				WhitelistedAccount::<T>::insert(&account, ());
+				WhitelistedAccount::<T>::insert(bootstrap_id, &account, ());
+				WhitelistAllowances::<T>::set(bootstrap_id, &account, allowance);
 			}
-			Self::deposit_event(Event::AccountsWhitelisted);
+			Self::deposit_event(Event::AccountsWhitelisted(bootstrap_id));
 			Ok(().into())
 		}
 
-		/// Used for starting/scheduling new bootstrap
+		/// Used for starting/scheduling new bootstrap. Scheduling a pair that already has a bootstrap
+		/// which is not started yet updates that bootstrap instead. Id of the bootstrap is published
+		/// with [`Event::BootstrapScheduled`].
 		///
 		/// # Args:
 		/// - `first_token_id` - first token of the tokens pair
@@ -444,6 +728,8 @@ pub mod pallet {
 		/// - `whitelist_phase_length`: - length of whitelist phase
 		/// - `public_phase_lenght`- length of public phase
 		/// - `promote_bootstrap_pool`- whether liquidity pool created by bootstrap should be promoted
+		/// - `min_valuations` - minimal valuations of (first token, second token) that needs to be
+		/// provisioned, otherwise pool is not created and bootstrap goes to [`BootstrapPhase::Failed`]
 		/// - `max_first_to_second_ratio` - represented as (numerator,denominator) - Ratio may be used to limit participations of second token id. Ratio between first and second token needs to be held during whole bootstrap. Whenever user tries to participate (using [`Pallet::provision`] extrinsic) the following conditions is check.
 		/// ```ignore
 		/// all previous first participations + first token participations             ratio numerator
@@ -489,7 +775,7 @@ pub mod pallet {
 		/// ----------------------------------------------------------------------- <= ------------------
 		/// all previous second token participations + second token participations            1
 		/// ```
-		#[pallet::weight(<<T as Config>::WeightInfo>::schedule_bootstrap())]
+		#[pallet::weight(<<T as Config>::WeightInfo>::schedule_bootstrap().saturating_add(T::DbWeight::get().reads(T::MaxActiveBootstraps::get().into())))]
 		#[transactional]
 		pub fn schedule_bootstrap(
 			origin: OriginFor<T>,
@@ -500,18 +786,34 @@ pub mod pallet {
 			public_phase_lenght: u32,
 			max_first_to_second_ratio: Option<(u128, u128)>,
 			promote_bootstrap_pool: bool,
+			min_valuations: Option<(Balance, Balance)>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
-			ensure!(Phase::<T>::get() == BootstrapPhase::BeforeStart, Error::<T>::AlreadyStarted);
+			let scheduled_bootstrap_id =
+				Self::bootstrap_id_of_pair(first_token_id, second_token_id);
+			let bootstrap_id = if let Some(bootstrap_id) = scheduled_bootstrap_id {
+				ensure!(
+					Phase::<T>::get(bootstrap_id) == BootstrapPhase::BeforeStart,
+					Error::<T>::AlreadyStarted
+				);
 
-			if let Some((scheduled_ido_start, _, _, _)) = BootstrapSchedule::<T>::get() {
+				let (scheduled_ido_start, _, _, _) = BootstrapSchedule::<T>::get(bootstrap_id)
+					.ok_or(Error::<T>::BootstrapNotSchduled)?;
 				let now = <frame_system::Pallet<T>>::block_number();
 				ensure!(
 					now.saturating_add(T::BootstrapUpdateBuffer::get()) < scheduled_ido_start,
 					Error::<T>::TooLateToUpdateBootstrap
 				);
-			}
+				bootstrap_id
+			} else {
+				ensure!(
+					BootstrapSchedule::<T>::iter_keys().count() <
+						T::MaxActiveBootstraps::get() as usize,
+					Error::<T>::TooManyActiveBootstraps
+				);
+				Self::next_bootstrap_id()
+			};
 
 			ensure!(first_token_id != second_token_id, Error::<T>::SameToken);
 
@@ -552,42 +854,62 @@ pub mod pallet {
 				Error::<T>::PoolAlreadyExists
 			);
 
-			ActivePair::<T>::put((first_token_id, second_token_id));
-			BootstrapSchedule::<T>::put((
-				ido_start,
-				whitelist_phase_length,
-				public_phase_lenght,
-				max_first_to_second_ratio,
-			));
+			if scheduled_bootstrap_id.is_none() {
+				NextBootstrapId::<T>::put(
+					bootstrap_id.checked_add(1).ok_or(Error::<T>::MathOverflow)?,
+				);
+			}
+
+			ActivePair::<T>::insert(bootstrap_id, (first_token_id, second_token_id));
+			BootstrapSchedule::<T>::insert(
+				bootstrap_id,
+				(ido_start, whitelist_phase_length, public_phase_lenght, max_first_to_second_ratio),
+			);
+
+			PromoteBootstrapPool::<T>::insert(bootstrap_id, promote_bootstrap_pool);
+			MinValuations::<T>::insert(bootstrap_id, min_valuations.unwrap_or_default());
 
-			PromoteBootstrapPool::<T>::put(promote_bootstrap_pool);
+			Self::deposit_event(Event::BootstrapScheduled(
+				bootstrap_id,
+				first_token_id,
+				second_token_id,
+			));
 
 			Ok(().into())
 		}
 
-		/// Used to cancel active bootstrap. Can only be called before bootstrap is actually started
+		/// Used to cancel scheduled bootstrap. Can only be called before bootstrap is actually started
 		#[pallet::weight(T::DbWeight::get().reads_writes(3, 4).saturating_add(Weight::from_ref_time(1_000_000)))]
 		#[transactional]
-		pub fn cancel_bootstrap(origin: OriginFor<T>) -> DispatchResult {
+		pub fn cancel_bootstrap(origin: OriginFor<T>, bootstrap_id: BootstrapId) -> DispatchResult {
 			ensure_root(origin)?;
 
 			// BootstrapSchedule should exist but not after BootstrapUpdateBuffer blocks before start
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			let (ido_start, _, _, _) =
-				BootstrapSchedule::<T>::get().ok_or(Error::<T>::BootstrapNotSchduled)?;
-			ensure!(Phase::<T>::get() == BootstrapPhase::BeforeStart, Error::<T>::AlreadyStarted);
+			let (ido_start, _, _, _) = BootstrapSchedule::<T>::get(bootstrap_id)
+				.ok_or(Error::<T>::BootstrapNotSchduled)?;
+			ensure!(
+				Phase::<T>::get(bootstrap_id) == BootstrapPhase::BeforeStart,
+				Error::<T>::AlreadyStarted
+			);
 
 			ensure!(
 				now.saturating_add(T::BootstrapUpdateBuffer::get()) < ido_start,
 				Error::<T>::TooLateToUpdateBootstrap
 			);
 
-			ActivePair::<T>::kill();
-			BootstrapSchedule::<T>::kill();
-			PromoteBootstrapPool::<T>::kill();
+			ActivePair::<T>::remove(bootstrap_id);
+			BootstrapSchedule::<T>::remove(bootstrap_id);
+			PromoteBootstrapPool::<T>::remove(bootstrap_id);
 			// Unnecessary
-			Phase::<T>::put(BootstrapPhase::BeforeStart);
+			Phase::<T>::remove(bootstrap_id);
+			MinValuations::<T>::remove(bootstrap_id);
+			LockupBonus::<T>::remove(bootstrap_id);
+			ProvisionCaps::<T>::remove(bootstrap_id);
+			// whitelist is set by root only, so it is cleared at once
+			let _ = WhitelistedAccount::<T>::clear_prefix(bootstrap_id, u32::MAX, None);
+			let _ = WhitelistAllowances::<T>::clear_prefix(bootstrap_id, u32::MAX, None);
 
 			Ok(().into())
 		}
@@ -597,6 +919,7 @@ pub mod pallet {
 		// can be used to enable or disable automatic pool promotion of liquidity pool. Updates [`PromoteBootstrapPool`]
 		pub fn update_promote_bootstrap_pool(
 			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
 			promote_bootstrap_pool: bool,
 		) -> DispatchResult {
 			ensure_root(origin)?;
@@ -604,10 +927,90 @@ pub mod pallet {
 			// BootstrapSchedule should exist but not finalized
 			// we allow this to go thru if the BootstrapSchedule exists and the phase is before finalized
 
-			ensure!(BootstrapSchedule::<T>::get().is_some(), Error::<T>::BootstrapNotSchduled);
-			ensure!(Phase::<T>::get() != BootstrapPhase::Finished, Error::<T>::BootstrapFinished);
+			ensure!(
+				BootstrapSchedule::<T>::contains_key(bootstrap_id),
+				Error::<T>::BootstrapNotSchduled
+			);
+			let phase = Phase::<T>::get(bootstrap_id);
+			ensure!(
+				phase != BootstrapPhase::Finished && phase != BootstrapPhase::Failed,
+				Error::<T>::BootstrapFinished
+			);
 
-			PromoteBootstrapPool::<T>::put(promote_bootstrap_pool);
+			PromoteBootstrapPool::<T>::insert(bootstrap_id, promote_bootstrap_pool);
+
+			Ok(().into())
+		}
+
+		/// Used to set or remove lockup bonus of the bootstrap. Can only be called before bootstrap
+		/// is started. Updates [`LockupBonus`]
+		///
+		/// # Args:
+		/// - `bootstrap_id` - id of the bootstrap
+		/// - `lockup_bonus` - lockup length (in blocks since the end of the bootstrap) and
+		/// (numerator, denominator) of bonus multiplier, multiplier cannot be lower than 1
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1).saturating_add(Weight::from_ref_time(1_000_000)))]
+		#[transactional]
+		pub fn update_lockup_bonus(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+			lockup_bonus: Option<(u32, (u128, u128))>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(
+				BootstrapSchedule::<T>::contains_key(bootstrap_id),
+				Error::<T>::BootstrapNotSchduled
+			);
+			ensure!(
+				Phase::<T>::get(bootstrap_id) == BootstrapPhase::BeforeStart,
+				Error::<T>::AlreadyStarted
+			);
+
+			if let Some((lockup_length, (multiplier_numerator, multiplier_denominator))) =
+				lockup_bonus
+			{
+				ensure!(lockup_length > 0, Error::<T>::LockupLengthCannotBeZero);
+				ensure!(multiplier_denominator != 0, Error::<T>::WrongRatio);
+				ensure!(multiplier_numerator >= multiplier_denominator, Error::<T>::WrongRatio);
+				LockupBonus::<T>::insert(
+					bootstrap_id,
+					(lockup_length, (multiplier_numerator, multiplier_denominator)),
+				);
+			} else {
+				LockupBonus::<T>::remove(bootstrap_id);
+			}
+
+			Ok(().into())
+		}
+
+		/// Used to set or remove maximal provisions single account can make in bootstrap. Can only be
+		/// called before bootstrap is started. Updates [`ProvisionCaps`]
+		///
+		/// # Args:
+		/// - `bootstrap_id` - id of the bootstrap
+		/// - `provision_caps` - maximal provisions of (first token, second token) of single account,
+		/// whitelisted accounts with allowance (see [`Pallet::whitelist_accounts`]) are limited by
+		/// their allowance in whitelist phase instead
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1).saturating_add(Weight::from_ref_time(1_000_000)))]
+		#[transactional]
+		pub fn update_provision_caps(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+			provision_caps: Option<(Balance, Balance)>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(
+				BootstrapSchedule::<T>::contains_key(bootstrap_id),
+				Error::<T>::BootstrapNotSchduled
+			);
+			ensure!(
+				Phase::<T>::get(bootstrap_id) == BootstrapPhase::BeforeStart,
+				Error::<T>::AlreadyStarted
+			);
+
+			ProvisionCaps::<T>::set(bootstrap_id, provision_caps);
 
 			Ok(().into())
 		}
@@ -615,23 +1018,58 @@ pub mod pallet {
 		/// When bootstrap is in [`BootstrapPhase::Finished`] state user can claim his part of liquidity tokens.
 		#[pallet::weight(<<T as Config>::WeightInfo>::claim_and_activate_liquidity_tokens())]
 		#[transactional]
-		pub fn claim_liquidity_tokens(origin: OriginFor<T>) -> DispatchResult {
+		pub fn claim_liquidity_tokens(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::do_claim_liquidity_tokens(&sender, false)
+			Self::do_claim_liquidity_tokens(bootstrap_id, &sender, false)
 		}
 
 		/// When bootstrap is in [`BootstrapPhase::Finished`] state user can claim his part of liquidity tokens comparing to `claim_liquidity_tokens` when calling `claim_and_activate_liquidity_tokens` tokens will be automatically activated.
 		#[pallet::weight(<<T as Config>::WeightInfo>::claim_and_activate_liquidity_tokens())]
 		#[transactional]
-		pub fn claim_and_activate_liquidity_tokens(origin: OriginFor<T>) -> DispatchResult {
+		pub fn claim_and_activate_liquidity_tokens(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			Self::do_claim_liquidity_tokens(&sender, true)
+			Self::do_claim_liquidity_tokens(bootstrap_id, &sender, true)
+		}
+
+		/// When bootstrap is in [`BootstrapPhase::Failed`] state user can withdraw all the tokens he
+		/// provisioned. Vested provisions are locked again with the schedule they were provisioned with.
+		#[pallet::weight(<<T as Config>::WeightInfo>::withdraw_provisions())]
+		#[transactional]
+		pub fn withdraw_provisions(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_withdraw_provisions(bootstrap_id, &sender)
+		}
+
+		/// Allows withdrawing provisions of failed bootstrap for some account that haven't done that
+		/// yet, so bootstrap can be finalized.
+		/// # Args:
+		/// - `bootstrap_id` - id of the failed bootstrap
+		/// - `account` - account in behalf of which provisions should be withdrawn
+		#[pallet::weight(<<T as Config>::WeightInfo>::withdraw_provisions())]
+		#[transactional]
+		pub fn withdraw_provisions_for_account(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+			account: T::AccountId,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			Self::do_withdraw_provisions(bootstrap_id, &account)
 		}
 
 		/// Used to reset Bootstrap state and prepare it for running another bootstrap.
 		/// It should be called multiple times until it produces [`Event::BootstrapFinalized`] event.
 		///
 		/// # Args:
+		/// * `bootstrap_id` - id of the bootstrap to finalize
 		/// * `limit` - limit of storage entries to be removed in single call. Should be set to some
 		/// reasonable balue like `100`.
 		///
@@ -639,71 +1077,119 @@ pub mod pallet {
 		/// bootstrap might not fit in a single block. As a result tx can be rejected !!!**
 		#[pallet::weight(<<T as Config>::WeightInfo>::finalize().saturating_add(T::DbWeight::get().reads_writes(1, 1).saturating_mul(Into::<u64>::into(*limit).saturating_add(u64::one()))))]
 		#[transactional]
-		pub fn finalize(origin: OriginFor<T>, mut limit: u32) -> DispatchResult {
+		pub fn finalize(
+			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
+			mut limit: u32,
+		) -> DispatchResult {
 			ensure_root(origin)?;
 
-			ensure!(Self::phase() == BootstrapPhase::Finished, Error::<T>::NotFinishedYet);
+			let phase = Self::phase(bootstrap_id);
+			ensure!(
+				phase == BootstrapPhase::Finished || phase == BootstrapPhase::Failed,
+				Error::<T>::NotFinishedYet
+			);
 
 			ensure!(
-				ProvisionAccounts::<T>::iter_keys().next().is_none(),
+				ProvisionAccounts::<T>::iter_key_prefix(bootstrap_id).next().is_none(),
 				Error::<T>::BootstrapNotReadyToBeFinished
 			);
 
-			match VestedProvisions::<T>::clear(limit, None).into() {
+			match VestedProvisions::<T>::clear_prefix(bootstrap_id, limit, None).into() {
 				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
 				KillStorageResult::SomeRemaining(_) => {
-					Self::deposit_event(Event::BootstrapParitallyFinalized);
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
 					return Ok(().into())
 				},
 			}
 
-			match WhitelistedAccount::<T>::clear(limit, None).into() {
+			match WhitelistedAccount::<T>::clear_prefix(bootstrap_id, limit, None).into() {
 				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
 				KillStorageResult::SomeRemaining(_) => {
-					Self::deposit_event(Event::BootstrapParitallyFinalized);
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
 					return Ok(().into())
 				},
 			}
 
-			match ClaimedRewards::<T>::clear(limit, None).into() {
+			match WhitelistAllowances::<T>::clear_prefix(bootstrap_id, limit, None).into() {
 				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
 				KillStorageResult::SomeRemaining(_) => {
-					Self::deposit_event(Event::BootstrapParitallyFinalized);
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
 					return Ok(().into())
 				},
 			}
 
-			match Provisions::<T>::clear(limit, None).into() {
+			match ClaimedRewards::<T>::clear_prefix(bootstrap_id, limit, None).into() {
 				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
 				KillStorageResult::SomeRemaining(_) => {
-					Self::deposit_event(Event::BootstrapParitallyFinalized);
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
 					return Ok(().into())
 				},
 			}
 
-			Phase::<T>::put(BootstrapPhase::BeforeStart);
-			let (liq_token_id, _) = MintedLiquidity::<T>::take();
-			let balance = T::Currency::free_balance(liq_token_id.into(), &Self::vault_address());
-			if balance > 0_u128.into() {
-				T::Currency::transfer(
-					liq_token_id.into(),
-					&Self::vault_address(),
-					&T::TreasuryPalletId::get().into_account_truncating(),
-					balance,
-					ExistenceRequirement::AllowDeath,
-				)?;
+			match Provisions::<T>::clear_prefix(bootstrap_id, limit, None).into() {
+				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
+				KillStorageResult::SomeRemaining(_) => {
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
+					return Ok(().into())
+				},
+			}
+
+			match LockupBonusProvisions::<T>::clear_prefix(bootstrap_id, limit, None).into() {
+				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
+				KillStorageResult::SomeRemaining(_) => {
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
+					return Ok(().into())
+				},
+			}
+
+			match LockupBonusVestedProvisions::<T>::clear_prefix(bootstrap_id, limit, None).into() {
+				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
+				KillStorageResult::SomeRemaining(_) => {
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
+					return Ok(().into())
+				},
+			}
+
+			match LockedUpAccounts::<T>::clear_prefix(bootstrap_id, limit, None).into() {
+				KillStorageResult::AllRemoved(num_iter) => limit = limit.saturating_sub(num_iter),
+				KillStorageResult::SomeRemaining(_) => {
+					Self::deposit_event(Event::BootstrapParitallyFinalized(bootstrap_id));
+					return Ok(().into())
+				},
 			}
-			Valuations::<T>::kill();
-			ActivePair::<T>::kill();
-			PromoteBootstrapPool::<T>::kill();
 
-			if let Some(bootstrap) = BootstrapSchedule::<T>::take() {
+			Phase::<T>::remove(bootstrap_id);
+			// failed bootstrap has not minted any liquidity tokens
+			if MintedLiquidity::<T>::contains_key(bootstrap_id) {
+				let (liq_token_id, _) = MintedLiquidity::<T>::take(bootstrap_id);
+				let balance =
+					T::Currency::free_balance(liq_token_id.into(), &Self::vault_address());
+				if balance > 0_u128.into() {
+					T::Currency::transfer(
+						liq_token_id.into(),
+						&Self::vault_address(),
+						&T::TreasuryPalletId::get().into_account_truncating(),
+						balance,
+						ExistenceRequirement::AllowDeath,
+					)?;
+				}
+			}
+			Valuations::<T>::remove(bootstrap_id);
+			ActivePair::<T>::remove(bootstrap_id);
+			PromoteBootstrapPool::<T>::remove(bootstrap_id);
+			MinValuations::<T>::remove(bootstrap_id);
+			LockupBonus::<T>::remove(bootstrap_id);
+			LockupBonusValuations::<T>::remove(bootstrap_id);
+			ProvisionCaps::<T>::remove(bootstrap_id);
+
+			if let Some(bootstrap) = BootstrapSchedule::<T>::take(bootstrap_id) {
 				ArchivedBootstrap::<T>::mutate(|v| {
 					v.push(bootstrap);
 				});
 			}
 
-			Self::deposit_event(Event::BootstrapFinalized);
+			Self::deposit_event(Event::BootstrapFinalized(bootstrap_id));
 
 			Ok(().into())
 		}
@@ -711,16 +1197,18 @@ pub mod pallet {
 		/// Allows claiming rewards for some account that haven't done that yet. The only difference between
 		/// calling [`Pallet::claim_liquidity_tokens_for_account`] by some other account and calling [`Pallet::claim_liquidity_tokens`] directly by that account is account that will be charged for transaction fee.
 		/// # Args:
+		/// - `bootstrap_id` - id of the bootstrap to claim liquidity tokens from
 		/// - `other` - account in behalf of which liquidity tokens should be claimed
 		#[pallet::weight(<<T as Config>::WeightInfo>::claim_and_activate_liquidity_tokens())]
 		#[transactional]
 		pub fn claim_liquidity_tokens_for_account(
 			origin: OriginFor<T>,
+			bootstrap_id: BootstrapId,
 			account: T::AccountId,
 			activate_rewards: bool,
 		) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
-			Self::do_claim_liquidity_tokens(&account, activate_rewards)
+			Self::do_claim_liquidity_tokens(bootstrap_id, &account, activate_rewards)
 		}
 	}
 
@@ -770,25 +1258,49 @@ pub mod pallet {
 		/// Bootstrap can only be updated or cancelled
 		/// BootstrapUpdateBuffer blocks or more before bootstrap start
 		TooLateToUpdateBootstrap,
+		/// Maximum number of active bootstraps reached
+		TooManyActiveBootstraps,
+		/// Provisions can only be withdrawn from failed bootstrap
+		BootstrapNotFailed,
+		/// no provisions to withdraw
+		NothingToWithdraw,
+		/// Lockup bonus is not set for the bootstrap
+		LockupNotAvailable,
+		/// Account has already locked up its provisions
+		AlreadyLockedUp,
+		/// Lockup cannot last 0 blocks
+		LockupLengthCannotBeZero,
+		/// Provision exceeds whitelist allowance of the account
+		WhitelistAllowanceExceeded,
+		/// Provision exceeds maximal provision of single account
+		ProvisionCapExceeded,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
+		/// Bootstrap scheduled or updated
+		BootstrapScheduled(BootstrapId, TokenId, TokenId),
 		/// Funds provisioned
-		Provisioned(TokenId, Balance),
+		Provisioned(BootstrapId, TokenId, Balance),
 		/// Funds provisioned using vested tokens
-		VestedProvisioned(TokenId, Balance),
+		VestedProvisioned(BootstrapId, TokenId, Balance),
 		/// The activation of the rewards liquidity tokens failed
 		RewardsLiquidityAcitvationFailed(T::AccountId, TokenId, Balance),
 		/// Rewards claimed
 		RewardsClaimed(TokenId, Balance),
 		/// account whitelisted
-		AccountsWhitelisted,
+		AccountsWhitelisted(BootstrapId),
 		/// finalization process tarted
-		BootstrapParitallyFinalized,
+		BootstrapParitallyFinalized(BootstrapId),
 		/// finalization process finished
-		BootstrapFinalized,
+		BootstrapFinalized(BootstrapId),
+		/// Minimal valuations were not reached, provisions can be withdrawn
+		BootstrapFailed(BootstrapId),
+		/// Provisioned funds withdrawn from failed bootstrap
+		ProvisionWithdrawn(BootstrapId, TokenId, Balance),
+		/// Account locked up liquidity tokens of its provisions in exchange for lockup bonus
+		ProvisionsLockedUp(BootstrapId, T::AccountId),
 	}
 }
 
@@ -802,6 +1314,8 @@ pub enum BootstrapPhase {
 	Public,
 	/// Bootstrap has finished. At this phase users that participated in bootstrap during previous phases can claim their share of minted `liquidity tokens`. `Bootstrap::finalize` can be call to reset pallet state schedule following bootstrap again.
 	Finished,
+	/// Bootstrap has finished without reaching minimal valuations (see [`MinValuations`]), pool is not created. At this phase users that participated in bootstrap can withdraw their provisions. `Bootstrap::finalize` can be call to reset pallet state.
+	Failed,
 }
 
 impl Default for BootstrapPhase {
@@ -811,22 +1325,96 @@ impl Default for BootstrapPhase {
 }
 
 impl<T: Config> Pallet<T> {
-	fn is_whitelisted(account: &T::AccountId) -> bool {
-		WhitelistedAccount::<T>::try_get(account).is_ok()
+	fn is_whitelisted(bootstrap_id: BootstrapId, account: &T::AccountId) -> bool {
+		WhitelistedAccount::<T>::try_get(bootstrap_id, account).is_ok()
 	}
 
 	fn vault_address() -> T::AccountId {
 		PALLET_ID.into_account_truncating()
 	}
 
+	/// Id of the bootstrap (not finalized yet) of given pair of tokens, order of tokens does not matter
+	fn bootstrap_id_of_pair(first: TokenId, second: TokenId) -> Option<BootstrapId> {
+		ActivePair::<T>::iter().find_map(|(bootstrap_id, pair)| {
+			if pair == (first, second) || pair == (second, first) {
+				Some(bootstrap_id)
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Finishes bootstrap and creates liquidity pool out of all provisioned tokens, cost is
+	/// covered by [`WeightInfo::finish_bootstrap`]
+	fn finish_bootstrap(bootstrap_id: BootstrapId) {
+		let (second_token_valuation, first_token_valuation) = Valuations::<T>::get(bootstrap_id);
+		let (first_token_min_valuation, second_token_min_valuation) =
+			MinValuations::<T>::get(bootstrap_id);
+		if first_token_valuation < first_token_min_valuation ||
+			second_token_valuation < second_token_min_valuation
+		{
+			Self::fail_bootstrap(bootstrap_id);
+			return
+		}
+
+		let (first_token_id, second_token_id) = match ActivePair::<T>::get(bootstrap_id) {
+			Some(pair) => pair,
+			None => {
+				log!(error, "bootstrap {} has no pair!", bootstrap_id);
+				Self::fail_bootstrap(bootstrap_id);
+				return
+			},
+		};
+		// XykFunctionsTrait R: 11 W:12
+		// PoolCreateApi::pool_create R:2  +
+		// ---------------------------------
+		// R: 13 W 12
+		// partial changes of failed pool creation are rolled back
+		let created_pool = with_transaction(|| {
+			match T::PoolCreateApi::pool_create(
+				Self::vault_address(),
+				first_token_id,
+				first_token_valuation,
+				second_token_id,
+				second_token_valuation,
+			) {
+				Some(pool) => TransactionOutcome::Commit(Ok::<_, DispatchError>(Some(pool))),
+				None => TransactionOutcome::Rollback(Ok(None)),
+			}
+		})
+		.unwrap_or_default();
+		if let Some((liq_asset_id, issuance)) = created_pool {
+			Phase::<T>::insert(bootstrap_id, BootstrapPhase::Finished); // 1 WRINTE
+			log!(info, "bootstrap {} event finished", bootstrap_id);
+			MintedLiquidity::<T>::insert(bootstrap_id, (liq_asset_id, issuance)); // W:1
+			if PromoteBootstrapPool::<T>::get(bootstrap_id) {
+				if !T::RewardsApi::promote_pool(liq_asset_id) {
+					log!(error, "pool already promoted!");
+				}
+			}
+		} else {
+			// provisioned tokens stay in the vault so participants can withdraw them
+			log!(error, "cannot create pool!");
+			Self::fail_bootstrap(bootstrap_id);
+		}
+	}
+
+	fn fail_bootstrap(bootstrap_id: BootstrapId) {
+		Phase::<T>::insert(bootstrap_id, BootstrapPhase::Failed);
+		log!(info, "bootstrap {} event failed", bootstrap_id);
+		Self::deposit_event(Event::BootstrapFailed(bootstrap_id));
+	}
+
 	fn claim_liquidity_tokens_from_single_currency(
+		bootstrap_id: BootstrapId,
 		who: &T::AccountId,
 		provision_token_id: &TokenId,
 		rewards: Balance,
 		rewards_vested: Balance,
 		lock: (BlockNrAsBalance, BlockNrAsBalance),
+		lockup: Option<(BlockNrAsBalance, BlockNrAsBalance)>,
 	) -> DispatchResult {
-		let (liq_token_id, _) = Self::minted_liquidity();
+		let (liq_token_id, _) = Self::minted_liquidity(bootstrap_id);
 		let total_rewards = rewards.checked_add(rewards_vested).ok_or(Error::<T>::MathOverflow)?;
 		if total_rewards == 0 {
 			return Ok(().into())
@@ -840,22 +1428,42 @@ impl<T: Config> Pallet<T> {
 			ExistenceRequirement::KeepAlive,
 		)?;
 
-		ClaimedRewards::<T>::try_mutate(who, provision_token_id, |rewards| {
-			if let Some(val) = rewards.checked_add(total_rewards) {
-				*rewards = val;
-				Ok(())
-			} else {
-				Err(Error::<T>::MathOverflow)
+		ClaimedRewards::<T>::try_mutate(
+			bootstrap_id,
+			(who.clone(), *provision_token_id),
+			|rewards| {
+				if let Some(val) = rewards.checked_add(total_rewards) {
+					*rewards = val;
+					Ok(())
+				} else {
+					Err(Error::<T>::MathOverflow)
+				}
+			},
+		)?;
+
+		if let Some((lockup_start, lockup_end)) = lockup {
+			if rewards > 0 {
+				<<T as Config>::VestingProvider>::lock_tokens(
+					&who,
+					liq_token_id.into(),
+					rewards.into(),
+					Some(lockup_start.saturated_into()),
+					lockup_end.into(),
+				)?;
 			}
-		})?;
+		}
 
 		if rewards_vested > 0 {
+			// vested rewards of locked up provisions do not start vesting before the end of lockup
+			let (lock_start, lock_end) = lockup.map_or(lock, |(lockup_start, lockup_end)| {
+				(lock.0.max(lockup_start), lock.1.max(lockup_end))
+			});
 			<<T as Config>::VestingProvider>::lock_tokens(
 				&who,
 				liq_token_id.into(),
 				rewards_vested.into(),
-				Some(lock.0.saturated_into()),
-				lock.1.into(),
+				Some(lock_start.saturated_into()),
+				lock_end.into(),
 			)?;
 		}
 
@@ -872,24 +1480,31 @@ impl<T: Config> Pallet<T> {
 	/// actual_nominator * expected_denominator     expected_nominator * actual_denominator
 	/// ---------------------------------------- <= ----------------------------------------
 	/// actual_denominator * expected_denominator    expected_denominator * actual_nominator
-	fn is_ratio_kept(ratio_nominator: u128, ratio_denominator: u128) -> bool {
-		let (second_token_valuation, first_token_valuation) = Valuations::<T>::get();
+	fn is_ratio_kept(
+		bootstrap_id: BootstrapId,
+		ratio_nominator: u128,
+		ratio_denominator: u128,
+	) -> bool {
+		let (second_token_valuation, first_token_valuation) = Valuations::<T>::get(bootstrap_id);
 		let left = U256::from(first_token_valuation) * U256::from(ratio_denominator);
 		let right = U256::from(ratio_nominator) * U256::from(second_token_valuation);
 		left <= right
 	}
 
 	pub fn do_provision(
+		bootstrap_id: BootstrapId,
 		sender: &T::AccountId,
 		token_id: TokenId,
 		amount: Balance,
 		is_vested: ProvisionKind,
 	) -> DispatchResult {
-		let is_first_token = token_id == Self::first_token_id();
-		let is_second_token = token_id == Self::second_token_id();
-		let is_public_phase = Phase::<T>::get() == BootstrapPhase::Public;
-		let is_whitelist_phase = Phase::<T>::get() == BootstrapPhase::Whitelist;
-		let am_i_whitelisted = Self::is_whitelisted(sender);
+		let (first_token_id, second_token_id) =
+			ActivePair::<T>::get(bootstrap_id).ok_or(Error::<T>::BootstrapNotSchduled)?;
+		let is_first_token = token_id == first_token_id;
+		let is_second_token = token_id == second_token_id;
+		let is_public_phase = Phase::<T>::get(bootstrap_id) == BootstrapPhase::Public;
+		let is_whitelist_phase = Phase::<T>::get(bootstrap_id) == BootstrapPhase::Whitelist;
+		let am_i_whitelisted = Self::is_whitelisted(bootstrap_id, sender);
 
 		ensure!(is_first_token || is_second_token, Error::<T>::UnsupportedTokenId);
 
@@ -898,9 +1513,15 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::Unauthorized
 		);
 
-		let schedule = BootstrapSchedule::<T>::get();
-		ensure!(schedule.is_some(), Error::<T>::Unauthorized);
-		let (_, _, _, (ratio_nominator, ratio_denominator)) = schedule.unwrap();
+		let (start, whitelist_length, public_length, (ratio_nominator, ratio_denominator)) =
+			BootstrapSchedule::<T>::get(bootstrap_id).ok_or(Error::<T>::Unauthorized)?;
+
+		// finish of the bootstrap might be postponed when there is no room for it in `on_initialize`
+		ensure!(
+			<frame_system::Pallet<T>>::block_number() <
+				start + whitelist_length.into() + public_length.into(),
+			Error::<T>::Unauthorized
+		);
 
 		<T as Config>::Currency::transfer(
 			token_id.into(),
@@ -911,17 +1532,25 @@ impl<T: Config> Pallet<T> {
 		)
 		.or(Err(Error::<T>::NotEnoughAssets))?;
 
+		let vested_amount = match is_vested {
+			ProvisionKind::Regular => 0,
+			ProvisionKind::Vested(_, _) => amount,
+		};
 		match is_vested {
 			ProvisionKind::Regular => {
 				ensure!(
-					Provisions::<T>::try_mutate(sender, token_id, |provision| {
-						if let Some(val) = provision.checked_add(amount) {
-							*provision = val;
-							Ok(())
-						} else {
-							Err(())
+					Provisions::<T>::try_mutate(
+						bootstrap_id,
+						(sender.clone(), token_id),
+						|provision| {
+							if let Some(val) = provision.checked_add(amount) {
+								*provision = val;
+								Ok(())
+							} else {
+								Err(())
+							}
 						}
-					})
+					)
 					.is_ok(),
 					Error::<T>::MathOverflow
 				);
@@ -929,8 +1558,8 @@ impl<T: Config> Pallet<T> {
 			ProvisionKind::Vested(provision_start_block, provision_end_block) => {
 				ensure!(
 					VestedProvisions::<T>::try_mutate(
-						sender,
-						token_id,
+						bootstrap_id,
+						(sender.clone(), token_id),
 						|(provision, start_block, end_block)| {
 							if let Some(val) = provision.checked_add(amount) {
 								*provision = val;
@@ -948,20 +1577,42 @@ impl<T: Config> Pallet<T> {
 			},
 		}
 
-		let (pre_second_token_valuation, _) = Valuations::<T>::get();
+		// whitelist allowance takes precedence over provision caps in whitelist phase
+		let whitelist_allowance = if is_whitelist_phase {
+			WhitelistAllowances::<T>::get(bootstrap_id, sender)
+		} else {
+			None
+		};
+		let provision_limit = match whitelist_allowance {
+			Some(allowance) => Some((allowance, Error::<T>::WhitelistAllowanceExceeded)),
+			None => ProvisionCaps::<T>::get(bootstrap_id)
+				.map(|caps| (caps, Error::<T>::ProvisionCapExceeded)),
+		};
+		if let Some(((first_token_limit, second_token_limit), err)) = provision_limit {
+			let (vested_provision, _, _) =
+				VestedProvisions::<T>::get(bootstrap_id, (sender.clone(), token_id));
+			let total_provision = Provisions::<T>::get(bootstrap_id, (sender.clone(), token_id))
+				.checked_add(vested_provision)
+				.ok_or(Error::<T>::MathOverflow)?;
+			let limit = if is_first_token { first_token_limit } else { second_token_limit };
+			ensure!(total_provision <= limit, err);
+		}
+
+		let (pre_second_token_valuation, _) = Valuations::<T>::get(bootstrap_id);
 		ensure!(
-			token_id != Self::first_token_id() || pre_second_token_valuation != 0,
+			token_id != first_token_id || pre_second_token_valuation != 0,
 			Error::<T>::FirstProvisionInSecondTokenId
 		);
 
 		ensure!(
 			Valuations::<T>::try_mutate(
+				bootstrap_id,
 				|(second_token_valuation, first_token_valuation)| -> Result<(), ()> {
-					if token_id == Self::second_token_id() {
+					if token_id == second_token_id {
 						*second_token_valuation =
 							second_token_valuation.checked_add(amount).ok_or(())?;
 					}
-					if token_id == Self::first_token_id() {
+					if token_id == first_token_id {
 						*first_token_valuation =
 							first_token_valuation.checked_add(amount).ok_or(())?;
 					}
@@ -972,33 +1623,146 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::MathOverflow
 		);
 
-		if token_id == Self::first_token_id() {
+		if LockedUpAccounts::<T>::contains_key(bootstrap_id, sender) {
+			Self::add_lockup_bonus(bootstrap_id, sender, token_id, amount, vested_amount)?;
+		}
+
+		if token_id == first_token_id {
 			ensure!(
-				Self::is_ratio_kept(ratio_nominator, ratio_denominator),
+				Self::is_ratio_kept(bootstrap_id, ratio_nominator, ratio_denominator),
 				Error::<T>::ValuationRatio
 			);
 		}
 		Ok(().into())
 	}
 
-	fn get_valuation(token_id: &TokenId) -> Balance {
-		if *token_id == Self::first_token_id() {
-			Self::valuations().1
-		} else if *token_id == Self::second_token_id() {
-			Self::valuations().0
+	fn get_valuation(bootstrap_id: BootstrapId, token_id: &TokenId) -> Balance {
+		if *token_id == Self::first_token_id(bootstrap_id) {
+			Self::valuations(bootstrap_id).1
+		} else if *token_id == Self::second_token_id(bootstrap_id) {
+			Self::valuations(bootstrap_id).0
+		} else {
+			0
+		}
+	}
+
+	fn get_lockup_bonus_valuation(bootstrap_id: BootstrapId, token_id: &TokenId) -> Balance {
+		if *token_id == Self::first_token_id(bootstrap_id) {
+			Self::lockup_bonus_valuations(bootstrap_id).1
+		} else if *token_id == Self::second_token_id(bootstrap_id) {
+			Self::lockup_bonus_valuations(bootstrap_id).0
 		} else {
 			0
 		}
 	}
 
+	/// Adds bonus part of `amount` provisioned by participant that locked up its liquidity tokens,
+	/// `vested_amount` is the vested part of `amount`
+	fn add_lockup_bonus(
+		bootstrap_id: BootstrapId,
+		who: &T::AccountId,
+		token_id: TokenId,
+		amount: Balance,
+		vested_amount: Balance,
+	) -> DispatchResult {
+		let (_, (multiplier_numerator, multiplier_denominator)) =
+			LockupBonus::<T>::get(bootstrap_id).ok_or(Error::<T>::LockupNotAvailable)?;
+		let bonus_of = |provision| {
+			multiply_by_rational_with_rounding(
+				provision,
+				multiplier_numerator.saturating_sub(multiplier_denominator),
+				multiplier_denominator,
+				Rounding::Down,
+			)
+			.ok_or(Error::<T>::MathOverflow)
+		};
+		let bonus = bonus_of(amount)?;
+		if bonus == 0 {
+			return Ok(().into())
+		}
+
+		let vested_bonus = bonus_of(vested_amount)?.min(bonus);
+		if vested_bonus > 0 {
+			LockupBonusVestedProvisions::<T>::try_mutate(
+				bootstrap_id,
+				(who.clone(), token_id),
+				|provision_bonus| -> DispatchResult {
+					*provision_bonus = provision_bonus
+						.checked_add(vested_bonus)
+						.ok_or(Error::<T>::MathOverflow)?;
+					Ok(().into())
+				},
+			)?;
+		}
+
+		LockupBonusProvisions::<T>::try_mutate(
+			bootstrap_id,
+			(who.clone(), token_id),
+			|provision_bonus| -> DispatchResult {
+				*provision_bonus =
+					provision_bonus.checked_add(bonus).ok_or(Error::<T>::MathOverflow)?;
+				Ok(().into())
+			},
+		)?;
+		LockupBonusValuations::<T>::try_mutate(
+			bootstrap_id,
+			|(second_token_bonus, first_token_bonus)| -> DispatchResult {
+				if token_id == Self::second_token_id(bootstrap_id) {
+					*second_token_bonus =
+						second_token_bonus.checked_add(bonus).ok_or(Error::<T>::MathOverflow)?;
+				} else {
+					*first_token_bonus =
+						first_token_bonus.checked_add(bonus).ok_or(Error::<T>::MathOverflow)?;
+				}
+				Ok(().into())
+			},
+		)
+	}
+
+	/// Returns (start, end) of the lock of liquidity tokens claimed by participant that locked up
+	/// its provisions, unless the lockup is already over. Lock starts a block before the end of
+	/// the lockup so that all the tokens are unlocked at once when the lockup is over
+	fn lockup_lock(
+		bootstrap_id: BootstrapId,
+		who: &T::AccountId,
+	) -> Option<(BlockNrAsBalance, BlockNrAsBalance)> {
+		if !LockedUpAccounts::<T>::contains_key(bootstrap_id, who) {
+			return None
+		}
+		let (lockup_length, _) = LockupBonus::<T>::get(bootstrap_id)?;
+		let (start, whitelist_length, public_length, _) =
+			BootstrapSchedule::<T>::get(bootstrap_id)?;
+		let lockup_start: BlockNrAsBalance =
+			(start + whitelist_length.into() + public_length.into()).saturated_into();
+		let lockup_end = lockup_start.saturating_add(lockup_length.into());
+		let now: BlockNrAsBalance = <frame_system::Pallet<T>>::block_number().saturated_into();
+		if lockup_end > now {
+			Some((lockup_end.saturating_sub(1), lockup_end))
+		} else {
+			None
+		}
+	}
+
 	fn calculate_rewards(
+		bootstrap_id: BootstrapId,
 		who: &T::AccountId,
 		token_id: &TokenId,
 	) -> Result<(Balance, Balance, (BlockNrAsBalance, BlockNrAsBalance)), Error<T>> {
-		let valuation = Self::get_valuation(token_id);
-		let provision = Self::provisions(who, token_id);
-		let (vested_provision, lock_start, lock_end) = Self::vested_provisions(who, token_id);
-		let (_, liquidity) = Self::minted_liquidity();
+		// provisions of participants that locked up their liquidity tokens are increased by bonus
+		let valuation = Self::get_valuation(bootstrap_id, token_id)
+			.checked_add(Self::get_lockup_bonus_valuation(bootstrap_id, token_id))
+			.ok_or(Error::<T>::MathOverflow)?;
+		let bonus = Self::lockup_bonus_provisions(bootstrap_id, (who.clone(), *token_id));
+		let vested_bonus =
+			Self::lockup_bonus_vested_provisions(bootstrap_id, (who.clone(), *token_id));
+		let provision = Self::provisions(bootstrap_id, (who.clone(), *token_id))
+			.checked_add(bonus.saturating_sub(vested_bonus))
+			.ok_or(Error::<T>::MathOverflow)?;
+		let (vested_provision, lock_start, lock_end) =
+			Self::vested_provisions(bootstrap_id, (who.clone(), *token_id));
+		let vested_provision =
+			vested_provision.checked_add(vested_bonus).ok_or(Error::<T>::MathOverflow)?;
+		let (_, liquidity) = Self::minted_liquidity(bootstrap_id);
 		let rewards =
 			multiply_by_rational_with_rounding(liquidity / 2, provision, valuation, Rounding::Down)
 				.ok_or(Error::<T>::MathOverflow)?;
@@ -1012,29 +1776,84 @@ impl<T: Config> Pallet<T> {
 		Ok((rewards, vested_rewards, (lock_start, lock_end)))
 	}
 
-	fn do_claim_liquidity_tokens(who: &T::AccountId, activate_rewards: bool) -> DispatchResult {
-		ensure!(Self::phase() == BootstrapPhase::Finished, Error::<T>::NotFinishedYet);
+	fn do_withdraw_provisions(bootstrap_id: BootstrapId, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			Self::phase(bootstrap_id) == BootstrapPhase::Failed,
+			Error::<T>::BootstrapNotFailed
+		);
+		ensure!(
+			ProvisionAccounts::<T>::get(bootstrap_id, who).is_some(),
+			Error::<T>::NothingToWithdraw
+		);
+
+		for token_id in [Self::first_token_id(bootstrap_id), Self::second_token_id(bootstrap_id)] {
+			let provision = Provisions::<T>::take(bootstrap_id, (who.clone(), token_id));
+			let (vested_provision, lock_start, lock_end) =
+				VestedProvisions::<T>::take(bootstrap_id, (who.clone(), token_id));
+			let total_provision =
+				provision.checked_add(vested_provision).ok_or(Error::<T>::MathOverflow)?;
+			if total_provision == 0 {
+				continue
+			}
+
+			T::Currency::transfer(
+				token_id.into(),
+				&Self::vault_address(),
+				who,
+				total_provision.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			if vested_provision > 0 {
+				<<T as Config>::VestingProvider>::lock_tokens(
+					&who,
+					token_id.into(),
+					vested_provision.into(),
+					Some(lock_start.saturated_into()),
+					lock_end.into(),
+				)?;
+			}
+
+			Self::deposit_event(Event::ProvisionWithdrawn(bootstrap_id, token_id, total_provision));
+		}
+
+		ProvisionAccounts::<T>::remove(bootstrap_id, who);
+		Ok(().into())
+	}
+
+	fn do_claim_liquidity_tokens(
+		bootstrap_id: BootstrapId,
+		who: &T::AccountId,
+		activate_rewards: bool,
+	) -> DispatchResult {
+		ensure!(Self::phase(bootstrap_id) == BootstrapPhase::Finished, Error::<T>::NotFinishedYet);
 
-		let (liq_token_id, _) = Self::minted_liquidity();
+		let (liq_token_id, _) = Self::minted_liquidity(bootstrap_id);
+		let first_token_id = Self::first_token_id(bootstrap_id);
+		let second_token_id = Self::second_token_id(bootstrap_id);
 
 		// for backward compatibility
 		if Self::archived().len() > 0 {
-			ensure!(ProvisionAccounts::<T>::get(who).is_some(), Error::<T>::NothingToClaim);
+			ensure!(
+				ProvisionAccounts::<T>::get(bootstrap_id, who).is_some(),
+				Error::<T>::NothingToClaim
+			);
 		} else {
 			ensure!(
-				!ClaimedRewards::<T>::contains_key(&who, &Self::first_token_id()),
+				!ClaimedRewards::<T>::contains_key(bootstrap_id, (who.clone(), first_token_id)),
 				Error::<T>::NothingToClaim
 			);
 			ensure!(
-				!ClaimedRewards::<T>::contains_key(&who, &Self::second_token_id()),
+				!ClaimedRewards::<T>::contains_key(bootstrap_id, (who.clone(), second_token_id)),
 				Error::<T>::NothingToClaim
 			);
 		}
 
+		let lockup = Self::lockup_lock(bootstrap_id, who);
 		let (first_token_rewards, first_token_rewards_vested, first_token_lock) =
-			Self::calculate_rewards(&who, &Self::first_token_id())?;
+			Self::calculate_rewards(bootstrap_id, &who, &first_token_id)?;
 		let (second_token_rewards, second_token_rewards_vested, second_token_lock) =
-			Self::calculate_rewards(&who, &Self::second_token_id())?;
+			Self::calculate_rewards(bootstrap_id, &who, &second_token_id)?;
 
 		let total_rewards_claimed = second_token_rewards
 			.checked_add(second_token_rewards_vested)
@@ -1045,11 +1864,13 @@ impl<T: Config> Pallet<T> {
 			.ok_or(Error::<T>::MathOverflow)?;
 
 		Self::claim_liquidity_tokens_from_single_currency(
+			bootstrap_id,
 			&who,
-			&Self::second_token_id(),
+			&second_token_id,
 			second_token_rewards,
 			second_token_rewards_vested,
 			second_token_lock,
+			lockup,
 		)?;
 		log!(
 			info,
@@ -1060,11 +1881,13 @@ impl<T: Config> Pallet<T> {
 		);
 
 		Self::claim_liquidity_tokens_from_single_currency(
+			bootstrap_id,
 			&who,
-			&Self::first_token_id(),
+			&first_token_id,
 			first_token_rewards,
 			first_token_rewards_vested,
 			first_token_lock,
+			lockup,
 		)?;
 		log!(
 			info,
@@ -1074,9 +1897,13 @@ impl<T: Config> Pallet<T> {
 			first_token_rewards + first_token_rewards_vested
 		);
 
-		ProvisionAccounts::<T>::remove(who);
+		ProvisionAccounts::<T>::remove(bootstrap_id, who);
 
-		if activate_rewards && <T as Config>::RewardsApi::can_activate(liq_token_id.into()) {
+		// liquidity tokens of locked up provisions are locked so they cannot be activated
+		if activate_rewards &&
+			lockup.is_none() &&
+			<T as Config>::RewardsApi::can_activate(liq_token_id.into())
+		{
 			let non_vested_rewards = second_token_rewards
 				.checked_add(first_token_rewards)
 				.ok_or(Error::<T>::MathOverflow)?;
@@ -1110,18 +1937,17 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
-	fn first_token_id() -> TokenId {
-		ActivePair::<T>::get().map(|(first, _)| first).unwrap_or(4_u32)
+	fn first_token_id(bootstrap_id: BootstrapId) -> TokenId {
+		ActivePair::<T>::get(bootstrap_id).map(|(first, _)| first).unwrap_or_default()
 	}
 
-	fn second_token_id() -> TokenId {
-		ActivePair::<T>::get().map(|(_, second)| second).unwrap_or(0_u32)
+	fn second_token_id(bootstrap_id: BootstrapId) -> TokenId {
+		ActivePair::<T>::get(bootstrap_id).map(|(_, second)| second).unwrap_or_default()
 	}
 }
 
 impl<T: Config> Contains<(TokenId, TokenId)> for Pallet<T> {
 	fn contains(pair: &(TokenId, TokenId)) -> bool {
-		pair == &(Self::first_token_id(), Self::second_token_id()) ||
-			pair == &(Self::second_token_id(), Self::first_token_id())
+		Self::bootstrap_id_of_pair(pair.0, pair.1).is_some()
 	}
 }
@@ -17,124 +17,182 @@
 
 use super::*;
 
-pub mod v1 {
+pub mod v3 {
+	//! Keys bootstrap storage by [`BootstrapId`] so multiple bootstraps can be run at the same
+	//! time. State of the bootstrap stored before (if any) becomes bootstrap with id `0`.
+	//! Bootstrap has to be finalized before the upgrade, otherwise storage is left untouched.
+
 	use super::*;
 	use crate::log;
-	use frame_support::traits::OnRuntimeUpgrade;
+	use frame_support::{storage_alias, traits::GetStorageVersion};
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+	pub const MIGRATED_BOOTSTRAP_ID: BootstrapId = 0;
+
+	#[storage_alias]
+	pub type Provisions<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Twox64Concat,
+		TokenId,
+		Balance,
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type VestedProvisions<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Twox64Concat,
+		TokenId,
+		(Balance, BlockNrAsBalance, BlockNrAsBalance),
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type WhitelistedAccount<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type ClaimedRewards<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Twox64Concat,
+		TokenId,
+		Balance,
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type ProvisionAccounts<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type Phase<T: Config> = StorageValue<Pallet<T>, BootstrapPhase, OptionQuery>;
+
+	#[storage_alias]
+	pub type Valuations<T: Config> = StorageValue<Pallet<T>, (Balance, Balance), OptionQuery>;
+
+	#[storage_alias]
+	pub type BootstrapSchedule<T: Config> = StorageValue<
+		Pallet<T>,
+		(<T as frame_system::Config>::BlockNumber, u32, u32, (u128, u128)),
+		OptionQuery,
+	>;
+
+	#[storage_alias]
+	pub type MintedLiquidity<T: Config> = StorageValue<Pallet<T>, (TokenId, Balance), OptionQuery>;
+
+	#[storage_alias]
+	pub type ActivePair<T: Config> = StorageValue<Pallet<T>, (TokenId, TokenId), OptionQuery>;
+
+	#[storage_alias]
+	pub type PromoteBootstrapPool<T: Config> = StorageValue<Pallet<T>, bool, OptionQuery>;
+
+	// Per account maps are left empty by `finalize`, bootstrap has to be finalized before
+	// the upgrade so that only storage values have to be moved
+	fn has_account_entries<T: Config>() -> bool {
+		Provisions::<T>::iter_keys().next().is_some() ||
+			VestedProvisions::<T>::iter_keys().next().is_some() ||
+			WhitelistedAccount::<T>::iter_keys().next().is_some() ||
+			ClaimedRewards::<T>::iter_keys().next().is_some() ||
+			ProvisionAccounts::<T>::iter_keys().next().is_some()
+	}
 
-	/// Trivial migration which makes the roles of each pool optional.
-	///
-	/// Note: The depositor is not optional since he can never change.
-	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
-	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
-		fn on_runtime_upgrade() -> Weight {
-			let current = Pallet::<T>::current_storage_version();
-			let onchain = Pallet::<T>::on_chain_storage_version();
+	pub fn migrate<T: Config>() -> Weight {
+		let db_weight = T::DbWeight::get();
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			return db_weight.reads(1)
+		}
 
+		if has_account_entries::<T>() {
 			log!(
-				info,
-				"Running V1 migration with current storage version {:?} / onchain {:?}",
-				current,
-				onchain
+				error,
+				"bootstrap is not finalized, storage is left at version {:?}",
+				Pallet::<T>::on_chain_storage_version()
 			);
-
-			if current == 2 && onchain == 0 {
-				// this is safe to execute on any runtime that has a bounded number of pools.
-
-				if Phase::<T>::get() == BootstrapPhase::Finished && ActivePair::<T>::get().is_none()
-				{
-					ActivePair::<T>::put((4_u32, 0_u32));
-					log!(info, "Filled ActivePair with default value");
-					T::DbWeight::get().reads_writes(3, 1)
-				} else {
-					log!(info, "No extra actions needed");
-					T::DbWeight::get().reads_writes(3, 0)
-				}
-			} else {
-				log!(info, "Migration did not executed. This probably should be removed");
-				T::DbWeight::get().reads(2)
-			}
+			return db_weight.reads(6)
 		}
 
-		#[cfg(feature = "try-runtime")]
-		fn pre_upgrade() -> Result<(), &'static str> {
-			log!(info, "Bootstrap::pre_upgrade");
-			assert_eq!(Pallet::<T>::on_chain_storage_version(), 1);
-			Ok(())
+		let mut entries = 0_u64;
+		if let Some(phase) = Phase::<T>::take() {
+			crate::Phase::<T>::insert(MIGRATED_BOOTSTRAP_ID, phase);
+			entries += 1;
 		}
-
-		#[cfg(feature = "try-runtime")]
-		fn post_upgrade() -> Result<(), &'static str> {
-			log!(info, "Bootstrap::post_upgrade");
-			assert_eq!(Pallet::<T>::on_chain_storage_version(), 1);
-			Ok(())
+		if let Some(valuations) = Valuations::<T>::take() {
+			crate::Valuations::<T>::insert(MIGRATED_BOOTSTRAP_ID, valuations);
+			entries += 1;
+		}
+		if let Some(minted_liquidity) = MintedLiquidity::<T>::take() {
+			crate::MintedLiquidity::<T>::insert(MIGRATED_BOOTSTRAP_ID, minted_liquidity);
+			entries += 1;
+		}
+		if let Some(promote) = PromoteBootstrapPool::<T>::take() {
+			crate::PromoteBootstrapPool::<T>::insert(MIGRATED_BOOTSTRAP_ID, promote);
+			entries += 1;
 		}
-	}
-}
 
-pub mod v2 {
-	use super::*;
-	use crate::log;
-	use frame_support::traits::OnRuntimeUpgrade;
-	use sp_runtime::traits::UniqueSaturatedInto;
+		let schedule = BootstrapSchedule::<T>::take();
+		let pair = ActivePair::<T>::take();
+		if schedule.is_some() || pair.is_some() {
+			if let Some(schedule) = schedule {
+				crate::BootstrapSchedule::<T>::insert(MIGRATED_BOOTSTRAP_ID, schedule);
+				entries += 1;
+			}
+			if let Some(pair) = pair {
+				crate::ActivePair::<T>::insert(MIGRATED_BOOTSTRAP_ID, pair);
+				entries += 1;
+			}
+			NextBootstrapId::<T>::put(MIGRATED_BOOTSTRAP_ID + 1);
+			log!(info, "bootstrap migrated as bootstrap {}", MIGRATED_BOOTSTRAP_ID);
+		}
 
-	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
-	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
-		fn on_runtime_upgrade() -> Weight {
-			let current = Pallet::<T>::current_storage_version();
-			let onchain = Pallet::<T>::on_chain_storage_version();
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log!(info, "bootstrap storage migrated to storage version 3, {} entries", entries);
 
-			log!(
-				info,
-				"Running V2 migration with current storage version {:?} / onchain {:?}",
-				current,
-				onchain
-			);
+		db_weight
+			.reads_writes(entries.saturating_add(13), entries.saturating_mul(2).saturating_add(2))
+	}
 
-			if current == 2 && onchain < current {
-				if let Some((start, whitelist_length, public_length, _)) =
-					BootstrapSchedule::<T>::get()
-				{
-					let start_block: u32 = start.unique_saturated_into();
-					log!(info, "migrating vested provisions storage");
-					VestedProvisions::<T>::translate::<(Balance, BlockNrAsBalance), _>(
-						|_: T::AccountId,
-						 _: TokenId,
-						 (amount, lock_end): (Balance, BlockNrAsBalance)| {
-							Some((
-								amount,
-								(start_block + whitelist_length + public_length).into(),
-								lock_end,
-							))
-						},
-					);
-
-					T::DbWeight::get().reads_writes(
-						1,
-						(VestedProvisions::<T>::iter().count() as usize).try_into().unwrap(),
-					)
-				} else {
-					log!(info, "No ongoing bootstrap, migration not needed");
-					T::DbWeight::get().reads(1)
-				}
-			} else {
-				log!(info, "Migration did not executed. This probably should be removed");
-				T::DbWeight::get().reads(2)
-			}
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 3 {
+			return Ok(())
 		}
 
-		#[cfg(feature = "try-runtime")]
-		fn pre_upgrade() -> Result<(), &'static str> {
-			log!(info, "Bootstrap::pre_upgrade");
-			assert_eq!(Pallet::<T>::on_chain_storage_version(), 2);
-			Ok(())
-		}
+		ensure!(!has_account_entries::<T>(), "bootstrap has to be finalized before the upgrade");
+		Pallet::<T>::set_temp_storage(BootstrapSchedule::<T>::get(), "bootstrap_schedule_v2");
+		Ok(())
+	}
 
-		#[cfg(feature = "try-runtime")]
-		fn post_upgrade() -> Result<(), &'static str> {
-			log!(info, "Bootstrap::post_upgrade");
-			assert_eq!(Pallet::<T>::on_chain_storage_version(), 2);
-			Ok(())
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+		ensure!(Pallet::<T>::on_chain_storage_version() == 3, "storage version is not 3");
+
+		if let Some(schedule) = Pallet::<T>::get_temp_storage::<
+			Option<(T::BlockNumber, u32, u32, (u128, u128))>,
+		>("bootstrap_schedule_v2")
+		{
+			ensure!(
+				crate::BootstrapSchedule::<T>::get(MIGRATED_BOOTSTRAP_ID) == schedule,
+				"bootstrap schedule was not carried over"
+			);
 		}
+		Ok(())
 	}
 }
@@ -33,6 +33,7 @@ use orml_traits::parameter_type_with_key;
 use pallet_xyk::AssetMetadataMutationTrait;
 use sp_runtime::{Perbill, Percent};
 use sp_std::convert::TryFrom;
+use std::cell::RefCell;
 
 pub(crate) type AccountId = u128;
 
@@ -124,14 +125,6 @@ impl pallet_vesting_mangata::Config for Test {
 	const MAX_VESTING_SCHEDULES: u32 = 28;
 }
 
-pub struct RewardsMigrateAccountProvider<T: frame_system::Config>(PhantomData<T>);
-impl<T: frame_system::Config> Get<T::AccountId> for RewardsMigrateAccountProvider<T> {
-	fn get() -> T::AccountId {
-		T::AccountId::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
-			.expect("Infinite input; no dead input space; qed")
-	}
-}
-
 impl pallet_xyk::XykBenchmarkingConfig for Test {}
 
 pub struct AssetMetadataMutation;
@@ -164,7 +157,7 @@ impl pallet_xyk::Config for Test {
 	type DisabledTokens = Nothing;
 	type VestingProvider = Vesting;
 	type AssetMetadataMutation = AssetMetadataMutation;
-	type RewardsMigrateAccount = RewardsMigrateAccountProvider<Test>;
+	type Call = Call;
 }
 
 impl BootstrapBenchmarkingConfig for Test {}
@@ -279,6 +272,23 @@ mockall::mock! {
 
 parameter_types! {
 	pub const BootstrapUpdateBuffer: <Test as frame_system::Config>::BlockNumber = 10;
+	pub const MaxActiveBootstraps: u32 = 3;
+}
+
+thread_local! {
+	static INITIALIZE_WEIGHT_BUDGET: RefCell<Weight> = RefCell::new(Weight::from_ref_time(u64::MAX));
+}
+
+pub struct InitializeWeightBudget;
+impl InitializeWeightBudget {
+	pub fn set(budget: Weight) {
+		INITIALIZE_WEIGHT_BUDGET.with(|v| *v.borrow_mut() = budget);
+	}
+}
+impl Get<Weight> for InitializeWeightBudget {
+	fn get() -> Weight {
+		INITIALIZE_WEIGHT_BUDGET.with(|v| *v.borrow())
+	}
 }
 
 #[cfg(not(feature = "runtime-benchmarks"))]
@@ -288,6 +298,8 @@ impl pallet_bootstrap::Config for Test {
 	type PoolCreateApi = MockPoolCreateApi;
 	type BootstrapUpdateBuffer = BootstrapUpdateBuffer;
 	type TreasuryPalletId = TreasuryPalletId;
+	type MaxActiveBootstraps = MaxActiveBootstraps;
+	type InitializeWeightBudget = InitializeWeightBudget;
 	type Currency = orml_tokens::MultiTokenCurrencyAdapter<Test>;
 	type VestingProvider = Vesting;
 	type RewardsApi = MockRewardsApi;
@@ -301,6 +313,8 @@ impl pallet_bootstrap::Config for Test {
 	type PoolCreateApi = Xyk;
 	type BootstrapUpdateBuffer = BootstrapUpdateBuffer;
 	type TreasuryPalletId = TreasuryPalletId;
+	type MaxActiveBootstraps = MaxActiveBootstraps;
+	type InitializeWeightBudget = InitializeWeightBudget;
 	type Currency = orml_tokens::MultiTokenCurrencyAdapter<Test>;
 	type VestingProvider = Vesting;
 	type RewardsApi = Xyk;
@@ -384,6 +398,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		.build_storage::<Test>()
 		.expect("Frame system builds valid default genesis config");
 
+	InitializeWeightBudget::set(Weight::from_ref_time(u64::MAX));
+
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
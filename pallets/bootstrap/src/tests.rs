@@ -13,6 +13,8 @@ const PROVISION_USER2_ID: u128 = 201;
 const ANOTHER_USER_ID: u128 = 100;
 const INITIAL_AMOUNT: u128 = 1_000_000;
 const DUMMY_ID: u32 = 2;
+const BOOTSTRAP_ID: BootstrapId = 0;
+const OTHER_BOOTSTRAP_ID: BootstrapId = 1;
 const LIQ_TOKEN_ID: TokenId = 10_u32;
 const LIQ_TOKEN_AMOUNT: Balance = 1_000_000_u128;
 const DEFAULT_RATIO: (u128, u128) = (1_u128, 10_000_u128);
@@ -46,10 +48,11 @@ fn jump_to_whitelist_phase() {
 		10,
 		Some(DEFAULT_RATIO),
 		false,
+		None,
 	)
 	.unwrap();
 	Bootstrap::on_initialize(15_u32.into());
-	assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+	assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 }
 
 fn jump_to_public_phase() {
@@ -65,10 +68,11 @@ fn jump_to_public_phase() {
 		10,
 		Some(DEFAULT_RATIO),
 		false,
+		None,
 	)
 	.unwrap();
 	Bootstrap::on_initialize(25_u32.into());
-	assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+	assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 }
 
 #[test]
@@ -76,8 +80,9 @@ fn jump_to_public_phase() {
 fn do_not_allow_for_provision_in_unsupported_currency() {
 	new_test_ext().execute_with(|| {
 		set_up();
+		jump_to_public_phase();
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), DUMMY_ID, 1000),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, DUMMY_ID, 1000),
 			Error::<Test>::UnsupportedTokenId
 		)
 	});
@@ -91,7 +96,7 @@ fn test_first_provision_with_ksm_fails() {
 		jump_to_public_phase();
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
 			Error::<Test>::FirstProvisionInSecondTokenId
 		);
 	});
@@ -103,10 +108,13 @@ fn test_event_is_published_after_successful_provision() {
 	new_test_ext().execute_with(|| {
 		set_up();
 		jump_to_public_phase();
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1).unwrap();
 
-		let event =
-			crate::mock::Event::Bootstrap(crate::Event::<Test>::Provisioned(MGAId::get(), 1));
+		let event = crate::mock::Event::Bootstrap(crate::Event::<Test>::Provisioned(
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			1,
+		));
 
 		assert!(System::events().iter().any(|record| record.event == event));
 	});
@@ -120,25 +128,25 @@ fn test_dont_allow_for_ksm_donation_before_minimal_valuation_fro_mga_is_provided
 
 		jump_to_public_phase();
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1).unwrap();
-		assert_eq!(1, Bootstrap::provisions(USER_ID, MGAId::get()));
-		assert_eq!((1, 0), Bootstrap::valuations());
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1).unwrap();
+		assert_eq!(1, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+		assert_eq!((1, 0), Bootstrap::valuations(BOOTSTRAP_ID));
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
 			Error::<Test>::ValuationRatio
 		);
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 9999).unwrap();
-		assert_eq!(10000, Bootstrap::provisions(USER_ID, MGAId::get()));
-		assert_eq!((10000, 0), Bootstrap::valuations());
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 9999).unwrap();
+		assert_eq!(10000, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+		assert_eq!((10000, 0), Bootstrap::valuations(BOOTSTRAP_ID));
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1).unwrap();
-		assert_eq!(1, Bootstrap::provisions(USER_ID, KSMId::get()));
-		assert_eq!((10000, 1), Bootstrap::valuations());
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1).unwrap();
+		assert_eq!(1, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, KSMId::get())));
+		assert_eq!((10000, 1), Bootstrap::valuations(BOOTSTRAP_ID));
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
 			Error::<Test>::ValuationRatio
 		);
 	});
@@ -151,17 +159,17 @@ fn test_donation_in_supported_tokens() {
 		set_up();
 		jump_to_public_phase();
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 10000).unwrap();
-		assert_eq!(10000, Bootstrap::provisions(USER_ID, MGAId::get()));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 10000).unwrap();
+		assert_eq!(10000, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1).unwrap();
-		assert_eq!(1, Bootstrap::provisions(USER_ID, KSMId::get()));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1).unwrap();
+		assert_eq!(1, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, KSMId::get())));
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 20000).unwrap();
-		assert_eq!(30000, Bootstrap::provisions(USER_ID, MGAId::get()));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 20000).unwrap();
+		assert_eq!(30000, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 2).unwrap();
-		assert_eq!(3, Bootstrap::provisions(USER_ID, KSMId::get()));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 2).unwrap();
+		assert_eq!(3, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, KSMId::get())));
 	});
 }
 
@@ -174,12 +182,22 @@ fn test_donation_with_more_tokens_than_available() {
 		jump_to_public_phase();
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), INITIAL_AMOUNT * 2),
+			Bootstrap::provision(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				KSMId::get(),
+				INITIAL_AMOUNT * 2
+			),
 			Error::<Test>::NotEnoughAssets
 		);
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), INITIAL_AMOUNT * 2),
+			Bootstrap::provision(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				MGAId::get(),
+				INITIAL_AMOUNT * 2
+			),
 			Error::<Test>::NotEnoughAssets
 		);
 	});
@@ -203,11 +221,17 @@ fn test_prevent_provisions_in_before_start_phase() {
 			20,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), INITIAL_AMOUNT * 2),
+			Bootstrap::provision(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				KSMId::get(),
+				INITIAL_AMOUNT * 2
+			),
 			Error::<Test>::Unauthorized
 		);
 	});
@@ -232,6 +256,7 @@ fn test_fail_scheudle_bootstrap_with_same_token() {
 				20,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::SameToken
 		);
@@ -257,6 +282,7 @@ fn test_prevent_schedule_bootstrap_with_pair_that_does_not_exists() {
 				20,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::TokenIdDoesNotExists
 		);
@@ -281,13 +307,19 @@ fn test_prevent_provisions_in_finished_phase() {
 			20,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
-		Phase::<Test>::put(BootstrapPhase::Finished);
+		Phase::<Test>::insert(BOOTSTRAP_ID, BootstrapPhase::Finished);
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), INITIAL_AMOUNT * 2),
+			Bootstrap::provision(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				KSMId::get(),
+				INITIAL_AMOUNT * 2
+			),
 			Error::<Test>::Unauthorized
 		);
 	});
@@ -301,9 +333,9 @@ fn test_prevent_non_whitelited_account_to_provision_in_whitelisted_phase() {
 
 		jump_to_whitelist_phase();
 
-		assert!(!Bootstrap::is_whitelisted(&USER_ID));
+		assert!(!Bootstrap::is_whitelisted(BOOTSTRAP_ID, &USER_ID));
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1000),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1000),
 			Error::<Test>::Unauthorized
 		);
 	});
@@ -317,8 +349,8 @@ fn test_allow_non_whitelited_account_to_provision_in_whitelisted_phase_with_mga(
 
 		jump_to_whitelist_phase();
 
-		assert!(!Bootstrap::is_whitelisted(&USER_ID));
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1000).unwrap();
+		assert!(!Bootstrap::is_whitelisted(BOOTSTRAP_ID, &USER_ID));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1000).unwrap();
 	});
 }
 
@@ -327,10 +359,13 @@ fn test_allow_non_whitelited_account_to_provision_in_whitelisted_phase_with_mga(
 fn test_whitelist_account_deposit_event() {
 	new_test_ext().execute_with(|| {
 		set_up();
-		Bootstrap::whitelist_accounts(Origin::root(), vec![USER_ID]).unwrap();
+		jump_to_whitelist_phase();
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![USER_ID], None).unwrap();
 
 		assert!(System::events().iter().any(|record| record.event ==
-			crate::mock::Event::Bootstrap(crate::Event::<Test>::AccountsWhitelisted)));
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::AccountsWhitelisted(
+				BOOTSTRAP_ID
+			))));
 	});
 }
 
@@ -342,14 +377,16 @@ fn test_incremental_whitliested_donation() {
 
 		jump_to_whitelist_phase();
 
-		Bootstrap::whitelist_accounts(Origin::root(), vec![USER_ID]).unwrap();
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1000).unwrap();
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![USER_ID], None).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1000).unwrap();
 
 		Bootstrap::transfer(MGAId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 10_000).unwrap();
-		Bootstrap::whitelist_accounts(Origin::root(), vec![ANOTHER_USER_ID]).unwrap();
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![ANOTHER_USER_ID], None)
+			.unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1000).unwrap();
-		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), MGAId::get(), 1000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1000).unwrap();
+		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 1000)
+			.unwrap();
 		assert_ne!(USER_ID, ANOTHER_USER_ID);
 	});
 }
@@ -377,18 +414,19 @@ fn test_bootstrap_promotion_can_be_updated() {
 			9,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		));
 
 		System::set_block_number(109);
 		Bootstrap::on_initialize(109_u32.into());
 
-		assert_ok!(Bootstrap::update_promote_bootstrap_pool(Origin::root(), true));
+		assert_ok!(Bootstrap::update_promote_bootstrap_pool(Origin::root(), BOOTSTRAP_ID, true));
 
 		System::set_block_number(111);
 		Bootstrap::on_initialize(111_u32.into());
 
 		assert_err!(
-			Bootstrap::update_promote_bootstrap_pool(Origin::root(), false),
+			Bootstrap::update_promote_bootstrap_pool(Origin::root(), BOOTSTRAP_ID, false),
 			Error::<Test>::BootstrapFinished
 		);
 	});
@@ -411,6 +449,7 @@ fn test_scheduled_bootstrap_can_be_updated() {
 			9,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		));
 
 		System::set_block_number(80);
@@ -425,6 +464,7 @@ fn test_scheduled_bootstrap_can_be_updated() {
 			9,
 			Some((100, 10)),
 			false,
+			None,
 		));
 
 		System::set_block_number(95);
@@ -440,6 +480,7 @@ fn test_scheduled_bootstrap_can_be_updated() {
 				9,
 				Some((1000, 1)),
 				false,
+				None,
 			),
 			Error::<Test>::TooLateToUpdateBootstrap
 		);
@@ -463,20 +504,31 @@ fn test_scheduled_bootstrap_can_be_cancelled() {
 			9,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		));
 
 		System::set_block_number(95);
 		Bootstrap::on_initialize(95_u32.into());
 
 		assert_err!(
-			Bootstrap::cancel_bootstrap(Origin::root()),
+			Bootstrap::cancel_bootstrap(Origin::root(), BOOTSTRAP_ID),
 			Error::<Test>::TooLateToUpdateBootstrap
 		);
 
 		System::set_block_number(80);
 		Bootstrap::on_initialize(80_u32.into());
+		Bootstrap::whitelist_accounts(
+			Origin::root(),
+			BOOTSTRAP_ID,
+			vec![USER_ID, ANOTHER_USER_ID],
+			Some((100, 10)),
+		)
+		.unwrap();
 
-		assert_ok!(Bootstrap::cancel_bootstrap(Origin::root()));
+		assert_ok!(Bootstrap::cancel_bootstrap(Origin::root(), BOOTSTRAP_ID));
+		assert!(!Bootstrap::is_whitelisted(BOOTSTRAP_ID, &USER_ID));
+		assert!(!Bootstrap::is_whitelisted(BOOTSTRAP_ID, &ANOTHER_USER_ID));
+		assert_eq!(WhitelistAllowances::<Test>::iter_prefix(BOOTSTRAP_ID).count(), 0);
 	});
 }
 
@@ -495,6 +547,7 @@ fn test_non_root_user_can_not_schedule_bootstrap() {
 				1,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			BadOrigin
 		);
@@ -506,7 +559,10 @@ fn test_non_root_user_can_not_schedule_bootstrap() {
 fn test_non_root_user_can_not_whitelist_accounts() {
 	new_test_ext().execute_with(|| {
 		set_up();
-		assert_err!(Bootstrap::whitelist_accounts(Origin::signed(USER_ID), vec![],), BadOrigin);
+		assert_err!(
+			Bootstrap::whitelist_accounts(Origin::signed(USER_ID), BOOTSTRAP_ID, vec![], None),
+			BadOrigin
+		);
 	});
 }
 
@@ -515,7 +571,8 @@ fn test_non_root_user_can_not_whitelist_accounts() {
 fn test_only_root_can_whitelist_accounts() {
 	new_test_ext().execute_with(|| {
 		set_up();
-		Bootstrap::whitelist_accounts(Origin::root(), vec![]).unwrap();
+		jump_to_whitelist_phase();
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![], None).unwrap();
 	});
 }
 
@@ -535,6 +592,7 @@ fn test_ido_start_cannot_happen_in_the_past() {
 				1,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::BootstrapStartInThePast
 		);
@@ -557,6 +615,7 @@ fn test_ido_start_can_not_be_initialize_with_0_ratio() {
 				1,
 				Some((1, 0)),
 				false,
+				None,
 			),
 			Error::<Test>::WrongRatio
 		);
@@ -570,6 +629,7 @@ fn test_ido_start_can_not_be_initialize_with_0_ratio() {
 				1,
 				Some((0, 1)),
 				false,
+				None,
 			),
 			Error::<Test>::WrongRatio
 		);
@@ -594,6 +654,7 @@ fn test_can_schedule_bootstrap_with_whitelist_phase_length_equal_zero() {
 			1,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		));
 	});
 }
@@ -613,6 +674,7 @@ fn test_cannot_schedule_bootstrap_with_public_phase_length_equal_zero() {
 				0,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::PhaseLengthCannotBeZero
 		);
@@ -637,6 +699,7 @@ fn test_bootstrap_can_be_modified_only_before_its_started() {
 			20,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
@@ -649,6 +712,7 @@ fn test_bootstrap_can_be_modified_only_before_its_started() {
 			20,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
@@ -664,11 +728,15 @@ fn test_bootstrap_can_be_modified_only_before_its_started() {
 				20,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::AlreadyStarted
 		);
 
-		assert_eq!(Some((100_u32.into(), 10_u32, 20_u32, DEFAULT_RATIO)), Bootstrap::config());
+		assert_eq!(
+			Some((100_u32.into(), 10_u32, 20_u32, DEFAULT_RATIO)),
+			Bootstrap::config(BOOTSTRAP_ID)
+		);
 	});
 }
 
@@ -697,34 +765,35 @@ fn test_bootstrap_state_transitions() {
 			(BOOTSTRAP_FINISH - BOOTSTRAP_PUBLIC_START).try_into().unwrap(),
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		for i in 1..BOOTSTRAP_WHITELIST_START {
 			Bootstrap::on_initialize(i);
-			assert_eq!(Bootstrap::phase(), BootstrapPhase::BeforeStart);
+			assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
 		}
 
 		Bootstrap::on_initialize(BOOTSTRAP_WHITELIST_START);
-		assert_eq!(Bootstrap::phase(), BootstrapPhase::Whitelist);
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Whitelist);
 
 		for i in BOOTSTRAP_WHITELIST_START..BOOTSTRAP_PUBLIC_START {
 			Bootstrap::on_initialize(i);
-			assert_eq!(Bootstrap::phase(), BootstrapPhase::Whitelist);
+			assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Whitelist);
 		}
 
 		Bootstrap::on_initialize(BOOTSTRAP_PUBLIC_START);
-		assert_eq!(Bootstrap::phase(), BootstrapPhase::Public);
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Public);
 
-		println!("{:?}", Bootstrap::phase());
+		println!("{:?}", Bootstrap::phase(BOOTSTRAP_ID));
 		for i in BOOTSTRAP_PUBLIC_START..BOOTSTRAP_FINISH {
 			Bootstrap::on_initialize(i);
-			println!("{:?}", Bootstrap::phase());
-			assert_eq!(Bootstrap::phase(), BootstrapPhase::Public);
+			println!("{:?}", Bootstrap::phase(BOOTSTRAP_ID));
+			assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Public);
 		}
 
 		Bootstrap::on_initialize(BOOTSTRAP_FINISH);
-		assert_eq!(Bootstrap::phase(), BootstrapPhase::Finished);
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Finished);
 	});
 }
 
@@ -752,12 +821,13 @@ fn test_bootstrap_state_transitions_when_on_initialized_is_not_called() {
 			(BOOTSTRAP_FINISH - BOOTSTRAP_PUBLIC_START).try_into().unwrap(),
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
-		assert_eq!(Bootstrap::phase(), BootstrapPhase::BeforeStart);
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
 		Bootstrap::on_initialize(200);
-		assert_eq!(Bootstrap::phase(), BootstrapPhase::Finished);
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Finished);
 	});
 }
 
@@ -777,6 +847,7 @@ fn test_bootstrap_schedule_overflow() {
 				1_u32,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::MathOverflow
 		);
@@ -791,6 +862,7 @@ fn test_bootstrap_schedule_overflow() {
 				u32::MAX,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::MathOverflow
 		);
@@ -805,6 +877,7 @@ fn test_bootstrap_schedule_overflow() {
 				u32::MAX,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::MathOverflow
 		);
@@ -828,6 +901,7 @@ fn test_do_not_allow_for_creating_starting_bootstrap_for_existing_pool() {
 				10,
 				Some(DEFAULT_RATIO),
 				false,
+				None,
 			),
 			Error::<Test>::PoolAlreadyExists
 		);
@@ -869,12 +943,15 @@ fn test_crate_pool_is_called_with_proper_arguments_after_bootstrap_finish() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(110_u32.into());
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), MGA_PROVISON).unwrap();
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), KSM_PROVISON).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), MGA_PROVISON)
+			.unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), KSM_PROVISON)
+			.unwrap();
 		Bootstrap::on_initialize(120_u32.into());
 	});
 }
@@ -897,17 +974,18 @@ fn test_cannot_claim_liquidity_tokens_when_bootstrap_is_not_finished() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(110_u32.into());
-		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		assert_err!(
-			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)),
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID),
 			Error::<Test>::NotFinishedYet
 		);
 	});
@@ -948,22 +1026,25 @@ fn test_rewards_are_distributed_properly_with_single_user() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(110_u32.into());
-		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), MGA_PROVISON).unwrap();
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), KSM_PROVISON).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), MGA_PROVISON)
+			.unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), KSM_PROVISON)
+			.unwrap();
 
 		Bootstrap::on_initialize(120_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
 		let liquidity_token_id = *(liq_token_id.lock().unwrap());
 		let liquidity_token_amount = (mga_valuation + ksm_valuation) / 2;
 
@@ -971,13 +1052,22 @@ fn test_rewards_are_distributed_properly_with_single_user() {
 			Bootstrap::balance(liquidity_token_id, Bootstrap::vault_address()),
 			liquidity_token_amount
 		);
-		assert_eq!(Bootstrap::minted_liquidity(), (liquidity_token_id, liquidity_token_amount));
+		assert_eq!(
+			Bootstrap::minted_liquidity(BOOTSTRAP_ID),
+			(liquidity_token_id, liquidity_token_amount)
+		);
 
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
 
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, MGAId::get()), liquidity_token_amount / 2);
+		assert_eq!(
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, MGAId::get())),
+			liquidity_token_amount / 2
+		);
 
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, KSMId::get()), liquidity_token_amount / 2);
+		assert_eq!(
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, KSMId::get())),
+			liquidity_token_amount / 2
+		);
 
 		assert_eq!(
 			Bootstrap::balance(liquidity_token_id, USER_ID),
@@ -995,7 +1085,11 @@ fn test_rewards_are_distributed_properly_with_multiple_user() {
 		set_up();
 
 		let provisioned_ev = |id, amount| {
-			crate::mock::Event::Bootstrap(crate::Event::<Test>::Provisioned(id, amount))
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::Provisioned(
+				BOOTSTRAP_ID,
+				id,
+				amount,
+			))
 		};
 
 		let rewards_claimed_ev = |id, amount| {
@@ -1032,29 +1126,44 @@ fn test_rewards_are_distributed_properly_with_multiple_user() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(110_u32.into());
-		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::transfer(MGAId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 500_000).unwrap();
 		Bootstrap::transfer(KSMId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 500_000).unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), USER_MGA_PROVISON).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			USER_MGA_PROVISON,
+		)
+		.unwrap();
 		Bootstrap::provision(
 			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
 			MGAId::get(),
 			ANOTHER_USER_MGA_PROVISON,
 		)
 		.unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), USER_KSM_PROVISON).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			KSMId::get(),
+			USER_KSM_PROVISON,
+		)
+		.unwrap();
 		Bootstrap::provision(
 			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
 			KSMId::get(),
 			ANOTHER_USER_KSM_PROVISON,
 		)
@@ -1074,9 +1183,9 @@ fn test_rewards_are_distributed_properly_with_multiple_user() {
 			.any(|record| record.event == provisioned_ev(KSMId::get(), ANOTHER_USER_KSM_PROVISON)));
 
 		Bootstrap::on_initialize(120_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
 		assert_eq!(mga_valuation, 500_000);
 		assert_eq!(ksm_valuation, 35);
 		let liquidity_token_id = *(liq_token_id.lock().unwrap());
@@ -1086,12 +1195,15 @@ fn test_rewards_are_distributed_properly_with_multiple_user() {
 			Bootstrap::balance(liquidity_token_id, Bootstrap::vault_address()),
 			liquidity_token_amount
 		);
-		assert_eq!(Bootstrap::minted_liquidity(), (liquidity_token_id, liquidity_token_amount));
+		assert_eq!(
+			Bootstrap::minted_liquidity(BOOTSTRAP_ID),
+			(liquidity_token_id, liquidity_token_amount)
+		);
 
-		assert_eq!(Bootstrap::claimed_rewards(ANOTHER_USER_ID, MGAId::get()), 0);
-		assert_eq!(Bootstrap::claimed_rewards(ANOTHER_USER_ID, KSMId::get()), 0);
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, MGAId::get()), 0);
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, KSMId::get()), 0);
+		assert_eq!(Bootstrap::claimed_rewards(BOOTSTRAP_ID, (ANOTHER_USER_ID, MGAId::get())), 0);
+		assert_eq!(Bootstrap::claimed_rewards(BOOTSTRAP_ID, (ANOTHER_USER_ID, KSMId::get())), 0);
+		assert_eq!(Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, MGAId::get())), 0);
+		assert_eq!(Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, KSMId::get())), 0);
 
 		let user_expected_ksm_rewards =
 			liquidity_token_amount / 2 * USER_KSM_PROVISON / ksm_valuation;
@@ -1105,26 +1217,32 @@ fn test_rewards_are_distributed_properly_with_multiple_user() {
 			liquidity_token_amount / 2 * ANOTHER_USER_MGA_PROVISON / mga_valuation;
 		let user2_expected_liq_amount = user2_expected_ksm_rewards + user2_expected_mga_rewards;
 
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
-		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID).unwrap();
 
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, MGAId::get()), user_expected_mga_rewards);
-		assert_eq!(Bootstrap::claimed_rewards(USER_ID, KSMId::get()), user_expected_ksm_rewards);
 		assert_eq!(
-			Bootstrap::claimed_rewards(ANOTHER_USER_ID, MGAId::get()),
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, MGAId::get())),
+			user_expected_mga_rewards
+		);
+		assert_eq!(
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (USER_ID, KSMId::get())),
+			user_expected_ksm_rewards
+		);
+		assert_eq!(
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (ANOTHER_USER_ID, MGAId::get())),
 			user2_expected_mga_rewards
 		);
 		assert_eq!(
-			Bootstrap::claimed_rewards(ANOTHER_USER_ID, KSMId::get()),
+			Bootstrap::claimed_rewards(BOOTSTRAP_ID, (ANOTHER_USER_ID, KSMId::get())),
 			user2_expected_ksm_rewards
 		);
 
 		assert_err!(
-			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)),
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID),
 			Error::<Test>::NothingToClaim
 		);
 		assert_err!(
-			Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID)),
+			Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID),
 			Error::<Test>::NothingToClaim
 		);
 
@@ -1159,138 +1277,189 @@ fn dont_allow_for_provision_in_vested_tokens_without_dedicated_extrinsic() {
 		.unwrap();
 
 		assert_err!(
-			Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1),
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
 			Error::<Test>::NotEnoughAssets
 		);
 	});
 }
 
-// #[test]
-// #[serial]
-// fn fail_vested_token_provision_if_user_doesnt_have_vested_tokens() {
-// 	new_test_ext().execute_with(|| {
-// 		set_up();
-// 		jump_to_public_phase();
-
-// 		assert_err!(
-// 			Bootstrap::provision_vested(Origin::signed(USER_ID), MGAId::get(), 1),
-// 			Error::<Test>::NotEnoughVestedAssets
-// 		);
-// 	});
-// }
-
-// #[test]
-// #[serial]
-// fn successful_vested_provision_using_vested_tokens_only_when_user_has_both_vested_and_non_vested_tokens(
-// ) {
-// 	new_test_ext().execute_with(|| {
-// 		set_up();
-// 		jump_to_public_phase();
-
-// 		let provision_amount = 10_000;
-// 		let lock_start: u128 = 1;
-// 		let lock_end: u128 = 150;
-
-// 		<Test as Config>::VestingProvider::lock_tokens(
-// 			&USER_ID,
-// 			MGAId::get(),
-// 			provision_amount,
-// 			Some(lock_start.saturated_into()),
-// 			lock_end.into(),
-// 		)
-// 		.unwrap();
-
-// 		let non_vested_initial_amount = Bootstrap::balance(MGAId::get(), USER_ID);
-
-// 		Bootstrap::provision_vested(Origin::signed(USER_ID), MGAId::get(), provision_amount)
-// 			.unwrap();
-
-// 		assert_eq!(non_vested_initial_amount, Bootstrap::balance(MGAId::get(), USER_ID));
-
-// 		assert_eq!(0, Bootstrap::locked_balance(MGAId::get(), USER_ID));
-
-// 		assert_eq!(
-// 			(provision_amount, lock_start, lock_end + 1),
-// 			Bootstrap::vested_provisions(USER_ID, MGAId::get())
-// 		);
-// 	});
-// }
-
-// #[test]
-// #[serial]
-// fn successful_vested_provision_is_stored_properly_in_storage() {
-// 	new_test_ext().execute_with(|| {
-// 		set_up();
-// 		jump_to_public_phase();
-
-// 		let mga_amount = Bootstrap::balance(MGAId::get(), USER_ID);
-// 		let lock_start: u128 = 1;
-// 		let lock_end: u128 = 150;
-
-// 		<Test as Config>::VestingProvider::lock_tokens(
-// 			&USER_ID,
-// 			MGAId::get(),
-// 			mga_amount,
-// 			Some(lock_start.saturated_into()),
-// 			lock_end.into(),
-// 		)
-// 		.unwrap();
-// 		Bootstrap::provision_vested(Origin::signed(USER_ID), MGAId::get(), mga_amount).unwrap();
-
-// 		assert_eq!(
-// 			(mga_amount, lock_start, lock_end + 1),
-// 			Bootstrap::vested_provisions(USER_ID, MGAId::get())
-// 		);
-// 	});
-// }
-
-// #[test]
-// #[serial]
-// fn successful_merged_vested_provision_is_stored_properly_in_storage() {
-// 	new_test_ext().execute_with(|| {
-// 		set_up();
-// 		jump_to_public_phase();
-
-// 		let mga_amount = Bootstrap::balance(MGAId::get(), USER_ID);
-// 		let first_lock_start: u128 = 1;
-// 		let first_lock_end: u128 = 150;
-// 		let first_lock_amount = mga_amount / 2;
-// 		let second_lock_start: u128 = 2;
-// 		let second_lock_end: u128 = 300;
-// 		let second_lock_amount = mga_amount - first_lock_amount;
-
-// 		<Test as Config>::VestingProvider::lock_tokens(
-// 			&USER_ID,
-// 			MGAId::get(),
-// 			first_lock_amount,
-// 			Some(first_lock_start.saturated_into()),
-// 			first_lock_end.into(),
-// 		)
-// 		.unwrap();
-// 		<Test as Config>::VestingProvider::lock_tokens(
-// 			&USER_ID,
-// 			MGAId::get(),
-// 			second_lock_amount,
-// 			Some(second_lock_start.saturated_into()),
-// 			second_lock_end.into(),
-// 		)
-// 		.unwrap();
-
-// 		Bootstrap::provision_vested(Origin::signed(USER_ID), MGAId::get(), first_lock_amount)
-// 			.unwrap();
-// 		assert_eq!(
-// 			(first_lock_amount, first_lock_start, first_lock_end + 1),
-// 			Bootstrap::vested_provisions(USER_ID, MGAId::get())
-// 		);
-
-// 		Bootstrap::provision_vested(Origin::signed(USER_ID), MGAId::get(), second_lock_amount)
-// 			.unwrap();
-// 		assert_eq!(
-// 			(mga_amount, second_lock_start, second_lock_end + 1),
-// 			Bootstrap::vested_provisions(USER_ID, MGAId::get())
-// 		);
-// 	});
-// }
+#[test]
+#[serial]
+fn fail_vested_token_provision_if_user_doesnt_have_vested_tokens() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_public_phase();
+
+		assert_err!(
+			Bootstrap::provision_vested(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::NotEnoughVestedAssets
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn successful_vested_provision_using_vested_tokens_only_when_user_has_both_vested_and_non_vested_tokens(
+) {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_public_phase();
+
+		let provision_amount = 10_000;
+		let lock_start: u128 = 1;
+		let lock_end: u128 = 150;
+
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			provision_amount,
+			Some(lock_start.saturated_into()),
+			lock_end.into(),
+		)
+		.unwrap();
+
+		let non_vested_initial_amount = Bootstrap::balance(MGAId::get(), USER_ID);
+
+		Bootstrap::provision_vested(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			provision_amount,
+		)
+		.unwrap();
+
+		assert_eq!(non_vested_initial_amount, Bootstrap::balance(MGAId::get(), USER_ID));
+
+		assert_eq!(0, Bootstrap::locked_balance(MGAId::get(), USER_ID));
+
+		assert_eq!(
+			(provision_amount, lock_start, lock_end + 1),
+			Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get()))
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn successful_vested_provision_is_stored_properly_in_storage() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_public_phase();
+
+		let mga_amount = Bootstrap::balance(MGAId::get(), USER_ID);
+		let lock_start: u128 = 1;
+		let lock_end: u128 = 150;
+
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			mga_amount,
+			Some(lock_start.saturated_into()),
+			lock_end.into(),
+		)
+		.unwrap();
+		Bootstrap::provision_vested(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			mga_amount,
+		)
+		.unwrap();
+
+		assert_eq!(
+			(mga_amount, lock_start, lock_end + 1),
+			Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get()))
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn successful_merged_vested_provision_is_stored_properly_in_storage() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_public_phase();
+
+		let mga_amount = Bootstrap::balance(MGAId::get(), USER_ID);
+		let first_lock_start: u128 = 1;
+		let first_lock_end: u128 = 150;
+		let first_lock_amount = mga_amount / 2;
+		let second_lock_start: u128 = 2;
+		let second_lock_end: u128 = 300;
+		let second_lock_amount = mga_amount - first_lock_amount;
+
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			first_lock_amount,
+			Some(first_lock_start.saturated_into()),
+			first_lock_end.into(),
+		)
+		.unwrap();
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			second_lock_amount,
+			Some(second_lock_start.saturated_into()),
+			second_lock_end.into(),
+		)
+		.unwrap();
+
+		Bootstrap::provision_vested(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			first_lock_amount,
+		)
+		.unwrap();
+		assert_eq!(
+			(first_lock_amount, first_lock_start, first_lock_end + 1),
+			Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get()))
+		);
+
+		Bootstrap::provision_vested(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			second_lock_amount,
+		)
+		.unwrap();
+		assert_eq!(
+			(mga_amount, second_lock_start, second_lock_end + 1),
+			Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get()))
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn vested_tokens_stay_locked_when_vested_provision_fails() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let provision_amount = 10_000;
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			provision_amount,
+			Some(1_u32.into()),
+			150_u32.into(),
+		)
+		.unwrap();
+
+		assert_err!(
+			Bootstrap::provision_vested(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				MGAId::get(),
+				provision_amount
+			),
+			Error::<Test>::BootstrapNotSchduled
+		);
+		assert_eq!(provision_amount, Bootstrap::locked_balance(MGAId::get(), USER_ID));
+		assert_eq!((0, 0, 0), Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+	});
+}
 
 #[macro_export]
 macro_rules! init_mocks {
@@ -1323,106 +1492,111 @@ fn provisions(
 		)
 		.unwrap();
 
-		// if let ProvisionKind::Vested(begin, end) = lock {
-		// 	<Test as Config>::VestingProvider::lock_tokens(
-		// 		&user_id,
-		// 		token_id,
-		// 		amount,
-		// 		Some(begin.saturated_into()),
-		// 		end.into(),
-		// 	)
-		// 	.unwrap();
-		// 	Bootstrap::provision_vested(Origin::signed(user_id), token_id, amount).unwrap();
-		// } else {
-		Bootstrap::provision(Origin::signed(user_id), token_id, amount).unwrap();
-		// }
+		if let ProvisionKind::Vested(begin, end) = lock {
+			<Test as Config>::VestingProvider::lock_tokens(
+				&user_id,
+				token_id,
+				amount,
+				Some(begin.saturated_into()),
+				end.into(),
+			)
+			.unwrap();
+			Bootstrap::provision_vested(Origin::signed(user_id), BOOTSTRAP_ID, token_id, amount)
+				.unwrap();
+		} else {
+			Bootstrap::provision(Origin::signed(user_id), BOOTSTRAP_ID, token_id, amount).unwrap();
+		}
 	}
 }
 
-// #[test]
-// #[serial]
-// fn vested_provision_included_in_valuation() {
-// 	new_test_ext().execute_with(|| {
-// 		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
-// 		set_up();
-// 		jump_to_public_phase();
-// 		init_mocks!();
-// 		let liq_token_id = Tokens::next_asset_id();
-
-// 		// ACT
-// 		provisions(vec![
-// 			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Vested(1, 150)),
-// 			(PROVISION_USER2_ID, KSMId::get(), 100, ProvisionKind::Regular),
-// 		]);
-// 		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
-// 		let liq_token_minted = (mga_valuation + ksm_valuation) / 2;
-// 		assert_eq!(mga_valuation, 1_000_000);
-// 		assert_eq!(ksm_valuation, 100);
-
-// 		Bootstrap::on_initialize(100_u32.into());
-// 		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
-// 		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-// 		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
-
-// 		// ASSERT
-// 		assert_eq!(
-// 			liq_token_minted / 2,
-// 			Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID)
-// 		);
-// 		assert_eq!(0, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
-
-// 		assert_eq!(liq_token_minted / 2, Bootstrap::balance(liq_token_id, PROVISION_USER2_ID));
-// 		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID));
-// 	});
-// }
-
-// #[test]
-// #[serial]
-// fn multi_provisions() {
-// 	new_test_ext().execute_with(|| {
-// 		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
-// 		set_up();
-// 		jump_to_public_phase();
-// 		init_mocks!();
-// 		let liq_token_id = Tokens::next_asset_id();
-
-// 		// ACT
-// 		provisions(vec![
-// 			(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
-// 			(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
-// 			(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Vested(1, 150)),
-// 			(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
-// 		]);
-// 		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
-// 		let liq_token_minted = (mga_valuation + ksm_valuation) / 2;
-// 		assert_eq!(mga_valuation, 400_000);
-// 		assert_eq!(ksm_valuation, 40);
-
-// 		Bootstrap::on_initialize(100_u32.into());
-// 		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
-// 		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-// 		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
-
-// 		// ASSERT
-// 		assert_eq!(
-// 			liq_token_minted / 2 * 10 / ksm_valuation,
-// 			Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID)
-// 		);
-// 		assert_eq!(
-// 			liq_token_minted / 2 * 100_000 / mga_valuation,
-// 			Bootstrap::balance(liq_token_id, PROVISION_USER1_ID)
-// 		);
-
-// 		assert_eq!(
-// 			liq_token_minted / 2 * 30 / ksm_valuation,
-// 			Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID)
-// 		);
-// 		assert_eq!(
-// 			liq_token_minted / 2 * 300_000 / mga_valuation,
-// 			Bootstrap::balance(liq_token_id, PROVISION_USER2_ID)
-// 		);
-// 	});
-// }
+#[test]
+#[serial]
+fn vested_provision_included_in_valuation() {
+	new_test_ext().execute_with(|| {
+		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
+		set_up();
+		jump_to_public_phase();
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		// ACT
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Vested(1, 150)),
+			(PROVISION_USER2_ID, KSMId::get(), 100, ProvisionKind::Regular),
+		]);
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
+		let liq_token_minted = (mga_valuation + ksm_valuation) / 2;
+		assert_eq!(mga_valuation, 1_000_000);
+		assert_eq!(ksm_valuation, 100);
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+			.unwrap();
+
+		// ASSERT
+		assert_eq!(
+			liq_token_minted / 2,
+			Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID)
+		);
+		assert_eq!(0, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
+
+		assert_eq!(liq_token_minted / 2, Bootstrap::balance(liq_token_id, PROVISION_USER2_ID));
+		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn multi_provisions() {
+	new_test_ext().execute_with(|| {
+		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
+		set_up();
+		jump_to_public_phase();
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		// ACT
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
+			(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Vested(1, 150)),
+			(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
+		]);
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
+		let liq_token_minted = (mga_valuation + ksm_valuation) / 2;
+		assert_eq!(mga_valuation, 400_000);
+		assert_eq!(ksm_valuation, 40);
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+			.unwrap();
+
+		// ASSERT
+		assert_eq!(
+			liq_token_minted / 2 * 10 / ksm_valuation,
+			Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID)
+		);
+		assert_eq!(
+			liq_token_minted / 2 * 100_000 / mga_valuation,
+			Bootstrap::balance(liq_token_id, PROVISION_USER1_ID)
+		);
+
+		assert_eq!(
+			liq_token_minted / 2 * 30 / ksm_valuation,
+			Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID)
+		);
+		assert_eq!(
+			liq_token_minted / 2 * 300_000 / mga_valuation,
+			Bootstrap::balance(liq_token_id, PROVISION_USER2_ID)
+		);
+	});
+}
 
 #[test]
 #[serial]
@@ -1441,15 +1615,17 @@ fn multi_provisions_only_with_non_vested() {
 			(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
 			(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
 		]);
-		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
 		let liq_token_minted = (mga_valuation + ksm_valuation) / 2;
 		assert_eq!(mga_valuation, 400_000);
 		assert_eq!(ksm_valuation, 40);
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
-		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+			.unwrap();
 
 		// ASSERT
 		assert_eq!(
@@ -1464,148 +1640,6 @@ fn multi_provisions_only_with_non_vested() {
 	});
 }
 
-// // formula KSM/MGA for provision calculation
-// // (KSM valuation + MGA valuation)   User KSM/MGA token provision
-// // ------------------------------- * ---------------------------- = liquidity tokens rewards
-// //                2                      KSM/MGA valuation
-// //  EX:
-
-// #[test_case(
-// 			vec![
-// 				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
-// 			],
-// 			(25002, 25002),
-// 			(75007, 75007);
-// 			"two users provision both vested and non vested tokens")
-// ]
-// #[test_case(
-// 			vec![
-// 				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
-// 				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
-// 			],
-// 			(50_004, 0),
-// 			(0, 0);
-// 			"non vested provisions from single user")
-// ]
-// #[test_case(
-// 			vec![
-// 				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Vested(1, 150)),
-// 			],
-// 			(0, 50_004),
-// 			(0, 0);
-// 			"vested provisions from single user")
-// ]
-// #[test_case(
-// 			vec![
-// 				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
-// 			],
-// 			(0, 50_004), // 400040 / 2 / 2 * 1 / 4
-// 			(150014, 0); // 400040 / 2 / 2 * 3 / 4
-// 			"vested provisions from single user & non vested form second one")
-// ]
-// #[test_case(
-// 			vec![
-// 				(PROVISION_USER1_ID, MGAId::get(), 10_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-// 				(PROVISION_USER1_ID, MGAId::get(), 20_000, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-// 				(PROVISION_USER1_ID, MGAId::get(), 30_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER1_ID, KSMId::get(), 1, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-// 				(PROVISION_USER1_ID, MGAId::get(), 40_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, MGAId::get(), 200_000, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER2_ID, KSMId::get(), 10, ProvisionKind::Regular),
-// 				(PROVISION_USER2_ID, KSMId::get(), 15, ProvisionKind::Regular),
-// 				(PROVISION_USER1_ID, KSMId::get(), 4, ProvisionKind::Vested(1, 150)),
-// 				(PROVISION_USER1_ID, KSMId::get(), 5, ProvisionKind::Vested(1, 150)),
-// 			],
-// 			(5_000, 45_004),
-// 			(125012, 25_002);
-// 			"multiple provisions from multiple accounts mixed")
-// ]
-// #[serial]
-// fn test_multi_provisions(
-// 	provisions_list: Vec<(
-// 		<Test as frame_system::Config>::AccountId,
-// 		TokenId,
-// 		Balance,
-// 		ProvisionKind,
-// 	)>,
-// 	user1_rewards: (Balance, Balance),
-// 	user2_rewards: (Balance, Balance),
-// ) {
-// 	new_test_ext().execute_with(|| {
-// 		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
-// 		set_up();
-// 		jump_to_public_phase();
-// 		init_mocks!();
-// 		let liq_token_id = Tokens::next_asset_id();
-// 		let user1_has_provisions =
-// 			provisions_list.iter().any(|(who, _, _, _)| *who == PROVISION_USER1_ID);
-// 		let user2_has_provisions =
-// 			provisions_list.iter().any(|(who, _, _, _)| *who == PROVISION_USER2_ID);
-// 		let total_ksm_provision: u128 = provisions_list
-// 			.iter()
-// 			.filter_map(
-// 				|(_, token_id, amount, _)| {
-// 					if *token_id == KSMId::get() {
-// 						Some(amount)
-// 					} else {
-// 						None
-// 					}
-// 				},
-// 			)
-// 			.sum();
-// 		let total_mga_provision: u128 = provisions_list
-// 			.iter()
-// 			.filter_map(
-// 				|(_, token_id, amount, _)| {
-// 					if *token_id == MGAId::get() {
-// 						Some(amount)
-// 					} else {
-// 						None
-// 					}
-// 				},
-// 			)
-// 			.sum();
-
-// 		// ACT
-// 		provisions(provisions_list);
-
-// 		Bootstrap::on_initialize(100_u32.into());
-// 		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
-
-// 		if user1_has_provisions {
-// 			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-// 		}
-
-// 		if user2_has_provisions {
-// 			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
-// 		}
-
-// 		// ASSERT
-// 		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
-// 		assert_eq!(total_ksm_provision, ksm_valuation);
-// 		assert_eq!(total_mga_provision, mga_valuation);
-
-// 		assert_eq!(user1_rewards.0, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
-// 		assert_eq!(user1_rewards.1, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
-
-// 		assert_eq!(user2_rewards.0, Bootstrap::balance(liq_token_id, PROVISION_USER2_ID));
-// 		assert_eq!(user2_rewards.1, Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID));
-// 	})
-// }
-
 // formula KSM/MGA for provision calculation
 // (KSM valuation + MGA valuation)   User KSM/MGA token provision
 // ------------------------------- * ---------------------------- = liquidity tokens rewards
@@ -1614,13 +1648,13 @@ fn multi_provisions_only_with_non_vested() {
 
 #[test_case(
 			vec![
-				(PROVISION_USER1_ID, MGAId::get(), 100_000,ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
 				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
-				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Vested(1, 150)),
 				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
 			],
-			(50_004, 0),
-			(150014, 0);
+			(25002, 25002),
+			(75007, 75007);
 			"two users provision both vested and non vested tokens")
 ]
 #[test_case(
@@ -1634,36 +1668,180 @@ fn multi_provisions_only_with_non_vested() {
 ]
 #[test_case(
 			vec![
-				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
-				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Vested(1, 150)),
 			],
-			(50_004, 0),
+			(0, 50_004),
 			(0, 0);
 			"vested provisions from single user")
 ]
 #[test_case(
 			vec![
-				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
-				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Vested(1, 150)),
 				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
 			],
-			(50_004, 0), // 400040 / 2 / 2 * 1 / 4
+			(0, 50_004), // 400040 / 2 / 2 * 1 / 4
 			(150014, 0); // 400040 / 2 / 2 * 3 / 4
 			"vested provisions from single user & non vested form second one")
 ]
 #[test_case(
 			vec![
-				(PROVISION_USER1_ID, MGAId::get(), 10_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 10_000, ProvisionKind::Vested(1, 150)),
 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
 				(PROVISION_USER1_ID, MGAId::get(), 20_000, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-				(PROVISION_USER1_ID, MGAId::get(), 30_000, ProvisionKind::Regular),
-				(PROVISION_USER1_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 30_000, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER1_ID, KSMId::get(), 1, ProvisionKind::Vested(1, 150)),
 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
-				(PROVISION_USER1_ID, MGAId::get(), 40_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 40_000, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER2_ID, MGAId::get(), 200_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, MGAId::get(), 100_000, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER2_ID, KSMId::get(), 10, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 15, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 4, ProvisionKind::Vested(1, 150)),
+				(PROVISION_USER1_ID, KSMId::get(), 5, ProvisionKind::Vested(1, 150)),
+			],
+			(5_000, 45_004),
+			(125012, 25_002);
+			"multiple provisions from multiple accounts mixed")
+]
+#[serial]
+fn test_multi_provisions(
+	provisions_list: Vec<(
+		<Test as frame_system::Config>::AccountId,
+		TokenId,
+		Balance,
+		ProvisionKind,
+	)>,
+	user1_rewards: (Balance, Balance),
+	user2_rewards: (Balance, Balance),
+) {
+	new_test_ext().execute_with(|| {
+		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
+		set_up();
+		jump_to_public_phase();
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+		let user1_has_provisions =
+			provisions_list.iter().any(|(who, _, _, _)| *who == PROVISION_USER1_ID);
+		let user2_has_provisions =
+			provisions_list.iter().any(|(who, _, _, _)| *who == PROVISION_USER2_ID);
+		let total_ksm_provision: u128 = provisions_list
+			.iter()
+			.filter_map(
+				|(_, token_id, amount, _)| {
+					if *token_id == KSMId::get() {
+						Some(amount)
+					} else {
+						None
+					}
+				},
+			)
+			.sum();
+		let total_mga_provision: u128 = provisions_list
+			.iter()
+			.filter_map(
+				|(_, token_id, amount, _)| {
+					if *token_id == MGAId::get() {
+						Some(amount)
+					} else {
+						None
+					}
+				},
+			)
+			.sum();
+
+		// ACT
+		provisions(provisions_list);
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+
+		if user1_has_provisions {
+			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+				.unwrap();
+		}
+
+		if user2_has_provisions {
+			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+				.unwrap();
+		}
+
+		// ASSERT
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
+		assert_eq!(total_ksm_provision, ksm_valuation);
+		assert_eq!(total_mga_provision, mga_valuation);
+
+		assert_eq!(user1_rewards.0, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
+		assert_eq!(user1_rewards.1, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		assert_eq!(user2_rewards.0, Bootstrap::balance(liq_token_id, PROVISION_USER2_ID));
+		assert_eq!(user2_rewards.1, Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID));
+	})
+}
+
+// formula KSM/MGA for provision calculation
+// (KSM valuation + MGA valuation)   User KSM/MGA token provision
+// ------------------------------- * ---------------------------- = liquidity tokens rewards
+//                2                      KSM/MGA valuation
+//  EX:
+
+#[test_case(
+			vec![
+				(PROVISION_USER1_ID, MGAId::get(), 100_000,ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
+			],
+			(50_004, 0),
+			(150014, 0);
+			"two users provision both vested and non vested tokens")
+]
+#[test_case(
+			vec![
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+			],
+			(50_004, 0),
+			(0, 0);
+			"non vested provisions from single user")
+]
+#[test_case(
+			vec![
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+			],
+			(50_004, 0),
+			(0, 0);
+			"vested provisions from single user")
+]
+#[test_case(
+			vec![
+				(PROVISION_USER1_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 10, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, MGAId::get(), 300_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 30, ProvisionKind::Regular),
+			],
+			(50_004, 0), // 400040 / 2 / 2 * 1 / 4
+			(150014, 0); // 400040 / 2 / 2 * 3 / 4
+			"vested provisions from single user & non vested form second one")
+]
+#[test_case(
+			vec![
+				(PROVISION_USER1_ID, MGAId::get(), 10_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 20_000, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 30_000, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER2_ID, KSMId::get(), 1, ProvisionKind::Regular),
+				(PROVISION_USER1_ID, MGAId::get(), 40_000, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, MGAId::get(), 200_000, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, MGAId::get(), 100_000, ProvisionKind::Regular),
 				(PROVISION_USER2_ID, KSMId::get(), 10, ProvisionKind::Regular),
@@ -1725,18 +1903,20 @@ fn test_multi_provisions_only_with_non_vested(
 		provisions(provisions_list);
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		if user1_has_provisions {
-			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
+			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+				.unwrap();
 		}
 
 		if user2_has_provisions {
-			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
+			Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+				.unwrap();
 		}
 
 		// ASSERT
-		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
 		assert_eq!(total_ksm_provision, ksm_valuation);
 		assert_eq!(total_mga_provision, mga_valuation);
 
@@ -1781,44 +1961,63 @@ fn test_restart_bootstrap() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 		Bootstrap::on_initialize(110_u32.into());
 		Bootstrap::transfer(MGAId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 500_000).unwrap();
 		Bootstrap::transfer(KSMId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 500_000).unwrap();
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), USER_MGA_PROVISON).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			USER_MGA_PROVISON,
+		)
+		.unwrap();
 		Bootstrap::provision(
 			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
 			MGAId::get(),
 			ANOTHER_USER_MGA_PROVISON,
 		)
 		.unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), USER_KSM_PROVISON).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			KSMId::get(),
+			USER_KSM_PROVISON,
+		)
+		.unwrap();
 		Bootstrap::provision(
 			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
 			KSMId::get(),
 			ANOTHER_USER_KSM_PROVISON,
 		)
 		.unwrap();
 
-		assert_err!(Bootstrap::finalize(Origin::root(), 200), Error::<Test>::NotFinishedYet);
+		assert_err!(
+			Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 200),
+			Error::<Test>::NotFinishedYet
+		);
 
 		Bootstrap::on_initialize(120_u32.into());
 
 		assert_eq!(0, Bootstrap::balance(liq_token_id, USER_ID));
 		assert_eq!(0, Bootstrap::balance(liq_token_id, ANOTHER_USER_ID));
 
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
 
 		// not all rewards claimed
 		assert_err!(
-			Bootstrap::finalize(Origin::root(), 200),
+			Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 200),
 			Error::<Test>::BootstrapNotReadyToBeFinished
 		);
 
 		Bootstrap::claim_liquidity_tokens_for_account(
 			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
 			ANOTHER_USER_ID,
 			false,
 		)
@@ -1827,17 +2026,17 @@ fn test_restart_bootstrap() {
 		assert_ne!(0, Bootstrap::balance(liq_token_id, USER_ID));
 		assert_ne!(0, Bootstrap::balance(liq_token_id, ANOTHER_USER_ID));
 
-		Bootstrap::finalize(Origin::root(), 200).unwrap();
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 200).unwrap();
 		assert!(Provisions::<Test>::iter_keys().next().is_none());
 		assert!(VestedProvisions::<Test>::iter_keys().next().is_none());
 		assert!(WhitelistedAccount::<Test>::iter_keys().next().is_none());
 		assert!(ClaimedRewards::<Test>::iter_keys().next().is_none());
 		assert!(ProvisionAccounts::<Test>::iter_keys().next().is_none());
-		assert_eq!(Valuations::<Test>::get(), (0, 0));
-		assert_eq!(Phase::<Test>::get(), BootstrapPhase::BeforeStart);
-		assert_eq!(BootstrapSchedule::<Test>::get(), None);
-		assert_eq!(MintedLiquidity::<Test>::get(), (0, 0));
-		assert_eq!(ActivePair::<Test>::get(), None);
+		assert_eq!(Valuations::<Test>::get(BOOTSTRAP_ID), (0, 0));
+		assert_eq!(Phase::<Test>::get(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
+		assert_eq!(BootstrapSchedule::<Test>::get(BOOTSTRAP_ID), None);
+		assert_eq!(MintedLiquidity::<Test>::get(BOOTSTRAP_ID), (0, 0));
+		assert_eq!(ActivePair::<Test>::get(BOOTSTRAP_ID), None);
 
 		Bootstrap::schedule_bootstrap(
 			Origin::root(),
@@ -1848,6 +2047,7 @@ fn test_restart_bootstrap() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 	});
@@ -1888,6 +2088,7 @@ fn claim_liquidity_tokens_even_if_sum_of_rewards_is_zero_because_of_small_provis
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 		Bootstrap::on_initialize(110_u32.into());
@@ -1896,29 +2097,32 @@ fn claim_liquidity_tokens_even_if_sum_of_rewards_is_zero_because_of_small_provis
 
 		Bootstrap::provision(
 			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
 			MGAId::get(),
 			1_000_000_000_000_000_000_000_000_000_000_000_u128,
 		)
 		.unwrap();
-		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), MGAId::get(), 1).unwrap();
+		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 1)
+			.unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 1_000_000_u128).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1_000_000_u128)
+			.unwrap();
 
 		Bootstrap::on_initialize(120_u32.into());
 
 		assert_eq!(0, Bootstrap::balance(liq_token_id, USER_ID));
 		assert_eq!(0, Bootstrap::balance(liq_token_id, ANOTHER_USER_ID));
 
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
-		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID).unwrap();
 
 		assert_err!(
-			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)),
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID),
 			Error::<Test>::NothingToClaim
 		);
 
 		assert_err!(
-			Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID)),
+			Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID),
 			Error::<Test>::NothingToClaim
 		);
 	});
@@ -1955,31 +2159,40 @@ fn transfer_dust_to_treasury() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 		Bootstrap::on_initialize(110_u32.into());
 		Bootstrap::transfer(MGAId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 1).unwrap();
 		Bootstrap::transfer(KSMId::get(), USER_ID.into(), ANOTHER_USER_ID.into(), 1).unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1_000_000_000_u128).unwrap();
-		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), MGAId::get(), 1).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			1_000_000_000_u128,
+		)
+		.unwrap();
+		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 1)
+			.unwrap();
 
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 100_u128).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 100_u128)
+			.unwrap();
 
 		Bootstrap::on_initialize(120_u32.into());
 
 		assert_eq!(0, Bootstrap::balance(liq_token_id, USER_ID));
 		assert_eq!(0, Bootstrap::balance(liq_token_id, ANOTHER_USER_ID));
 
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
-		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID).unwrap();
 
 		let before_finalize = Bootstrap::balance(
 			liq_token_id,
 			<mock::Test as Config>::TreasuryPalletId::get().into_account_truncating(),
 		);
 
-		Bootstrap::finalize(Origin::root(), 200).unwrap();
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 200).unwrap();
 
 		let after_finalize = Bootstrap::balance(
 			liq_token_id,
@@ -2018,16 +2231,24 @@ fn archive_previous_bootstrap_schedules() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 		Bootstrap::on_initialize(110_u32.into());
-		Bootstrap::provision(Origin::signed(USER_ID), MGAId::get(), 1_000_000_000_u128).unwrap();
-		Bootstrap::provision(Origin::signed(USER_ID), KSMId::get(), 100_u128).unwrap();
+		Bootstrap::provision(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			1_000_000_000_u128,
+		)
+		.unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 100_u128)
+			.unwrap();
 		Bootstrap::on_initialize(120_u32.into());
-		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID)).unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
 		assert_eq!(0, Bootstrap::archived().len());
-		Bootstrap::finalize(Origin::root(), 1).unwrap();
-		assert_eq!(0, Bootstrap::provisions(USER_ID, KSMId::get()));
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 1).unwrap();
+		assert_eq!(0, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, KSMId::get())));
 
 		assert_eq!(1, Bootstrap::archived().len());
 	})
@@ -2062,88 +2283,104 @@ fn test_activate_liq_tokens_is_called_with_all_liq_tokens_when_pool_is_promoted_
 			(PROVISION_USER1_ID, MGAId::get(), mga_provision, ProvisionKind::Regular),
 			(PROVISION_USER2_ID, KSMId::get(), ksm_provision, ProvisionKind::Regular),
 		]);
-		let (mga_valuation, ksm_valuation) = Bootstrap::valuations();
+		let (mga_valuation, ksm_valuation) = Bootstrap::valuations(BOOTSTRAP_ID);
 		assert_eq!(mga_valuation, 1_000_000);
 		assert_eq!(ksm_valuation, 100);
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER1_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER2_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
 	});
 }
 
-// #[test]
-// #[serial]
-// fn test_dont_activate_liq_tokens_when_pool_is_promoted_but_all_provisions_are_vested() {
-// 	new_test_ext().execute_with(|| {
-// 		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
-// 		set_up();
-// 		jump_to_public_phase();
-// 		init_mocks!();
+#[test]
+#[serial]
+fn test_dont_activate_liq_tokens_when_pool_is_promoted_but_all_provisions_are_vested() {
+	new_test_ext().execute_with(|| {
+		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
+		set_up();
+		jump_to_public_phase();
+		init_mocks!();
 
-// 		let mga_provision = 1_000_000_u128;
-// 		let ksm_provision = 100_u128;
+		let mga_provision = 1_000_000_u128;
+		let ksm_provision = 100_u128;
 
-// 		let can_activate_mock = MockRewardsApi::can_activate_context();
-// 		can_activate_mock.expect().return_const(true);
+		let can_activate_mock = MockRewardsApi::can_activate_context();
+		can_activate_mock.expect().return_const(true);
 
-// 		let activate_liquidity_tokens = MockRewardsApi::activate_liquidity_tokens_context();
-// 		activate_liquidity_tokens.expect().times(0);
+		let activate_liquidity_tokens = MockRewardsApi::activate_liquidity_tokens_context();
+		activate_liquidity_tokens.expect().times(0);
 
-// 		// ACT
-// 		provisions(vec![
-// 			(PROVISION_USER1_ID, MGAId::get(), mga_provision, ProvisionKind::Regular),
-// 			(PROVISION_USER2_ID, KSMId::get(), ksm_provision, ProvisionKind::Vested(1, 150)),
-// 		]);
+		// ACT
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), mga_provision, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, KSMId::get(), ksm_provision, ProvisionKind::Vested(1, 150)),
+		]);
 
-// 		Bootstrap::on_initialize(100_u32.into());
-// 		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-// 		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
-// 	});
-// }
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER2_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
+	});
+}
 
-// #[test]
-// #[serial]
-// fn test_activate_liquidity_tokens_is_called_only_with_non_vested_liq_tokens_when_pool_is_promoted()
-// {
-// 	new_test_ext().execute_with(|| {
-// 		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
-// 		set_up();
-// 		jump_to_public_phase();
-// 		init_mocks!();
+#[test]
+#[serial]
+fn test_activate_liquidity_tokens_is_called_only_with_non_vested_liq_tokens_when_pool_is_promoted()
+{
+	new_test_ext().execute_with(|| {
+		// ARRANGE - USER provides vested MGA tokens, ANOTHER_USER provides KSM tokens
+		set_up();
+		jump_to_public_phase();
+		init_mocks!();
 
-// 		let mga_provision = 1_000_000_u128;
-// 		let ksm_provision = 100_u128;
-// 		let expected_liq_tokens_amount = (mga_provision + ksm_provision) / 2;
-// 		let expected_liq_tokens_amount_per_user = expected_liq_tokens_amount / 2;
-// 		let expected_non_vested_liq_tokens_amount_per_user =
-// 			expected_liq_tokens_amount_per_user / 2;
+		let mga_provision = 1_000_000_u128;
+		let ksm_provision = 100_u128;
+		let expected_liq_tokens_amount = (mga_provision + ksm_provision) / 2;
+		let expected_liq_tokens_amount_per_user = expected_liq_tokens_amount / 2;
+		let expected_non_vested_liq_tokens_amount_per_user =
+			expected_liq_tokens_amount_per_user / 2;
 
-// 		let can_activate_mock = MockRewardsApi::can_activate_context();
-// 		can_activate_mock.expect().return_const(true);
+		let can_activate_mock = MockRewardsApi::can_activate_context();
+		can_activate_mock.expect().return_const(true);
 
-// 		let activate_liquidity_tokens = MockRewardsApi::activate_liquidity_tokens_context();
-// 		activate_liquidity_tokens.expect().returning(move |_, _, activated_amount| {
-// 			assert_eq!(expected_non_vested_liq_tokens_amount_per_user, activated_amount);
-// 			Ok(().into())
-// 		});
+		let activate_liquidity_tokens = MockRewardsApi::activate_liquidity_tokens_context();
+		activate_liquidity_tokens.expect().returning(move |_, _, activated_amount| {
+			assert_eq!(expected_non_vested_liq_tokens_amount_per_user, activated_amount);
+			Ok(().into())
+		});
 
-// 		// ACT
-// 		provisions(vec![
-// 			(PROVISION_USER1_ID, MGAId::get(), mga_provision, ProvisionKind::Regular),
-// 			(PROVISION_USER2_ID, KSMId::get(), ksm_provision / 2, ProvisionKind::Regular),
-// 			(PROVISION_USER2_ID, KSMId::get(), ksm_provision / 2, ProvisionKind::Vested(1, 150)),
-// 		]);
+		// ACT
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), mga_provision, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, KSMId::get(), ksm_provision / 2, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, KSMId::get(), ksm_provision / 2, ProvisionKind::Vested(1, 150)),
+		]);
 
-// 		Bootstrap::on_initialize(100_u32.into());
-// 		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-// 		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
-// 	});
-// }
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER2_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
+	});
+}
 
 #[test]
 #[serial]
@@ -2170,10 +2407,18 @@ fn test_dont_activate_liquidity_tokens_when_pool_is_not_promoted_and_provisions_
 		]);
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER1_ID)).unwrap();
-		Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(PROVISION_USER2_ID)).unwrap();
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER1_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER2_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
 	});
 }
 
@@ -2204,11 +2449,12 @@ fn test_claim_and_activate_doesnt_fail_when_tokens_activations_fails() {
 			(PROVISION_USER2_ID, KSMId::get(), ksm_provision, ProvisionKind::Regular),
 		]);
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 
-		assert_ok!(Bootstrap::claim_and_activate_liquidity_tokens(Origin::signed(
-			PROVISION_USER1_ID
-		)));
+		assert_ok!(Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER1_ID),
+			BOOTSTRAP_ID
+		));
 	});
 }
 
@@ -2250,17 +2496,18 @@ fn test_pool_is_promoted_if_scheduled_to() {
 			10,
 			Some(DEFAULT_RATIO),
 			true,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(110_u32.into());
-		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(120_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
 	});
 }
 
@@ -2302,16 +2549,1268 @@ fn test_pool_is_not_promoted_if_not_scheduled_to() {
 			10,
 			Some(DEFAULT_RATIO),
 			false,
+			None,
 		)
 		.unwrap();
 
 		Bootstrap::on_initialize(100_u32.into());
-		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(110_u32.into());
-		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
 
 		Bootstrap::on_initialize(120_u32.into());
-		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn test_concurrent_bootstraps_have_separate_state() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		let pool_create_mock = MockPoolCreateApi::pool_create_context();
+		pool_create_mock.expect().times(2).returning(
+			move |addr, _, first_amount, _, second_amount| {
+				let issuance = (first_amount + second_amount) / 2;
+				let id = Bootstrap::create_new_token(&addr, issuance);
+				Some((id, issuance))
+			},
+		);
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			100_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			DUMMY_ID,
+			MGAId::get(),
+			110_u32.into(),
+			None,
+			20,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::BootstrapScheduled(
+				BOOTSTRAP_ID,
+				KSMId::get(),
+				MGAId::get()
+			))));
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::BootstrapScheduled(
+				OTHER_BOOTSTRAP_ID,
+				DUMMY_ID,
+				MGAId::get()
+			))));
+		assert_eq!(Bootstrap::next_bootstrap_id(), 2);
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Whitelist);
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
+
+		Bootstrap::whitelist_accounts(Origin::root(), OTHER_BOOTSTRAP_ID, vec![USER_ID], None)
+			.unwrap();
+		assert!(!Bootstrap::is_whitelisted(BOOTSTRAP_ID, &USER_ID));
+		assert!(Bootstrap::is_whitelisted(OTHER_BOOTSTRAP_ID, &USER_ID));
+
+		Bootstrap::on_initialize(110_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Public);
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::Public);
+
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), OTHER_BOOTSTRAP_ID, KSMId::get(), 1),
+			Error::<Test>::UnsupportedTokenId
+		);
+
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 100_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 10).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), OTHER_BOOTSTRAP_ID, MGAId::get(), 200_000)
+			.unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), OTHER_BOOTSTRAP_ID, DUMMY_ID, 20).unwrap();
+
+		assert_eq!(Bootstrap::valuations(BOOTSTRAP_ID), (100_000, 10));
+		assert_eq!(Bootstrap::valuations(OTHER_BOOTSTRAP_ID), (200_000, 20));
+		assert_eq!(Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())), 100_000);
+		assert_eq!(Bootstrap::provisions(OTHER_BOOTSTRAP_ID, (USER_ID, MGAId::get())), 200_000);
+
+		Bootstrap::on_initialize(120_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Finished);
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::Public);
+
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		assert_err!(
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), OTHER_BOOTSTRAP_ID),
+			Error::<Test>::NotFinishedYet
+		);
+
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100).unwrap();
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::Public);
+		assert_eq!(Bootstrap::valuations(OTHER_BOOTSTRAP_ID), (200_000, 20));
+		assert_eq!(Bootstrap::provisions(OTHER_BOOTSTRAP_ID, (USER_ID, MGAId::get())), 200_000);
+		assert!(Bootstrap::is_whitelisted(OTHER_BOOTSTRAP_ID, &USER_ID));
+
+		Bootstrap::on_initialize(130_u32.into());
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::Finished);
+
+		let (liq_token_id, liq_token_amount) = Bootstrap::minted_liquidity(OTHER_BOOTSTRAP_ID);
+		Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), OTHER_BOOTSTRAP_ID).unwrap();
+		assert_eq!(Bootstrap::balance(liq_token_id, USER_ID), liq_token_amount);
+	});
+}
+
+#[test]
+#[serial]
+fn test_pairs_of_all_scheduled_bootstraps_are_disallowed() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			100_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			DUMMY_ID,
+			MGAId::get(),
+			100_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+
+		assert!(<Bootstrap as Contains<_>>::contains(&(KSMId::get(), MGAId::get())));
+		assert!(<Bootstrap as Contains<_>>::contains(&(MGAId::get(), KSMId::get())));
+		assert!(<Bootstrap as Contains<_>>::contains(&(MGAId::get(), DUMMY_ID)));
+		assert!(!<Bootstrap as Contains<_>>::contains(&(KSMId::get(), DUMMY_ID)));
+
+		Bootstrap::cancel_bootstrap(Origin::root(), OTHER_BOOTSTRAP_ID).unwrap();
+		assert!(!<Bootstrap as Contains<_>>::contains(&(MGAId::get(), DUMMY_ID)));
+		assert!(<Bootstrap as Contains<_>>::contains(&(KSMId::get(), MGAId::get())));
+	});
+}
+
+#[test]
+#[serial]
+fn test_number_of_active_bootstraps_is_limited() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		let first_token_id = Bootstrap::create_new_token(&USER_ID, INITIAL_AMOUNT);
+		let second_token_id = Bootstrap::create_new_token(&USER_ID, INITIAL_AMOUNT);
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		for token_id in [KSMId::get(), DUMMY_ID, first_token_id] {
+			Bootstrap::schedule_bootstrap(
+				Origin::root(),
+				token_id,
+				MGAId::get(),
+				100_u32.into(),
+				Some(10),
+				10,
+				Some(DEFAULT_RATIO),
+				false,
+				None,
+			)
+			.unwrap();
+		}
+		assert_eq!(
+			BootstrapSchedule::<Test>::iter_keys().count(),
+			<Test as Config>::MaxActiveBootstraps::get() as usize
+		);
+
+		assert_err!(
+			Bootstrap::schedule_bootstrap(
+				Origin::root(),
+				second_token_id,
+				MGAId::get(),
+				100_u32.into(),
+				Some(10),
+				10,
+				Some(DEFAULT_RATIO),
+				false,
+				None,
+			),
+			Error::<Test>::TooManyActiveBootstraps
+		);
+
+		// already scheduled bootstrap can still be updated
+		assert_ok!(Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			200_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		));
+		assert_eq!(
+			Some((200_u32.into(), 10_u32, 10_u32, DEFAULT_RATIO)),
+			Bootstrap::config(BOOTSTRAP_ID)
+		);
+
+		Bootstrap::cancel_bootstrap(Origin::root(), BOOTSTRAP_ID).unwrap();
+		assert_ok!(Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			second_token_id,
+			MGAId::get(),
+			100_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		));
+		assert_eq!(Bootstrap::pair(3), Some((second_token_id, MGAId::get())));
+	});
+}
+
+#[test]
+#[serial]
+fn test_whitelisting_requires_scheduled_bootstrap() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		assert_err!(
+			Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![USER_ID], None),
+			Error::<Test>::BootstrapNotSchduled
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_bootstrap_finish_is_postponed_when_it_does_not_fit_into_weight_budget() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		let pool_create_mock = MockPoolCreateApi::pool_create_context();
+		pool_create_mock.expect().times(2).returning(
+			move |addr, _, first_amount, _, second_amount| {
+				let issuance = (first_amount + second_amount) / 2;
+				let id = Bootstrap::create_new_token(&addr, issuance);
+				Some((id, issuance))
+			},
+		);
+
+		InitializeWeightBudget::set(Weight::from_ref_time(1));
+
+		for token_id in [KSMId::get(), DUMMY_ID] {
+			Bootstrap::schedule_bootstrap(
+				Origin::root(),
+				token_id,
+				MGAId::get(),
+				100_u32.into(),
+				Some(10),
+				10,
+				Some(DEFAULT_RATIO),
+				false,
+				None,
+			)
+			.unwrap();
+		}
+
+		Bootstrap::on_initialize(110_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Public);
+		assert_eq!(Bootstrap::phase(OTHER_BOOTSTRAP_ID), BootstrapPhase::Public);
+
+		System::set_block_number(120);
+		let consumed_weight = Bootstrap::on_initialize(120_u32.into());
+		assert_eq!(consumed_weight, <Test as Config>::WeightInfo::finish_bootstrap());
+
+		let postponed = [BOOTSTRAP_ID, OTHER_BOOTSTRAP_ID]
+			.into_iter()
+			.filter(|id| Bootstrap::phase(*id) != BootstrapPhase::Finished)
+			.collect::<Vec<_>>();
+		assert_eq!(postponed.len(), 1);
+		assert_eq!(Bootstrap::phase(postponed[0]), BootstrapPhase::Public);
+
+		// bootstrap is over even if it was not finished yet
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), postponed[0], MGAId::get(), 1),
+			Error::<Test>::Unauthorized
+		);
+
+		System::set_block_number(121);
+		Bootstrap::on_initialize(121_u32.into());
+		assert_eq!(Bootstrap::phase(postponed[0]), BootstrapPhase::Finished);
+	});
+}
+
+#[test]
+#[serial]
+fn migration_moves_bootstrap_state_under_bootstrap_id() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::GetStorageVersion;
+		StorageVersion::new(2).put::<Bootstrap>();
+
+		migrations::v3::ActivePair::<Test>::put((KSMId::get(), MGAId::get()));
+		migrations::v3::BootstrapSchedule::<Test>::put((100_u64, 10_u32, 10_u32, DEFAULT_RATIO));
+		migrations::v3::Phase::<Test>::put(BootstrapPhase::BeforeStart);
+		migrations::v3::PromoteBootstrapPool::<Test>::put(true);
+
+		migrations::v3::migrate::<Test>();
+
+		assert_eq!(Bootstrap::on_chain_storage_version(), 3);
+		assert_eq!(Bootstrap::next_bootstrap_id(), 1);
+		assert_eq!(Bootstrap::pair(BOOTSTRAP_ID), Some((KSMId::get(), MGAId::get())));
+		assert_eq!(Bootstrap::config(BOOTSTRAP_ID), Some((100_u64, 10_u32, 10_u32, DEFAULT_RATIO)));
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
+		assert!(Bootstrap::get_promote_bootstrap_pool(BOOTSTRAP_ID));
+		assert_eq!(MintedLiquidity::<Test>::iter().count(), 0);
+		assert!(migrations::v3::BootstrapSchedule::<Test>::get().is_none());
+	});
+}
+
+#[test]
+#[serial]
+fn migration_leaves_storage_of_not_finalized_bootstrap_untouched() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::GetStorageVersion;
+		StorageVersion::new(2).put::<Bootstrap>();
+
+		migrations::v3::ActivePair::<Test>::put((KSMId::get(), MGAId::get()));
+		migrations::v3::BootstrapSchedule::<Test>::put((100_u64, 10_u32, 10_u32, DEFAULT_RATIO));
+		migrations::v3::Phase::<Test>::put(BootstrapPhase::Public);
+		migrations::v3::Provisions::<Test>::insert(USER_ID, MGAId::get(), 1_000);
+
+		migrations::v3::migrate::<Test>();
+
+		assert_eq!(Bootstrap::on_chain_storage_version(), 2);
+		assert_eq!(Bootstrap::pair(BOOTSTRAP_ID), None);
+		assert_eq!(migrations::v3::Phase::<Test>::get(), Some(BootstrapPhase::Public));
+		assert_eq!(migrations::v3::Provisions::<Test>::get(USER_ID, MGAId::get()), Some(1_000));
+	});
+}
+
+#[test]
+#[serial]
+fn test_bootstrap_fails_when_min_valuations_are_not_reached() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		let pool_create_mock = MockPoolCreateApi::pool_create_context();
+		pool_create_mock.expect().times(0);
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			Some((100, 1_000_000)),
+		)
+		.unwrap();
+		assert_eq!(Bootstrap::min_valuations(BOOTSTRAP_ID), (100, 1_000_000));
+		Bootstrap::on_initialize(25_u32.into());
+
+		let mga_initial_balance = Bootstrap::balance(MGAId::get(), USER_ID);
+		let ksm_initial_balance = Bootstrap::balance(KSMId::get(), USER_ID);
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 500_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 50).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Failed);
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::BootstrapFailed(BOOTSTRAP_ID))));
+
+		assert_err!(
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::NotFinishedYet
+		);
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::Unauthorized
+		);
+
+		Bootstrap::withdraw_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		assert_eq!(mga_initial_balance, Bootstrap::balance(MGAId::get(), USER_ID));
+		assert_eq!(ksm_initial_balance, Bootstrap::balance(KSMId::get(), USER_ID));
+		assert_eq!(0, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+		assert_eq!(0, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, KSMId::get())));
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::ProvisionWithdrawn(
+				BOOTSTRAP_ID,
+				MGAId::get(),
+				500_000
+			))));
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::ProvisionWithdrawn(
+				BOOTSTRAP_ID,
+				KSMId::get(),
+				50
+			))));
+
+		assert_err!(
+			Bootstrap::withdraw_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::NothingToWithdraw
+		);
+
+		// tokens provisioned to other bootstraps are not touched during finalization
+		Bootstrap::transfer(MGAId::get(), USER_ID, Bootstrap::vault_address(), 1_000).unwrap();
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100).unwrap();
+		assert_eq!(1_000, Bootstrap::balance(MGAId::get(), Bootstrap::vault_address()));
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
+		assert_eq!(Bootstrap::config(BOOTSTRAP_ID), None);
+		assert_eq!(Bootstrap::min_valuations(BOOTSTRAP_ID), (0, 0));
+	});
+}
+
+#[test]
+#[serial]
+fn test_bootstrap_fails_when_pool_cannot_be_created() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		// pool creation fails after part of provisioned tokens was already moved out of vault
+		let pool_create_mock = MockPoolCreateApi::pool_create_context();
+		pool_create_mock
+			.expect()
+			.times(1)
+			.returning(|vault, first, first_amount, _, _| {
+				Bootstrap::transfer(first, vault, ANOTHER_USER_ID, first_amount).unwrap();
+				None
+			});
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::on_initialize(25_u32.into());
+
+		let mga_initial_balance = Bootstrap::balance(MGAId::get(), USER_ID);
+		let ksm_initial_balance = Bootstrap::balance(KSMId::get(), USER_ID);
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 500_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 50).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Failed);
+		assert!(!MintedLiquidity::<Test>::contains_key(BOOTSTRAP_ID));
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::BootstrapFailed(BOOTSTRAP_ID))));
+
+		assert_err!(
+			Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::NotFinishedYet
+		);
+
+		Bootstrap::withdraw_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+		assert_eq!(mga_initial_balance, Bootstrap::balance(MGAId::get(), USER_ID));
+		assert_eq!(ksm_initial_balance, Bootstrap::balance(KSMId::get(), USER_ID));
+
+		// vault is not swept as no liquidity tokens were minted
+		Bootstrap::transfer(MGAId::get(), USER_ID, Bootstrap::vault_address(), 1_000).unwrap();
+		Bootstrap::transfer(KSMId::get(), USER_ID, Bootstrap::vault_address(), 1_000).unwrap();
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100).unwrap();
+		assert_eq!(1_000, Bootstrap::balance(MGAId::get(), Bootstrap::vault_address()));
+		assert_eq!(1_000, Bootstrap::balance(KSMId::get(), Bootstrap::vault_address()));
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::BeforeStart);
+	});
+}
+
+#[test]
+#[serial]
+fn test_withdraw_provisions_of_failed_bootstrap_locks_vested_provisions_again() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			Some((0, 1_000_000)),
+		)
+		.unwrap();
+		Bootstrap::on_initialize(25_u32.into());
+
+		let provision_amount = 10_000;
+		<Test as Config>::VestingProvider::lock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			provision_amount,
+			Some(1_u32.into()),
+			150_u32.into(),
+		)
+		.unwrap();
+		let non_vested_initial_amount = Bootstrap::balance(MGAId::get(), USER_ID);
+
+		Bootstrap::provision_vested(
+			Origin::signed(USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			provision_amount,
+		)
+		.unwrap();
+		let (_, lock_start, lock_end) =
+			Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get()));
+		assert_eq!(0, Bootstrap::locked_balance(MGAId::get(), USER_ID));
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Failed);
+
+		Bootstrap::withdraw_provisions_for_account(
+			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
+			USER_ID,
+		)
+		.unwrap();
+
+		assert_eq!(non_vested_initial_amount, Bootstrap::balance(MGAId::get(), USER_ID));
+		assert_eq!(provision_amount, Bootstrap::locked_balance(MGAId::get(), USER_ID));
+		assert_eq!((0, 0, 0), Bootstrap::vested_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+
+		let (unlocked_start, unlocked_end) = <Test as Config>::VestingProvider::unlock_tokens(
+			&USER_ID,
+			MGAId::get(),
+			provision_amount,
+		)
+		.unwrap();
+		assert_eq!((lock_start, lock_end), (unlocked_start.saturated_into::<u128>(), unlocked_end));
+
+		assert_ok!(Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100));
+	});
+}
+
+#[test]
+#[serial]
+fn test_bootstrap_finishes_when_min_valuations_are_reached() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		init_mocks!();
+
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			Some((50, 500_000)),
+		)
+		.unwrap();
+		Bootstrap::on_initialize(25_u32.into());
+
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 500_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 50).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(Bootstrap::phase(BOOTSTRAP_ID), BootstrapPhase::Finished);
+
+		assert_err!(
+			Bootstrap::withdraw_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::BootstrapNotFailed
+		);
+		assert_ok!(Bootstrap::claim_liquidity_tokens(Origin::signed(USER_ID), BOOTSTRAP_ID));
+	});
+}
+
+fn schedule_bootstrap_with_lockup_bonus(lockup_bonus: (u32, (u128, u128))) {
+	let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+	pool_exists_mock.expect().return_const(false);
+
+	Bootstrap::schedule_bootstrap(
+		Origin::root(),
+		KSMId::get(),
+		MGAId::get(),
+		10_u32.into(),
+		Some(10),
+		10,
+		Some(DEFAULT_RATIO),
+		false,
+		None,
+	)
+	.unwrap();
+	Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some(lockup_bonus)).unwrap();
+}
+
+#[test]
+#[serial]
+fn test_update_lockup_bonus() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		assert_err!(
+			Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((100, (3, 2)))),
+			Error::<Test>::BootstrapNotSchduled
+		);
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+
+		assert_err!(
+			Bootstrap::update_lockup_bonus(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				Some((100, (3, 2)))
+			),
+			BadOrigin
+		);
+		assert_err!(
+			Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((0, (3, 2)))),
+			Error::<Test>::LockupLengthCannotBeZero
+		);
+		assert_err!(
+			Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((100, (1, 2)))),
+			Error::<Test>::WrongRatio
+		);
+		assert_err!(
+			Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((100, (1, 0)))),
+			Error::<Test>::WrongRatio
+		);
+
+		Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((100, (3, 2)))).unwrap();
+		assert_eq!(Bootstrap::lockup_bonus(BOOTSTRAP_ID), Some((100, (3, 2))));
+
+		Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, None).unwrap();
+		assert_eq!(Bootstrap::lockup_bonus(BOOTSTRAP_ID), None);
+
+		Bootstrap::on_initialize(15_u32.into());
+		assert_err!(
+			Bootstrap::update_lockup_bonus(Origin::root(), BOOTSTRAP_ID, Some((100, (3, 2)))),
+			Error::<Test>::AlreadyStarted
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_lock_up_provisions_is_only_allowed_once_when_lockup_bonus_is_set() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_public_phase();
+
+		assert_err!(
+			Bootstrap::lock_up_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::LockupNotAvailable
+		);
+	});
+
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+
+		assert_err!(
+			Bootstrap::lock_up_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::Unauthorized
+		);
+
+		Bootstrap::on_initialize(25_u32.into());
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1_000).unwrap();
+		Bootstrap::lock_up_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID).unwrap();
+
+		assert!(Bootstrap::locked_up_accounts(BOOTSTRAP_ID, USER_ID).is_some());
+		assert_eq!(500, Bootstrap::lockup_bonus_provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+		assert_eq!((500, 0), Bootstrap::lockup_bonus_valuations(BOOTSTRAP_ID));
+		assert!(System::events().iter().any(|record| record.event ==
+			crate::mock::Event::Bootstrap(crate::Event::<Test>::ProvisionsLockedUp(
+				BOOTSTRAP_ID,
+				USER_ID
+			))));
+
+		assert_err!(
+			Bootstrap::lock_up_provisions(Origin::signed(USER_ID), BOOTSTRAP_ID),
+			Error::<Test>::AlreadyLockedUp
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_locked_up_provisions_get_bonus_share_of_locked_liquidity_tokens() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 500_000, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, MGAId::get(), 500_000, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+		// provisions made after locking up are increased by bonus as well
+		provisions(vec![
+			(PROVISION_USER1_ID, KSMId::get(), 50, ProvisionKind::Regular),
+			(PROVISION_USER2_ID, KSMId::get(), 50, ProvisionKind::Regular),
+		]);
+		assert_eq!((1_000_000, 100), Bootstrap::valuations(BOOTSTRAP_ID));
+		assert_eq!((250_000, 25), Bootstrap::lockup_bonus_valuations(BOOTSTRAP_ID));
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+		let (_, minted_liquidity) = Bootstrap::minted_liquidity(BOOTSTRAP_ID);
+		assert_eq!(500_050, minted_liquidity);
+
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER2_ID), BOOTSTRAP_ID)
+			.unwrap();
+
+		// 3/2 of the share of participant that did not lock up
+		assert_eq!(0, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
+		assert_eq!(300_030, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+		assert_eq!(200_020, Bootstrap::balance(liq_token_id, PROVISION_USER2_ID));
+		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER2_ID));
+		assert_eq!(0, Bootstrap::balance(liq_token_id, Bootstrap::vault_address()));
+
+		let (unlocked_start, unlocked_end) = <Test as Config>::VestingProvider::unlock_tokens(
+			&PROVISION_USER1_ID,
+			liq_token_id,
+			300_030,
+		)
+		.unwrap();
+		assert_eq!((129, 130), (unlocked_start.saturated_into::<u128>(), unlocked_end));
+
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100).unwrap();
+		assert_eq!(None, Bootstrap::lockup_bonus(BOOTSTRAP_ID));
+		assert_eq!((0, 0), Bootstrap::lockup_bonus_valuations(BOOTSTRAP_ID));
+		assert_eq!(None, Bootstrap::locked_up_accounts(BOOTSTRAP_ID, PROVISION_USER1_ID));
+		assert_eq!(
+			0,
+			Bootstrap::lockup_bonus_provisions(BOOTSTRAP_ID, (PROVISION_USER1_ID, MGAId::get()))
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_locked_up_liquidity_tokens_are_unlocked_at_once_at_the_end_of_lockup() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Regular),
+			(PROVISION_USER1_ID, KSMId::get(), 100, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		assert_eq!(500_050, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		// lockup lasts from 30 till 130
+		System::set_block_number(80);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		assert_eq!(500_050, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		System::set_block_number(129);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		assert_eq!(500_050, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		System::set_block_number(130);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+		assert_eq!(500_050, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn test_vested_liquidity_tokens_of_locked_up_provisions_start_vesting_at_the_end_of_lockup() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Vested(1, 200)),
+			(PROVISION_USER1_ID, KSMId::get(), 100, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		assert_eq!(500_050, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		System::set_block_number(129);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		assert_eq!(500_050, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		// regular part is unlocked at once, vested part keeps vesting till its original end
+		System::set_block_number(130);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		let locked = Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID);
+		assert!(0 < locked && locked < 500_050);
+
+		System::set_block_number(200);
+		Vesting::vest(Origin::signed(PROVISION_USER1_ID), liq_token_id).unwrap();
+		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn test_bonus_of_vested_provisions_is_vested() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Vested(1, 200)),
+			(PROVISION_USER1_ID, KSMId::get(), 50, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+		provisions(vec![(PROVISION_USER1_ID, KSMId::get(), 50, ProvisionKind::Vested(1, 200))]);
+		assert_eq!(
+			500_000,
+			Bootstrap::lockup_bonus_vested_provisions(
+				BOOTSTRAP_ID,
+				(PROVISION_USER1_ID, MGAId::get())
+			)
+		);
+		assert_eq!(
+			25,
+			Bootstrap::lockup_bonus_vested_provisions(
+				BOOTSTRAP_ID,
+				(PROVISION_USER1_ID, KSMId::get())
+			)
+		);
+		assert_eq!(
+			50,
+			Bootstrap::lockup_bonus_provisions(BOOTSTRAP_ID, (PROVISION_USER1_ID, KSMId::get()))
+		);
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(500_050, Bootstrap::minted_liquidity(BOOTSTRAP_ID).1);
+		// bonus is split between regular and vested rewards same as provisions
+		let (rewards, vested_rewards, _) =
+			Bootstrap::calculate_rewards(BOOTSTRAP_ID, &PROVISION_USER1_ID, &KSMId::get()).unwrap();
+		assert_eq!((125_012, 125_012), (rewards, vested_rewards));
+		let (rewards, vested_rewards, _) =
+			Bootstrap::calculate_rewards(BOOTSTRAP_ID, &PROVISION_USER1_ID, &MGAId::get()).unwrap();
+		assert_eq!((0, 250_025), (rewards, vested_rewards));
+
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		assert_eq!(500_049, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+
+		Bootstrap::finalize(Origin::root(), BOOTSTRAP_ID, 100).unwrap();
+		assert_eq!(
+			0,
+			Bootstrap::lockup_bonus_vested_provisions(
+				BOOTSTRAP_ID,
+				(PROVISION_USER1_ID, MGAId::get())
+			)
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_liquidity_tokens_of_locked_up_provisions_are_not_activated() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+
+		let can_activate_mock = MockRewardsApi::can_activate_context();
+		can_activate_mock.expect().return_const(true);
+
+		let activate_liquidity_tokens = MockRewardsApi::activate_liquidity_tokens_context();
+		activate_liquidity_tokens.expect().times(0);
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Regular),
+			(PROVISION_USER1_ID, KSMId::get(), 100, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		assert_eq!(BootstrapPhase::Finished, Phase::<Test>::get(BOOTSTRAP_ID));
+
+		Bootstrap::claim_and_activate_liquidity_tokens(
+			Origin::signed(PROVISION_USER1_ID),
+			BOOTSTRAP_ID,
+		)
+		.unwrap();
+	});
+}
+
+#[test]
+#[serial]
+fn test_liquidity_tokens_are_not_locked_when_lockup_is_over() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		schedule_bootstrap_with_lockup_bonus((100, (3, 2)));
+		Bootstrap::on_initialize(25_u32.into());
+		init_mocks!();
+		let liq_token_id = Tokens::next_asset_id();
+
+		provisions(vec![
+			(PROVISION_USER1_ID, MGAId::get(), 1_000_000, ProvisionKind::Regular),
+			(PROVISION_USER1_ID, KSMId::get(), 100, ProvisionKind::Regular),
+		]);
+		Bootstrap::lock_up_provisions(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID).unwrap();
+
+		Bootstrap::on_initialize(100_u32.into());
+		System::set_block_number(130);
+
+		Bootstrap::claim_liquidity_tokens(Origin::signed(PROVISION_USER1_ID), BOOTSTRAP_ID)
+			.unwrap();
+		assert_eq!(500_050, Bootstrap::balance(liq_token_id, PROVISION_USER1_ID));
+		assert_eq!(0, Bootstrap::locked_balance(liq_token_id, PROVISION_USER1_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn test_update_provision_caps() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		assert_err!(
+			Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000))),
+			Error::<Test>::BootstrapNotSchduled
+		);
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+
+		assert_err!(
+			Bootstrap::update_provision_caps(
+				Origin::signed(USER_ID),
+				BOOTSTRAP_ID,
+				Some((10, 100_000))
+			),
+			BadOrigin
+		);
+
+		Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000)))
+			.unwrap();
+		assert_eq!(Bootstrap::provision_caps(BOOTSTRAP_ID), Some((10, 100_000)));
+
+		Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, None).unwrap();
+		assert_eq!(Bootstrap::provision_caps(BOOTSTRAP_ID), None);
+
+		Bootstrap::on_initialize(15_u32.into());
+		assert_err!(
+			Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000))),
+			Error::<Test>::AlreadyStarted
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_whitelist_allowance_limits_provisions_in_whitelist_phase() {
+	new_test_ext().execute_with(|| {
+		set_up();
+		jump_to_whitelist_phase();
+
+		Bootstrap::whitelist_accounts(
+			Origin::root(),
+			BOOTSTRAP_ID,
+			vec![USER_ID],
+			Some((10, 100_000)),
+		)
+		.unwrap();
+		assert_eq!(Bootstrap::whitelist_allowances(BOOTSTRAP_ID, USER_ID), Some((10, 100_000)));
+
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 100_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 10).unwrap();
+
+		let mga_balance = Bootstrap::balance(MGAId::get(), USER_ID);
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::WhitelistAllowanceExceeded
+		);
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
+			Error::<Test>::WhitelistAllowanceExceeded
+		);
+		assert_eq!(mga_balance, Bootstrap::balance(MGAId::get(), USER_ID));
+		assert_eq!((100_000, 10), Bootstrap::valuations(BOOTSTRAP_ID));
+
+		// whitelisting account again overrides its allowance
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![USER_ID], None).unwrap();
+		assert_eq!(Bootstrap::whitelist_allowances(BOOTSTRAP_ID, USER_ID), None);
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1).unwrap();
+
+		Bootstrap::whitelist_accounts(
+			Origin::root(),
+			BOOTSTRAP_ID,
+			vec![USER_ID],
+			Some((10, 100_001)),
+		)
+		.unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::WhitelistAllowanceExceeded
+		);
+
+		// allowance does not apply in public phase
+		Bootstrap::on_initialize(25_u32.into());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1).unwrap();
+		assert_eq!(100_002, Bootstrap::provisions(BOOTSTRAP_ID, (USER_ID, MGAId::get())));
+	});
+}
+
+#[test]
+#[serial]
+fn test_provision_caps_limit_regular_and_vested_provisions_together() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000)))
+			.unwrap();
+		Bootstrap::on_initialize(25_u32.into());
+		assert_eq!(BootstrapPhase::Public, Phase::<Test>::get(BOOTSTRAP_ID));
+
+		Bootstrap::transfer(MGAId::get(), USER_ID, ANOTHER_USER_ID, 200_000).unwrap();
+		<Test as Config>::VestingProvider::lock_tokens(
+			&ANOTHER_USER_ID,
+			MGAId::get(),
+			40_000,
+			Some(1_u32.into()),
+			150_u32.into(),
+		)
+		.unwrap();
+
+		Bootstrap::provision_vested(
+			Origin::signed(ANOTHER_USER_ID),
+			BOOTSTRAP_ID,
+			MGAId::get(),
+			40_000,
+		)
+		.unwrap();
+
+		let mga_balance = Bootstrap::balance(MGAId::get(), ANOTHER_USER_ID);
+		assert_err!(
+			Bootstrap::provision(
+				Origin::signed(ANOTHER_USER_ID),
+				BOOTSTRAP_ID,
+				MGAId::get(),
+				60_001
+			),
+			Error::<Test>::ProvisionCapExceeded
+		);
+		assert_eq!(mga_balance, Bootstrap::balance(MGAId::get(), ANOTHER_USER_ID));
+
+		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 60_000)
+			.unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 10).unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+		assert_eq!((100_000, 10), Bootstrap::valuations(BOOTSTRAP_ID));
+	});
+}
+
+#[test]
+#[serial]
+fn test_provision_caps_apply_in_whitelist_phase_to_accounts_without_allowance() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000)))
+			.unwrap();
+		Bootstrap::whitelist_accounts(
+			Origin::root(),
+			BOOTSTRAP_ID,
+			vec![USER_ID],
+			Some((20, 200_000)),
+		)
+		.unwrap();
+		Bootstrap::on_initialize(15_u32.into());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
+
+		// non whitelisted account can provision second token up to the cap
+		Bootstrap::transfer(MGAId::get(), USER_ID, ANOTHER_USER_ID, 200_000).unwrap();
+		Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 100_000)
+			.unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(ANOTHER_USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+
+		// allowance of whitelisted account takes precedence over the cap
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 200_000).unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::WhitelistAllowanceExceeded
+		);
+
+		// provisions made in whitelist phase count towards the cap in public phase
+		Bootstrap::on_initialize(25_u32.into());
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn test_provision_caps_apply_in_whitelist_phase_to_whitelisted_accounts_without_allowance() {
+	new_test_ext().execute_with(|| {
+		set_up();
+
+		let pool_exists_mock = MockPoolCreateApi::pool_exists_context();
+		pool_exists_mock.expect().return_const(false);
+		Bootstrap::schedule_bootstrap(
+			Origin::root(),
+			KSMId::get(),
+			MGAId::get(),
+			10_u32.into(),
+			Some(10),
+			10,
+			Some(DEFAULT_RATIO),
+			false,
+			None,
+		)
+		.unwrap();
+		Bootstrap::update_provision_caps(Origin::root(), BOOTSTRAP_ID, Some((10, 100_000)))
+			.unwrap();
+		Bootstrap::whitelist_accounts(Origin::root(), BOOTSTRAP_ID, vec![USER_ID], None).unwrap();
+		assert!(Bootstrap::is_whitelisted(BOOTSTRAP_ID, &USER_ID));
+		assert_eq!(Bootstrap::whitelist_allowances(BOOTSTRAP_ID, USER_ID), None);
+		Bootstrap::on_initialize(15_u32.into());
+		assert_eq!(BootstrapPhase::Whitelist, Phase::<Test>::get(BOOTSTRAP_ID));
+
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 100_000).unwrap();
+		Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 10).unwrap();
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, MGAId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+		assert_err!(
+			Bootstrap::provision(Origin::signed(USER_ID), BOOTSTRAP_ID, KSMId::get(), 1),
+			Error::<Test>::ProvisionCapExceeded
+		);
+		assert_eq!((100_000, 10), Bootstrap::valuations(BOOTSTRAP_ID));
 	});
 }
@@ -60,6 +60,9 @@ pub trait WeightInfo {
 	fn provision_vested() -> Weight;
 	fn claim_and_activate_liquidity_tokens() -> Weight;
 	fn finalize() -> Weight;
+	fn finish_bootstrap() -> Weight;
+	fn withdraw_provisions() -> Weight;
+	fn lock_up_provisions() -> Weight;
 }
 
 
@@ -68,21 +71,21 @@ impl WeightInfo for () {
 	fn schedule_bootstrap() -> Weight {
 		Weight::from_ref_time(26_587_000)
 			.saturating_add(RocksDbWeight::get().reads(4 as u64))
-			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
 	fn provision() -> Weight {
 		Weight::from_ref_time(89_870_000)
-			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
 			.saturating_add(RocksDbWeight::get().writes(6 as u64))
 	}
 	fn provision_vested() -> Weight {
 		Weight::from_ref_time(183_414_000)
-			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().reads(15 as u64))
 			.saturating_add(RocksDbWeight::get().writes(8 as u64))
 	}
 	fn claim_and_activate_liquidity_tokens() -> Weight {
 		Weight::from_ref_time(432_898_000)
-			.saturating_add(RocksDbWeight::get().reads(21 as u64))
+			.saturating_add(RocksDbWeight::get().reads(25 as u64))
 			.saturating_add(RocksDbWeight::get().writes(12 as u64))
 	}
 	fn finalize() -> Weight {
@@ -90,4 +93,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6 as u64))
 			.saturating_add(RocksDbWeight::get().writes(12 as u64))
 	}
+	fn finish_bootstrap() -> Weight {
+		Weight::from_ref_time(95_640_000)
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(14 as u64))
+	}
+	fn withdraw_provisions() -> Weight {
+		Weight::from_ref_time(104_310_000)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn lock_up_provisions() -> Weight {
+		Weight::from_ref_time(52_110_000)
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
 }
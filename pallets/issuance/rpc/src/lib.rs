@@ -0,0 +1,71 @@
+// Copyright (C) 2021 Mangata team
+
+use codec::Codec;
+pub use issuance_runtime_api::IssuanceApi as IssuanceRuntimeApi;
+use issuance_runtime_api::RpcRoundIssuance;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::sync::Arc;
+
+#[rpc(client, server)]
+pub trait IssuanceApi<BlockHash, ResponseTypeIssuanceProjection> {
+	#[method(name = "issuance_get_issuance_projection")]
+	fn get_issuance_projection(
+		&self,
+		from_round: u32,
+		rounds: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypeIssuanceProjection>;
+}
+
+pub struct Issuance<C, M> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<M>,
+}
+
+impl<C, P> Issuance<C, P> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+#[async_trait]
+impl<C, Block, Balance> IssuanceApiServer<<Block as BlockT>::Hash, Vec<RpcRoundIssuance<Balance>>>
+	for Issuance<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>,
+	C: HeaderBackend<Block>,
+	C::Api: IssuanceRuntimeApi<Block, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn get_issuance_projection(
+		&self,
+		from_round: u32,
+		rounds: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RpcRoundIssuance<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		api.get_issuance_projection(&at, from_round, rounds).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+}
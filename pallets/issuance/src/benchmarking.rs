@@ -99,5 +99,25 @@ benchmarks! {
 
 	}
 
+	set_pool_weight {
+		let liquidity_token_id: TokenId = 1;
+		assert!(Issuance::<T>::promote_pool(liquidity_token_id));
+	}: _(RawOrigin::Root, liquidity_token_id, 5)
+	verify {
+		assert_eq!(PromotedPoolsWeights::<T>::get(liquidity_token_id), Some(5));
+	}
+
+	set_issuance_schedule {
+		let x in 1..MAX_ISSUANCE_EPOCHS;
+
+		assert_ok!(Issuance::<T>::finalize_tge(RawOrigin::Root.into()));
+		assert_ok!(Issuance::<T>::init_issuance_config(RawOrigin::Root.into()));
+
+		let issuance_schedule: Vec<IssuanceEpoch> = (0..x).map(|_| IssuanceEpoch { rounds: 1, issuance_per_round: 1 }).collect();
+	}: _(RawOrigin::Root, issuance_schedule.clone())
+	verify {
+		assert_eq!(IssuanceConfigStore::<T>::get().unwrap().issuance_schedule, issuance_schedule);
+	}
+
 	impl_benchmark_test_suite!(Issuance, crate::mock::new_test_ext_without_issuance_config(), crate::mock::Test)
 }
@@ -31,6 +31,8 @@ mod tests;
 
 mod benchmarking;
 
+pub mod migrations;
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct IssuanceInfo {
@@ -47,6 +49,18 @@ pub struct IssuanceInfo {
 	pub staking_split: Perbill,
 	// The total mga allocated to crowdloan rewards
 	pub total_crowdloan_allocation: Balance,
+	// Epochs of issuance counted from round 0, linear issuance over linear_issuance_blocks
+	// is used if empty. After the last epoch its issuance per round is kept until cap is reached
+	pub issuance_schedule: Vec<IssuanceEpoch>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct IssuanceEpoch {
+	// Number of rounds the epoch lasts
+	pub rounds: u32,
+	// MGA issued in every round of the epoch
+	pub issuance_per_round: Balance,
 }
 
 #[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo)]
@@ -57,22 +71,36 @@ pub struct TgeInfo<A> {
 	pub amount: Balance,
 }
 
+/// Weight of promoted pool in liquidity mining issuance split, unless set otherwise
+pub const DEFAULT_POOL_WEIGHT: u8 = 1;
+
+/// Maximum number of epochs in issuance schedule
+pub const MAX_ISSUANCE_EPOCHS: u32 = 100;
+
+/// Maximum number of rounds returned by issuance projection
+pub const MAX_ISSUANCE_PROJECTION_ROUNDS: u32 = 10_000;
+
 pub trait PoolPromoteApi {
 	/// Returns true if pool was promoted, false if it has been promoted already
 	fn promote_pool(liquidity_token_id: TokenId) -> bool;
+	/// Returns true if pool was unpromoted, false if it was not promoted
 	fn unpromote_pool(liquidity_token_id: TokenId) -> bool;
 
 	/// Returns available reward for pool
 	fn get_pool_rewards_v2(liquidity_token_id: TokenId) -> Option<U256>;
 
-	fn len_v2() -> usize;
+	/// Returns weight of pool in liquidity mining issuance split, None if pool is not promoted
+	fn get_pool_weight(liquidity_token_id: TokenId) -> Option<u8>;
+
+	/// Returns sum of weights of all activated promoted pools, liquidity mining issuance
+	/// of a pool is its weight divided by this sum
+	fn get_total_pools_weight() -> u32;
 
-	//REWARDS V1 to be removed
-	fn claim_pool_rewards(liquidity_token_id: TokenId, claimed_amount: Balance) -> bool;
-	//REWARDS V1 to be removed
-	fn get_pool_rewards(liquidity_token_id: TokenId) -> Option<Balance>;
-	//REWARDS V1 to be removed
-	fn len() -> usize;
+	/// Returns liquidity mining issuance of pool in each of the recorded rounds,
+	/// as (round, issuance) ordered by round
+	fn get_pool_rewards_history(liquidity_token_id: TokenId) -> Vec<(u32, Balance)>;
+
+	fn len_v2() -> usize;
 }
 
 /// Weight functions needed for pallet_xyk.
@@ -80,6 +108,8 @@ pub trait WeightInfo {
 	fn init_issuance_config() -> Weight;
 	fn finalize_tge() -> Weight;
 	fn execute_tge(x: u32) -> Weight;
+	fn set_pool_weight() -> Weight;
+	fn set_issuance_schedule(x: u32) -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -102,6 +132,17 @@ impl WeightInfo for () {
 			// Standard Error: 1_000
 			.saturating_add((Weight::from_ref_time(130_000)).saturating_mul(l as u64))
 	}
+	// Storage: Issuance PromotedPoolsRewardsV2 (r:1 w:0)
+	// Storage: Issuance PromotedPoolsWeights (r:0 w:1)
+	fn set_pool_weight() -> Weight {
+		Weight::from_ref_time(19_530_000)
+	}
+	// Storage: Issuance IssuanceConfigStore (r:1 w:1)
+	fn set_issuance_schedule(x: u32) -> Weight {
+		Weight::from_ref_time(21_402_000)
+			// Standard Error: 1_000
+			.saturating_add((Weight::from_ref_time(96_000)).saturating_mul(x as u64))
+	}
 }
 
 pub trait ActivedPoolQueryApi {
@@ -115,13 +156,30 @@ pub mod pallet {
 
 	use super::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::v1::migrate::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::v1::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::v1::post_upgrade::<T>()
+		}
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -196,17 +254,30 @@ pub mod pallet {
 	pub type SessionIssuance<T: Config> =
 		StorageMap<_, Twox64Concat, u32, Option<(Balance, Balance)>, ValueQuery>;
 
-	//to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn get_promoted_pools_rewards)]
-	pub type PromotedPoolsRewards<T: Config> =
-		StorageMap<_, Twox64Concat, TokenId, Balance, ValueQuery>;
-
 	#[pallet::storage]
 	#[pallet::getter(fn get_promoted_pools_rewards_v2)]
 	pub type PromotedPoolsRewardsV2<T: Config> =
 		StorageMap<_, Twox64Concat, TokenId, U256, ValueQuery>;
 
+	/// Weights of promoted pools in liquidity mining issuance split,
+	/// promoted pools without entry have `DEFAULT_POOL_WEIGHT`
+	#[pallet::storage]
+	#[pallet::getter(fn get_promoted_pools_weights)]
+	pub type PromotedPoolsWeights<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, u8, OptionQuery>;
+
+	/// Liquidity mining issuance of promoted pools per round,
+	/// kept for the last `HistoryLimit` rounds as `SessionIssuance`
+	#[pallet::storage]
+	#[pallet::getter(fn get_pools_issuance_history)]
+	pub type PoolsIssuanceHistory<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, TokenId, Balance, ValueQuery>;
+
+	/// Oldest round of `PoolsIssuanceHistory` which is not pruned yet
+	#[pallet::storage]
+	#[pallet::getter(fn get_pools_issuance_history_prune_round)]
+	pub type PoolsIssuanceHistoryPruneRound<T: Config> = StorageValue<_, u32, OptionQuery>;
+
 	#[pallet::error]
 	/// Errors
 	pub enum Error<T> {
@@ -224,6 +295,8 @@ pub mod pallet {
 		MathError,
 		/// unknown pool
 		UnknownPool,
+		/// Pool weight has to be higher than zero
+		InvalidPoolWeight,
 	}
 
 	// XYK extrinsics.
@@ -303,6 +376,50 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Sets weight of promoted pool in liquidity mining issuance split, each activated
+		/// promoted pool receives share of issuance proportional to its weight
+		#[pallet::weight(T::WeightInfo::set_pool_weight())]
+		pub fn set_pool_weight(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			weight: u8,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			ensure!(
+				PromotedPoolsRewardsV2::<T>::contains_key(liquidity_token_id),
+				Error::<T>::UnknownPool
+			);
+			ensure!(!weight.is_zero(), Error::<T>::InvalidPoolWeight);
+
+			PromotedPoolsWeights::<T>::insert(liquidity_token_id, weight);
+
+			Pallet::<T>::deposit_event(Event::PoolWeightSet(liquidity_token_id, weight));
+
+			Ok(().into())
+		}
+
+		/// Replaces issuance schedule, epochs are counted from round 0 so already passed
+		/// epochs should be kept as they were. Issuance of all epochs together can not exceed
+		/// what is left to be issued up to cap, and no round issues more than is left up to cap
+		#[pallet::weight(T::WeightInfo::set_issuance_schedule(issuance_schedule.len() as u32))]
+		pub fn set_issuance_schedule(
+			origin: OriginFor<T>,
+			issuance_schedule: Vec<IssuanceEpoch>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let mut issuance_config =
+				IssuanceConfigStore::<T>::get().ok_or(Error::<T>::IssuanceConfigNotInitialized)?;
+			issuance_config.issuance_schedule = issuance_schedule.clone();
+
+			Pallet::<T>::build_issuance_config(issuance_config)?;
+
+			Pallet::<T>::deposit_event(Event::IssuanceScheduleUpdated(issuance_schedule));
+
+			Ok(().into())
+		}
 	}
 
 	#[pallet::event]
@@ -320,6 +437,10 @@ pub mod pallet {
 		TGEInstanceFailed(TgeInfo<T::AccountId>),
 		/// A TGE instance has succeeded
 		TGEInstanceSucceeded(TgeInfo<T::AccountId>),
+		/// Weight of promoted pool in liquidity mining issuance split has been set
+		PoolWeightSet(TokenId, u8),
+		/// Issuance schedule has been replaced
+		IssuanceScheduleUpdated(Vec<IssuanceEpoch>),
 	}
 }
 
@@ -354,41 +475,39 @@ impl<T: Config> PoolPromoteApi for Pallet<T> {
 		PromotedPoolsRewardsV2::<T>::try_get(liquidity_token_id).ok()
 	}
 
-	fn unpromote_pool(liquidity_token_id: TokenId) -> bool {
+	fn get_pool_weight(liquidity_token_id: TokenId) -> Option<u8> {
 		if PromotedPoolsRewardsV2::<T>::contains_key(liquidity_token_id) {
-			true
+			Some(Self::pool_weight(liquidity_token_id))
 		} else {
-			PromotedPoolsRewardsV2::<T>::remove(liquidity_token_id);
-			false
+			None
 		}
 	}
 
-	// TODO
-	// Specifically account for this in benchmarking
-	fn len() -> usize {
-		PromotedPoolsRewards::<T>::iter_keys().count()
+	fn get_total_pools_weight() -> u32 {
+		Self::activated_pools().iter().map(|(_, _, _, weight)| *weight as u32).sum()
 	}
 
-	fn len_v2() -> usize {
-		PromotedPoolsRewardsV2::<T>::iter_keys().count()
+	fn get_pool_rewards_history(liquidity_token_id: TokenId) -> Vec<(u32, Balance)> {
+		let mut rounds: Vec<u32> = SessionIssuance::<T>::iter_keys().collect();
+		rounds.sort_unstable();
+		rounds
+			.into_iter()
+			.map(|round| (round, PoolsIssuanceHistory::<T>::get(round, liquidity_token_id)))
+			.collect()
 	}
 
-	//REWARDS V1 to be removed
-	fn get_pool_rewards(liquidity_token_id: TokenId) -> Option<Balance> {
-		PromotedPoolsRewards::<T>::try_get(liquidity_token_id).ok()
+	fn unpromote_pool(liquidity_token_id: TokenId) -> bool {
+		if PromotedPoolsRewardsV2::<T>::contains_key(liquidity_token_id) {
+			PromotedPoolsRewardsV2::<T>::remove(liquidity_token_id);
+			PromotedPoolsWeights::<T>::remove(liquidity_token_id);
+			true
+		} else {
+			false
+		}
 	}
 
-	//REWARDS V1 to be removed
-	fn claim_pool_rewards(liquidity_token_id: TokenId, claimed_amount: Balance) -> bool {
-		PromotedPoolsRewards::<T>::try_mutate(liquidity_token_id, |rewards| {
-			if let Some(val) = rewards.checked_sub(claimed_amount) {
-				*rewards = val;
-				Ok(())
-			} else {
-				Err(())
-			}
-		})
-		.is_ok()
+	fn len_v2() -> usize {
+		PromotedPoolsRewardsV2::<T>::iter_keys().count()
 	}
 }
 
@@ -444,6 +563,7 @@ impl<T: Config> Pallet<T> {
 			liquidity_mining_split: T::LiquidityMiningSplit::get(),
 			staking_split: T::StakingSplit::get(),
 			total_crowdloan_allocation: T::TotalCrowdloanAllocation::get(),
+			issuance_schedule: Vec::new(),
 		};
 
 		Pallet::<T>::build_issuance_config(issuance_config.clone())?;
@@ -479,52 +599,152 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::IssuanceConfigInvalid
 		);
 		ensure!(T::BlocksPerRound::get() != u32::zero(), Error::<T>::IssuanceConfigInvalid);
+		ensure!(
+			issuance_config.issuance_schedule.len() <= MAX_ISSUANCE_EPOCHS as usize,
+			Error::<T>::IssuanceConfigInvalid
+		);
+		let mut scheduled_issuance: Balance = Zero::zero();
+		for epoch in issuance_config.issuance_schedule.iter() {
+			ensure!(!epoch.rounds.is_zero(), Error::<T>::IssuanceConfigInvalid);
+			scheduled_issuance = epoch
+				.issuance_per_round
+				.checked_mul(epoch.rounds as Balance)
+				.and_then(|x| x.checked_add(scheduled_issuance))
+				.ok_or(Error::<T>::IssuanceConfigInvalid)?;
+		}
+		ensure!(
+			scheduled_issuance <= Self::to_be_issued(&issuance_config)?,
+			Error::<T>::IssuanceConfigInvalid
+		);
 		IssuanceConfigStore::<T>::put(issuance_config.clone());
 		Ok(())
 	}
 
-	pub fn calculate_and_store_round_issuance(current_round: u32) -> DispatchResult {
-		let issuance_config =
-			IssuanceConfigStore::<T>::get().ok_or(Error::<T>::IssuanceConfigNotInitialized)?;
-		let to_be_issued: Balance = issuance_config
+	// MGA to be issued over all rounds, up to cap
+	fn to_be_issued(issuance_config: &IssuanceInfo) -> Result<Balance, DispatchError> {
+		Ok(issuance_config
 			.cap
 			.checked_sub(issuance_config.issuance_at_init)
 			.ok_or(Error::<T>::MathError)?
 			.checked_sub(issuance_config.total_crowdloan_allocation)
-			.ok_or(Error::<T>::MathError)?;
+			.ok_or(Error::<T>::MathError)?)
+	}
+
+	// Issuance schedule in effect, linear issuance is a single epoch lasting
+	// linear_issuance_blocks
+	fn issuance_epochs(
+		issuance_config: &IssuanceInfo,
+	) -> Result<Vec<IssuanceEpoch>, DispatchError> {
+		if !issuance_config.issuance_schedule.is_empty() {
+			return Ok(issuance_config.issuance_schedule.clone())
+		}
+
 		let linear_issuance_sessions: u32 = issuance_config
 			.linear_issuance_blocks
 			.checked_div(T::BlocksPerRound::get())
 			.ok_or(Error::<T>::MathError)?;
-		let linear_issuance_per_session = to_be_issued
+		let linear_issuance_per_session = Self::to_be_issued(issuance_config)?
 			.checked_div(linear_issuance_sessions as Balance)
 			.ok_or(Error::<T>::MathError)?;
 
-		let current_round_issuance: Balance;
-		// We do not want issuance to overshoot
-		// Sessions begin from 0 and linear_issuance_sessions is the total number of linear sessions including 0
+		Ok(vec![IssuanceEpoch {
+			rounds: linear_issuance_sessions,
+			issuance_per_round: linear_issuance_per_session,
+		}])
+	}
+
+	// Issuance of round, given total MGA issuance at its beginning
+	fn round_issuance(
+		issuance_config: &IssuanceInfo,
+		issuance_epochs: &[IssuanceEpoch],
+		round: u32,
+		current_mga_total_issuance: Balance,
+	) -> Result<Balance, DispatchError> {
+		// Sessions begin from 0 and epoch end is the total number of sessions including 0
 		// So we stop before that
-		if current_round < linear_issuance_sessions {
-			current_round_issuance = linear_issuance_per_session;
-		} else {
-			let current_mga_total_issuance: Balance =
-				T::Tokens::total_issuance(T::NativeCurrencyId::get().into()).into();
-			if issuance_config.cap > current_mga_total_issuance {
-				// TODO
-				// Here we assume that the crowdloan ends before linear issuance period ends
-				// We could get the amount that the crowdloan rewards still need to mint and account for that
-				// But that largely depends on on how the next crowdloan will be implemented
-				// Not very useful for the first crowdloan, as we know that it will end before linear issuance period ends and we check for this
-				current_round_issuance = linear_issuance_per_session.min(
-					issuance_config
-						.cap
-						.checked_sub(current_mga_total_issuance)
-						.ok_or(Error::<T>::MathError)?,
-				)
-			} else {
-				current_round_issuance = Zero::zero();
+		let mut epoch_end: u32 = 0;
+		let mut scheduled_issuance_per_round = None;
+		for epoch in issuance_epochs {
+			epoch_end = epoch_end.saturating_add(epoch.rounds);
+			if round < epoch_end {
+				scheduled_issuance_per_round = Some(epoch.issuance_per_round);
+				break
 			}
 		}
+		let issuance_per_round = scheduled_issuance_per_round.unwrap_or_else(|| {
+			issuance_epochs.last().map(|epoch| epoch.issuance_per_round).unwrap_or_default()
+		});
+
+		// We do not want issuance to overshoot, not even within the schedule as it may be
+		// replaced after some of its epochs have already been issued
+		if issuance_config.cap > current_mga_total_issuance {
+			// TODO
+			// Here we assume that the crowdloan ends before linear issuance period ends
+			// We could get the amount that the crowdloan rewards still need to mint and account for that
+			// But that largely depends on on how the next crowdloan will be implemented
+			// Not very useful for the first crowdloan, as we know that it will end before linear issuance period ends and we check for this
+			Ok(issuance_per_round.min(
+				issuance_config
+					.cap
+					.checked_sub(current_mga_total_issuance)
+					.ok_or(Error::<T>::MathError)?,
+			))
+		} else {
+			Ok(Zero::zero())
+		}
+	}
+
+	/// Projected issuance of `rounds` rounds starting at `from_round`, as
+	/// (round, liquidity mining issuance, staking issuance), assuming total MGA issuance
+	/// changes only by issuance of the projected rounds
+	pub fn project_issuance(from_round: u32, rounds: u32) -> Vec<(u32, Balance, Balance)> {
+		let mut projection = Vec::new();
+		let issuance_config = match IssuanceConfigStore::<T>::get() {
+			Some(issuance_config) => issuance_config,
+			None => return projection,
+		};
+		let issuance_epochs = match Self::issuance_epochs(&issuance_config) {
+			Ok(issuance_epochs) => issuance_epochs,
+			Err(_) => return projection,
+		};
+		let mut mga_total_issuance: Balance =
+			T::Tokens::total_issuance(T::NativeCurrencyId::get().into()).into();
+
+		let to_round = from_round.saturating_add(rounds.min(MAX_ISSUANCE_PROJECTION_ROUNDS));
+		for round in from_round..to_round {
+			let round_issuance = match Self::round_issuance(
+				&issuance_config,
+				&issuance_epochs,
+				round,
+				mga_total_issuance,
+			) {
+				Ok(round_issuance) => round_issuance,
+				Err(_) => break,
+			};
+			mga_total_issuance = mga_total_issuance.saturating_add(round_issuance);
+			projection.push((
+				round,
+				issuance_config.liquidity_mining_split * round_issuance,
+				issuance_config.staking_split * round_issuance,
+			));
+		}
+
+		projection
+	}
+
+	pub fn calculate_and_store_round_issuance(current_round: u32) -> DispatchResult {
+		let issuance_config =
+			IssuanceConfigStore::<T>::get().ok_or(Error::<T>::IssuanceConfigNotInitialized)?;
+		let issuance_epochs = Self::issuance_epochs(&issuance_config)?;
+		let current_mga_total_issuance: Balance =
+			T::Tokens::total_issuance(T::NativeCurrencyId::get().into()).into();
+
+		let current_round_issuance: Balance = Self::round_issuance(
+			&issuance_config,
+			&issuance_epochs,
+			current_round,
+			current_mga_total_issuance,
+		)?;
 
 		let liquidity_mining_issuance =
 			issuance_config.liquidity_mining_split * current_round_issuance;
@@ -532,26 +752,28 @@ impl<T: Config> Pallet<T> {
 		let staking_issuance = issuance_config.staking_split * current_round_issuance;
 
 		// benchmark with max of X prom pools
-		let activated_pools: Vec<_> = PromotedPoolsRewardsV2::<T>::iter()
-			.filter_map(|(token_id, rewards)| {
-				T::ActivedPoolQueryApiType::get_pool_activate_amount(token_id)
-					.map(|activated_amount| (token_id, rewards, activated_amount))
-			})
-			.collect();
-		let mut liquidity_mining_issuance_per_pool = liquidity_mining_issuance;
-
-		if activated_pools.len() > 0 {
-			liquidity_mining_issuance_per_pool /= activated_pools.len() as u128;
-		}
+		let activated_pools = Self::activated_pools();
+		let total_weight: u32 =
+			activated_pools.iter().map(|(_, _, _, weight)| *weight as u32).sum();
+
+		for (token_id, rewards, activated_amount, weight) in activated_pools {
+			let liquidity_mining_issuance_per_pool: U256 = U256::from(liquidity_mining_issuance)
+				.checked_mul(weight.into())
+				.and_then(|x| x.checked_div(total_weight.into()))
+				.ok_or_else(|| DispatchError::from(Error::<T>::MathError))?;
 
-		for (token_id, rewards, activated_amount) in activated_pools {
-			let rewards_per_liquidity: U256 = U256::from(liquidity_mining_issuance_per_pool)
+			let rewards_per_liquidity: U256 = liquidity_mining_issuance_per_pool
 				.checked_mul(U256::from(u128::MAX))
 				.and_then(|x| x.checked_div(activated_amount.into()))
 				.and_then(|x| x.checked_add(rewards.into()))
 				.ok_or_else(|| DispatchError::from(Error::<T>::MathError))?;
 
+			let pool_issuance: Balance = liquidity_mining_issuance_per_pool
+				.try_into()
+				.map_err(|_| DispatchError::from(Error::<T>::MathError))?;
+
 			PromotedPoolsRewardsV2::<T>::insert(token_id, rewards_per_liquidity);
+			PoolsIssuanceHistory::<T>::insert(current_round, token_id, pool_issuance);
 		}
 
 		{
@@ -586,10 +808,52 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn pool_weight(liquidity_token_id: TokenId) -> u8 {
+		PromotedPoolsWeights::<T>::get(liquidity_token_id).unwrap_or(DEFAULT_POOL_WEIGHT)
+	}
+
+	// Promoted pools with some liquidity activated, as (id, rewards, activated amount, weight)
+	fn activated_pools() -> Vec<(TokenId, U256, Balance, u8)> {
+		PromotedPoolsRewardsV2::<T>::iter()
+			.filter_map(|(token_id, rewards)| {
+				T::ActivedPoolQueryApiType::get_pool_activate_amount(token_id).map(
+					|activated_amount| {
+						(token_id, rewards, activated_amount, Self::pool_weight(token_id))
+					},
+				)
+			})
+			.collect()
+	}
+
 	pub fn clear_round_issuance_history(current_round: u32) -> DispatchResult {
 		if current_round >= T::HistoryLimit::get() {
 			SessionIssuance::<T>::remove(current_round - T::HistoryLimit::get());
 		}
+		Self::prune_pools_issuance_history(current_round);
 		Ok(())
 	}
+
+	// Removes pools issuance of rounds out of history, at most one more entry than there are
+	// promoted pools is removed per round, so rounds which recorded pools unpromoted since then
+	// are pruned over following rounds
+	fn prune_pools_issuance_history(current_round: u32) {
+		let oldest_kept_round =
+			current_round.saturating_add(1).saturating_sub(T::HistoryLimit::get());
+		let mut round = PoolsIssuanceHistoryPruneRound::<T>::get()
+			.unwrap_or_else(|| current_round.saturating_sub(T::HistoryLimit::get()));
+		let promoted_pools: u32 =
+			PromotedPoolsRewardsV2::<T>::iter_keys().count().try_into().unwrap_or(u32::MAX);
+		let mut limit = promoted_pools.saturating_add(1);
+
+		while round < oldest_kept_round && limit > 0 {
+			let removal = PoolsIssuanceHistory::<T>::clear_prefix(round, limit, None);
+			limit = limit.saturating_sub(removal.loops.max(1));
+			if removal.maybe_cursor.is_some() {
+				break
+			}
+			round = round.saturating_add(1);
+		}
+
+		PoolsIssuanceHistoryPruneRound::<T>::put(round);
+	}
 }
@@ -0,0 +1,99 @@
+// Copyright (C) 2022 Mangata team
+
+use super::*;
+
+pub mod v1 {
+	//! Adds issuance schedule to issuance config, configs stored before keep linear issuance.
+	//! Config which cannot be decoded is left as is and storage version is not bumped, so that
+	//! it can be fixed and migrated by a later upgrade.
+
+	use super::*;
+	use frame_support::traits::GetStorageVersion;
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+	#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+	pub struct OldIssuanceInfo {
+		pub cap: Balance,
+		pub issuance_at_init: Balance,
+		pub linear_issuance_blocks: u32,
+		pub liquidity_mining_split: Perbill,
+		pub staking_split: Perbill,
+		pub total_crowdloan_allocation: Balance,
+	}
+
+	impl From<OldIssuanceInfo> for IssuanceInfo {
+		fn from(old: OldIssuanceInfo) -> Self {
+			IssuanceInfo {
+				cap: old.cap,
+				issuance_at_init: old.issuance_at_init,
+				linear_issuance_blocks: old.linear_issuance_blocks,
+				liquidity_mining_split: old.liquidity_mining_split,
+				staking_split: old.staking_split,
+				total_crowdloan_allocation: old.total_crowdloan_allocation,
+				issuance_schedule: Vec::new(),
+			}
+		}
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let db_weight = T::DbWeight::get();
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return db_weight.reads(1)
+		}
+
+		let translated =
+			IssuanceConfigStore::<T>::translate::<OldIssuanceInfo, _>(|old| old.map(Into::into));
+		if translated.is_err() {
+			log::error!(
+				target: "issuance",
+				"issuance config could not be decoded, it is left as is and not migrated"
+			);
+			return db_weight.reads(2)
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!(target: "issuance", "issuance config migrated to storage version 1");
+
+		db_weight.reads_writes(2, 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return Ok(())
+		}
+
+		let old = frame_support::migration::get_storage_value::<OldIssuanceInfo>(
+			<Pallet<T> as PalletInfoAccess>::name().as_bytes(),
+			b"IssuanceConfigStore",
+			&[],
+		);
+		ensure!(
+			old.is_some() || !IssuanceConfigStore::<T>::exists(),
+			"issuance config cannot be decoded as config of storage version 0"
+		);
+		Pallet::<T>::set_temp_storage(old, "issuance_config_v0");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+		ensure!(
+			IssuanceConfigStore::<T>::get().is_some() || !IssuanceConfigStore::<T>::exists(),
+			"issuance config cannot be decoded"
+		);
+		ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version is not 1");
+
+		if let Some(old) =
+			Pallet::<T>::get_temp_storage::<Option<OldIssuanceInfo>>("issuance_config_v0")
+		{
+			ensure!(
+				IssuanceConfigStore::<T>::get() == old.map(Into::into),
+				"issuance config was not carried over"
+			);
+		}
+		Ok(())
+	}
+}
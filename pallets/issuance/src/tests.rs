@@ -5,7 +5,7 @@ use mock::{
 };
 use sp_runtime::SaturatedConversion;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::GetStorageVersion};
 
 #[test]
 fn init_issuance_config_works() {
@@ -26,6 +26,7 @@ fn init_issuance_config_works() {
 				liquidity_mining_split: Perbill::from_parts(555555556),
 				staking_split: Perbill::from_parts(444444444),
 				total_crowdloan_allocation: 200_000_000u128,
+				issuance_schedule: vec![],
 			})
 		);
 	});
@@ -73,6 +74,7 @@ fn cannot_init_issuance_config_when_already_init() {
 				liquidity_mining_split: Perbill::from_parts(555555556),
 				staking_split: Perbill::from_parts(444444444),
 				total_crowdloan_allocation: 200_000_000u128,
+				issuance_schedule: vec![],
 			})
 		);
 		assert_noop!(
@@ -389,6 +391,175 @@ fn issuance_after_linear_period_never_execeeds_linear() {
 	});
 }
 
+#[test]
+fn issuance_follows_issuance_schedule() {
+	new_test_ext().execute_with(|| {
+		let issuance_schedule = vec![
+			IssuanceEpoch { rounds: 10, issuance_per_round: 1_000_000 },
+			IssuanceEpoch { rounds: 10, issuance_per_round: 500_000 },
+			IssuanceEpoch { rounds: 10, issuance_per_round: 250_000 },
+		];
+		assert_ok!(Issuance::set_issuance_schedule(Origin::root(), issuance_schedule.clone()));
+		assert_eq!(Issuance::get_issuance_config().unwrap().issuance_schedule, issuance_schedule);
+
+		// Round 0 was calculated before the schedule was set
+		roll_to_while_minting(4, Some(81008));
+		roll_to_while_minting(49, Some(200000));
+		roll_to_while_minting(99, Some(100000));
+		roll_to_while_minting(149, Some(50000));
+
+		// Last epoch issuance is kept after the schedule is over
+		roll_to_while_minting(300, Some(50000));
+	});
+}
+
+#[test]
+fn set_issuance_schedule_validates_schedule() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Issuance::set_issuance_schedule(
+				Origin::signed(1),
+				vec![IssuanceEpoch { rounds: 10, issuance_per_round: 1_000_000 }]
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Issuance::set_issuance_schedule(
+				Origin::root(),
+				vec![IssuanceEpoch { rounds: 0, issuance_per_round: 1_000_000 }]
+			),
+			Error::<Test>::IssuanceConfigInvalid
+		);
+		// 1_800_000_000 is left to be issued up to cap
+		assert_noop!(
+			Issuance::set_issuance_schedule(
+				Origin::root(),
+				vec![
+					IssuanceEpoch { rounds: 1_000, issuance_per_round: 1_000_000 },
+					IssuanceEpoch { rounds: 1, issuance_per_round: 800_000_001 },
+				]
+			),
+			Error::<Test>::IssuanceConfigInvalid
+		);
+		assert_noop!(
+			Issuance::set_issuance_schedule(
+				Origin::root(),
+				vec![
+					IssuanceEpoch { rounds: 1, issuance_per_round: 1 };
+					MAX_ISSUANCE_EPOCHS as usize + 1
+				]
+			),
+			Error::<Test>::IssuanceConfigInvalid
+		);
+
+		assert_ok!(Issuance::set_issuance_schedule(
+			Origin::root(),
+			vec![
+				IssuanceEpoch { rounds: 1_000, issuance_per_round: 1_000_000 },
+				IssuanceEpoch { rounds: 1, issuance_per_round: 800_000_000 },
+			]
+		));
+
+		// Linear issuance is restored with empty schedule
+		assert_ok!(Issuance::set_issuance_schedule(Origin::root(), vec![]));
+		roll_to_while_minting(100, Some(81008));
+	});
+}
+
+#[test]
+fn replacing_elapsed_epochs_of_issuance_schedule_does_not_overshoot_cap() {
+	new_test_ext().execute_with(|| {
+		let cap = Issuance::get_issuance_config().unwrap().cap;
+		assert_ok!(Issuance::set_issuance_schedule(
+			Origin::root(),
+			vec![IssuanceEpoch { rounds: 20, issuance_per_round: 90_000_000 }]
+		));
+
+		let issue_round = |round: u32| {
+			assert_ok!(Issuance::calculate_and_store_round_issuance(round));
+			let (liquidity_mining_issuance, staking_issuance) =
+				Issuance::get_all_issuance(round).unwrap();
+			assert_ok!(StakeCurrency::mint(
+				MGA_TOKEN_ID,
+				&99999,
+				liquidity_mining_issuance + staking_issuance
+			));
+			assert!(Tokens::total_issuance(0u32) as Balance <= cap);
+			liquidity_mining_issuance + staking_issuance
+		};
+
+		for round in 1..10 {
+			issue_round(round);
+		}
+
+		// Elapsed epoch is replaced with a cheaper one, so the schedule as a whole still
+		// fits under cap while the following rounds would overshoot it
+		assert_ok!(Issuance::set_issuance_schedule(
+			Origin::root(),
+			vec![
+				IssuanceEpoch { rounds: 10, issuance_per_round: 1_000_000 },
+				IssuanceEpoch { rounds: 10, issuance_per_round: 170_000_000 },
+			]
+		));
+
+		for round in 10..20 {
+			issue_round(round);
+		}
+		assert_eq!(issue_round(20), 0);
+		assert_eq!(Tokens::total_issuance(0u32) as Balance, cap);
+	});
+}
+
+#[test]
+fn set_issuance_schedule_requires_issuance_config() {
+	new_test_ext_without_issuance_config().execute_with(|| {
+		assert_noop!(
+			Issuance::set_issuance_schedule(
+				Origin::root(),
+				vec![IssuanceEpoch { rounds: 10, issuance_per_round: 1_000_000 }]
+			),
+			Error::<Test>::IssuanceConfigNotInitialized
+		);
+	});
+}
+
+#[test]
+fn issuance_projection_matches_issuance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Issuance::set_issuance_schedule(
+			Origin::root(),
+			vec![
+				IssuanceEpoch { rounds: 2, issuance_per_round: 405_040 },
+				IssuanceEpoch { rounds: 2, issuance_per_round: 1_000_000 },
+				IssuanceEpoch { rounds: 2, issuance_per_round: 500_000 },
+			]
+		));
+
+		let projection = Issuance::project_issuance(1, 8);
+		assert_eq!(projection.len(), 8);
+
+		roll_to_while_minting(44, None);
+		for (round, liquidity_mining_issuance, staking_issuance) in projection {
+			assert_eq!(
+				Issuance::get_all_issuance(round),
+				Some((liquidity_mining_issuance, staking_issuance))
+			);
+		}
+
+		assert_eq!(
+			Issuance::project_issuance(0, u32::MAX).len(),
+			MAX_ISSUANCE_PROJECTION_ROUNDS as usize
+		);
+	});
+}
+
+#[test]
+fn issuance_projection_is_empty_without_issuance_config() {
+	new_test_ext_without_issuance_config().execute_with(|| {
+		assert!(Issuance::project_issuance(0, 10).is_empty());
+	});
+}
+
 #[test]
 fn promote_pool_api_works() {
 	new_test_ext().execute_with(|| {
@@ -429,6 +600,117 @@ fn promote_pool_api_works() {
 	});
 }
 
+#[test]
+fn liquidity_mining_issuance_is_split_by_pool_weights() {
+	new_test_ext().execute_with(|| {
+		Issuance::promote_pool(1);
+		Issuance::promote_pool(2);
+		assert_ok!(Issuance::set_pool_weight(Origin::root(), 2, 3));
+
+		assert_eq!(Issuance::get_pool_weight(1), Some(DEFAULT_POOL_WEIGHT));
+		assert_eq!(Issuance::get_pool_weight(2), Some(3));
+		assert_eq!(Issuance::get_total_pools_weight(), 4);
+
+		// 225022 of liquidity mining issuance per session, activated amount of each pool is 1
+		roll_to_while_minting(4, None);
+		assert_eq!(
+			U256::from(56255_u128) * U256::from(u128::MAX),
+			Issuance::get_pool_rewards_v2(1).unwrap()
+		);
+		assert_eq!(
+			U256::from(168766_u128) * U256::from(u128::MAX),
+			Issuance::get_pool_rewards_v2(2).unwrap()
+		);
+	});
+}
+
+#[test]
+fn pools_issuance_history_is_recorded_and_pruned() {
+	new_test_ext().execute_with(|| {
+		Issuance::promote_pool(1);
+		Issuance::promote_pool(2);
+		assert_ok!(Issuance::set_pool_weight(Origin::root(), 2, 3));
+
+		roll_to_while_minting(4, None);
+		assert_eq!(Issuance::get_pool_rewards_history(1), vec![(0, 0), (1, 56255)]);
+		assert_eq!(Issuance::get_pool_rewards_history(2), vec![(0, 0), (1, 168766)]);
+		assert_eq!(Issuance::get_pool_rewards_history(3), vec![(0, 0), (1, 0)]);
+
+		// history is kept for HistoryLimit rounds only
+		roll_to_while_minting(59, None);
+		let history = Issuance::get_pool_rewards_history(1);
+		assert_eq!(history.len(), 10);
+		assert_eq!(history.first(), Some(&(3, 56255)));
+		assert_eq!(history.last(), Some(&(12, 56255)));
+		assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(2).count(), 0);
+	});
+}
+
+#[test]
+fn pools_issuance_history_of_unpromoted_pools_is_pruned_over_following_rounds() {
+	new_test_ext().execute_with(|| {
+		Issuance::promote_pool(1);
+		Issuance::promote_pool(2);
+
+		roll_to_while_minting(4, None);
+		// round 1 recorded issuance of pools unpromoted since then
+		for pool in 10..15 {
+			PoolsIssuanceHistory::<Test>::insert(1, pool, 1000);
+		}
+		assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(1).count(), 7);
+
+		// round 1 leaves history in round 11, at most 3 entries are pruned per round
+		roll_to_while_minting(54, None);
+		assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(1).count(), 4);
+		assert_eq!(Issuance::get_pools_issuance_history_prune_round(), Some(1));
+
+		roll_to_while_minting(59, None);
+		assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(1).count(), 1);
+
+		roll_to_while_minting(79, None);
+		for round in 1..=4 {
+			assert_eq!(PoolsIssuanceHistory::<Test>::iter_prefix(round).count(), 0);
+		}
+		let history = Issuance::get_pool_rewards_history(1);
+		assert_eq!(history.len(), 10);
+		assert_eq!(history.first().map(|(round, _)| *round), Some(7));
+	});
+}
+
+#[test]
+fn set_pool_weight_fails_for_invalid_input() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Issuance::set_pool_weight(Origin::root(), 1, 3), Error::<Test>::UnknownPool);
+
+		Issuance::promote_pool(1);
+		assert_noop!(
+			Issuance::set_pool_weight(Origin::root(), 1, 0),
+			Error::<Test>::InvalidPoolWeight
+		);
+		assert_noop!(
+			Issuance::set_pool_weight(Origin::signed(1), 1, 3),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_eq!(Issuance::get_pool_weight(1), Some(DEFAULT_POOL_WEIGHT));
+		assert_eq!(Issuance::get_pool_weight(2), None);
+	});
+}
+
+#[test]
+fn migration_leaves_issuance_config_which_cannot_be_decoded_intact() {
+	new_test_ext_without_issuance_config().execute_with(|| {
+		let key = IssuanceConfigStore::<Test>::hashed_key();
+		frame_support::storage::unhashed::put_raw(&key, &[1, 2, 3]);
+		StorageVersion::new(0).put::<Issuance>();
+
+		migrations::v1::migrate::<Test>();
+
+		assert_eq!(frame_support::storage::unhashed::get_raw(&key), Some(vec![1, 2, 3]));
+		assert_eq!(Issuance::on_chain_storage_version(), 0);
+	});
+}
+
 // #[test]
 // fn mock_use_demo() {
 // 	new_test_ext().execute_with(|| {
@@ -169,6 +169,24 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for MockXyk<T> {
 		unimplemented!()
 	}
 
+	fn multiswap_sell_asset(
+		sender: T::AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		sold_asset_amount: Self::Balance,
+		min_amount_out: Self::Balance,
+	) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn multiswap_buy_asset(
+		sender: T::AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		bought_asset_amount: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> DispatchResult {
+		unimplemented!()
+	}
+
 	fn mint_liquidity(
 		sender: T::AccountId,
 		first_asset_id: Self::CurrencyId,
@@ -200,6 +218,10 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for MockXyk<T> {
 		unimplemented!()
 	}
 
+	fn unpromote_pool(liquidity_token_id: TokenId) -> DispatchResult {
+		unimplemented!()
+	}
+
 	fn is_liquidity_token(liquidity_asset_id: TokenId) -> bool {
 		true
 	}
@@ -235,13 +257,6 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for MockXyk<T> {
 	) -> DispatchResult {
 		unimplemented!()
 	}
-
-	fn rewards_migrate_v1_to_v2(
-		account: T::AccountId,
-		liquidity_token_id: Self::CurrencyId,
-	) -> DispatchResult {
-		unimplemented!()
-	}
 }
 
 // This function basically just builds a genesis storage key/value store according to
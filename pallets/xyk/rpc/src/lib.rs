@@ -17,7 +17,10 @@ use sp_runtime::{
 use sp_std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 pub use xyk_runtime_api::XykApi as XykRuntimeApi;
-use xyk_runtime_api::{RpcAmountsResult, XYKRpcResult};
+use xyk_runtime_api::{
+	RpcAmountsResult, RpcLiquidityPosition, RpcPoolInfo, RpcPoolRewardsStats, RpcSwapPathResult,
+	XYKRpcResult,
+};
 
 #[rpc(client, server)]
 pub trait XykApi<
@@ -27,9 +30,15 @@ pub trait XykApi<
 	AccountId,
 	ResponseTypePrice,
 	ResponseTypeAmounts,
+	ResponseTypePath,
+	ResponseTypePools,
+	ResponseTypePortfolio,
+	ResponseTypeRewardsStats,
 	BalanceOutput,
 >
 {
+	/// Deprecated, uses default fees and constant product math regardless of pool,
+	/// use `xyk_calculate_sell_price_id` instead
 	#[method(name = "xyk_calculate_sell_price")]
 	fn calculate_sell_price(
 		&self,
@@ -39,6 +48,8 @@ pub trait XykApi<
 		at: Option<BlockHash>,
 	) -> RpcResult<ResponseTypePrice>;
 
+	/// Deprecated, uses default fees and constant product math regardless of pool,
+	/// use `xyk_calculate_buy_price_id` instead
 	#[method(name = "xyk_calculate_buy_price")]
 	fn calculate_buy_price(
 		&self,
@@ -98,6 +109,49 @@ pub trait XykApi<
 		liquidity_asset_id: TokenId,
 		at: Option<BlockHash>,
 	) -> RpcResult<ResponseTypePrice>;
+
+	#[method(name = "xyk_get_best_sell_path")]
+	fn get_best_sell_path(
+		&self,
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		sell_amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypePath>;
+
+	#[method(name = "xyk_get_best_buy_path")]
+	fn get_best_buy_path(
+		&self,
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		buy_amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypePath>;
+
+	#[method(name = "xyk_get_pools")]
+	fn get_pools(
+		&self,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypePools>;
+
+	#[method(name = "xyk_get_user_portfolio")]
+	fn get_user_portfolio(
+		&self,
+		user: AccountId,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypePortfolio>;
+
+	#[method(name = "xyk_get_pools_rewards_stats")]
+	fn get_pools_rewards_stats(
+		&self,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<ResponseTypeRewardsStats>;
 }
 
 pub struct Xyk<C, M> {
@@ -136,6 +190,10 @@ impl<C, Block, Balance, TokenId, AccountId>
 		AccountId,
 		XYKRpcResult<Balance>,
 		RpcAmountsResult<Balance>,
+		RpcSwapPathResult<Balance, TokenId>,
+		Vec<RpcPoolInfo<Balance, TokenId>>,
+		Vec<RpcLiquidityPosition<Balance, TokenId>>,
+		Vec<RpcPoolRewardsStats<Balance, TokenId>>,
 		Balance,
 	> for Xyk<C, Block>
 where
@@ -345,4 +403,119 @@ where
 			)))
 		})
 	}
+
+	fn get_best_sell_path(
+		&self,
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		sell_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RpcSwapPathResult<Balance, TokenId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		let runtime_api_result = api.get_best_sell_path(
+			&at,
+			sold_token_id,
+			bought_token_id,
+			sell_amount.try_into_balance()?,
+		);
+		runtime_api_result.map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_best_buy_path(
+		&self,
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		buy_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RpcSwapPathResult<Balance, TokenId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		let runtime_api_result = api.get_best_buy_path(
+			&at,
+			sold_token_id,
+			bought_token_id,
+			buy_amount.try_into_balance()?,
+		);
+		runtime_api_result.map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_pools(
+		&self,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RpcPoolInfo<Balance, TokenId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		api.get_pools(&at, start_after, limit).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_user_portfolio(
+		&self,
+		user: AccountId,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RpcLiquidityPosition<Balance, TokenId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		api.get_user_portfolio(&at, user, start_after, limit).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
+
+	fn get_pools_rewards_stats(
+		&self,
+		start_after: Option<TokenId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<RpcPoolRewardsStats<Balance, TokenId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::<Block>::hash(at.unwrap_or_else(||
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash));
+
+		api.get_pools_rewards_stats(&at, start_after, limit).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				1,
+				"Unable to serve the request",
+				Some(format!("{:?}", e)),
+			)))
+		})
+	}
 }
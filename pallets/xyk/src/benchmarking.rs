@@ -88,6 +88,30 @@ benchmarks! {
 
    }
 
+   create_stable_pool {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let first_asset_amount = MILION;
+	   let second_asset_amount = MILION;
+	   let first_asset_id = <T as Config>::Currency::create(&caller, first_asset_amount.into()).unwrap();
+	   let second_asset_id = <T as Config>::Currency::create(&caller, second_asset_amount.into()).unwrap();
+	   let liquidity_asset_id = second_asset_id.into() + 1;
+
+   }: create_stable_pool(RawOrigin::Signed(caller.clone().into()), first_asset_id.into(), first_asset_amount.into(), second_asset_id.into(), second_asset_amount.into(), 100)
+   verify {
+
+	   assert_eq!(
+		   Xyk::<T>::asset_pool((first_asset_id.into(), second_asset_id.into())),
+		   (first_asset_amount, second_asset_amount)
+	   );
+
+	   assert_eq!(
+		   Xyk::<T>::pool_kinds(liquidity_asset_id),
+		   Some(PoolKind::StableSwap { amplification: 100 })
+	   );
+
+   }
+
    sell_asset {
 	   // NOTE: duplicates test case XYK::buy_and_burn_sell_none_have_mangata_pair
 
@@ -133,7 +157,79 @@ benchmarks! {
 	   assert_eq!(<T as Config>::Currency::free_balance(non_native_asset_id2.into(), &caller).into(), 850000000000001);
    }
 
+   flash_swap {
+	   // NOTE: same trade as buy_asset, with no-op callback dispatched in between
+
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), native_asset_id.into(), 100000000000000, non_native_asset_id1.into(), 100000000000000).unwrap();
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), 100000000000000, non_native_asset_id2.into(), 100000000000000).unwrap();
+	   let call: Box<<T as Config>::Call> = Box::new(frame_system::Call::<T>::remark { remark: Vec::new() }.into());
 
+   }: flash_swap(RawOrigin::Signed(caller.clone().into()), non_native_asset_id2.into(), non_native_asset_id1.into(), 33266599933266, 50000000000001, call)
+   verify {
+	   assert_eq!(<T as Config>::Currency::free_balance(non_native_asset_id1.into(), &caller).into(), 833266599933266);
+	   assert_eq!(<T as Config>::Currency::free_balance(non_native_asset_id2.into(), &caller).into(), 850000000000001);
+	   assert_eq!(Xyk::<T>::flash_swap_lock((non_native_asset_id2.into(), non_native_asset_id1.into())), None);
+   }
+
+
+
+   multiswap_sell_asset {
+	   // NOTE: every hop goes through a pool without mangata pair, so fees end up in treasuries
+
+	   let x in 2..MAX_MULTISWAP_PATH_LENGTH;
+
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000;
+	   let pool_amount:mangata_types::Balance = 100000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let mut swap_token_list: Vec<TokenId> = Vec::new();
+	   for _ in 0..x {
+		   swap_token_list.push(<T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into());
+	   }
+
+	   for hop in swap_token_list.windows(2) {
+		   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), hop[0], pool_amount, hop[1], pool_amount).unwrap();
+	   }
+	   let bought_asset_id = *swap_token_list.last().unwrap();
+
+   }: multiswap_sell_asset(RawOrigin::Signed(caller.clone().into()), swap_token_list.clone(), 10000000000000, 0)
+   verify {
+	   assert!(<T as Config>::Currency::free_balance(bought_asset_id.into(), &caller).into() > initial_amount - pool_amount);
+   }
+
+   multiswap_buy_asset {
+	   // NOTE: every hop goes through a pool without mangata pair, so fees end up in treasuries
+
+	   let x in 2..MAX_MULTISWAP_PATH_LENGTH;
+
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000;
+	   let pool_amount:mangata_types::Balance = 100000000000000;
+	   let bought_amount:mangata_types::Balance = 1000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let mut swap_token_list: Vec<TokenId> = Vec::new();
+	   for _ in 0..x {
+		   swap_token_list.push(<T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into());
+	   }
+
+	   for hop in swap_token_list.windows(2) {
+		   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), hop[0], pool_amount, hop[1], pool_amount).unwrap();
+	   }
+	   let bought_asset_id = *swap_token_list.last().unwrap();
+
+   }: multiswap_buy_asset(RawOrigin::Signed(caller.clone().into()), swap_token_list.clone(), bought_amount, initial_amount)
+   verify {
+	   assert_eq!(<T as Config>::Currency::free_balance(bought_asset_id.into(), &caller).into(), initial_amount - pool_amount + bought_amount);
+   }
 
    mint_liquidity {
 	   // 1. create,
@@ -173,6 +269,7 @@ benchmarks! {
 		   non_native_asset_id2.into(),
 		   pool_mint_first_token_amount,
 		   pool_mint_second_token_amount,
+		   0,
 	   ).unwrap();
 
 	   let liquidity_amount_after_first_mint = <T as Config>::Currency::total_issuance(liquidity_asset_id.into());
@@ -181,7 +278,7 @@ benchmarks! {
 
 	   forward_to_next_session!();
 
-   }: mint_liquidity(RawOrigin::Signed(caller.clone().into()), non_native_asset_id1.into(), non_native_asset_id2.into(), 20000000000000000000, 30000000000000000001)
+   }: mint_liquidity(RawOrigin::Signed(caller.clone().into()), non_native_asset_id1.into(), non_native_asset_id2.into(), 20000000000000000000, 30000000000000000001, 0)
    verify {
 	   let liquidity_amount_after_second_mint = <T as Config>::Currency::total_issuance(liquidity_asset_id.into());
 
@@ -275,13 +372,13 @@ benchmarks! {
 
 	   assert!(Xyk::<T>::liquidity_pool(liquidity_asset_id).is_some());
 
-	   Xyk::<T>::mint_liquidity(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), non_native_asset_id2.into(), pool_mint_first_token_amount, pool_mint_second_token_amount).unwrap();
+	   Xyk::<T>::mint_liquidity(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), non_native_asset_id2.into(), pool_mint_first_token_amount, pool_mint_second_token_amount, 0).unwrap();
 
 	   forward_to_next_session!();
 	   let total_liquidity_after_minting = <T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into();
 
 
-   }: burn_liquidity(RawOrigin::Signed(caller.clone().into()), non_native_asset_id1.into(), non_native_asset_id2.into(), total_liquidity_after_minting)
+   }: burn_liquidity(RawOrigin::Signed(caller.clone().into()), non_native_asset_id1.into(), non_native_asset_id2.into(), total_liquidity_after_minting, 0, 0)
    verify {
 	   assert!(Xyk::<T>::liquidity_pool(liquidity_asset_id).is_none());
    }
@@ -386,6 +483,78 @@ benchmarks! {
    }
 
 
+   claim_rewards_all_pools_v2 {
+	   // NOTE: every visited pool has some rewards to claim
+
+	   let x in 1..20;
+
+	   init!();
+	   let caller: <T as frame_system::Config>::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let mut liquidity_asset_ids: Vec<TokenId> = Vec::new();
+	   for _ in 0..x {
+		   let first_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+		   let second_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+		   let liquidity_asset_id = second_asset_id + 1;
+		   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), first_asset_id, 40000000000000000000, second_asset_id, 60000000000000000000).unwrap();
+		   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+		   liquidity_asset_ids.push(liquidity_asset_id);
+	   }
+
+	   forward_to_next_session!();
+
+	   for liquidity_asset_id in liquidity_asset_ids.iter() {
+		   let quater_of_minted_liquidity = <T as Config>::Currency::total_issuance((*liquidity_asset_id).into()).into() / 4_u128;
+		   Xyk::<T>::activate_liquidity_v2(RawOrigin::Signed(caller.clone().into()).into(), *liquidity_asset_id, quater_of_minted_liquidity, None).unwrap();
+	   }
+
+	   forward_to_next_session!();
+	   forward_to_next_session!();
+
+	   for liquidity_asset_id in liquidity_asset_ids.iter() {
+		   assert!(Xyk::<T>::calculate_rewards_amount_v2(caller.clone(), *liquidity_asset_id).unwrap() > 0);
+	   }
+
+   }: claim_rewards_all_pools_v2(RawOrigin::Signed(caller.clone().into()), None, x)
+   verify {
+	   for liquidity_asset_id in liquidity_asset_ids.iter() {
+		   assert_eq!(Xyk::<T>::calculate_rewards_amount_v2(caller.clone(), *liquidity_asset_id).unwrap(), 0);
+	   }
+   }
+
+   compound_rewards {
+	   // NOTE: minted liquidity is activated, which is the most expensive path
+
+	   init!();
+	   let caller: <T as frame_system::Config>::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000;
+	   let expected_native_asset_id : TokenId = <T as Config>::NativeCurrencyId::get().into();
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = non_native_asset_id1 + 1;
+	   assert_eq!(native_asset_id, expected_native_asset_id);
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), native_asset_id.into(), 40000000000000000000, non_native_asset_id1.into(), 60000000000000000000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+
+	   let quater_of_minted_liquidity = <T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into() / 4_u128;
+
+	   forward_to_next_session!();
+
+	   Xyk::<T>::activate_liquidity_v2(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id.into(), quater_of_minted_liquidity, None).unwrap();
+
+	   forward_to_next_session!();
+	   forward_to_next_session!();
+
+	   assert!(Xyk::<T>::calculate_rewards_amount_v2(caller.clone(), liquidity_asset_id).unwrap() > 0);
+
+   }: compound_rewards(RawOrigin::Signed(caller.clone().into()), liquidity_asset_id, 1, true)
+   verify {
+	   assert!(Xyk::<T>::get_rewards_info(caller.clone(), liquidity_asset_id).activated_amount > quater_of_minted_liquidity);
+	   assert_eq!(Xyk::<T>::calculate_rewards_amount_v2(caller.clone(), liquidity_asset_id).unwrap(), 0);
+   }
+
    promote_pool {
 	   // NOTE: that duplicates test XYK::liquidity_rewards_claim_W
 	   //
@@ -409,6 +578,68 @@ benchmarks! {
 	   );
    }
 
+   unpromote_pool {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000;
+
+	   let asset_id_1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let asset_id_2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = asset_id_2 + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), asset_id_1.into(), 5000, asset_id_2.into(), 5000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+
+   }: unpromote_pool(RawOrigin::Root, liquidity_asset_id)
+
+   verify {
+	   assert!(Xyk::<T>::unpromoted_pool_rewards(liquidity_asset_id).is_some());
+	   assert_err!(
+		   Xyk::<T>::unpromote_pool(RawOrigin::Root.into(), liquidity_asset_id),
+		   Error::<T>::NotAPromotedPool
+	   );
+   }
+
+   set_pool_fee {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000;
+
+	   let asset_id_1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let asset_id_2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = asset_id_2 + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), asset_id_1.into(), 5000, asset_id_2.into(), 5000).unwrap();
+
+	   let pool_fee = PoolFeeInfo{ pool_fee_percentage: 4, treasury_fee_percentage: 1, buy_and_burn_fee_percentage: 1 };
+
+   }: set_pool_fee(RawOrigin::Root, liquidity_asset_id, Some(pool_fee))
+
+   verify {
+	   assert_eq!(Xyk::<T>::pool_fees(liquidity_asset_id), Some(pool_fee));
+   }
+
+   set_reward_curve {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000;
+
+	   let asset_id_1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let asset_id_2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = asset_id_2 + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), asset_id_1.into(), 5000, asset_id_2.into(), 5000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+
+	   let quotient = FixedU128::saturating_from_rational(110, 100);
+
+   }: set_reward_curve(RawOrigin::Root, liquidity_asset_id, quotient, 5)
+
+   verify {
+	   assert_eq!(Xyk::<T>::get_reward_curve(liquidity_asset_id).quotient, quotient);
+	   assert_eq!(Xyk::<T>::get_reward_curve(liquidity_asset_id).distribution_period, 5);
+   }
+
    activate_liquidity_v2 {
 	   // activate :
 	   // 1 crate pool
@@ -500,52 +731,39 @@ benchmarks! {
 	   );
    }
 
-   rewards_migrate_v1_to_v2 {
+   rewards_v1_migration_step {
 	   // 0 fill storage with rew1 values
 	   // 1 crate pool
 	   // 2 promote pool v2
-	   // 3 migrate (last migration to be checked)
-
-	//    XykStorage::transfer(
-	// 	0,
-	// 	2,
-	// 	<Test as Config>::LiquidityMiningIssuanceVault::get(),
-	// 	10000000000000,
-	// )
-	//.unwrap();
+	   // 3 migrate step (migrates last user, to be checked)
 
 	   init!();
-	   let caller: <T as frame_system::Config>::AccountId = whitelisted_caller();
 	   let user1: T::AccountId = account("user1", 0, 0);
 	   let user2: T::AccountId = account("user2", 1, 0);
 
-	   pallet_issuance::PromotedPoolsRewards::<T>::insert(4, 200000);
+	   migrations::v1::PromotedPoolsRewards::insert(4, 200000);
 	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), 4);
 
-	   LiquidityMiningActiveUser::<T>::insert((user1.clone(), 4), 10000);
-	   LiquidityMiningActiveUser::<T>::insert((user2.clone(), 4), 10000);
-	   LiquidityMiningActivePool::<T>::insert(4, 20000);
-	   LiquidityMiningUser::<T>::insert((user1.clone(), 4), (0, U256::from(0_u64), U256::from(10000_u64)));
-	   LiquidityMiningUser::<T>::insert((user2.clone(), 4), (10, U256::from(0_u64), U256::from(10000_u64)));
-	   LiquidityMiningPool::<T>::insert(4, (10, U256::from(21985_u64), U256::from(15584_u64)));
-	   LiquidityMiningUserToBeClaimed::<T>::insert((user1.clone(), 4), 0);
-	   LiquidityMiningUserClaimed::<T>::insert((user1.clone(), 4), 0);
-	   LiquidityMiningUserToBeClaimed::<T>::insert((user2.clone(), 4), 0);
-	   LiquidityMiningUserClaimed::<T>::insert((user2.clone(), 4), 0);
+	   migrations::v1::LiquidityMiningActiveUser::<T>::insert((user1.clone(), 4), 10000);
+	   migrations::v1::LiquidityMiningActiveUser::<T>::insert((user2.clone(), 4), 10000);
+	   migrations::v1::LiquidityMiningActivePool::<T>::insert(4, 20000);
+	   migrations::v1::LiquidityMiningUser::<T>::insert((user1.clone(), 4), (0, U256::from(0_u64), U256::from(10000_u64)));
+	   migrations::v1::LiquidityMiningUser::<T>::insert((user2.clone(), 4), (10, U256::from(0_u64), U256::from(10000_u64)));
+	   migrations::v1::LiquidityMiningPool::<T>::insert(4, (10, U256::from(21985_u64), U256::from(15584_u64)));
+	   migrations::v1::LiquidityMiningUserToBeClaimed::<T>::insert((user1.clone(), 4), 0);
+	   migrations::v1::LiquidityMiningUserClaimed::<T>::insert((user1.clone(), 4), 0);
+	   migrations::v1::LiquidityMiningUserToBeClaimed::<T>::insert((user2.clone(), 4), 0);
+	   migrations::v1::LiquidityMiningUserClaimed::<T>::insert((user2.clone(), 4), 0);
+	   StorageVersion::new(0).put::<Xyk<T>>();
 
 	   frame_system::Pallet::<T>::set_block_number(24000_u32.into());
 	   T::PoolPromoteApi::compute_issuance(24000_u32.into());
 
-		let account32: sp_runtime::AccountId32 =
-			hex_literal::hex!["0e33df23356eb2e9e3baf0e8a5faae15bc70a6a5cce88f651a9faf6e8e937324"]
-				.into();
-		let mut init_account32 = sp_runtime::AccountId32::as_ref(&account32);
-		let acc = T::AccountId::decode(&mut init_account32).unwrap();
-
-		Xyk::<T>::rewards_migrate_v1_to_v2(RawOrigin::Signed(acc.clone()).into(),user1.clone(), 4);
-
+	   migrations::v1::migrate_user::<T>(user1.clone(), 4).unwrap();
+	   let step_weight = <T as Config>::WeightInfo::rewards_v1_migration_step()
+		   .saturating_add(T::DbWeight::get().reads(1));
 
-   }: rewards_migrate_v1_to_v2(RawOrigin::Signed(acc.clone()),user2.clone(), 4)
+   }: { migrations::v1::migrate_rewards_step::<T>(step_weight) }
    verify {
 
 	   assert_eq!(Xyk::<T>::get_rewards_info(user1.clone(), 4).activated_amount, 10000);
@@ -561,7 +779,131 @@ benchmarks! {
 	   assert_eq!(Xyk::<T>::get_rewards_info(user2.clone(), 4).last_checkpoint, 20);
 	   assert_eq!(Xyk::<T>::get_rewards_info(user2.clone(), 4).pool_ratio_at_last_checkpoint, U256::from(0_u64)); //these values will be from rew2, but reading pool_ratio_at_last_checkpoint works
 	   assert_eq!(Xyk::<T>::get_rewards_info(user2.clone(), 4).missing_at_last_checkpoint, U256::from(5584_u64));
+	   assert_eq!(migrations::v1::LiquidityMiningActivePool::<T>::get(4), 0);
+
+   }
+
+   rewards_migrate_reward_curve {
+	   // NOTE: migrated checkpoint of activated liquidity has rewards to carry over
 
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000;
+	   let asset_id_1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let asset_id_2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = asset_id_2 + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), asset_id_1.into(), 40000000000000000000, asset_id_2.into(), 60000000000000000000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+	   let quater_of_minted_liquidity = <T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into() / 4_u128;
+	   Xyk::<T>::activate_liquidity_v2(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id, quater_of_minted_liquidity, None).unwrap();
+
+	   forward_to_next_session!();
+	   forward_to_next_session!();
+
+	   Xyk::<T>::set_reward_curve(RawOrigin::Root.into(), liquidity_asset_id, FixedU128::saturating_from_rational(110, 100), 5).unwrap();
+	   let step_weight = <T as Config>::WeightInfo::rewards_migrate_reward_curve()
+		   .saturating_add(T::DbWeight::get().reads_writes(1, 1));
+
+   }: { migrations::reward_curve::migrate_rewards_step::<T>(step_weight) }
+   verify {
+	   assert_eq!(
+		   Xyk::<T>::get_rewards_info(caller.clone(), liquidity_asset_id).last_checkpoint,
+		   Xyk::<T>::get_reward_curve(liquidity_asset_id).start_time
+	   );
+	   assert!(Xyk::<T>::get_rewards_info(caller.clone(), liquidity_asset_id).rewards_not_yet_claimed > 0);
+   }
+
+   create_reward_schedule {
+	   // NOTE: previous schedule of the same token has ended and its leftovers are refunded
+
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let reward_token_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = reward_token_id + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), 40000000000000000000, non_native_asset_id2.into(), 60000000000000000000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+	   Xyk::<T>::set_reward_token_whitelisted(RawOrigin::Root.into(), reward_token_id, true).unwrap();
+
+	   let end_block = frame_system::Pallet::<T>::block_number() + 5_u32.into();
+	   Xyk::<T>::create_reward_schedule(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id, reward_token_id, 1000000, end_block).unwrap();
+
+	   forward_to_next_session!();
+	   let end_block = frame_system::Pallet::<T>::block_number() + 100_u32.into();
+
+   }: create_reward_schedule(RawOrigin::Signed(caller.clone().into()), liquidity_asset_id, reward_token_id, 1000000, end_block)
+   verify {
+	   assert_eq!(Xyk::<T>::reward_schedule(liquidity_asset_id, reward_token_id).unwrap().amount_per_block, 10000);
+	   assert_eq!(<T as Config>::Currency::free_balance(reward_token_id.into(), &Xyk::<T>::reward_schedules_account_id()).into(), 1000000);
+   }
+
+   claim_schedule_rewards {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let reward_token_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = reward_token_id + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), 40000000000000000000, non_native_asset_id2.into(), 60000000000000000000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+	   Xyk::<T>::set_reward_token_whitelisted(RawOrigin::Root.into(), reward_token_id, true).unwrap();
+
+	   let quater_of_minted_liquidity = <T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into() / 4_u128;
+	   Xyk::<T>::activate_liquidity_v2(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id.into(), quater_of_minted_liquidity, None).unwrap();
+
+	   let end_block = frame_system::Pallet::<T>::block_number() + 100_u32.into();
+	   Xyk::<T>::create_reward_schedule(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id, reward_token_id, 1000000, end_block).unwrap();
+
+	   forward_to_next_session!();
+
+	   let balance_before = <T as Config>::Currency::free_balance(reward_token_id.into(), &caller).into();
+	   let available_rewards = Xyk::<T>::calculate_schedule_rewards_amount(caller.clone(), liquidity_asset_id, reward_token_id).unwrap();
+	   assert!(available_rewards > 0);
+
+   }: claim_schedule_rewards(RawOrigin::Signed(caller.clone().into()), liquidity_asset_id, reward_token_id)
+   verify {
+	   assert_eq!(<T as Config>::Currency::free_balance(reward_token_id.into(), &caller).into(), balance_before + available_rewards);
+	   assert_eq!(Xyk::<T>::calculate_schedule_rewards_amount(caller.clone(), liquidity_asset_id, reward_token_id).unwrap(), 0);
+   }
+
+   refund_reward_schedule {
+	   init!();
+	   let caller: T::AccountId = whitelisted_caller();
+	   let initial_amount:mangata_types::Balance = 1000000000000000000000000;
+	   let native_asset_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id1 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let non_native_asset_id2 : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let reward_token_id : TokenId= <T as Config>::Currency::create(&caller, initial_amount.into()).unwrap().into();
+	   let liquidity_asset_id = reward_token_id + 1;
+
+	   Xyk::<T>::create_pool(RawOrigin::Signed(caller.clone().into()).into(), non_native_asset_id1.into(), 40000000000000000000, non_native_asset_id2.into(), 60000000000000000000).unwrap();
+	   Xyk::<T>::promote_pool(RawOrigin::Root.into(), liquidity_asset_id).unwrap();
+	   Xyk::<T>::set_reward_token_whitelisted(RawOrigin::Root.into(), reward_token_id, true).unwrap();
+
+	   let end_block = frame_system::Pallet::<T>::block_number() + 5_u32.into();
+	   Xyk::<T>::create_reward_schedule(RawOrigin::Signed(caller.clone().into()).into(), liquidity_asset_id, reward_token_id, 1000000, end_block).unwrap();
+
+	   forward_to_next_session!();
+
+   }: refund_reward_schedule(RawOrigin::Signed(caller.clone().into()), liquidity_asset_id, reward_token_id)
+   verify {
+	   assert_eq!(Xyk::<T>::reward_schedule(liquidity_asset_id, reward_token_id).unwrap().undistributed, 0);
+	   assert_eq!(<T as Config>::Currency::free_balance(reward_token_id.into(), &caller).into(), initial_amount);
+   }
+
+   set_reward_token_whitelisted {
+	   let reward_token_id: TokenId = 1;
+   }: set_reward_token_whitelisted(RawOrigin::Root, reward_token_id, true)
+   verify {
+	   assert!(Xyk::<T>::is_reward_token_whitelisted(reward_token_id));
    }
 
 
@@ -8,6 +8,23 @@
 //! - burn_liquidity
 //! - sell_asset
 //! - buy_asset
+//! - multiswap_sell_asset
+//! - multiswap_buy_asset
+//! - sell_asset_with_deadline
+//! - buy_asset_with_deadline
+//! - mint_liquidity_with_deadline
+//! - burn_liquidity_with_deadline
+//! - set_pool_fee
+//! - create_stable_pool
+//! - flash_swap
+//! - create_reward_schedule
+//! - claim_schedule_rewards
+//! - refund_reward_schedule
+//! - unpromote_pool
+//! - claim_rewards_all_pools_v2
+//! - compound_rewards
+//! - set_reward_curve
+//! - set_reward_token_whitelisted
 //!
 //! ### Supporting public functions:
 //! - calculate_sell_price
@@ -16,6 +33,7 @@
 //! - calculate_buy_price_id
 //! - get_liquidity_token
 //! - get_burn_amount
+//! - get_twap
 //! - account_id
 //! - settle_treasury_buy_and_burn
 //!
@@ -126,6 +144,67 @@
 //!
 //! `InsufficientInputAmount` - sold tokens to pay is higher then maximum acceptable value of max_amount_in
 //!
+//! # fn multiswap_sell_asset
+//! -Sells set amount of the first token of the path and swaps it hop by hop through pools of consecutive tokens
+//! -Fees are charged and settled on every hop, minimal output is checked only on the last token of the path
+//! ### arguments
+//! `origin` - sender of a fn, user creating the pool
+//!
+//! `swap_token_list` - path of tokens, first one is sold, last one is bought
+//!
+//! `sold_asset_amount` - amount of first token to be sold
+//!
+//! `min_amount_out` - minimal acceptable amount of last token received after all swaps
+//!
+//! ### Example
+//! ```ignore
+//! multiswap_sell_asset (
+//!    Origin::signed(1),
+//!    vec![0, 1, 2],
+//!    1000,
+//!    800,
+//!)
+//! ```
+//! Account_id 1 sells 1000 token 0 for token 1 and then all received token 1 for token 2, while requiring at least 800 token 2
+//!
+//! ### Errors
+//! `MultiswapPathTooShort` - path contains less than two tokens
+//!
+//! `MultiswapPathTooLong` - path contains more than `MAX_MULTISWAP_PATH_LENGTH` tokens
+//!
+//! `MultiswapSameTokenConsecutively` - path contains the same token twice in a row
+//!
+//! `InsufficientOutputAmount` - amount of last token to receive is lower then required min_amount_out
+//!
+//! and all errors of sell_token for every hop
+//!
+//! # fn multiswap_buy_asset
+//! -Buys set amount of the last token of the path paying with the first token, through pools of consecutive tokens
+//! -Fees are charged on every hop, but settled only after all hops are executed, so the amounts calculated
+//! for the whole path upfront are not altered by settling fees through pools of the path
+//! -Maximal input is checked only on the first token of the path
+//! ### arguments
+//! `origin` - sender of a fn, user creating the pool
+//!
+//! `swap_token_list` - path of tokens, first one is sold, last one is bought
+//!
+//! `bought_asset_amount` - amount of last token to be bought
+//!
+//! `max_amount_in` - maximal acceptable amount of first token to pay for requested bought amount
+//!
+//! ### Errors
+//! `MultiswapPathTooShort` - path contains less than two tokens
+//!
+//! `MultiswapPathTooLong` - path contains more than `MAX_MULTISWAP_PATH_LENGTH` tokens
+//!
+//! `MultiswapSameTokenConsecutively` - path contains the same token twice in a row
+//!
+//! `MultiswapBuyRepeatedPool` - path goes through the same pool more than once
+//!
+//! `InsufficientInputAmount` - first token amount to pay is higher then maximum acceptable value of max_amount_in
+//!
+//! and all errors of buy_token for every hop
+//!
 //! # fn mint_liquidity
 //! -Adds liquidity to pool, providing both tokens in actual ratio
 //! -First token amount is provided by user, second token amount is calculated by function, depending on actual ratio
@@ -140,6 +219,10 @@
 //!
 //! first_token_amount - amount of first_token_id, second token amount will be calculated
 //!
+//! expected_second_token_amount - maximal amount of second_token_id user is willing to provide
+//!
+//! min_liquidity_minted - minimal amount of liquidity tokens user is willing to receive
+//!
 //! ### Example
 //! ```ignore
 //! mint_liquidity (
@@ -147,6 +230,8 @@
 //!    0,
 //!    1,
 //!    1000,
+//!    2000,
+//!    3000,
 //!)
 //! ```
 //! If pool token 0 - token 1 has tokens in amounts 9000:18000 (total liquidity tokens 27000)
@@ -162,6 +247,8 @@
 //!
 //! `NotEnoughTokens` -  minting with more tokens then user owns, either first_token_id or second_token_id
 //!
+//! `InsufficientLiquidityMinted` - amount of liquidity tokens minted is lower than min_liquidity_minted
+//!
 //! # fn burn_liquidity
 //! -Removes tokens from liquidity pool and transfers them to user, by burning user owned liquidity tokens
 //! -Amount of tokens is determined by their ratio in pool and amount of liq tokens burned
@@ -175,6 +262,10 @@
 //!
 //! liquidity_token_amount - amount of liquidity token amount to burn
 //!
+//! min_first_token_amount - minimal amount of first_token_id user is willing to receive
+//!
+//! min_second_token_amount - minimal amount of second_token_id user is willing to receive
+//!
 //! ### Example
 //! ```ignore
 //! burn_liquidity (
@@ -182,6 +273,8 @@
 //!    0,
 //!    1,
 //!    3000,
+//!    1000,
+//!    2000,
 //!)
 //! ```
 //! If pool token 0 - token 1 has tokens in amounts 10000:20000 (total liquidity tokens 30000)
@@ -196,18 +289,244 @@
 //!
 //! `NotEnoughTokens` -  burning more liquidity tokens than user owns
 //!
+//! `InsufficientBurnedAssetsAmount` - amount of first or second token received is lower than required minimum
+//!
+//! # fn sell_asset_with_deadline, buy_asset_with_deadline, mint_liquidity_with_deadline, burn_liquidity_with_deadline
+//! -Same as sell_asset, buy_asset, mint_liquidity and burn_liquidity, but executed only if the transaction is included in a block
+//! with number lower or equal to `deadline`, so it cannot be executed at stale price after staying in the transaction pool for too long
+//!
+//! ### arguments
+//! all arguments of corresponding function, followed by
+//!
+//! `deadline` - last block number in which the transaction can be executed
+//!
+//! ### Example
+//! ```ignore
+//! sell_asset_with_deadline (
+//!    Origin::signed(1),
+//!    0,
+//!    1,
+//!    1000,
+//!    800,
+//!    100,
+//!)
+//! ```
+//! Account_id 1 sells 1000 token 0 for at least 800 token 1, fails if executed after block 100
+//!
+//! ### Errors
+//! `DeadlineExceeded` - current block number is higher than `deadline`
+//!
+//! and all errors of corresponding function
+//!
+//! # fn set_pool_fee
+//! -Sets pool, treasury and buy and burn fees, in basis points, charged on swaps in a single pool, overriding default fees from pallet config
+//! -Passing `None` removes override, pool then uses default fees again
+//!
+//! ### arguments
+//! `origin` - root
+//!
+//! `liquidity_token_id` - liquidity token of the pool
+//!
+//! `pool_fee` - fees of the pool, or `None`
+//!
+//! ### Errors
+//! `NoSuchPool` - pool of liquidity_token_id does not exist
+//!
+//! `InvalidPoolFee` - sum of fees is 100% or higher
+//!
+//! # fn create_stable_pool
+//! -Same as create_pool, but swaps in the pool are priced by stable swap invariant with amplification coefficient instead of x * y = k,
+//! which keeps slippage low while pool stays close to 1:1 ratio, for pairs of pegged tokens
+//! -Minting and burning liquidity is proportional to reserves, same as in constant product pools
+//!
+//! ### arguments
+//! all arguments of create_pool, followed by
+//!
+//! `amplification` - amplification coefficient, the higher the flatter the price curve around 1:1 ratio
+//!
+//! ### Errors
+//! `InvalidAmplification` - amplification is zero or higher than maximal allowed value
+//!
+//! and all errors of create_pool
+//!
+//! # fn flash_swap
+//! -Transfers bought asset out of the pool to the user first, then dispatches callback call on behalf of the user
+//! and only after that charges the user for bought amount, same as buy_asset, including fees
+//! -Pool is locked while callback is executed, any swap, mint, burn or flash swap in the same pool fails until the pool is repaid
+//! -Before reserves are updated, constant product invariant with fees is checked, whole transaction is reverted if any step fails
+//!
+//! ### arguments
+//! `origin` - sender of a fn, user borrowing from the pool
+//!
+//! `sold_asset_id` - token which is paid to the pool after callback
+//!
+//! `bought_asset_id` - token which is transferred to the user before callback
+//!
+//! `bought_asset_amount` - amount of bought_asset_id transferred to the user
+//!
+//! `max_amount_in` - maximal amount of sold_asset_id user pays for bought amount
+//!
+//! `call` - callback dispatched with signed origin of the user
+//!
+//! ### Errors
+//! `PoolLocked` - callback tried to use the pool of the flash swap
+//!
+//! `FlashSwapNotSupported` - pool is not a constant product pool
+//!
+//! `InsufficientInputAmount` - price of bought amount is higher than max_amount_in
+//!
+//! `NotEnoughAssets` - user does not hold enough sold_asset_id after callback
+//!
+//! `FlashSwapPoolChanged` - pool or its reserves were changed by the callback
+//!
+//! `FlashSwapInvariantViolated` - pool would end up with lower invariant than before flash swap
+//!
+//! and all errors of buy_asset and of the callback
+//!
+//! # fn create_reward_schedule
+//! -Anyone can sponsor promoted pool with native token or token whitelisted by root, deposited amount is distributed evenly in every block until end_block
+//! -Rewards are split between activated liquidity of the pool in the same way as liquidity mining rewards
+//! -Rewards of blocks with no activated liquidity in the pool, and remainder of amount not divisible by number of blocks, are refundable to the sponsor
+//! -New schedule of the same token for the pool can be created only after previous one ended, leftovers of previous one are refunded automatically
+//! -Ended schedules stop occupying one of MAX_REWARD_TOKENS_PER_POOL slots of the pool, they are pruned when a schedule of new token is created and their rewards stay claimable
+//!
+//! ### arguments
+//! `origin` - sender of a fn, sponsor of the schedule
+//!
+//! `liquidity_token_id` - liquidity token of the sponsored pool
+//!
+//! `reward_token_id` - token distributed as rewards
+//!
+//! `amount` - total amount of reward_token_id distributed
+//!
+//! `end_block` - block in which distribution ends
+//!
+//! ### Errors
+//! `NotAPromotedPool` - pool is not promoted
+//!
+//! `InvalidRewardSchedule` - end_block is not in future or amount is lower than number of blocks
+//!
+//! `RewardScheduleActive` - schedule of the same token for the pool has not ended yet
+//!
+//! `RewardTokenNotWhitelisted` - reward_token_id is neither native token nor whitelisted
+//!
+//! `TooManyRewardTokens` - pool has maximal number of different tokens in schedules that have not ended yet
+//!
+//! # fn claim_schedule_rewards
+//! -Claims all rewards of reward_token_id earned by user from schedules of the pool
+//!
+//! ### Errors
+//! `NoSuchRewardSchedule` - pool was never sponsored with reward_token_id
+//!
+//! `NotEnoughtRewardsEarned` - user has no rewards to claim
+//!
+//! # fn refund_reward_schedule
+//! -Transfers undistributed rewards of ended schedule back to the sponsor
+//!
+//! ### Errors
+//! `NoSuchRewardSchedule` - pool was never sponsored with reward_token_id
+//!
+//! `NotRewardScheduleSponsor` - sender is not sponsor of the schedule
+//!
+//! `RewardScheduleActive` - schedule has not ended yet
+//!
+//! # fn unpromote_pool
+//! -Root only, stops liquidity mining rewards of promoted pool, rewards ratio of the pool is frozen at its current value
+//! -Users can still claim rewards earned before unpromotion, deactivate and burn liquidity, but cannot activate liquidity anymore
+//! -Pool can be promoted again with promote_pool only after all its liquidity is deactivated
+//!
+//! ### arguments
+//! `liquidity_token_id` - liquidity token of the pool
+//!
+//! ### Errors
+//! `NotAPromotedPool` - pool is not promoted
+//!
+//! # fn claim_rewards_all_pools_v2
+//! -Claims all available liquidity mining rewards from every pool user has rewards info for, same as claim_rewards_all_v2 for each of them
+//! -Pools are visited in storage order starting after start_after, only pools that rewards are claimed from count towards max_pools
+//! -Pools without rewards are skipped, at most MAX_CLAIM_ALL_SKIPPED_POOLS of them in single call
+//! -Emits RewardsClaimed for every pool and RewardsClaimedFromAllPools with the total and last visited liquidity token, which is passed as start_after to continue
+//!
+//! ### arguments
+//! `origin` - sender of a fn, user claiming rewards
+//!
+//! `start_after` - liquidity token after which pools are visited, None starts from the first pool
+//!
+//! `max_pools` - maximal number of pools claimed from, weight of the call grows with it
+//!
+//! ### Errors
+//! `NotEnoughtRewardsEarned` - user has no rewards to claim in any of visited pools
+//!
+//! # fn compound_rewards
+//! -Claims all liquidity mining rewards of liquidity_token_id, sells part of them for the other token of the pool and mints liquidity with both, in one transaction
+//! -Sold part is chosen so that remaining rewards and bought tokens match ratio of reserves after the sale, tokens which cannot be minted due to rounding stay with the user
+//! -Minted liquidity tokens can be activated right away
+//!
+//! ### arguments
+//! `origin` - sender of a fn, user compounding rewards
+//!
+//! `liquidity_token_id` - liquidity token of constant product pool paired with native token
+//!
+//! `min_liquidity_minted` - minimal amount of liquidity tokens minted from rewards
+//!
+//! `activate_minted_liquidity` - activates minted liquidity tokens for liquidity mining
+//!
+//! ### Errors
+//! `NotMangataLiquidityAsset` - pool is not paired with native token
+//!
+//! `NotEnoughtRewardsEarned` - user has no rewards to claim
+//!
+//! `InsufficientLiquidityMinted` - amount of liquidity tokens minted is lower than min_liquidity_minted
+//!
+//! and all errors of sell_asset, mint_liquidity and activate_liquidity_v2
+//!
+//! # fn set_reward_curve
+//! -Root only, sets how fast liquidity mining rewards of activated liquidity ramp up in promoted pool, pools without curve use Q quotient and RewardsDistributionPeriod
+//! -Time of rewards checkpoints of the pool continues from the time of previous curve, part of period of previous curve not finished when curve is set is not counted
+//! -Checkpoints of activated liquidity taken with previous curve are checkpointed under it at the time of the change, in on_idle or on the first use of them, whichever comes first
+//! -Curve of a pool cannot be set again until checkpoints of its previous change are migrated, curves of other pools can
+//! -Curve cannot be set until accounts with rewards info stored before pallet storage version 2 are indexed by pool in on_idle
+//!
+//! ### arguments
+//! `liquidity_token_id` - liquidity token of promoted pool
+//!
+//! `quotient` - ratio in which rewards ramp up every period, above 1, at most 2, with two decimal places
+//!
+//! `distribution_period` - number of blocks in one period of the curve
+//!
+//! ### Errors
+//! `NotAPromotedPool` - pool is not promoted
+//!
+//! `InvalidRewardCurve` - quotient or distribution_period out of allowed range
+//!
+//! `RewardCurveMigrationPending` - checkpoints of previous change of reward curve of the pool are not migrated yet, or accounts with rewards info are not indexed yet
+//!
+//! # fn set_reward_token_whitelisted
+//! -Root only, allows or disallows token in new reward schedules, existing schedules are not affected
+//! -Native token is always allowed
+//!
+//! ### arguments
+//! `reward_token_id` - token distributed as rewards
+//!
+//! `whitelisted` - whether new reward schedules can use the token
+//!
 //! # calculate_sell_price
-//! - Supporting public function accessible through rpc call which calculates and returns bought_token_amount while providing sold_token_amount and respective reserves
+//! - Supporting public function accessible through rpc call which calculates and returns bought_token_amount while providing sold_token_amount and respective reserves, using default fees
+//! - Deprecated, pool fee overrides and stable swap pools are not accounted for, use calculate_sell_price_id instead
 //! # calculate_buy_price
-//! - Supporting public function accessible through rpc call which calculates and returns sold_token_amount while providing bought_token_amount and respective reserves
+//! - Supporting public function accessible through rpc call which calculates and returns sold_token_amount while providing bought_token_amount and respective reserves, using default fees
+//! - Deprecated, pool fee overrides and stable swap pools are not accounted for, use calculate_buy_price_id instead
 //! # calculate_sell_price_id
-//! - Same as calculate_sell_price, but providing token_id instead of reserves. Reserves are fetched by function.
+//! - Same as calculate_sell_price, but providing token_id instead of reserves. Reserves and fee of the pool are fetched by function.
 //! # calculate_buy_price_id
-//! - Same as calculate_buy_price, but providing token_id instead of reserves. Reserves are fetched by function.
+//! - Same as calculate_buy_price, but providing token_id instead of reserves. Reserves and fee of the pool are fetched by function.
 //! # get_liquidity_token
 //! - Supporting public function accessible through rpc call which returns liquidity_token_id while providing pair token ids
 //! # get_burn_amount
 //! - Supporting public function accessible through rpc call which returns amounts of tokens received by burning provided liquidity_token_amount in pool of provided token ids
+//! # get_twap
+//! - Supporting public function accessible through runtime api and `PoolPriceOracleTrait` which returns time weighted average prices of pool tokens over provided number of last blocks
+//! - Prices are accumulated at the first reserve change of every block, using reserves from the end of previous block, so prices moved within a block do not affect average until next block
 //! # account_id
 //! - Returns palled account_id
 //! # settle_treasury_buy_and_burn
@@ -230,23 +549,32 @@ use sp_core::U256;
 use codec::FullCodec;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{tokens::currency::MultiTokenCurrency, ExistenceRequirement, Get, WithdrawReasons},
-	transactional, Parameter,
+	traits::{
+		tokens::currency::MultiTokenCurrency, ExistenceRequirement, Get, GetStorageVersion,
+		StorageVersion, WithdrawReasons,
+	},
+	transactional,
+	weights::{GetDispatchInfo, PostDispatchInfo},
+	Parameter,
 };
 use frame_system::pallet_prelude::*;
 use mangata_types::{Balance, TokenId};
 use mp_bootstrap::PoolCreateApi;
 use mp_multipurpose_liquidity::ActivateKind;
-use mp_traits::{ActivationReservesProviderTrait, XykFunctionsTrait};
+use mp_traits::{ActivationReservesProviderTrait, PoolPriceOracleTrait, XykFunctionsTrait};
 use orml_tokens::{MultiTokenCurrencyExtended, MultiTokenReservableCurrency};
 use pallet_issuance::{ActivedPoolQueryApi, ComputeIssuance, PoolPromoteApi};
 use pallet_vesting_mangata::MultiTokenVestingLocks;
-use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, per_things::Rounding};
+use sp_arithmetic::{
+	helpers_128bit::multiply_by_rational_with_rounding, per_things::Rounding, FixedPointNumber,
+	FixedU128,
+};
 use sp_runtime::traits::{
-	AccountIdConversion, AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member,
+	AccountIdConversion, AtLeast32BitUnsigned, Dispatchable, MaybeSerializeDeserialize, Member,
 	SaturatedConversion, Zero,
 };
 use sp_std::{
+	collections::btree_map::BTreeMap,
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	prelude::*,
@@ -268,6 +596,158 @@ pub struct RewardInfo {
 	pub missing_at_last_checkpoint: U256,
 }
 
+/// Fees charged on swaps in a pool, in basis points of the sold amount
+#[derive(
+	Encode, Decode, Clone, Copy, Default, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen,
+)]
+pub struct PoolFeeInfo {
+	pub pool_fee_percentage: u128,
+	pub treasury_fee_percentage: u128,
+	pub buy_and_burn_fee_percentage: u128,
+}
+
+/// Fees of a single swap that go to treasury and for buy and burn purpose, not settled yet
+struct FeeSettlement {
+	sold_asset_id: TokenId,
+	buy_and_burn_amount: Balance,
+	treasury_amount: Balance,
+	pool_fee: PoolFeeInfo,
+}
+
+impl PoolFeeInfo {
+	pub fn total(&self) -> u128 {
+		self.pool_fee_percentage
+			.saturating_add(self.treasury_fee_percentage)
+			.saturating_add(self.buy_and_burn_fee_percentage)
+	}
+}
+
+/// Activation curve of liquidity mining rewards of a pool, rewards of activated liquidity ramp up
+/// by `quotient` every `distribution_period` blocks
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct RewardCurve {
+	pub quotient: FixedU128,
+	pub distribution_period: u32,
+	// Block and curve time since which time of the pool is counted by this curve,
+	// so that checkpoints taken with previous curve stay valid
+	pub start_block: u32,
+	pub start_time: u32,
+}
+
+impl RewardCurve {
+	/// Number of periods passed since start of rewards, as used in rewards checkpoints
+	pub fn time_at(&self, block: u32) -> u32 {
+		self.start_time.saturating_add(
+			block.saturating_add(1).saturating_sub(self.start_block) / self.distribution_period,
+		)
+	}
+}
+
+/// Change of reward curve of a pool, rewards checkpoints of the pool taken before the change
+/// are checkpointed under `previous_curve` at the time of the change
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct RewardCurveMigration<AccountId> {
+	pub previous_curve: RewardCurve,
+	pub pool_ratio_at_change: U256,
+	// Last account with rewards info in the pool visited by on_idle migration, in storage order
+	pub last_visited: Option<AccountId>,
+}
+
+/// Cumulative prices of pool, sum of prices at the end of every block since pool creation,
+/// first price is price of first token of the pool in second token, second price vice versa
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PriceAccumulator {
+	pub last_updated: u32,
+	pub first_price_cumulative: U256,
+	pub second_price_cumulative: U256,
+	/// Index of the latest observation in `PriceObservations`
+	pub observation_index: u32,
+	pub observations_count: u32,
+}
+
+/// Snapshot of cumulative prices of pool taken at block in which they were updated
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct PriceObservation {
+	pub block: u32,
+	pub first_price_cumulative: U256,
+	pub second_price_cumulative: U256,
+}
+
+/// Time limited distribution of sponsor's token to activated liquidity of a pool
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct RewardSchedule<AccountId> {
+	pub sponsor: AccountId,
+	pub amount_per_block: Balance,
+	pub last_block: u32,
+	pub last_updated: u32,
+	// Cumulative rewards per activated liquidity token, multiplied by u128::MAX
+	pub rewards_per_liquidity: U256,
+	// Rewards not distributed to anyone, refundable to sponsor once schedule ends
+	pub undistributed: Balance,
+}
+
+/// Checkpoint of user's rewards from reward schedule of single token in single pool
+#[derive(Encode, Decode, Clone, Default, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct ScheduleRewardInfo {
+	pub rewards_per_liquidity_at_checkpoint: U256,
+	pub rewards_not_yet_claimed: Balance,
+}
+
+/// Description of pool returned by pool enumeration
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct PoolInfo {
+	pub liquidity_token_id: TokenId,
+	pub first_asset_id: TokenId,
+	pub second_asset_id: TokenId,
+	pub first_asset_reserve: Balance,
+	pub second_asset_reserve: Balance,
+	pub liquidity_token_issuance: Balance,
+	pub is_promoted: bool,
+	pub activated_amount: Balance,
+}
+
+/// Liquidity mining rewards of promoted pool over recent rounds together with
+/// its reserves valued in native currency, returned by APR queries
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct PoolRewardsStats {
+	pub liquidity_token_id: TokenId,
+	/// Liquidity mining issuance of pool as (round, issuance) ordered by round
+	pub rewards_history: Vec<(u32, Balance)>,
+	pub activated_amount: Balance,
+	/// Both pool reserves valued in native currency at pool price
+	pub reserves_valuation: Balance,
+	/// Part of reserves backing activated liquidity valued in native currency
+	pub activated_valuation: Balance,
+	/// Weight of pool in liquidity mining issuance split, zero if pool is not promoted
+	pub pool_weight: u8,
+	/// Sum of weights of all activated promoted pools, expected share of pool in the next
+	/// liquidity mining issuance is `pool_weight / total_pools_weight`
+	pub total_pools_weight: u32,
+}
+
+/// Position of account in single pool returned by portfolio queries
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct LiquidityPosition {
+	pub liquidity_token_id: TokenId,
+	pub first_asset_id: TokenId,
+	pub second_asset_id: TokenId,
+	pub free_amount: Balance,
+	pub reserved_amount: Balance,
+	pub activated_amount: Balance,
+	pub first_asset_amount: Balance,
+	pub second_asset_amount: Balance,
+	pub claimable_rewards: Balance,
+}
+
+/// Pricing formula used by pool
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum PoolKind {
+	/// x * y = k
+	ConstantProduct,
+	/// Stable swap invariant with amplification coefficient, for pairs of pegged tokens
+	StableSwap { amplification: u128 },
+}
+
 pub(crate) const LOG_TARGET: &'static str = "xyk";
 
 // syntactic sugar for logging.
@@ -282,11 +762,40 @@ macro_rules! log {
 }
 
 const PALLET_ID: PalletId = PalletId(*b"79b14c96");
-// Quocient ratio in which liquidity minting curve is rising
-const Q: f64 = 1.03;
+// Quocient ratio in which liquidity minting curve is rising, 1.03
+const Q: FixedU128 = FixedU128::from_inner(1_030_000_000_000_000_000);
+// Quocient ratio of liquidity minting curve of rewards v1, 1.06, used by v1 migration only
+const Q_V1: FixedU128 = FixedU128::from_inner(1_060_000_000_000_000_000);
+// Maximal quotient of reward curve settable for a pool, 2.0
+const MAX_REWARD_CURVE_QUOTIENT: FixedU128 = FixedU128::from_inner(2_000_000_000_000_000_000);
 // Precision used in rewards calculation rounding
 const REWARDS_PRECISION: u32 = 10000;
 
+// Maximal number of pools a swap path found by best path search can go through
+const MAX_SWAP_PATH_HOPS: usize = 3;
+// Maximal number of tokens in path of multiswap extrinsics
+pub const MAX_MULTISWAP_PATH_LENGTH: u32 = 5;
+
+// Maximal amplification coefficient of stable swap pool
+const MAX_AMPLIFICATION: u128 = 1_000_000;
+// Maximal number of newton's method iterations in stable swap math
+const STABLE_SWAP_MAX_ITERATIONS: u32 = 255;
+
+// Maximal number of pools returned by single page of pool enumeration
+const MAX_POOLS_PAGE_SIZE: u32 = 100;
+// Maximal number of pools without rewards skipped by single claim of rewards from all pools
+pub const MAX_CLAIM_ALL_SKIPPED_POOLS: u32 = 20;
+
+// Number of cumulative price observations kept per pool, limits the longest TWAP window
+const MAX_PRICE_OBSERVATIONS: u32 = 256;
+// Precision of prices in TWAP accumulators
+pub const TWAP_PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+// Maximal number of different tokens pool can be sponsored with by reward schedules
+const MAX_REWARD_TOKENS_PER_POOL: u32 = 8;
+// Derivation of account holding tokens deposited to reward schedules
+const REWARD_SCHEDULES_SUB_ACCOUNT: [u8; 4] = *b"rwsc";
+
 // Keywords for asset_info
 const LIQUIDITY_TOKEN_IDENTIFIER: &[u8] = b"LiquidityPoolToken";
 const HEX_INDICATOR: &[u8] = b"0x";
@@ -297,6 +806,7 @@ const DEFAULT_DECIMALS: u32 = 18u32;
 pub use pallet::*;
 
 mod benchmarking;
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -306,12 +816,42 @@ pub mod pallet {
 
 	use super::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let mut consumed_weight = migrations::v1::migrate_rewards_step::<T>(remaining_weight);
+			consumed_weight =
+				consumed_weight.saturating_add(migrations::v2::index_rewards_accounts_step::<T>(
+					remaining_weight.saturating_sub(consumed_weight),
+				));
+			consumed_weight.saturating_add(migrations::reward_curve::migrate_rewards_step::<T>(
+				remaining_weight.saturating_sub(consumed_weight),
+			))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			migrations::v1::pre_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			migrations::v1::post_upgrade::<T>()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+			migrations::v1::check_rewards_state::<T>()?;
+			migrations::v2::check_rewards_accounts::<T>()
+		}
+	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	pub trait XykBenchmarkingConfig: pallet_issuance::Config {}
@@ -348,8 +888,11 @@ pub mod pallet {
 		type VestingProvider: MultiTokenVestingLocks<Self::AccountId, Self::BlockNumber>;
 		type AssetMetadataMutation: AssetMetadataMutationTrait;
 		type WeightInfo: WeightInfo;
-		#[pallet::constant]
-		type RewardsMigrateAccount: Get<Self::AccountId>;
+		/// Callback call dispatched by flash swaps
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
 	}
 
 	#[pallet::error]
@@ -408,6 +951,57 @@ pub mod pallet {
 		CalculateCumulativeWorkMaxRatioMathError,
 		CalculateRewardsAllMathError,
 		NoRights,
+		/// Multiswap path must consist of at least two tokens
+		MultiswapPathTooShort,
+		/// Multiswap path cannot contain the same token twice in a row
+		MultiswapSameTokenConsecutively,
+		/// Multiswap buy path cannot go through the same pool more than once
+		MultiswapBuyRepeatedPool,
+		/// Transaction was executed after block number specified as its deadline
+		DeadlineExceeded,
+		/// Amount of liquidity tokens minted is lower than required min_liquidity_minted
+		InsufficientLiquidityMinted,
+		/// Amount of first or second asset received from burning is lower than required minimum
+		InsufficientBurnedAssetsAmount,
+		/// Total pool fee has to be lower than 100%
+		InvalidPoolFee,
+		/// Amplification coefficient of stable swap pool is out of allowed range
+		InvalidAmplification,
+		/// Stable swap math did not converge
+		StableSwapNotConverged,
+		/// TWAP window has to be at least one block long
+		InvalidTwapWindow,
+		/// Pool has no price observation old enough to cover requested TWAP window
+		NotEnoughPriceHistory,
+		/// Pool is in the middle of flash swap and cannot be used until it is repaid
+		PoolLocked,
+		/// Flash swaps are supported only in constant product pools
+		FlashSwapNotSupported,
+		/// Pool invariant was not restored after flash swap
+		FlashSwapInvariantViolated,
+		/// Reward schedule has to last at least one block and distribute non zero amount per block
+		InvalidRewardSchedule,
+		/// Reward schedule of the same token for the pool has not ended yet
+		RewardScheduleActive,
+		/// Pool cannot be sponsored with any more different tokens
+		TooManyRewardTokens,
+		/// No reward schedule of the token for the pool exists
+		NoSuchRewardSchedule,
+		/// Only sponsor of the reward schedule can refund it
+		NotRewardScheduleSponsor,
+		/// Unpromoted pool can be promoted again only after all its liquidity is deactivated
+		LiquidityStillActivated,
+		/// Reward curve quotient has to be above 1, at most 2 with two decimal places,
+		/// distribution period has to be non zero
+		InvalidRewardCurve,
+		/// Multiswap path cannot consist of more than `MAX_MULTISWAP_PATH_LENGTH` tokens
+		MultiswapPathTooLong,
+		/// Pool or its reserves were changed by flash swap callback
+		FlashSwapPoolChanged,
+		/// Only native token and tokens whitelisted by root can be used in reward schedules
+		RewardTokenNotWhitelisted,
+		/// Checkpoints of previous change of reward curve are not migrated yet
+		RewardCurveMigrationPending,
 	}
 
 	#[pallet::event]
@@ -418,9 +1012,23 @@ pub mod pallet {
 		LiquidityMinted(T::AccountId, TokenId, Balance, TokenId, Balance, TokenId, Balance),
 		LiquidityBurned(T::AccountId, TokenId, Balance, TokenId, Balance, TokenId, Balance),
 		PoolPromoted(TokenId),
+		PoolUnpromoted(TokenId),
 		LiquidityActivated(T::AccountId, TokenId, Balance),
 		LiquidityDeactivated(T::AccountId, TokenId, Balance),
 		RewardsClaimed(T::AccountId, TokenId, Balance),
+		/// Total rewards claimed from all pools, number of pools claimed from and last visited
+		/// liquidity token to continue from, each pool is also reported by RewardsClaimed
+		RewardsClaimedFromAllPools(T::AccountId, Balance, u32, TokenId),
+		/// Claimed rewards, liquidity tokens minted from them
+		RewardsCompounded(T::AccountId, TokenId, Balance, Balance),
+		PoolFeeSet(TokenId, Option<PoolFeeInfo>),
+		RewardCurveSet(TokenId, FixedU128, u32),
+		StableSwapPoolCreated(TokenId, u128),
+		FlashSwapExecuted(T::AccountId, TokenId, Balance, TokenId, Balance),
+		RewardScheduleCreated(T::AccountId, TokenId, TokenId, Balance, u32),
+		RewardScheduleRefunded(T::AccountId, TokenId, TokenId, Balance),
+		ScheduleRewardsClaimed(T::AccountId, TokenId, TokenId, Balance),
+		RewardTokenWhitelistSet(TokenId, bool),
 	}
 
 	#[pallet::storage]
@@ -438,6 +1046,105 @@ pub mod pallet {
 	pub type LiquidityPools<T: Config> =
 		StorageMap<_, Blake2_256, TokenId, Option<(TokenId, TokenId)>, ValueQuery>;
 
+	/// Fee overrides of pools keyed by liquidity token, pools without entry use `Config` fees
+	#[pallet::storage]
+	#[pallet::getter(fn pool_fees)]
+	pub type PoolFees<T: Config> = StorageMap<_, Twox64Concat, TokenId, PoolFeeInfo, OptionQuery>;
+
+	/// Reward curves of pools keyed by liquidity token, pools without entry use `Q` quotient
+	/// and `RewardsDistributionPeriod` from `Config`
+	#[pallet::storage]
+	#[pallet::getter(fn pool_reward_curves)]
+	pub type PoolRewardCurves<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, RewardCurve, OptionQuery>;
+
+	/// Last change of reward curve of pools keyed by liquidity token, as long as rewards
+	/// checkpoints of the pool taken before it are not migrated
+	#[pallet::storage]
+	#[pallet::getter(fn pending_reward_curve_migration)]
+	pub type PendingRewardCurveMigration<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, RewardCurveMigration<AccountIdOf<T>>, OptionQuery>;
+
+	/// Kinds of pools keyed by liquidity token, pools without entry are constant product pools
+	#[pallet::storage]
+	#[pallet::getter(fn pool_kinds)]
+	pub type PoolKinds<T: Config> = StorageMap<_, Twox64Concat, TokenId, PoolKind, OptionQuery>;
+
+	/// Pools with flash swap in progress, keyed same as `Pools`
+	#[pallet::storage]
+	#[pallet::getter(fn flash_swap_lock)]
+	pub type FlashSwapLocks<T: Config> =
+		StorageMap<_, Twox64Concat, (TokenId, TokenId), (), OptionQuery>;
+
+	/// Cumulative prices of pools keyed same as `Pools`
+	#[pallet::storage]
+	#[pallet::getter(fn price_accumulator)]
+	pub type PriceAccumulators<T: Config> =
+		StorageMap<_, Twox64Concat, (TokenId, TokenId), PriceAccumulator, OptionQuery>;
+
+	/// Ring buffer of last `MAX_PRICE_OBSERVATIONS` snapshots of cumulative prices of pools
+	#[pallet::storage]
+	#[pallet::getter(fn price_observation)]
+	pub type PriceObservations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(TokenId, TokenId),
+		Twox64Concat,
+		u32,
+		PriceObservation,
+		OptionQuery,
+	>;
+
+	/// Pool rewards ratio of unpromoted pools, frozen at the time of unpromotion
+	#[pallet::storage]
+	#[pallet::getter(fn unpromoted_pool_rewards)]
+	pub type UnpromotedPoolsRewards<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, U256, OptionQuery>;
+
+	/// Reward schedules keyed by liquidity token and reward token
+	#[pallet::storage]
+	#[pallet::getter(fn reward_schedule)]
+	pub type RewardSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		TokenId,
+		Twox64Concat,
+		TokenId,
+		RewardSchedule<AccountIdOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Tokens of reward schedules of the pool in order of first reward schedule, ended
+	/// schedules are pruned when schedule of new token is created
+	#[pallet::storage]
+	#[pallet::getter(fn pool_reward_tokens)]
+	pub type PoolRewardTokens<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TokenId,
+		BoundedVec<TokenId, ConstU32<MAX_REWARD_TOKENS_PER_POOL>>,
+		ValueQuery,
+	>;
+
+	/// Tokens other than native token allowed in reward schedules
+	#[pallet::storage]
+	#[pallet::getter(fn is_reward_token_whitelisted)]
+	pub type RewardTokensWhitelist<T: Config> =
+		StorageMap<_, Twox64Concat, TokenId, bool, ValueQuery>;
+
+	/// Checkpoints of users' rewards from reward schedules keyed by (liquidity token, reward token)
+	#[pallet::storage]
+	#[pallet::getter(fn schedule_rewards_info)]
+	pub type ScheduleRewardsInfo<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		AccountIdOf<T>,
+		Twox64Concat,
+		(TokenId, TokenId),
+		ScheduleRewardInfo,
+		ValueQuery,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_rewards_info)]
 	pub type RewardsInfo<T: Config> = StorageDoubleMap<
@@ -450,41 +1157,16 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Accounts with `RewardsInfo` keyed by liquidity token, so that rewards info of a pool
+	/// can be iterated
 	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_active_pool_v2)]
-	pub type LiquidityMiningActivePoolV2<T: Config> =
-		StorageMap<_, Twox64Concat, TokenId, u128, ValueQuery>;
+	pub type RewardsInfoAccounts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TokenId, Twox64Concat, AccountIdOf<T>, (), OptionQuery>;
 
-	// REWARDS V1 to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_user)]
-	pub type LiquidityMiningUser<T: Config> =
-		StorageMap<_, Blake2_256, (AccountIdOf<T>, TokenId), (u32, U256, U256), ValueQuery>;
-	// REWARDS V1 to be removed
 	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_pool)]
-	pub type LiquidityMiningPool<T: Config> =
-		StorageMap<_, Blake2_256, TokenId, (u32, U256, U256), ValueQuery>;
-	// REWARDS V1 to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_user_to_be_claimed)]
-	pub type LiquidityMiningUserToBeClaimed<T: Config> =
-		StorageMap<_, Blake2_256, (AccountIdOf<T>, TokenId), u128, ValueQuery>;
-	// REWARDS V1 to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_active_user)]
-	pub type LiquidityMiningActiveUser<T: Config> =
-		StorageMap<_, Twox64Concat, (AccountIdOf<T>, TokenId), u128, ValueQuery>;
-	// REWARDS V1 to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_active_pool)]
-	pub type LiquidityMiningActivePool<T: Config> =
+	#[pallet::getter(fn liquidity_mining_active_pool_v2)]
+	pub type LiquidityMiningActivePoolV2<T: Config> =
 		StorageMap<_, Twox64Concat, TokenId, u128, ValueQuery>;
-	// REWARDS V1 to be removed
-	#[pallet::storage]
-	#[pallet::getter(fn liquidity_mining_user_claimed)]
-	pub type LiquidityMiningUserClaimed<T: Config> =
-		StorageMap<_, Twox64Concat, (AccountIdOf<T>, TokenId), u128, ValueQuery>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -583,8 +1265,44 @@ pub mod pallet {
 			Ok(().into())
 		}
 
-		// you will sell your sold_asset_amount of sold_asset_id to get some amount of bought_asset_id
-		#[pallet::weight(<<T as Config>::WeightInfo>::sell_asset())]
+		/// Creates pool priced by stable swap invariant instead of constant product, suitable
+		/// for pairs of pegged tokens. Higher `amplification` means lower slippage around 1:1 ratio.
+		#[pallet::weight(<<T as Config>::WeightInfo>::create_stable_pool())]
+		#[transactional]
+		pub fn create_stable_pool(
+			origin: OriginFor<T>,
+			first_asset_id: TokenId,
+			first_asset_amount: Balance,
+			second_asset_id: TokenId,
+			second_asset_amount: Balance,
+			amplification: u128,
+		) -> DispatchResultWithPostInfo {
+			ensure!(
+				amplification > 0 && amplification <= MAX_AMPLIFICATION,
+				Error::<T>::InvalidAmplification
+			);
+
+			Self::create_pool(
+				origin,
+				first_asset_id,
+				first_asset_amount,
+				second_asset_id,
+				second_asset_amount,
+			)?;
+
+			let liquidity_asset_id = Self::get_liquidity_asset(first_asset_id, second_asset_id)?;
+			PoolKinds::<T>::insert(liquidity_asset_id, PoolKind::StableSwap { amplification });
+
+			Pallet::<T>::deposit_event(Event::StableSwapPoolCreated(
+				liquidity_asset_id,
+				amplification,
+			));
+
+			Ok(().into())
+		}
+
+		// you will sell your sold_asset_amount of sold_asset_id to get some amount of bought_asset_id
+		#[pallet::weight(<<T as Config>::WeightInfo>::sell_asset())]
 		pub fn sell_asset(
 			origin: OriginFor<T>,
 			sold_asset_id: TokenId,
@@ -624,6 +1342,134 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Sells `sold_asset_amount` of the first token in `swap_token_list` and routes the
+		/// proceeds through every consecutive pool of the list. Fees are charged and settled at
+		/// every hop, `min_amount_out` is enforced only on the last token of the path.
+		#[pallet::weight(<<T as Config>::WeightInfo>::multiswap_sell_asset(swap_token_list.len() as u32))]
+		#[transactional]
+		pub fn multiswap_sell_asset(
+			origin: OriginFor<T>,
+			swap_token_list: Vec<TokenId>,
+			sold_asset_amount: Balance,
+			min_amount_out: Balance,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			<Self as XykFunctionsTrait<T::AccountId>>::multiswap_sell_asset(
+				sender,
+				swap_token_list,
+				sold_asset_amount,
+				min_amount_out,
+			)?;
+			Ok(().into())
+		}
+
+		/// Buys `bought_asset_amount` of the last token in `swap_token_list` paying with the first
+		/// one, routing through every consecutive pool of the list. Fees are charged at every hop and
+		/// settled after the last one, `max_amount_in` is enforced only on the first token of the path.
+		#[pallet::weight(<<T as Config>::WeightInfo>::multiswap_buy_asset(swap_token_list.len() as u32))]
+		#[transactional]
+		pub fn multiswap_buy_asset(
+			origin: OriginFor<T>,
+			swap_token_list: Vec<TokenId>,
+			bought_asset_amount: Balance,
+			max_amount_in: Balance,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			<Self as XykFunctionsTrait<T::AccountId>>::multiswap_buy_asset(
+				sender,
+				swap_token_list,
+				bought_asset_amount,
+				max_amount_in,
+			)?;
+			Ok(().into())
+		}
+
+		/// Same as `sell_asset`, fails with `DeadlineExceeded` if executed after block `deadline`
+		#[pallet::weight(<<T as Config>::WeightInfo>::sell_asset())]
+		pub fn sell_asset_with_deadline(
+			origin: OriginFor<T>,
+			sold_asset_id: TokenId,
+			bought_asset_id: TokenId,
+			sold_asset_amount: Balance,
+			min_amount_out: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_deadline(deadline)?;
+			Self::sell_asset(
+				origin,
+				sold_asset_id,
+				bought_asset_id,
+				sold_asset_amount,
+				min_amount_out,
+			)
+		}
+
+		/// Same as `buy_asset`, fails with `DeadlineExceeded` if executed after block `deadline`
+		#[pallet::weight(<<T as Config>::WeightInfo>::buy_asset())]
+		pub fn buy_asset_with_deadline(
+			origin: OriginFor<T>,
+			sold_asset_id: TokenId,
+			bought_asset_id: TokenId,
+			bought_asset_amount: Balance,
+			max_amount_in: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_deadline(deadline)?;
+			Self::buy_asset(
+				origin,
+				sold_asset_id,
+				bought_asset_id,
+				bought_asset_amount,
+				max_amount_in,
+			)
+		}
+
+		/// Same as `mint_liquidity`, fails with `DeadlineExceeded` if executed after block `deadline`
+		#[pallet::weight(<<T as Config>::WeightInfo>::mint_liquidity())]
+		pub fn mint_liquidity_with_deadline(
+			origin: OriginFor<T>,
+			first_asset_id: TokenId,
+			second_asset_id: TokenId,
+			first_asset_amount: Balance,
+			expected_second_asset_amount: Balance,
+			min_liquidity_minted: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_deadline(deadline)?;
+			Self::mint_liquidity(
+				origin,
+				first_asset_id,
+				second_asset_id,
+				first_asset_amount,
+				expected_second_asset_amount,
+				min_liquidity_minted,
+			)
+		}
+
+		/// Same as `burn_liquidity`, fails with `DeadlineExceeded` if executed after block `deadline`
+		#[pallet::weight(<<T as Config>::WeightInfo>::burn_liquidity())]
+		pub fn burn_liquidity_with_deadline(
+			origin: OriginFor<T>,
+			first_asset_id: TokenId,
+			second_asset_id: TokenId,
+			liquidity_asset_amount: Balance,
+			min_first_asset_amount: Balance,
+			min_second_asset_amount: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_deadline(deadline)?;
+			Self::burn_liquidity(
+				origin,
+				first_asset_id,
+				second_asset_id,
+				liquidity_asset_amount,
+				min_first_asset_amount,
+				min_second_asset_amount,
+			)
+		}
+
 		#[pallet::weight(<<T as Config>::WeightInfo>::mint_liquidity_using_vesting_native_tokens())]
 		#[transactional]
 		pub fn mint_liquidity_using_vesting_native_tokens_by_vesting_index(
@@ -726,12 +1572,14 @@ pub mod pallet {
 		}
 
 		#[pallet::weight(<<T as Config>::WeightInfo>::mint_liquidity())]
+		#[transactional]
 		pub fn mint_liquidity(
 			origin: OriginFor<T>,
 			first_asset_id: TokenId,
 			second_asset_id: TokenId,
 			first_asset_amount: Balance,
 			expected_second_asset_amount: Balance,
+			min_liquidity_minted: Balance,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
@@ -741,14 +1589,20 @@ pub mod pallet {
 				Error::<T>::FunctionNotAvailableForThisToken
 			);
 
-			<Self as XykFunctionsTrait<T::AccountId>>::mint_liquidity(
-				sender,
-				first_asset_id,
-				second_asset_id,
-				first_asset_amount,
-				expected_second_asset_amount,
-				true,
-			)?;
+			let (_, liquidity_assets_minted) =
+				<Self as XykFunctionsTrait<T::AccountId>>::mint_liquidity(
+					sender,
+					first_asset_id,
+					second_asset_id,
+					first_asset_amount,
+					expected_second_asset_amount,
+					true,
+				)?;
+
+			ensure!(
+				liquidity_assets_minted >= min_liquidity_minted,
+				Error::<T>::InsufficientLiquidityMinted
+			);
 
 			Ok(().into())
 		}
@@ -759,9 +1613,19 @@ pub mod pallet {
 			first_asset_id: TokenId,
 			second_asset_id: TokenId,
 			liquidity_asset_amount: Balance,
+			min_first_asset_amount: Balance,
+			min_second_asset_amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
+			let (first_asset_amount, second_asset_amount) =
+				Self::get_burn_amount(first_asset_id, second_asset_id, liquidity_asset_amount)?;
+			ensure!(
+				first_asset_amount >= min_first_asset_amount &&
+					second_asset_amount >= min_second_asset_amount,
+				Error::<T>::InsufficientBurnedAssetsAmount
+			);
+
 			<Self as XykFunctionsTrait<T::AccountId>>::burn_liquidity(
 				sender,
 				first_asset_id,
@@ -806,6 +1670,104 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Claims all available rewards of every liquidity token user has activated or earned
+		/// rewards for after `start_after`, claiming from at most `max_pools` of them
+		#[transactional]
+		#[pallet::weight(<<T as Config>::WeightInfo>::claim_rewards_all_pools_v2(*max_pools)
+			.saturating_add(T::DbWeight::get().reads(3 * MAX_CLAIM_ALL_SKIPPED_POOLS as u64)))]
+		pub fn claim_rewards_all_pools_v2(
+			origin: OriginFor<T>,
+			start_after: Option<TokenId>,
+			max_pools: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Pallet::<T>::do_claim_rewards_all_pools_v2(sender, start_after, max_pools)?;
+
+			Ok(().into())
+		}
+
+		/// Claims all rewards of `liquidity_token_id`, sells part of them for the other token of
+		/// the pool and mints liquidity with both, optionally activating minted liquidity tokens
+		#[transactional]
+		#[pallet::weight(<<T as Config>::WeightInfo>::compound_rewards())]
+		pub fn compound_rewards(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			min_liquidity_minted: Balance,
+			activate_minted_liquidity: bool,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Pallet::<T>::do_compound_rewards(
+				sender,
+				liquidity_token_id,
+				min_liquidity_minted,
+				activate_minted_liquidity,
+			)?;
+
+			Ok(().into())
+		}
+
+		/// Deposits `amount` of `reward_token_id`, which is distributed evenly to activated
+		/// liquidity of the promoted pool of `liquidity_token_id` in every block until `end_block`.
+		/// Rewards not distributed to anyone are refundable to the sponsor once schedule ends.
+		#[transactional]
+		#[pallet::weight(<<T as Config>::WeightInfo>::create_reward_schedule())]
+		pub fn create_reward_schedule(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			reward_token_id: TokenId,
+			amount: Balance,
+			end_block: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Pallet::<T>::do_create_reward_schedule(
+				sender,
+				liquidity_token_id,
+				reward_token_id,
+				amount,
+				end_block.saturated_into::<u32>(),
+			)
+		}
+
+		/// Claims all rewards of `reward_token_id` earned from reward schedules of the pool
+		/// of `liquidity_token_id`
+		#[transactional]
+		#[pallet::weight(<<T as Config>::WeightInfo>::claim_schedule_rewards())]
+		pub fn claim_schedule_rewards(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			reward_token_id: TokenId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Pallet::<T>::do_claim_schedule_rewards(sender, liquidity_token_id, reward_token_id)
+		}
+
+		/// Returns rewards, which were not distributed because pool had no activated liquidity,
+		/// to the sponsor of ended reward schedule
+		#[transactional]
+		#[pallet::weight(<<T as Config>::WeightInfo>::refund_reward_schedule())]
+		pub fn refund_reward_schedule(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			reward_token_id: TokenId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let schedule =
+				Pallet::<T>::update_reward_schedule(liquidity_token_id, reward_token_id)?;
+			ensure!(schedule.sponsor == sender, Error::<T>::NotRewardScheduleSponsor);
+			ensure!(
+				Pallet::<T>::current_block() >= schedule.last_block,
+				Error::<T>::RewardScheduleActive
+			);
+
+			Pallet::<T>::refund_undistributed_rewards(liquidity_token_id, reward_token_id, schedule)
+		}
+
 		#[pallet::weight(<<T as Config>::WeightInfo>::promote_pool())]
 		pub fn promote_pool(origin: OriginFor<T>, liquidity_token_id: TokenId) -> DispatchResult {
 			ensure_root(origin)?;
@@ -813,6 +1775,134 @@ pub mod pallet {
 			<Self as XykFunctionsTrait<T::AccountId>>::promote_pool(liquidity_token_id)
 		}
 
+		/// Stops liquidity mining rewards of the pool of `liquidity_token_id`. Rewards earned
+		/// until now can still be claimed and liquidity deactivated, but not activated anymore.
+		#[pallet::weight(<<T as Config>::WeightInfo>::unpromote_pool())]
+		pub fn unpromote_pool(origin: OriginFor<T>, liquidity_token_id: TokenId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			<Self as XykFunctionsTrait<T::AccountId>>::unpromote_pool(liquidity_token_id)
+		}
+
+		/// Sets fees charged on swaps in pool of `liquidity_token_id`, `None` reverts the pool
+		/// back to default fees from `Config`
+		#[pallet::weight(<<T as Config>::WeightInfo>::set_pool_fee())]
+		pub fn set_pool_fee(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			pool_fee: Option<PoolFeeInfo>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(LiquidityPools::<T>::get(liquidity_token_id).is_some(), Error::<T>::NoSuchPool);
+
+			match pool_fee {
+				Some(fee) => {
+					ensure!(fee.total() < 10000, Error::<T>::InvalidPoolFee);
+					PoolFees::<T>::insert(liquidity_token_id, fee);
+				},
+				None => PoolFees::<T>::remove(liquidity_token_id),
+			}
+
+			Pallet::<T>::deposit_event(Event::PoolFeeSet(liquidity_token_id, pool_fee));
+
+			Ok(())
+		}
+
+		/// Sets reward curve of promoted pool of `liquidity_token_id`, rewards of activated
+		/// liquidity ramp up by `quotient` every `distribution_period` blocks. New curve applies
+		/// since the next block, checkpoints taken with previous curve are migrated in `on_idle`.
+		#[pallet::weight(<<T as Config>::WeightInfo>::set_reward_curve())]
+		pub fn set_reward_curve(
+			origin: OriginFor<T>,
+			liquidity_token_id: TokenId,
+			quotient: FixedU128,
+			distribution_period: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(
+				<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_token_id).is_some(),
+				Error::<T>::NotAPromotedPool
+			);
+			// Sum of the curve is calculated with quotient multiplied by 100 as integer
+			ensure!(
+				quotient > FixedU128::one() &&
+					quotient <= MAX_REWARD_CURVE_QUOTIENT &&
+					quotient.into_inner() % (FixedU128::DIV / 100) == 0 &&
+					!distribution_period.is_zero(),
+				Error::<T>::InvalidRewardCurve
+			);
+			// Checkpoints of the pool can be migrated only once accounts with rewards info
+			// are indexed
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() >= 2 &&
+					!PendingRewardCurveMigration::<T>::contains_key(liquidity_token_id),
+				Error::<T>::RewardCurveMigrationPending
+			);
+
+			let current_block = Pallet::<T>::current_block();
+			let previous_curve = Pallet::<T>::get_reward_curve(liquidity_token_id);
+			// New curve starts a period after every checkpoint taken with previous curve,
+			// so that not migrated checkpoints can be told apart
+			let start_time = previous_curve.time_at(current_block).saturating_add(1);
+			if !LiquidityMiningActivePoolV2::<T>::get(liquidity_token_id).is_zero() {
+				PendingRewardCurveMigration::<T>::insert(
+					liquidity_token_id,
+					RewardCurveMigration {
+						previous_curve,
+						pool_ratio_at_change: Pallet::<T>::pool_rewards_ratio(liquidity_token_id)?,
+						last_visited: None,
+					},
+				);
+			}
+			PoolRewardCurves::<T>::insert(
+				liquidity_token_id,
+				RewardCurve {
+					quotient,
+					distribution_period,
+					start_block: current_block.saturating_add(1),
+					start_time,
+				},
+			);
+
+			Pallet::<T>::deposit_event(Event::RewardCurveSet(
+				liquidity_token_id,
+				quotient,
+				distribution_period,
+			));
+
+			Ok(())
+		}
+
+		/// Transfers `bought_asset_amount` out of the pool to the caller, dispatches `call`
+		/// on behalf of the caller and only then charges the caller for the bought amount,
+		/// same as `buy_asset` would. The pool is locked until it is repaid, the whole
+		/// transaction is reverted if `call` fails or the pool is not repaid.
+		#[pallet::weight(<<T as Config>::WeightInfo>::flash_swap()
+			.saturating_add(call.get_dispatch_info().weight))]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			sold_asset_id: TokenId,
+			bought_asset_id: TokenId,
+			bought_asset_amount: Balance,
+			max_amount_in: Balance,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			Pallet::<T>::do_flash_swap(
+				sender,
+				sold_asset_id,
+				bought_asset_id,
+				bought_asset_amount,
+				max_amount_in,
+				*call,
+			)?;
+			Ok(().into())
+		}
+
 		#[transactional]
 		#[pallet::weight(<<T as Config>::WeightInfo>::activate_liquidity_v2())]
 		pub fn activate_liquidity_v2(
@@ -847,45 +1937,172 @@ pub mod pallet {
 			)
 		}
 
-		#[transactional]
-		#[pallet::weight(<<T as Config>::WeightInfo>::rewards_migrate_v1_to_v2())]
-		pub fn rewards_migrate_v1_to_v2(
+		/// Allows or disallows `reward_token_id` in new reward schedules, native token is
+		/// always allowed. Existing schedules of the token are not affected.
+		#[pallet::weight(<<T as Config>::WeightInfo>::set_reward_token_whitelisted())]
+		pub fn set_reward_token_whitelisted(
 			origin: OriginFor<T>,
-			account: T::AccountId,
-			liquidity_token_id: TokenId,
+			reward_token_id: TokenId,
+			whitelisted: bool,
 		) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
-			ensure!(sender == T::RewardsMigrateAccount::get(), Error::<T>::NoRights);
+			ensure_root(origin)?;
 
-			<Self as XykFunctionsTrait<T::AccountId>>::rewards_migrate_v1_to_v2(
-				account,
-				liquidity_token_id,
-			)
+			if whitelisted {
+				RewardTokensWhitelist::<T>::insert(reward_token_id, true);
+			} else {
+				RewardTokensWhitelist::<T>::remove(reward_token_id);
+			}
+
+			Pallet::<T>::deposit_event(Event::RewardTokenWhitelistSet(
+				reward_token_id,
+				whitelisted,
+			));
+
+			Ok(())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn total_fee() -> u128 {
-		T::PoolFeePercentage::get() +
-			T::TreasuryFeePercentage::get() +
-			T::BuyAndBurnFeePercentage::get()
+	fn default_pool_fee() -> PoolFeeInfo {
+		PoolFeeInfo {
+			pool_fee_percentage: T::PoolFeePercentage::get(),
+			treasury_fee_percentage: T::TreasuryFeePercentage::get(),
+			buy_and_burn_fee_percentage: T::BuyAndBurnFeePercentage::get(),
+		}
 	}
 
-	pub fn calculate_rewards_amount_v2(
-		user: AccountIdOf<T>,
-		liquidity_asset_id: TokenId,
-	) -> Result<Balance, DispatchError> {
-		let rewards_info: RewardInfo = Self::get_rewards_info(user.clone(), liquidity_asset_id);
+	// Fees of pool, falls back to `Config` fees if pool has no override
+	pub fn get_pool_fee(liquidity_asset_id: TokenId) -> PoolFeeInfo {
+		PoolFees::<T>::get(liquidity_asset_id).unwrap_or_else(Self::default_pool_fee)
+	}
 
-		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
+	fn default_reward_curve() -> RewardCurve {
+		RewardCurve {
+			quotient: Q,
+			distribution_period: T::RewardsDistributionPeriod::get(),
+			start_block: 0,
+			start_time: 0,
+		}
+	}
+
+	// Reward curve of pool, falls back to `Q` and `Config` period if pool has no override
+	pub fn get_reward_curve(liquidity_asset_id: TokenId) -> RewardCurve {
+		PoolRewardCurves::<T>::get(liquidity_asset_id).unwrap_or_else(Self::default_reward_curve)
+	}
+
+	// Stores rewards info of user and indexes the user among accounts with rewards info of the
+	// pool
+	pub(crate) fn insert_rewards_info(
+		user: &AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		rewards_info: RewardInfo,
+	) {
+		RewardsInfo::<T>::insert(user, liquidity_asset_id, rewards_info);
+		RewardsInfoAccounts::<T>::insert(liquidity_asset_id, user, ());
+	}
+
+	// Rewards info of user checkpointed under previous reward curve at the time of its change,
+	// if it was taken before change of curve which is not migrated yet
+	pub(crate) fn rewards_info_under_current_curve(
+		liquidity_asset_id: TokenId,
+		rewards_info: RewardInfo,
+	) -> Result<RewardInfo, DispatchError> {
+		let migration = match PendingRewardCurveMigration::<T>::get(liquidity_asset_id) {
+			Some(migration) => migration,
+			None => return Ok(rewards_info),
+		};
+		let change_time = Self::get_reward_curve(liquidity_asset_id).start_time;
+		if rewards_info.last_checkpoint >= change_time {
+			return Ok(rewards_info)
+		}
+
+		let time_passed = change_time
+			.saturating_sub(1)
+			.checked_sub(rewards_info.last_checkpoint)
+			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+		let liquidity_assets_amount = rewards_info.activated_amount;
+		if liquidity_assets_amount == 0 {
+			return Ok(RewardInfo {
+				last_checkpoint: change_time,
+				pool_ratio_at_last_checkpoint: migration.pool_ratio_at_change,
+				..rewards_info
+			})
+		}
+
+		let pool_ratio_at_change = if time_passed == 0 {
+			rewards_info.pool_ratio_at_last_checkpoint
+		} else {
+			migration.pool_ratio_at_change
+		};
+		let user_rewards = Self::calculate_rewards_for_time_passed(
+			liquidity_assets_amount,
+			time_passed,
+			rewards_info.pool_ratio_at_last_checkpoint,
+			rewards_info.missing_at_last_checkpoint,
+			pool_ratio_at_change,
+			migration.previous_curve.quotient,
+		)?;
+		let missing_at_change = Self::calculate_missing_at_checkpoint_v2(
+			time_passed,
+			rewards_info.missing_at_last_checkpoint,
+			migration.previous_curve.quotient,
+		)?;
+		let total_available_rewards = user_rewards
+			.checked_add(rewards_info.rewards_not_yet_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
+			.checked_sub(rewards_info.rewards_already_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		Ok(RewardInfo {
+			activated_amount: liquidity_assets_amount,
+			rewards_not_yet_claimed: total_available_rewards,
+			rewards_already_claimed: 0_u128,
+			last_checkpoint: change_time,
+			pool_ratio_at_last_checkpoint: pool_ratio_at_change,
+			missing_at_last_checkpoint: missing_at_change,
+		})
+	}
+
+	// Rewards info of user, migrated to current reward curve of the pool if needed
+	fn get_rewards_info_v2(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+	) -> Result<RewardInfo, DispatchError> {
+		Self::rewards_info_under_current_curve(
+			liquidity_asset_id,
+			Self::get_rewards_info(user, liquidity_asset_id),
+		)
+	}
+
+	// Kind of pool, pools created without kind are constant product pools
+	pub fn get_pool_kind(liquidity_asset_id: TokenId) -> PoolKind {
+		PoolKinds::<T>::get(liquidity_asset_id).unwrap_or(PoolKind::ConstantProduct)
+	}
+
+	fn total_fee(liquidity_asset_id: TokenId) -> u128 {
+		Self::get_pool_fee(liquidity_asset_id).total()
+	}
+
+	// Rewards ratio of promoted pool, or the frozen one if pool was unpromoted
+	fn pool_rewards_ratio(liquidity_asset_id: TokenId) -> Result<U256, DispatchError> {
+		<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
+			.or_else(|| UnpromotedPoolsRewards::<T>::get(liquidity_asset_id))
+			.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))
+	}
+
+	pub fn calculate_rewards_amount_v2(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+	) -> Result<Balance, DispatchError> {
+		let rewards_info: RewardInfo = Self::get_rewards_info_v2(user.clone(), liquidity_asset_id)?;
+
+		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
 
 		let mut current_rewards = 0;
 
 		if liquidity_assets_amount != 0 {
-			let pool_rewards_ratio_current =
-				<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
-					.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+			let pool_rewards_ratio_current = Self::pool_rewards_ratio(liquidity_asset_id)?;
 
 			let last_checkpoint = rewards_info.last_checkpoint;
 			let pool_ratio_at_last_checkpoint = rewards_info.pool_ratio_at_last_checkpoint;
@@ -922,18 +2139,34 @@ impl<T: Config> Pallet<T> {
 		missing_at_last_checkpoint: U256,
 		pool_rewards_ratio_current: U256,
 	) -> Result<Balance, DispatchError> {
-		ensure!(
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id).is_some(),
-			Error::<T>::NotAPromotedPool
-		);
+		Self::pool_rewards_ratio(liquidity_asset_id)?;
 
-		let current_time: u32 = (<frame_system::Pallet<T>>::block_number().saturated_into::<u32>() +
-			1) / T::RewardsDistributionPeriod::get();
+		let reward_curve = Self::get_reward_curve(liquidity_asset_id);
+		let current_time: u32 = reward_curve.time_at(Self::current_block());
 
 		let time_passed = current_time
 			.checked_sub(last_checkpoint)
 			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
 
+		Self::calculate_rewards_for_time_passed(
+			liquidity_assets_amount,
+			time_passed,
+			pool_rewards_ratio,
+			missing_at_last_checkpoint,
+			pool_rewards_ratio_current,
+			reward_curve.quotient,
+		)
+	}
+
+	// Rewards of activated liquidity for time_passed periods of reward curve with quotient
+	fn calculate_rewards_for_time_passed(
+		liquidity_assets_amount: u128,
+		time_passed: u32,
+		pool_rewards_ratio: U256,
+		missing_at_last_checkpoint: U256,
+		pool_rewards_ratio_current: U256,
+		quotient: FixedU128,
+	) -> Result<Balance, DispatchError> {
 		let pool_rewards_ratio_new = pool_rewards_ratio_current
 			.checked_sub(pool_rewards_ratio)
 			.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))?;
@@ -949,6 +2182,7 @@ impl<T: Config> Pallet<T> {
 				liquidity_assets_amount,
 				time_passed,
 				missing_at_last_checkpoint,
+				quotient,
 			)?;
 
 		let current_rewards = U256::from(user_rewards_base)
@@ -968,6 +2202,7 @@ impl<T: Config> Pallet<T> {
 		liquidity_assets_amount: u128,
 		time_passed: u32,
 		missing_at_last_checkpoint: U256,
+		quotient: FixedU128,
 	) -> Result<(U256, U256), DispatchError> {
 		let mut cummulative_work = U256::from(0);
 		let mut cummulative_work_max_possible_for_ratio = U256::from(1);
@@ -985,17 +2220,18 @@ impl<T: Config> Pallet<T> {
 
 			// whole formula: 	missing_at_last_checkpoint*Q*100/(Q*100-100) - missing_at_last_checkpoint*Q*100/(Q*100-100)*REWARDS_PRECISION/q_pow
 			// q_pow is multiplied by precision, thus there needs to be *precision in numenator as well
+			let q_times_100: u128 = quotient.saturating_mul_int(100_u128);
 			let base = missing_at_last_checkpoint
-				.checked_mul(U256::from(libm::floor(Q * 100 as f64) as u128))
+				.checked_mul(U256::from(q_times_100))
 				.ok_or_else(|| {
 					DispatchError::from(Error::<T>::CalculateCumulativeWorkMaxRatioMathError)
 				})?
-				.checked_div(U256::from(libm::floor(Q * 100 as f64 - 100 as f64) as u128))
+				.checked_div(U256::from(q_times_100 - 100))
 				.ok_or_else(|| {
 					DispatchError::from(Error::<T>::CalculateCumulativeWorkMaxRatioMathError)
 				})?;
 
-			let q_pow = Self::calculate_q_pow(Q, time_passed + 1);
+			let q_pow = Self::calculate_q_pow(quotient, time_passed + 1);
 
 			let cummulative_missing_new =
 				base - base * U256::from(REWARDS_PRECISION) / q_pow - missing_at_last_checkpoint;
@@ -1010,16 +2246,43 @@ impl<T: Config> Pallet<T> {
 		Ok((cummulative_work, cummulative_work_max_possible_for_ratio))
 	}
 
-	pub fn calculate_q_pow(q: f64, pow: u32) -> u128 {
-		libm::floor(libm::pow(q, pow as f64) * REWARDS_PRECISION as f64) as u128
+	/// Returns floor(q^pow * REWARDS_PRECISION), saturating at u128::MAX.
+	/// q^pow is computed by squaring in fixed point with precision of FixedU128, rounding down
+	/// after every multiplication. Results differ from exact ones by less than 1 part in 10^11,
+	/// see `calculate_q_pow_matches_floating_point_within_tolerance` test.
+	pub fn calculate_q_pow(q: FixedU128, pow: u32) -> u128 {
+		let precision = U256::from(FixedU128::DIV);
+		// q^pow above which the result saturates, caps intermediate results as well
+		let max_q_pow =
+			U256::from(u128::MAX) * precision / U256::from(REWARDS_PRECISION) + precision;
+		let multiply = |a: U256, b: U256| -> U256 {
+			a.checked_mul(b).map(|x| x / precision).unwrap_or(max_q_pow).min(max_q_pow)
+		};
+
+		let mut q_pow = precision;
+		// q^(2^i) for i-th bit of pow
+		let mut q_pow_of_two = U256::from(q.into_inner());
+		let mut pow = pow;
+		while pow != 0 {
+			if pow & 1 == 1 {
+				q_pow = multiply(q_pow, q_pow_of_two);
+			}
+			q_pow_of_two = multiply(q_pow_of_two, q_pow_of_two);
+			pow >>= 1;
+		}
+
+		(q_pow * U256::from(REWARDS_PRECISION) / precision)
+			.try_into()
+			.unwrap_or(u128::MAX)
 	}
 
 	/// 0R 0W
 	pub fn calculate_missing_at_checkpoint_v2(
 		time_passed: u32,
 		missing_at_last_checkpoint: U256,
+		quotient: FixedU128,
 	) -> Result<U256, DispatchError> {
-		let q_pow = Self::calculate_q_pow(Q, time_passed);
+		let q_pow = Self::calculate_q_pow(quotient, time_passed);
 
 		let missing_at_checkpoint: U256 =
 			missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) / q_pow;
@@ -1027,162 +2290,691 @@ impl<T: Config> Pallet<T> {
 		Ok(missing_at_checkpoint)
 	}
 
-	pub fn set_liquidity_minting_checkpoint_v2(
+	pub fn do_claim_rewards_all_pools_v2(
 		user: AccountIdOf<T>,
-		liquidity_asset_id: TokenId,
-		liquidity_assets_added: Balance,
-		use_balance_from: Option<ActivateKind>,
-	) -> DispatchResult {
-		let current_time: u32 = (<frame_system::Pallet<T>>::block_number().saturated_into::<u32>() +
-			1) / T::RewardsDistributionPeriod::get();
-		let mut pool_ratio_current =
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
-				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+		start_after: Option<TokenId>,
+		max_pools: u32,
+	) -> Result<Balance, DispatchError> {
+		// pools are collected first, claiming modifies iterated map
+		let max_visited = max_pools.saturating_add(MAX_CLAIM_ALL_SKIPPED_POOLS) as usize;
+		let liquidity_asset_ids: Vec<TokenId> = match start_after {
+			Some(start_after) => RewardsInfo::<T>::iter_key_prefix_from(
+				&user,
+				RewardsInfo::<T>::hashed_key_for(&user, start_after),
+			)
+			.take(max_visited)
+			.collect(),
+			None => RewardsInfo::<T>::iter_key_prefix(&user).take(max_visited).collect(),
+		};
 
-		let rewards_info = RewardsInfo::<T>::try_get(user.clone(), liquidity_asset_id)
-			.unwrap_or_else(|_| RewardInfo {
-				activated_amount: 0 as u128,
-				rewards_not_yet_claimed: 0 as u128,
-				rewards_already_claimed: 0 as u128,
-				last_checkpoint: current_time,
-				pool_ratio_at_last_checkpoint: pool_ratio_current,
-				missing_at_last_checkpoint: U256::from(liquidity_assets_added),
-			});
+		let mut total_claimed: Balance = Zero::zero();
+		let mut pools_claimed: u32 = 0;
+		let mut pools_skipped: u32 = 0;
+		let mut last_visited = start_after.unwrap_or_default();
 
-		let last_checkpoint = rewards_info.last_checkpoint;
-		let pool_ratio_at_last_checkpoint = rewards_info.pool_ratio_at_last_checkpoint;
-		let missing_at_last_checkpoint = rewards_info.missing_at_last_checkpoint;
-		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
+		for liquidity_asset_id in liquidity_asset_ids {
+			if pools_claimed >= max_pools || pools_skipped >= MAX_CLAIM_ALL_SKIPPED_POOLS {
+				break
+			}
+			last_visited = liquidity_asset_id;
+
+			// Pools which are not promoted anymore and never unpromoted have nothing to claim
+			let available_rewards =
+				Self::calculate_rewards_amount_v2(user.clone(), liquidity_asset_id)
+					.unwrap_or_default();
+			if available_rewards.is_zero() {
+				pools_skipped = pools_skipped.saturating_add(1);
+				continue
+			}
 
-		let time_passed = current_time
-			.checked_sub(last_checkpoint)
-			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+			<Self as XykFunctionsTrait<T::AccountId>>::claim_rewards_all_v2(
+				user.clone(),
+				liquidity_asset_id,
+			)?;
 
-		if time_passed == 0 {
-			pool_ratio_current = pool_ratio_at_last_checkpoint;
+			total_claimed = total_claimed
+				.checked_add(available_rewards)
+				.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsAllMathError))?;
+			pools_claimed = pools_claimed.saturating_add(1);
 		}
 
-		let missing_at_checkpoint_new = if liquidity_assets_amount == 0 {
-			U256::from(liquidity_assets_added)
-		} else {
-			Self::calculate_missing_at_checkpoint_v2(time_passed, missing_at_last_checkpoint)?
-				.checked_add(U256::from(liquidity_assets_added))
-				.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
-		};
+		ensure!(!total_claimed.is_zero(), Error::<T>::NotEnoughtRewardsEarned);
 
-		let user_current_rewards = if liquidity_assets_amount == 0 {
-			0
-		} else {
-			Self::calculate_rewards_v2(
-				liquidity_assets_amount,
-				liquidity_asset_id,
-				last_checkpoint,
-				pool_ratio_at_last_checkpoint,
-				missing_at_last_checkpoint,
-				pool_ratio_current,
-			)?
-		};
+		Self::deposit_event(Event::RewardsClaimedFromAllPools(
+			user,
+			total_claimed,
+			pools_claimed,
+			last_visited,
+		));
 
-		let activated_amount_new = liquidity_assets_amount
-			.checked_add(liquidity_assets_added)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+		Ok(total_claimed)
+	}
 
-		let total_available_rewards = user_current_rewards
-			.checked_add(rewards_info.rewards_not_yet_claimed)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
-			.checked_sub(rewards_info.rewards_already_claimed)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+	// Amount of sold asset to swap, so that what remains and what is bought are in the ratio
+	// of pool reserves after the swap, ignoring fees leaving the pool
+	fn calculate_compound_swap_amount(
+		amount: Balance,
+		input_reserve: Balance,
+		total_fee: u128,
+	) -> Result<Balance, DispatchError> {
+		let fee_denominator = U256::from(10_000_u128);
+		let fee = U256::from(total_fee);
+		let reserve = U256::from(input_reserve);
+		ensure!(fee < fee_denominator, Error::<T>::InvalidPoolFee);
+
+		// (sqrt(R^2 * (2F - f)^2 + 4F * (F - f) * A * R) - R * (2F - f)) / (2 * (F - f))
+		let reserve_times_fees = reserve
+			.checked_mul(fee_denominator * U256::from(2_u8) - fee)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+		let discriminant = reserve_times_fees
+			.checked_mul(reserve_times_fees)
+			.and_then(|x| {
+				fee_denominator
+					.checked_mul(fee_denominator - fee)
+					.and_then(|y| y.checked_mul(U256::from(4_u8)))
+					.and_then(|y| y.checked_mul(amount.into()))
+					.and_then(|y| y.checked_mul(reserve))
+					.and_then(|y| x.checked_add(y))
+			})
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
 
-		let rewards_info_new: RewardInfo = RewardInfo {
-			activated_amount: activated_amount_new,
-			rewards_not_yet_claimed: total_available_rewards,
-			rewards_already_claimed: 0_u128,
-			last_checkpoint: current_time,
-			pool_ratio_at_last_checkpoint: pool_ratio_current,
-			missing_at_last_checkpoint: missing_at_checkpoint_new,
+		discriminant
+			.integer_sqrt()
+			.checked_sub(reserve_times_fees)
+			.and_then(|x| x.checked_div((fee_denominator - fee) * U256::from(2_u8)))
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?
+			.try_into()
+			.map_err(|_| DispatchError::from(Error::<T>::MathOverflow))
+	}
+
+	pub fn do_compound_rewards(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		min_liquidity_minted: Balance,
+		activate_minted_liquidity: bool,
+	) -> Result<Balance, DispatchError> {
+		let native_asset_id = Self::native_token_id();
+		let (first_asset_id, second_asset_id) =
+			LiquidityPools::<T>::get(liquidity_asset_id).ok_or(Error::<T>::NoSuchLiquidityAsset)?;
+		let other_asset_id = if first_asset_id == native_asset_id {
+			second_asset_id
+		} else if second_asset_id == native_asset_id {
+			first_asset_id
+		} else {
+			return Err(Error::<T>::NotMangataLiquidityAsset.into())
 		};
+		ensure!(
+			!T::DisabledTokens::contains(&other_asset_id),
+			Error::<T>::FunctionNotAvailableForThisToken
+		);
+		ensure!(
+			Self::get_pool_kind(liquidity_asset_id) == PoolKind::ConstantProduct,
+			Error::<T>::FunctionNotAvailableForThisToken
+		);
 
-		RewardsInfo::<T>::insert(user.clone(), liquidity_asset_id, rewards_info_new);
+		let rewards = Self::calculate_rewards_amount_v2(user.clone(), liquidity_asset_id)?;
+		ensure!(!rewards.is_zero(), Error::<T>::NotEnoughtRewardsEarned);
+		<Self as XykFunctionsTrait<T::AccountId>>::claim_rewards_all_v2(
+			user.clone(),
+			liquidity_asset_id,
+		)?;
 
-		LiquidityMiningActivePoolV2::<T>::try_mutate(liquidity_asset_id, |active_amount| {
-			if let Some(val) = active_amount.checked_add(liquidity_assets_added) {
-				*active_amount = val;
-				Ok(())
-			} else {
-				Err(())
-			}
-		})
-		.map_err(|_| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+		let (native_reserve, _) = Self::get_reserves(native_asset_id, other_asset_id)?;
+		let sold_amount = Self::calculate_compound_swap_amount(
+			rewards,
+			native_reserve,
+			Self::get_pool_fee(liquidity_asset_id).total(),
+		)?;
 
-		// This must not fail due storage edits above
-		<T as Config>::ActivationReservesProvider::activate(
-			liquidity_asset_id.into(),
-			&user,
-			liquidity_assets_added.into(),
-			use_balance_from,
+		let other_balance_before: Balance =
+			<T as Config>::Currency::free_balance(other_asset_id.into(), &user).into();
+		<Self as XykFunctionsTrait<T::AccountId>>::sell_asset(
+			user.clone(),
+			native_asset_id,
+			other_asset_id,
+			sold_amount,
+			Zero::zero(),
 		)?;
+		let other_balance_after: Balance =
+			<T as Config>::Currency::free_balance(other_asset_id.into(), &user).into();
+		let bought_amount = other_balance_after.saturating_sub(other_balance_before);
+
+		// Part of fees leaves the pool, so bought amount may not be enough for all remaining
+		// rewards, what cannot be minted stays with the user
+		let (native_reserve, other_reserve) = Self::get_reserves(native_asset_id, other_asset_id)?;
+		ensure!(!other_reserve.is_zero(), Error::<T>::DivisionByZero);
+		let native_amount = multiply_by_rational_with_rounding(
+			bought_amount.saturating_sub(1),
+			native_reserve,
+			other_reserve,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)?
+		.min(rewards.saturating_sub(sold_amount));
 
-		Ok(())
-	}
+		let (_, liquidity_assets_minted) =
+			<Self as XykFunctionsTrait<T::AccountId>>::mint_liquidity(
+				user.clone(),
+				native_asset_id,
+				other_asset_id,
+				native_amount,
+				bought_amount,
+				false,
+			)?;
+		ensure!(
+			liquidity_assets_minted >= min_liquidity_minted,
+			Error::<T>::InsufficientLiquidityMinted
+		);
 
-	pub fn set_liquidity_burning_checkpoint_v2(
-		user: AccountIdOf<T>,
-		liquidity_asset_id: TokenId,
-		liquidity_assets_burned: Balance,
-	) -> DispatchResult {
-		let current_time: u32 = (<frame_system::Pallet<T>>::block_number().saturated_into::<u32>() +
-			1) / T::RewardsDistributionPeriod::get();
+		if activate_minted_liquidity {
+			<Self as XykFunctionsTrait<T::AccountId>>::activate_liquidity_v2(
+				user.clone(),
+				liquidity_asset_id,
+				liquidity_assets_minted,
+				Some(ActivateKind::AvailableBalance),
+			)?;
+		}
 
-		let mut pool_ratio_current =
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
-				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+		Self::deposit_event(Event::RewardsCompounded(
+			user,
+			liquidity_asset_id,
+			rewards,
+			liquidity_assets_minted,
+		));
 
-		let rewards_info: RewardInfo = Self::get_rewards_info(user.clone(), liquidity_asset_id);
+		Ok(liquidity_assets_minted)
+	}
 
-		let last_checkpoint = rewards_info.last_checkpoint;
-		let pool_ratio_at_last_checkpoint = rewards_info.pool_ratio_at_last_checkpoint;
-		let missing_at_last_checkpoint = rewards_info.missing_at_last_checkpoint;
-		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
+	fn current_block() -> u32 {
+		<frame_system::Pallet<T>>::block_number().saturated_into::<u32>()
+	}
 
-		let time_passed = current_time
-			.checked_sub(last_checkpoint)
-			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+	fn reward_schedules_account_id() -> T::AccountId {
+		PALLET_ID.into_sub_account_truncating(REWARD_SCHEDULES_SUB_ACCOUNT)
+	}
 
-		if time_passed == 0 {
-			pool_ratio_current = pool_ratio_at_last_checkpoint;
+	// Reward schedule with rewards distributed up to current block, without storing it
+	fn reward_schedule_at_current_block(
+		liquidity_asset_id: TokenId,
+		mut schedule: RewardSchedule<AccountIdOf<T>>,
+	) -> Result<RewardSchedule<AccountIdOf<T>>, DispatchError> {
+		let now = Self::current_block().min(schedule.last_block);
+		if now <= schedule.last_updated {
+			return Ok(schedule)
 		}
 
-		let missing_at_checkpoint_new =
-			Self::calculate_missing_at_checkpoint_v2(time_passed, missing_at_last_checkpoint)?;
+		let rewards = schedule
+			.amount_per_block
+			.checked_mul((now - schedule.last_updated).into())
+			.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))?;
+		let activated_amount = LiquidityMiningActivePoolV2::<T>::get(liquidity_asset_id);
 
-		let user_current_rewards = Self::calculate_rewards_v2(
-			liquidity_assets_amount,
-			liquidity_asset_id,
-			last_checkpoint,
-			pool_ratio_at_last_checkpoint,
-			missing_at_last_checkpoint,
-			pool_ratio_current,
-		)?;
+		if activated_amount.is_zero() {
+			schedule.undistributed = schedule
+				.undistributed
+				.checked_add(rewards)
+				.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))?;
+		} else {
+			schedule.rewards_per_liquidity = U256::from(rewards)
+				.checked_mul(U256::from(u128::MAX))
+				.and_then(|x| x.checked_div(activated_amount.into()))
+				.and_then(|x| x.checked_add(schedule.rewards_per_liquidity))
+				.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))?;
+		}
+		schedule.last_updated = now;
 
-		let activated_amount_new = liquidity_assets_amount
-			.checked_sub(liquidity_assets_burned)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+		Ok(schedule)
+	}
 
-		let activated_amount_new_u256: U256 = activated_amount_new.into();
+	fn update_reward_schedule(
+		liquidity_asset_id: TokenId,
+		reward_token_id: TokenId,
+	) -> Result<RewardSchedule<AccountIdOf<T>>, DispatchError> {
+		let schedule = RewardSchedules::<T>::get(liquidity_asset_id, reward_token_id)
+			.ok_or(Error::<T>::NoSuchRewardSchedule)?;
+		let schedule = Self::reward_schedule_at_current_block(liquidity_asset_id, schedule)?;
+		RewardSchedules::<T>::insert(liquidity_asset_id, reward_token_id, schedule.clone());
+		Ok(schedule)
+	}
 
-		let missing_at_checkpoint_after_burn: U256 = activated_amount_new_u256
-			.checked_mul(missing_at_checkpoint_new)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
-			.checked_div(liquidity_assets_amount.into())
-			.ok_or_else(|| DispatchError::from(Error::<T>::DivisionByZero))?;
+	fn calculate_schedule_rewards(
+		activated_amount: Balance,
+		rewards_info: &ScheduleRewardInfo,
+		schedule: &RewardSchedule<AccountIdOf<T>>,
+	) -> Result<Balance, DispatchError> {
+		let current_rewards: Balance = schedule
+			.rewards_per_liquidity
+			.checked_sub(rewards_info.rewards_per_liquidity_at_checkpoint)
+			.and_then(|x| x.checked_mul(activated_amount.into()))
+			.and_then(|x| x.checked_div(U256::from(u128::MAX)))
+			.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))?
+			.try_into()
+			.map_err(|_| DispatchError::from(Error::<T>::CalculateRewardsMathError))?;
 
-		let total_available_rewards = user_current_rewards
+		current_rewards
 			.checked_add(rewards_info.rewards_not_yet_claimed)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
-			.checked_sub(rewards_info.rewards_already_claimed)
-			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+			.ok_or_else(|| DispatchError::from(Error::<T>::CalculateRewardsMathError))
+	}
 
-		let rewards_info_new: RewardInfo = RewardInfo {
+	// Rewards of reward token user can claim from reward schedules of the pool
+	pub fn calculate_schedule_rewards_amount(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		reward_token_id: TokenId,
+	) -> Result<Balance, DispatchError> {
+		let schedule = RewardSchedules::<T>::get(liquidity_asset_id, reward_token_id)
+			.ok_or(Error::<T>::NoSuchRewardSchedule)?;
+		let schedule = Self::reward_schedule_at_current_block(liquidity_asset_id, schedule)?;
+
+		Self::calculate_schedule_rewards(
+			Self::get_rewards_info(user.clone(), liquidity_asset_id).activated_amount,
+			&Self::schedule_rewards_info(user, (liquidity_asset_id, reward_token_id)),
+			&schedule,
+		)
+	}
+
+	// Has to be called before activated amount of user in the pool changes
+	fn set_reward_schedules_checkpoint(
+		user: &AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+	) -> DispatchResult {
+		let activated_amount = Self::get_rewards_info(user, liquidity_asset_id).activated_amount;
+		let reward_tokens = PoolRewardTokens::<T>::get(liquidity_asset_id);
+
+		// pruned schedules are checkpointed as well, they are already updated till their end
+		for (reward_token_id, schedule) in RewardSchedules::<T>::iter_prefix(liquidity_asset_id) {
+			let schedule = if reward_tokens.contains(&reward_token_id) {
+				Self::update_reward_schedule(liquidity_asset_id, reward_token_id)?
+			} else {
+				schedule
+			};
+
+			ScheduleRewardsInfo::<T>::try_mutate(
+				user,
+				(liquidity_asset_id, reward_token_id),
+				|rewards_info| -> DispatchResult {
+					*rewards_info = ScheduleRewardInfo {
+						rewards_per_liquidity_at_checkpoint: schedule.rewards_per_liquidity,
+						rewards_not_yet_claimed: Self::calculate_schedule_rewards(
+							activated_amount,
+							rewards_info,
+							&schedule,
+						)?,
+					};
+					Ok(())
+				},
+			)?;
+		}
+
+		Ok(())
+	}
+
+	pub fn do_create_reward_schedule(
+		sponsor: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		reward_token_id: TokenId,
+		amount: Balance,
+		last_block: u32,
+	) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+		ensure!(
+			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id).is_some(),
+			Error::<T>::NotAPromotedPool
+		);
+		ensure!(
+			reward_token_id == T::NativeCurrencyId::get() ||
+				RewardTokensWhitelist::<T>::get(reward_token_id),
+			Error::<T>::RewardTokenNotWhitelisted
+		);
+
+		let now = Self::current_block();
+		let blocks = last_block.checked_sub(now).ok_or(Error::<T>::InvalidRewardSchedule)?;
+		ensure!(!blocks.is_zero(), Error::<T>::InvalidRewardSchedule);
+		let amount_per_block = amount / Balance::from(blocks);
+		ensure!(!amount_per_block.is_zero(), Error::<T>::InvalidRewardSchedule);
+
+		// Rewards per liquidity keep growing across schedules of the same token,
+		// so that checkpoints of users stay valid
+		let rewards_per_liquidity =
+			match RewardSchedules::<T>::get(liquidity_asset_id, reward_token_id) {
+				Some(_) => {
+					let previous_schedule =
+						Self::update_reward_schedule(liquidity_asset_id, reward_token_id)?;
+					ensure!(now >= previous_schedule.last_block, Error::<T>::RewardScheduleActive);
+					let previous_rewards_per_liquidity = previous_schedule.rewards_per_liquidity;
+					Self::refund_undistributed_rewards(
+						liquidity_asset_id,
+						reward_token_id,
+						previous_schedule,
+					)?;
+					previous_rewards_per_liquidity
+				},
+				None => U256::zero(),
+			};
+
+		// Token is new or its previous schedule was pruned
+		if !PoolRewardTokens::<T>::get(liquidity_asset_id).contains(&reward_token_id) {
+			Self::prune_ended_reward_schedules(liquidity_asset_id)?;
+			PoolRewardTokens::<T>::try_mutate(liquidity_asset_id, |reward_tokens| {
+				reward_tokens.try_push(reward_token_id)
+			})
+			.map_err(|_| Error::<T>::TooManyRewardTokens)?;
+		}
+
+		<T as Config>::Currency::transfer(
+			reward_token_id.into(),
+			&sponsor,
+			&Self::reward_schedules_account_id(),
+			amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		RewardSchedules::<T>::insert(
+			liquidity_asset_id,
+			reward_token_id,
+			RewardSchedule {
+				sponsor: sponsor.clone(),
+				amount_per_block,
+				last_block,
+				last_updated: now,
+				rewards_per_liquidity,
+				// Remainder of division by number of blocks is not distributed
+				undistributed: amount - amount_per_block * Balance::from(blocks),
+			},
+		);
+
+		Self::deposit_event(Event::RewardScheduleCreated(
+			sponsor,
+			liquidity_asset_id,
+			reward_token_id,
+			amount,
+			last_block,
+		));
+
+		Ok(())
+	}
+
+	// Ended schedules are updated till their last block and removed from reward tokens of the
+	// pool, they stay in RewardSchedules so that rewards of users can still be claimed
+	fn prune_ended_reward_schedules(liquidity_asset_id: TokenId) -> DispatchResult {
+		let now = Self::current_block();
+		let mut reward_tokens = PoolRewardTokens::<T>::get(liquidity_asset_id);
+		let mut ended_reward_tokens = Vec::new();
+		for reward_token_id in reward_tokens.iter() {
+			let schedule = Self::update_reward_schedule(liquidity_asset_id, *reward_token_id)?;
+			if now >= schedule.last_block {
+				ended_reward_tokens.push(*reward_token_id);
+			}
+		}
+
+		reward_tokens.retain(|reward_token_id| !ended_reward_tokens.contains(reward_token_id));
+		PoolRewardTokens::<T>::insert(liquidity_asset_id, reward_tokens);
+
+		Ok(())
+	}
+
+	fn refund_undistributed_rewards(
+		liquidity_asset_id: TokenId,
+		reward_token_id: TokenId,
+		mut schedule: RewardSchedule<AccountIdOf<T>>,
+	) -> DispatchResult {
+		let refunded_amount = schedule.undistributed;
+
+		if !refunded_amount.is_zero() {
+			<T as Config>::Currency::transfer(
+				reward_token_id.into(),
+				&Self::reward_schedules_account_id(),
+				&schedule.sponsor,
+				refunded_amount.into(),
+				ExistenceRequirement::AllowDeath,
+			)?;
+			schedule.undistributed = Zero::zero();
+			RewardSchedules::<T>::insert(liquidity_asset_id, reward_token_id, schedule.clone());
+		}
+
+		Self::deposit_event(Event::RewardScheduleRefunded(
+			schedule.sponsor,
+			liquidity_asset_id,
+			reward_token_id,
+			refunded_amount,
+		));
+
+		Ok(())
+	}
+
+	pub fn do_claim_schedule_rewards(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		reward_token_id: TokenId,
+	) -> DispatchResult {
+		let schedule = Self::update_reward_schedule(liquidity_asset_id, reward_token_id)?;
+
+		let rewards_info =
+			Self::schedule_rewards_info(&user, (liquidity_asset_id, reward_token_id));
+		let claimed_amount = Self::calculate_schedule_rewards(
+			Self::get_rewards_info(&user, liquidity_asset_id).activated_amount,
+			&rewards_info,
+			&schedule,
+		)?;
+		ensure!(!claimed_amount.is_zero(), Error::<T>::NotEnoughtRewardsEarned);
+
+		ScheduleRewardsInfo::<T>::insert(
+			&user,
+			(liquidity_asset_id, reward_token_id),
+			ScheduleRewardInfo {
+				rewards_per_liquidity_at_checkpoint: schedule.rewards_per_liquidity,
+				rewards_not_yet_claimed: Zero::zero(),
+			},
+		);
+
+		<T as Config>::Currency::transfer(
+			reward_token_id.into(),
+			&Self::reward_schedules_account_id(),
+			&user,
+			claimed_amount.into(),
+			ExistenceRequirement::AllowDeath,
+		)?;
+
+		Self::deposit_event(Event::ScheduleRewardsClaimed(
+			user,
+			liquidity_asset_id,
+			reward_token_id,
+			claimed_amount,
+		));
+
+		Ok(())
+	}
+
+	pub fn set_liquidity_minting_checkpoint_v2(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		liquidity_assets_added: Balance,
+		use_balance_from: Option<ActivateKind>,
+	) -> DispatchResult {
+		let pool_ratio_current =
+			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
+				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+
+		Self::checkpoint_activated_liquidity_v2(
+			user.clone(),
+			liquidity_asset_id,
+			liquidity_assets_added,
+			U256::from(liquidity_assets_added),
+			pool_ratio_current,
+		)?;
+
+		// This must not fail due storage edits above
+		<T as Config>::ActivationReservesProvider::activate(
+			liquidity_asset_id.into(),
+			&user,
+			liquidity_assets_added.into(),
+			use_balance_from,
+		)?;
+
+		Ok(())
+	}
+
+	// Adds already reserved liquidity to activated amount of user, `missing_added` is
+	// missing amount of rewards curve of added liquidity, equal to it for fresh liquidity
+	pub(crate) fn checkpoint_activated_liquidity_v2(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		liquidity_assets_added: Balance,
+		missing_added: U256,
+		mut pool_ratio_current: U256,
+	) -> DispatchResult {
+		Self::set_reward_schedules_checkpoint(&user, liquidity_asset_id)?;
+
+		let reward_curve = Self::get_reward_curve(liquidity_asset_id);
+		let current_time: u32 = reward_curve.time_at(Self::current_block());
+
+		let rewards_info = match RewardsInfo::<T>::try_get(user.clone(), liquidity_asset_id) {
+			Ok(rewards_info) =>
+				Self::rewards_info_under_current_curve(liquidity_asset_id, rewards_info)?,
+			Err(_) => RewardInfo {
+				activated_amount: 0 as u128,
+				rewards_not_yet_claimed: 0 as u128,
+				rewards_already_claimed: 0 as u128,
+				last_checkpoint: current_time,
+				pool_ratio_at_last_checkpoint: pool_ratio_current,
+				missing_at_last_checkpoint: missing_added,
+			},
+		};
+
+		let last_checkpoint = rewards_info.last_checkpoint;
+		let pool_ratio_at_last_checkpoint = rewards_info.pool_ratio_at_last_checkpoint;
+		let missing_at_last_checkpoint = rewards_info.missing_at_last_checkpoint;
+		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
+
+		let time_passed = current_time
+			.checked_sub(last_checkpoint)
+			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+
+		// Checkpoint without activated liquidity may predate pool being promoted again
+		if time_passed == 0 && liquidity_assets_amount != 0 {
+			pool_ratio_current = pool_ratio_at_last_checkpoint;
+		}
+
+		let missing_at_checkpoint_new = if liquidity_assets_amount == 0 {
+			missing_added
+		} else {
+			Self::calculate_missing_at_checkpoint_v2(
+				time_passed,
+				missing_at_last_checkpoint,
+				reward_curve.quotient,
+			)?
+			.checked_add(missing_added)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
+		};
+
+		let user_current_rewards = if liquidity_assets_amount == 0 {
+			0
+		} else {
+			Self::calculate_rewards_v2(
+				liquidity_assets_amount,
+				liquidity_asset_id,
+				last_checkpoint,
+				pool_ratio_at_last_checkpoint,
+				missing_at_last_checkpoint,
+				pool_ratio_current,
+			)?
+		};
+
+		let activated_amount_new = liquidity_assets_amount
+			.checked_add(liquidity_assets_added)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		let total_available_rewards = user_current_rewards
+			.checked_add(rewards_info.rewards_not_yet_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
+			.checked_sub(rewards_info.rewards_already_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		let rewards_info_new: RewardInfo = RewardInfo {
+			activated_amount: activated_amount_new,
+			rewards_not_yet_claimed: total_available_rewards,
+			rewards_already_claimed: 0_u128,
+			last_checkpoint: current_time,
+			pool_ratio_at_last_checkpoint: pool_ratio_current,
+			missing_at_last_checkpoint: missing_at_checkpoint_new,
+		};
+
+		Pallet::<T>::insert_rewards_info(&user, liquidity_asset_id, rewards_info_new);
+
+		LiquidityMiningActivePoolV2::<T>::try_mutate(liquidity_asset_id, |active_amount| {
+			if let Some(val) = active_amount.checked_add(liquidity_assets_added) {
+				*active_amount = val;
+				Ok(())
+			} else {
+				Err(())
+			}
+		})
+		.map_err(|_| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		Ok(())
+	}
+
+	pub fn set_liquidity_burning_checkpoint_v2(
+		user: AccountIdOf<T>,
+		liquidity_asset_id: TokenId,
+		liquidity_assets_burned: Balance,
+	) -> DispatchResult {
+		Self::set_reward_schedules_checkpoint(&user, liquidity_asset_id)?;
+
+		let reward_curve = Self::get_reward_curve(liquidity_asset_id);
+		let current_time: u32 = reward_curve.time_at(Self::current_block());
+
+		let mut pool_ratio_current = Self::pool_rewards_ratio(liquidity_asset_id)?;
+
+		let rewards_info: RewardInfo = Self::get_rewards_info_v2(user.clone(), liquidity_asset_id)?;
+
+		let last_checkpoint = rewards_info.last_checkpoint;
+		let pool_ratio_at_last_checkpoint = rewards_info.pool_ratio_at_last_checkpoint;
+		let missing_at_last_checkpoint = rewards_info.missing_at_last_checkpoint;
+		let liquidity_assets_amount: Balance = rewards_info.activated_amount;
+
+		let time_passed = current_time
+			.checked_sub(last_checkpoint)
+			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+
+		if time_passed == 0 {
+			pool_ratio_current = pool_ratio_at_last_checkpoint;
+		}
+
+		let missing_at_checkpoint_new = Self::calculate_missing_at_checkpoint_v2(
+			time_passed,
+			missing_at_last_checkpoint,
+			reward_curve.quotient,
+		)?;
+
+		let user_current_rewards = Self::calculate_rewards_v2(
+			liquidity_assets_amount,
+			liquidity_asset_id,
+			last_checkpoint,
+			pool_ratio_at_last_checkpoint,
+			missing_at_last_checkpoint,
+			pool_ratio_current,
+		)?;
+
+		let activated_amount_new = liquidity_assets_amount
+			.checked_sub(liquidity_assets_burned)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		let activated_amount_new_u256: U256 = activated_amount_new.into();
+
+		let missing_at_checkpoint_after_burn: U256 = activated_amount_new_u256
+			.checked_mul(missing_at_checkpoint_new)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
+			.checked_div(liquidity_assets_amount.into())
+			.ok_or_else(|| DispatchError::from(Error::<T>::DivisionByZero))?;
+
+		let total_available_rewards = user_current_rewards
+			.checked_add(rewards_info.rewards_not_yet_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?
+			.checked_sub(rewards_info.rewards_already_claimed)
+			.ok_or_else(|| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
+
+		let rewards_info_new: RewardInfo = RewardInfo {
 			activated_amount: activated_amount_new,
 			rewards_not_yet_claimed: total_available_rewards,
 			rewards_already_claimed: 0_u128,
@@ -1191,7 +2983,7 @@ impl<T: Config> Pallet<T> {
 			missing_at_last_checkpoint: missing_at_checkpoint_after_burn,
 		};
 
-		RewardsInfo::<T>::insert(user.clone(), liquidity_asset_id, rewards_info_new);
+		Pallet::<T>::insert_rewards_info(&user, liquidity_asset_id, rewards_info_new);
 
 		LiquidityMiningActivePoolV2::<T>::try_mutate(liquidity_asset_id, |active_amount| {
 			if let Some(val) = active_amount.checked_sub(liquidity_assets_burned) {
@@ -1286,17 +3078,34 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	// Calculate amount of tokens to be bought by sellling sell_amount
+	// Calculate amount of tokens to be bought by sellling sell_amount, using default fees
+	// Deprecated, only backs the reserve based rpc, prefer `calculate_sell_price_id`
 	pub fn calculate_sell_price(
 		input_reserve: Balance,
 		output_reserve: Balance,
 		sell_amount: Balance,
 	) -> Result<Balance, DispatchError> {
-		let after_fee_percentage: u128 = 10000 - Self::total_fee();
-		let input_reserve_saturated: U256 = input_reserve.into();
-		let output_reserve_saturated: U256 = output_reserve.into();
-		let sell_amount_saturated: U256 = sell_amount.into();
-
+		Self::calculate_sell_price_with_fee(
+			input_reserve,
+			output_reserve,
+			sell_amount,
+			Self::default_pool_fee().total(),
+		)
+	}
+
+	// Calculate amount of tokens to be bought by sellling sell_amount, when total_fee is charged
+	pub fn calculate_sell_price_with_fee(
+		input_reserve: Balance,
+		output_reserve: Balance,
+		sell_amount: Balance,
+		total_fee: u128,
+	) -> Result<Balance, DispatchError> {
+		let after_fee_percentage: u128 =
+			10000_u128.checked_sub(total_fee).ok_or(Error::<T>::InvalidPoolFee)?;
+		let input_reserve_saturated: U256 = input_reserve.into();
+		let output_reserve_saturated: U256 = output_reserve.into();
+		let sell_amount_saturated: U256 = sell_amount.into();
+
 		let input_amount_with_fee: U256 =
 			sell_amount_saturated.saturating_mul(after_fee_percentage.into());
 
@@ -1354,13 +3163,30 @@ impl<T: Config> Pallet<T> {
 		Ok(result)
 	}
 
-	// Calculate amount of tokens to be paid, when buying buy_amount
+	// Calculate amount of tokens to be paid, when buying buy_amount, using default fees
+	// Deprecated, only backs the reserve based rpc, prefer `calculate_buy_price_id`
 	pub fn calculate_buy_price(
 		input_reserve: Balance,
 		output_reserve: Balance,
 		buy_amount: Balance,
 	) -> Result<Balance, DispatchError> {
-		let after_fee_percentage: u128 = 10000 - Self::total_fee();
+		Self::calculate_buy_price_with_fee(
+			input_reserve,
+			output_reserve,
+			buy_amount,
+			Self::default_pool_fee().total(),
+		)
+	}
+
+	// Calculate amount of tokens to be paid, when buying buy_amount and total_fee is charged
+	pub fn calculate_buy_price_with_fee(
+		input_reserve: Balance,
+		output_reserve: Balance,
+		buy_amount: Balance,
+		total_fee: u128,
+	) -> Result<Balance, DispatchError> {
+		let after_fee_percentage: u128 =
+			10000_u128.checked_sub(total_fee).ok_or(Error::<T>::InvalidPoolFee)?;
 		let input_reserve_saturated: U256 = input_reserve.into();
 		let output_reserve_saturated: U256 = output_reserve.into();
 		let buy_amount_saturated: U256 = buy_amount.into();
@@ -1395,6 +3221,262 @@ impl<T: Config> Pallet<T> {
 		Ok(result)
 	}
 
+	// Calculate amount of tokens to be bought by sellling sell_amount, using math of pool's kind
+	pub fn calculate_sell_price_for_pool(
+		liquidity_asset_id: TokenId,
+		input_reserve: Balance,
+		output_reserve: Balance,
+		sell_amount: Balance,
+		total_fee: u128,
+	) -> Result<Balance, DispatchError> {
+		match Self::get_pool_kind(liquidity_asset_id) {
+			PoolKind::ConstantProduct => Self::calculate_sell_price_with_fee(
+				input_reserve,
+				output_reserve,
+				sell_amount,
+				total_fee,
+			),
+			PoolKind::StableSwap { amplification } => Self::calculate_stable_sell_price(
+				input_reserve,
+				output_reserve,
+				sell_amount,
+				total_fee,
+				amplification,
+			),
+		}
+	}
+
+	// Calculate amount of tokens to be paid, when buying buy_amount, using math of pool's kind
+	pub fn calculate_buy_price_for_pool(
+		liquidity_asset_id: TokenId,
+		input_reserve: Balance,
+		output_reserve: Balance,
+		buy_amount: Balance,
+		total_fee: u128,
+	) -> Result<Balance, DispatchError> {
+		match Self::get_pool_kind(liquidity_asset_id) {
+			PoolKind::ConstantProduct => Self::calculate_buy_price_with_fee(
+				input_reserve,
+				output_reserve,
+				buy_amount,
+				total_fee,
+			),
+			PoolKind::StableSwap { amplification } => Self::calculate_stable_buy_price(
+				input_reserve,
+				output_reserve,
+				buy_amount,
+				total_fee,
+				amplification,
+			),
+		}
+	}
+
+	// Stable swap invariant D of two token pool, solved by newton's method from
+	// A * n^n * (x + y) + D = A * n^n * D + D^(n+1) / (n^n * x * y), where n = 2
+	fn calculate_stable_invariant(
+		first_reserve: U256,
+		second_reserve: U256,
+		amplification: u128,
+	) -> Result<U256, DispatchError> {
+		let n: U256 = 2.into();
+		let sum = first_reserve.saturating_add(second_reserve);
+		if sum.is_zero() {
+			return Ok(U256::zero())
+		}
+		ensure!(!first_reserve.is_zero() && !second_reserve.is_zero(), Error::<T>::DivisionByZero);
+
+		let ann = U256::from(amplification).saturating_mul(n);
+		let mut d = sum;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			let d_p = d
+				.checked_mul(d)
+				.and_then(|d_p| d_p.checked_div(first_reserve.saturating_mul(n)))
+				.and_then(|d_p| d_p.checked_mul(d))
+				.and_then(|d_p| d_p.checked_div(second_reserve.saturating_mul(n)))
+				.ok_or(Error::<T>::MathOverflow)?;
+			let d_prev = d;
+
+			let numerator = ann
+				.checked_mul(sum)
+				.and_then(|v| v.checked_add(d_p.checked_mul(n)?))
+				.and_then(|v| v.checked_mul(d))
+				.ok_or(Error::<T>::MathOverflow)?;
+			let denominator = ann
+				.saturating_sub(1.into())
+				.checked_mul(d)
+				.and_then(|v| v.checked_add(d_p.checked_mul(U256::from(3))?))
+				.ok_or(Error::<T>::MathOverflow)?;
+			d = numerator.checked_div(denominator).ok_or(Error::<T>::DivisionByZero)?;
+
+			if d.max(d_prev) - d.min(d_prev) <= 1.into() {
+				return Ok(d)
+			}
+		}
+
+		Err(Error::<T>::StableSwapNotConverged.into())
+	}
+
+	// Reserve of second token keeping the stable swap invariant D, when reserve of first token
+	// is first_reserve, solved by newton's method from y^2 + (b - D) * y = c
+	fn calculate_stable_reserve(
+		first_reserve: U256,
+		invariant: U256,
+		amplification: u128,
+	) -> Result<U256, DispatchError> {
+		let n: U256 = 2.into();
+		let ann = U256::from(amplification).saturating_mul(n);
+		ensure!(!first_reserve.is_zero() && !ann.is_zero(), Error::<T>::DivisionByZero);
+
+		let c = invariant
+			.checked_mul(invariant)
+			.and_then(|c| c.checked_div(first_reserve.saturating_mul(n)))
+			.and_then(|c| c.checked_mul(invariant))
+			.and_then(|c| c.checked_div(ann.saturating_mul(n)))
+			.ok_or(Error::<T>::MathOverflow)?;
+		let b = first_reserve.saturating_add(invariant / ann);
+
+		let mut y = invariant;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			let y_prev = y;
+			let numerator = y
+				.checked_mul(y)
+				.and_then(|v| v.checked_add(c))
+				.ok_or(Error::<T>::MathOverflow)?;
+			let denominator = y
+				.saturating_mul(n)
+				.saturating_add(b)
+				.checked_sub(invariant)
+				.ok_or(Error::<T>::MathOverflow)?;
+			y = numerator.checked_div(denominator).ok_or(Error::<T>::DivisionByZero)?;
+
+			if y.max(y_prev) - y.min(y_prev) <= 1.into() {
+				return Ok(y)
+			}
+		}
+
+		Err(Error::<T>::StableSwapNotConverged.into())
+	}
+
+	// Calculate amount of tokens to be bought by sellling sell_amount in stable swap pool
+	pub fn calculate_stable_sell_price(
+		input_reserve: Balance,
+		output_reserve: Balance,
+		sell_amount: Balance,
+		total_fee: u128,
+		amplification: u128,
+	) -> Result<Balance, DispatchError> {
+		let after_fee_percentage: u128 =
+			10000_u128.checked_sub(total_fee).ok_or(Error::<T>::InvalidPoolFee)?;
+		let input_reserve_saturated: U256 = input_reserve.into();
+		let output_reserve_saturated: U256 = output_reserve.into();
+		let input_amount_with_fee: U256 =
+			U256::from(sell_amount).saturating_mul(after_fee_percentage.into()) / U256::from(10000);
+
+		let invariant = Self::calculate_stable_invariant(
+			input_reserve_saturated,
+			output_reserve_saturated,
+			amplification,
+		)?;
+		let output_reserve_updated = Self::calculate_stable_reserve(
+			input_reserve_saturated
+				.checked_add(input_amount_with_fee)
+				.ok_or(Error::<T>::MathOverflow)?,
+			invariant,
+			amplification,
+		)?;
+
+		// rounded down by one in favour of the pool
+		let result_u256 = output_reserve_saturated
+			.saturating_sub(output_reserve_updated)
+			.saturating_sub(1.into());
+
+		let result = Balance::try_from(result_u256)
+			.map_err(|_| DispatchError::from(Error::<T>::MathOverflow))?;
+		log!(
+			info,
+			"calculate_stable_sell_price: ({}, {}, {}, {}) -> {}",
+			input_reserve,
+			output_reserve,
+			sell_amount,
+			amplification,
+			result
+		);
+		Ok(result)
+	}
+
+	// Calculate amount of tokens to be paid, when buying buy_amount in stable swap pool
+	pub fn calculate_stable_buy_price(
+		input_reserve: Balance,
+		output_reserve: Balance,
+		buy_amount: Balance,
+		total_fee: u128,
+		amplification: u128,
+	) -> Result<Balance, DispatchError> {
+		let after_fee_percentage: u128 =
+			10000_u128.checked_sub(total_fee).ok_or(Error::<T>::InvalidPoolFee)?;
+		ensure!(!after_fee_percentage.is_zero(), Error::<T>::DivisionByZero);
+		let input_reserve_saturated: U256 = input_reserve.into();
+		let output_reserve_saturated: U256 = output_reserve.into();
+
+		let output_reserve_updated = output_reserve_saturated
+			.checked_sub(buy_amount.into())
+			.filter(|reserve| !reserve.is_zero())
+			.ok_or_else(|| DispatchError::from(Error::<T>::NotEnoughReserve))?;
+
+		let invariant = Self::calculate_stable_invariant(
+			input_reserve_saturated,
+			output_reserve_saturated,
+			amplification,
+		)?;
+		let input_reserve_updated =
+			Self::calculate_stable_reserve(output_reserve_updated, invariant, amplification)?;
+
+		// rounded up by one in favour of the pool, both before and after adding fee
+		let input_amount_with_fee = input_reserve_updated
+			.saturating_sub(input_reserve_saturated)
+			.saturating_add(1.into());
+		let result_u256 = input_amount_with_fee
+			.checked_mul(10000.into())
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?
+			.checked_div(after_fee_percentage.into())
+			.ok_or_else(|| DispatchError::from(Error::<T>::DivisionByZero))?
+			.saturating_add(1.into());
+
+		let result = Balance::try_from(result_u256)
+			.map_err(|_| DispatchError::from(Error::<T>::MathOverflow))?;
+		log!(
+			info,
+			"calculate_stable_buy_price: ({}, {}, {}, {}) -> {}",
+			input_reserve,
+			output_reserve,
+			buy_amount,
+			amplification,
+			result
+		);
+		Ok(result)
+	}
+
+	fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+		ensure!(
+			<frame_system::Pallet<T>>::block_number() <= deadline,
+			Error::<T>::DeadlineExceeded
+		);
+		Ok(())
+	}
+
+	fn ensure_valid_swap_path(swap_token_list: &[TokenId]) -> DispatchResult {
+		ensure!(swap_token_list.len() >= 2, Error::<T>::MultiswapPathTooShort);
+		ensure!(
+			swap_token_list.len() <= MAX_MULTISWAP_PATH_LENGTH as usize,
+			Error::<T>::MultiswapPathTooLong
+		);
+		ensure!(
+			swap_token_list.windows(2).all(|hop| hop[0] != hop[1]),
+			Error::<T>::MultiswapSameTokenConsecutively
+		);
+		Ok(())
+	}
+
 	// MAX: 2R
 	pub fn get_liquidity_asset(
 		first_asset_id: TokenId,
@@ -1416,8 +3498,15 @@ impl<T: Config> Pallet<T> {
 	) -> Result<Balance, DispatchError> {
 		let (input_reserve, output_reserve) =
 			Pallet::<T>::get_reserves(sold_token_id, bought_token_id)?;
+		let liquidity_asset_id = Self::get_liquidity_asset(sold_token_id, bought_token_id)?;
 
-		Self::calculate_sell_price(input_reserve, output_reserve, sell_amount)
+		Self::calculate_sell_price_for_pool(
+			liquidity_asset_id,
+			input_reserve,
+			output_reserve,
+			sell_amount,
+			Self::total_fee(liquidity_asset_id),
+		)
 	}
 
 	pub fn calculate_buy_price_id(
@@ -1427,8 +3516,130 @@ impl<T: Config> Pallet<T> {
 	) -> Result<Balance, DispatchError> {
 		let (input_reserve, output_reserve) =
 			Pallet::<T>::get_reserves(sold_token_id, bought_token_id)?;
+		let liquidity_asset_id = Self::get_liquidity_asset(sold_token_id, bought_token_id)?;
+
+		Self::calculate_buy_price_for_pool(
+			liquidity_asset_id,
+			input_reserve,
+			output_reserve,
+			buy_amount,
+			Self::total_fee(liquidity_asset_id),
+		)
+	}
+
+	// Calculate amount of last token in path to be bought by selling sell_amount of first token
+	pub fn calculate_sell_price_path(
+		swap_token_list: &[TokenId],
+		sell_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::ensure_valid_swap_path(swap_token_list)?;
+
+		swap_token_list.windows(2).try_fold(sell_amount, |amount, hop| {
+			Self::calculate_sell_price_id(hop[0], hop[1], amount)
+		})
+	}
+
+	// Calculate amount of first token in path to be paid, when buying buy_amount of last token
+	pub fn calculate_buy_price_path(
+		swap_token_list: &[TokenId],
+		buy_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Self::ensure_valid_swap_path(swap_token_list)?;
+
+		swap_token_list.windows(2).rev().try_fold(buy_amount, |amount, hop| {
+			Self::calculate_buy_price_id(hop[0], hop[1], amount)
+		})
+	}
+
+	// Finds path through existing pools which yields the most of bought token for sell_amount
+	pub fn get_best_sell_path(
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		sell_amount: Balance,
+	) -> Result<(Vec<TokenId>, Balance), DispatchError> {
+		let mut best_path: Option<(Vec<TokenId>, Balance)> = None;
+
+		for path in Self::get_swap_paths(sold_token_id, bought_token_id)? {
+			if let Ok(amount) = Self::calculate_sell_price_path(&path, sell_amount) {
+				let is_better = match &best_path {
+					Some((best, best_amount)) =>
+						amount > *best_amount || (amount == *best_amount && path.len() < best.len()),
+					None => true,
+				};
+				if is_better {
+					best_path = Some((path, amount));
+				}
+			}
+		}
+
+		best_path.ok_or_else(|| Error::<T>::NoSuchPool.into())
+	}
+
+	// Finds path through existing pools which requires the least of sold token to buy buy_amount
+	pub fn get_best_buy_path(
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+		buy_amount: Balance,
+	) -> Result<(Vec<TokenId>, Balance), DispatchError> {
+		let mut best_path: Option<(Vec<TokenId>, Balance)> = None;
+
+		for path in Self::get_swap_paths(sold_token_id, bought_token_id)? {
+			if let Ok(amount) = Self::calculate_buy_price_path(&path, buy_amount) {
+				let is_better = match &best_path {
+					Some((best, best_amount)) =>
+						amount < *best_amount || (amount == *best_amount && path.len() < best.len()),
+					None => true,
+				};
+				if is_better {
+					best_path = Some((path, amount));
+				}
+			}
+		}
+
+		best_path.ok_or_else(|| Error::<T>::NoSuchPool.into())
+	}
+
+	// Lists all paths without repeated tokens from sold to bought token, that go through at most
+	// MAX_SWAP_PATH_HOPS pools. As no token repeats, no pool is visited twice, so amounts calculated
+	// for such path match the ones of multiswap execution
+	fn get_swap_paths(
+		sold_token_id: TokenId,
+		bought_token_id: TokenId,
+	) -> Result<Vec<Vec<TokenId>>, DispatchError> {
+		ensure!(sold_token_id != bought_token_id, Error::<T>::SameAsset);
+
+		let mut neighbours: BTreeMap<TokenId, Vec<TokenId>> = BTreeMap::new();
+		for ((first_asset_id, second_asset_id), _) in Pools::<T>::iter() {
+			if T::DisabledTokens::contains(&first_asset_id) ||
+				T::DisabledTokens::contains(&second_asset_id)
+			{
+				continue
+			}
+			neighbours.entry(first_asset_id).or_default().push(second_asset_id);
+			neighbours.entry(second_asset_id).or_default().push(first_asset_id);
+		}
+
+		let mut paths: Vec<Vec<TokenId>> = Vec::new();
+		let mut unfinished_paths: Vec<Vec<TokenId>> = sp_std::vec![sp_std::vec![sold_token_id]];
+		while let Some(path) = unfinished_paths.pop() {
+			let last_token_id = path[path.len() - 1];
+			if last_token_id == bought_token_id {
+				paths.push(path);
+				continue
+			}
+			if path.len() > MAX_SWAP_PATH_HOPS {
+				continue
+			}
+			for next_token_id in neighbours.get(&last_token_id).into_iter().flatten() {
+				if !path.contains(next_token_id) {
+					let mut next_path = path.clone();
+					next_path.push(*next_token_id);
+					unfinished_paths.push(next_path);
+				}
+			}
+		}
 
-		Self::calculate_buy_price(input_reserve, output_reserve, buy_amount)
+		Ok(paths)
 	}
 
 	pub fn get_reserves(
@@ -1448,19 +3659,23 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// worst case scenario
-	/// MAX: 2R 1W
+	/// MAX: 4R 4W
 	pub fn set_reserves(
 		first_asset_id: TokenId,
 		first_asset_amount: Balance,
 		second_asset_id: TokenId,
 		second_asset_amount: Balance,
 	) -> DispatchResult {
+		Self::ensure_pool_not_locked(first_asset_id, second_asset_id)?;
+
 		if Pools::<T>::contains_key((first_asset_id, second_asset_id)) {
+			Self::update_price_accumulator((first_asset_id, second_asset_id));
 			Pools::<T>::insert(
 				(first_asset_id, second_asset_id),
 				(first_asset_amount, second_asset_amount),
 			);
 		} else if Pools::<T>::contains_key((second_asset_id, first_asset_id)) {
+			Self::update_price_accumulator((second_asset_id, first_asset_id));
 			Pools::<T>::insert(
 				(second_asset_id, first_asset_id),
 				(second_asset_amount, first_asset_amount),
@@ -1472,66 +3687,739 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	// Calculate first and second token amounts depending on liquidity amount to burn
-	pub fn get_burn_amount(
-		first_asset_id: TokenId,
-		second_asset_id: TokenId,
-		liquidity_asset_amount: Balance,
-	) -> Result<(Balance, Balance), DispatchError> {
-		// Get token reserves and liquidity asset id
-		let liquidity_asset_id = Self::get_liquidity_asset(first_asset_id, second_asset_id)?;
-		let (first_asset_reserve, second_asset_reserve) =
-			Pallet::<T>::get_reserves(first_asset_id, second_asset_id)?;
+	fn ensure_pool_not_locked(first_asset_id: TokenId, second_asset_id: TokenId) -> DispatchResult {
+		ensure!(
+			!FlashSwapLocks::<T>::contains_key((first_asset_id, second_asset_id)) &&
+				!FlashSwapLocks::<T>::contains_key((second_asset_id, first_asset_id)),
+			Error::<T>::PoolLocked
+		);
+		Ok(())
+	}
 
-		let (first_asset_amount, second_asset_amount) = Self::get_burn_amount_reserves(
-			first_asset_reserve,
-			second_asset_reserve,
-			liquidity_asset_id,
-			liquidity_asset_amount,
+	pub fn do_flash_swap(
+		sender: T::AccountId,
+		sold_asset_id: TokenId,
+		bought_asset_id: TokenId,
+		bought_asset_amount: Balance,
+		max_amount_in: Balance,
+		call: <T as Config>::Call,
+	) -> DispatchResult {
+		ensure!(!bought_asset_amount.is_zero(), Error::<T>::ZeroAmount);
+
+		ensure!(
+			!T::DisabledTokens::contains(&sold_asset_id) &&
+				!T::DisabledTokens::contains(&bought_asset_id),
+			Error::<T>::FunctionNotAvailableForThisToken
+		);
+
+		let liquidity_asset_id = Self::get_liquidity_asset(sold_asset_id, bought_asset_id)?;
+		ensure!(
+			Self::get_pool_kind(liquidity_asset_id) == PoolKind::ConstantProduct,
+			Error::<T>::FlashSwapNotSupported
+		);
+		Self::ensure_pool_not_locked(sold_asset_id, bought_asset_id)?;
+
+		let (input_reserve, output_reserve) = Self::get_reserves(sold_asset_id, bought_asset_id)?;
+		ensure!(output_reserve > bought_asset_amount, Error::<T>::NotEnoughReserve);
+
+		let vault = Self::account_id();
+		<T as Config>::Currency::transfer(
+			bought_asset_id.into(),
+			&vault,
+			&sender,
+			bought_asset_amount.into(),
+			ExistenceRequirement::KeepAlive,
 		)?;
 
-		log!(
-			info,
-			"get_burn_amount: ({}, {}, {}) -> ({}, {})",
-			first_asset_id,
-			second_asset_id,
-			liquidity_asset_amount,
-			first_asset_amount,
-			second_asset_amount
+		// Any attempt to use the pool from the callback fails, until the pool is repaid
+		FlashSwapLocks::<T>::insert((sold_asset_id, bought_asset_id), ());
+		call.dispatch(frame_system::RawOrigin::Signed(sender.clone()).into())
+			.map_err(|e| e.error)?;
+		FlashSwapLocks::<T>::remove((sold_asset_id, bought_asset_id));
+
+		// Lock should keep pool untouched, repay is calculated from reserves before callback
+		ensure!(
+			Self::get_liquidity_asset(sold_asset_id, bought_asset_id)? == liquidity_asset_id &&
+				Self::get_reserves(sold_asset_id, bought_asset_id)? ==
+					(input_reserve, output_reserve),
+			Error::<T>::FlashSwapPoolChanged
 		);
 
-		Ok((first_asset_amount, second_asset_amount))
-	}
+		let pool_fee = Self::get_pool_fee(liquidity_asset_id);
+		let sold_asset_amount = Self::calculate_buy_price_with_fee(
+			input_reserve,
+			output_reserve,
+			bought_asset_amount,
+			pool_fee.total(),
+		)?;
+		ensure!(sold_asset_amount <= max_amount_in, Error::<T>::InsufficientInputAmount);
+
+		let buy_and_burn_amount = multiply_by_rational_with_rounding(
+			sold_asset_amount,
+			pool_fee.buy_and_burn_fee_percentage,
+			10000,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)? +
+			1;
+
+		let treasury_amount = multiply_by_rational_with_rounding(
+			sold_asset_amount,
+			pool_fee.treasury_fee_percentage,
+			10000,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)? +
+			1;
+
+		let pool_amount = sold_asset_amount
+			.checked_sub(buy_and_burn_amount + treasury_amount)
+			.ok_or_else(|| DispatchError::from(Error::<T>::SoldAmountTooLow))?;
+
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&vault,
+			pool_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)
+		.or(Err(Error::<T>::NotEnoughAssets))?;
+
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&Self::treasury_account_id(),
+			treasury_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)
+		.or(Err(Error::<T>::NotEnoughAssets))?;
+
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&Self::bnb_treasury_account_id(),
+			buy_and_burn_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)
+		.or(Err(Error::<T>::NotEnoughAssets))?;
+
+		let input_reserve_updated = input_reserve
+			.checked_add(pool_amount)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+		let output_reserve_updated = output_reserve.saturating_sub(bought_asset_amount);
+
+		// x' * y' >= x * y
+		ensure!(
+			U256::from(input_reserve_updated).saturating_mul(U256::from(output_reserve_updated)) >=
+				U256::from(input_reserve).saturating_mul(U256::from(output_reserve)),
+			Error::<T>::FlashSwapInvariantViolated
+		);
+
+		Self::set_reserves(
+			sold_asset_id,
+			input_reserve_updated,
+			bought_asset_id,
+			output_reserve_updated,
+		)?;
+
+		log!(
+			info,
+			"flash_swap: ({:?}, {}, {}, {}, {}) -> {}",
+			sender,
+			sold_asset_id,
+			bought_asset_id,
+			bought_asset_amount,
+			max_amount_in,
+			sold_asset_amount
+		);
+
+		Self::deposit_event(Event::FlashSwapExecuted(
+			sender,
+			sold_asset_id,
+			sold_asset_amount,
+			bought_asset_id,
+			bought_asset_amount,
+		));
+
+		Self::settle_treasury_and_burn(
+			sold_asset_id,
+			buy_and_burn_amount,
+			treasury_amount,
+			&pool_fee,
+		)?;
+
+		Ok(())
+	}
+
+	fn price_of(reserve: Balance, other_reserve: Balance) -> U256 {
+		U256::from(other_reserve)
+			.saturating_mul(TWAP_PRICE_PRECISION.into())
+			.checked_div(reserve.into())
+			.unwrap_or_default()
+	}
+
+	fn reset_price_accumulator(pool: (TokenId, TokenId)) {
+		let now: u32 = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>();
+		PriceObservations::<T>::insert(
+			pool,
+			0,
+			PriceObservation { block: now, ..Default::default() },
+		);
+		PriceAccumulators::<T>::insert(
+			pool,
+			PriceAccumulator { last_updated: now, observations_count: 1, ..Default::default() },
+		);
+	}
+
+	// Accumulates prices given by reserves of pool before their first change in current block
+	// MAX: 2R 2W
+	fn update_price_accumulator(pool: (TokenId, TokenId)) {
+		let mut accumulator = match PriceAccumulators::<T>::get(pool) {
+			Some(accumulator) => accumulator,
+			// pools created before prices were tracked start accumulating now
+			None => return Self::reset_price_accumulator(pool),
+		};
+
+		let now: u32 = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>();
+		if accumulator.last_updated >= now {
+			return
+		}
+
+		let (first_reserve, second_reserve) = Pools::<T>::get(pool);
+		let elapsed: U256 = now.saturating_sub(accumulator.last_updated).into();
+		accumulator.first_price_cumulative = accumulator
+			.first_price_cumulative
+			.saturating_add(Self::price_of(first_reserve, second_reserve).saturating_mul(elapsed));
+		accumulator.second_price_cumulative = accumulator
+			.second_price_cumulative
+			.saturating_add(Self::price_of(second_reserve, first_reserve).saturating_mul(elapsed));
+		accumulator.last_updated = now;
+		accumulator.observation_index =
+			(accumulator.observation_index + 1) % MAX_PRICE_OBSERVATIONS;
+		accumulator.observations_count =
+			accumulator.observations_count.saturating_add(1).min(MAX_PRICE_OBSERVATIONS);
+
+		PriceObservations::<T>::insert(
+			pool,
+			accumulator.observation_index,
+			PriceObservation {
+				block: now,
+				first_price_cumulative: accumulator.first_price_cumulative,
+				second_price_cumulative: accumulator.second_price_cumulative,
+			},
+		);
+		PriceAccumulators::<T>::insert(pool, accumulator);
+	}
+
+	// Time weighted average prices of first_asset_id in second_asset_id and vice versa over last
+	// window blocks, scaled by TWAP_PRICE_PRECISION. Prices set during current block are not
+	// included, so they cannot be moved by swaps in the same block the TWAP is read
+	pub fn get_twap(
+		first_asset_id: TokenId,
+		second_asset_id: TokenId,
+		window: u32,
+	) -> Result<(Balance, Balance), DispatchError> {
+		ensure!(!window.is_zero(), Error::<T>::InvalidTwapWindow);
+
+		let (pool, reversed) = if Pools::<T>::contains_key((first_asset_id, second_asset_id)) {
+			((first_asset_id, second_asset_id), false)
+		} else if Pools::<T>::contains_key((second_asset_id, first_asset_id)) {
+			((second_asset_id, first_asset_id), true)
+		} else {
+			return Err(Error::<T>::NoSuchPool.into())
+		};
+
+		let accumulator =
+			PriceAccumulators::<T>::get(pool).ok_or(Error::<T>::NotEnoughPriceHistory)?;
+		let now: u32 = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>();
+		let target = now.checked_sub(window).ok_or(Error::<T>::NotEnoughPriceHistory)?;
+
+		// cumulative prices extended up to current block with current reserves
+		let (first_reserve, second_reserve) = Pools::<T>::get(pool);
+		let elapsed: U256 = now.saturating_sub(accumulator.last_updated).into();
+		let current = PriceObservation {
+			block: now,
+			first_price_cumulative: accumulator.first_price_cumulative.saturating_add(
+				Self::price_of(first_reserve, second_reserve).saturating_mul(elapsed),
+			),
+			second_price_cumulative: accumulator.second_price_cumulative.saturating_add(
+				Self::price_of(second_reserve, first_reserve).saturating_mul(elapsed),
+			),
+		};
+
+		// binary search for the latest observation not newer than target block
+		let oldest_index = (accumulator.observation_index + MAX_PRICE_OBSERVATIONS + 1 -
+			accumulator.observations_count) %
+			MAX_PRICE_OBSERVATIONS;
+		let observation_at = |position: u32| {
+			PriceObservations::<T>::get(pool, (oldest_index + position) % MAX_PRICE_OBSERVATIONS)
+				.ok_or(Error::<T>::UnexpectedFailure)
+		};
+		ensure!(observation_at(0)?.block <= target, Error::<T>::NotEnoughPriceHistory);
+		let (mut low, mut high) = (0, accumulator.observations_count.saturating_sub(1));
+		while low < high {
+			let mid = (low + high + 1) / 2;
+			if observation_at(mid)?.block <= target {
+				low = mid;
+			} else {
+				high = mid - 1;
+			}
+		}
+		let before = observation_at(low)?;
+		let after = if low + 1 < accumulator.observations_count {
+			observation_at(low + 1)?
+		} else {
+			current.clone()
+		};
+
+		// price is constant between two observations, so cumulative price at target block can be
+		// interpolated
+		let cumulative_at_target = |before_cumulative: U256, after_cumulative: U256| {
+			let span: U256 = after.block.saturating_sub(before.block).into();
+			if span.is_zero() {
+				return before_cumulative
+			}
+			before_cumulative.saturating_add(
+				after_cumulative
+					.saturating_sub(before_cumulative)
+					.saturating_mul(target.saturating_sub(before.block).into()) /
+					span,
+			)
+		};
+		let twap = |current_cumulative: U256, target_cumulative: U256| {
+			Balance::try_from(
+				current_cumulative.saturating_sub(target_cumulative) / U256::from(window),
+			)
+			.map_err(|_| Error::<T>::MathOverflow)
+		};
+
+		let first_price = twap(
+			current.first_price_cumulative,
+			cumulative_at_target(before.first_price_cumulative, after.first_price_cumulative),
+		)?;
+		let second_price = twap(
+			current.second_price_cumulative,
+			cumulative_at_target(before.second_price_cumulative, after.second_price_cumulative),
+		)?;
+
+		if reversed {
+			Ok((second_price, first_price))
+		} else {
+			Ok((first_price, second_price))
+		}
+	}
+
+	// Liquidity token ids and assets of existing pools after start_after, ordered by liquidity
+	// token id
+	fn get_pools_after(start_after: Option<TokenId>) -> Vec<(TokenId, (TokenId, TokenId))> {
+		let mut pools: Vec<(TokenId, (TokenId, TokenId))> = LiquidityPools::<T>::iter()
+			.filter_map(|(liquidity_token_id, pool)| pool.map(|pool| (liquidity_token_id, pool)))
+			.filter(|(liquidity_token_id, _)| {
+				start_after.map_or(true, |start_after| *liquidity_token_id > start_after)
+			})
+			.collect();
+		pools.sort_unstable_by_key(|(liquidity_token_id, _)| *liquidity_token_id);
+		pools
+	}
+
+	// Page of pools ordered by liquidity token id, starting after liquidity token start_after,
+	// at most MAX_POOLS_PAGE_SIZE pools are returned
+	pub fn get_pools(start_after: Option<TokenId>, limit: u32) -> Vec<PoolInfo> {
+		Self::get_pools_after(start_after)
+			.into_iter()
+			.take(limit.min(MAX_POOLS_PAGE_SIZE) as usize)
+			.map(|(liquidity_token_id, (first_asset_id, second_asset_id))| {
+				let (first_asset_reserve, second_asset_reserve) =
+					Pallet::<T>::get_reserves(first_asset_id, second_asset_id).unwrap_or_default();
+				PoolInfo {
+					liquidity_token_id,
+					first_asset_id,
+					second_asset_id,
+					first_asset_reserve,
+					second_asset_reserve,
+					liquidity_token_issuance: <T as Config>::Currency::total_issuance(
+						liquidity_token_id.into(),
+					)
+					.into(),
+					is_promoted: <T as Config>::PoolPromoteApi::get_pool_rewards_v2(
+						liquidity_token_id,
+					)
+					.is_some(),
+					activated_amount: LiquidityMiningActivePoolV2::<T>::get(liquidity_token_id),
+				}
+			})
+			.collect()
+	}
+
+	// Rewards history and valuation of promoted pools ordered by liquidity token id,
+	// pools unpromoted within recorded history are included as long as they were issued
+	// any rewards, page starts after liquidity token start_after and at most MAX_POOLS_PAGE_SIZE
+	// pools are returned
+	pub fn get_pools_rewards_stats(
+		start_after: Option<TokenId>,
+		limit: u32,
+	) -> Vec<PoolRewardsStats> {
+		let total_pools_weight = <T as Config>::PoolPromoteApi::get_total_pools_weight();
+		Self::get_pools_after(start_after)
+			.into_iter()
+			.filter_map(|(liquidity_token_id, _)| {
+				let rewards_history =
+					<T as Config>::PoolPromoteApi::get_pool_rewards_history(liquidity_token_id);
+				let pool_weight =
+					<T as Config>::PoolPromoteApi::get_pool_weight(liquidity_token_id);
+				if pool_weight.is_none() &&
+					rewards_history.iter().all(|(_, rewards)| rewards.is_zero())
+				{
+					return None
+				}
+
+				let liquidity_token_issuance: Balance =
+					<T as Config>::Currency::total_issuance(liquidity_token_id.into()).into();
+				let activated_amount = LiquidityMiningActivePoolV2::<T>::get(liquidity_token_id);
+				// native reserve is half of pool value at pool price
+				let valuate = |amount| {
+					<Self as Valuate>::valuate_liquidity_token(liquidity_token_id, amount)
+						.saturating_mul(2)
+				};
+
+				Some(PoolRewardsStats {
+					liquidity_token_id,
+					rewards_history,
+					activated_amount,
+					reserves_valuation: valuate(liquidity_token_issuance),
+					activated_valuation: valuate(activated_amount),
+					pool_weight: pool_weight.unwrap_or_default(),
+					total_pools_weight,
+				})
+			})
+			.take(limit.min(MAX_POOLS_PAGE_SIZE) as usize)
+			.collect()
+	}
+
+	// Positions of user in pools after liquidity token start_after ordered by liquidity token
+	// id, pools where user holds no liquidity tokens and has no rewards to claim are skipped
+	// and do not count towards limit, at most MAX_POOLS_PAGE_SIZE positions are returned
+	pub fn get_user_liquidity_positions(
+		user: &AccountIdOf<T>,
+		start_after: Option<TokenId>,
+		limit: u32,
+	) -> Result<Vec<LiquidityPosition>, DispatchError> {
+		let limit = limit.min(MAX_POOLS_PAGE_SIZE) as usize;
+		let mut positions = Vec::new();
+		for (liquidity_token_id, (first_asset_id, second_asset_id)) in
+			Self::get_pools_after(start_after)
+		{
+			if positions.len() >= limit {
+				break
+			}
+			let free_amount: Balance =
+				<T as Config>::Currency::free_balance(liquidity_token_id.into(), user).into();
+			let reserved_amount: Balance =
+				<T as Config>::Currency::reserved_balance(liquidity_token_id.into(), user).into();
+			let claimable_rewards =
+				Self::calculate_rewards_amount_v2(user.clone(), liquidity_token_id)?;
+
+			let liquidity_amount = free_amount
+				.checked_add(reserved_amount)
+				.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+			if liquidity_amount.is_zero() && claimable_rewards.is_zero() {
+				continue
+			}
+
+			let (first_asset_amount, second_asset_amount) = if liquidity_amount.is_zero() {
+				(Zero::zero(), Zero::zero())
+			} else {
+				let (first_asset_reserve, second_asset_reserve) =
+					Pallet::<T>::get_reserves(first_asset_id, second_asset_id)?;
+				Pallet::<T>::get_burn_amount_reserves(
+					first_asset_reserve,
+					second_asset_reserve,
+					liquidity_token_id,
+					liquidity_amount,
+				)?
+			};
+
+			positions.push(LiquidityPosition {
+				liquidity_token_id,
+				first_asset_id,
+				second_asset_id,
+				free_amount,
+				reserved_amount,
+				activated_amount: Self::get_rewards_info(user, liquidity_token_id).activated_amount,
+				first_asset_amount,
+				second_asset_amount,
+				claimable_rewards,
+			});
+		}
+
+		Ok(positions)
+	}
+
+	// Calculate first and second token amounts depending on liquidity amount to burn
+	pub fn get_burn_amount(
+		first_asset_id: TokenId,
+		second_asset_id: TokenId,
+		liquidity_asset_amount: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		// Get token reserves and liquidity asset id
+		let liquidity_asset_id = Self::get_liquidity_asset(first_asset_id, second_asset_id)?;
+		let (first_asset_reserve, second_asset_reserve) =
+			Pallet::<T>::get_reserves(first_asset_id, second_asset_id)?;
+
+		let (first_asset_amount, second_asset_amount) = Self::get_burn_amount_reserves(
+			first_asset_reserve,
+			second_asset_reserve,
+			liquidity_asset_id,
+			liquidity_asset_amount,
+		)?;
+
+		log!(
+			info,
+			"get_burn_amount: ({}, {}, {}) -> ({}, {})",
+			first_asset_id,
+			second_asset_id,
+			liquidity_asset_amount,
+			first_asset_amount,
+			second_asset_amount
+		);
+
+		Ok((first_asset_amount, second_asset_amount))
+	}
+
+	pub fn get_burn_amount_reserves(
+		first_asset_reserve: Balance,
+		second_asset_reserve: Balance,
+		liquidity_asset_id: TokenId,
+		liquidity_asset_amount: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		// Get token reserves and liquidity asset id
+
+		let total_liquidity_assets: Balance =
+			<T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into();
+
+		// Calculate first and second token amount to be withdrawn
+		ensure!(!total_liquidity_assets.is_zero(), Error::<T>::DivisionByZero);
+		let first_asset_amount = multiply_by_rational_with_rounding(
+			first_asset_reserve,
+			liquidity_asset_amount,
+			total_liquidity_assets,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)?;
+		let second_asset_amount = multiply_by_rational_with_rounding(
+			second_asset_reserve,
+			liquidity_asset_amount,
+			total_liquidity_assets,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)?;
+
+		Ok((first_asset_amount, second_asset_amount))
+	}
+
+	/// Executes buy without settling fees that go to treasury and for buy and burn purpose,
+	/// returns amount of sold asset paid and fees to be settled with `settle_fees`
+	fn do_buy_asset(
+		sender: T::AccountId,
+		sold_asset_id: TokenId,
+		bought_asset_id: TokenId,
+		bought_asset_amount: Balance,
+		max_amount_in: Balance,
+	) -> Result<(Balance, FeeSettlement), DispatchError> {
+		ensure!(
+			!T::DisabledTokens::contains(&sold_asset_id) &&
+				!T::DisabledTokens::contains(&bought_asset_id),
+			Error::<T>::FunctionNotAvailableForThisToken
+		);
+
+		// Get token reserves
+		let (input_reserve, output_reserve) =
+			Pallet::<T>::get_reserves(sold_asset_id, bought_asset_id)?;
+
+		// Ensure there are enough tokens in reserves
+		ensure!(output_reserve > bought_asset_amount, Error::<T>::NotEnoughReserve,);
+
+		// Ensure not buying zero amount
+		ensure!(!bought_asset_amount.is_zero(), Error::<T>::ZeroAmount,);
+
+		// Calculate amount to be paid from bought amount
+		// MAX: 2R
+		let liquidity_asset_id = Pallet::<T>::get_liquidity_asset(sold_asset_id, bought_asset_id)?;
+		// MAX: 1R
+		let pool_fee = Pallet::<T>::get_pool_fee(liquidity_asset_id);
+
+		// MAX: 1R
+		let sold_asset_amount = Pallet::<T>::calculate_buy_price_for_pool(
+			liquidity_asset_id,
+			input_reserve,
+			output_reserve,
+			bought_asset_amount,
+			pool_fee.total(),
+		)?;
+
+		let buy_and_burn_amount = multiply_by_rational_with_rounding(
+			sold_asset_amount,
+			pool_fee.buy_and_burn_fee_percentage,
+			10000,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)? +
+			1;
+
+		let treasury_amount = multiply_by_rational_with_rounding(
+			sold_asset_amount,
+			pool_fee.treasury_fee_percentage,
+			10000,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)? +
+			1;
+
+		let pool_fee_amount = multiply_by_rational_with_rounding(
+			sold_asset_amount,
+			pool_fee.pool_fee_percentage,
+			10000,
+			Rounding::Down,
+		)
+		.ok_or(Error::<T>::UnexpectedFailure)? +
+			1;
+
+		// for future implementation of min fee if necessary
+		// let min_fee: u128 = 0;
+		// if buy_and_burn_amount + treasury_amount + pool_fee_amount < min_fee {
+		//     buy_and_burn_amount = min_fee * Self::total_fee() / T::BuyAndBurnFeePercentage::get();
+		//     treasury_amount = min_fee * Self::total_fee() / T::TreasuryFeePercentage::get();
+		//     pool_fee_amount = min_fee - buy_and_burn_amount - treasury_amount;
+		// }
+
+		ensure!(input_reserve.checked_add(sold_asset_amount).is_some(), Error::<T>::MathOverflow);
+
+		// Ensure user has enough tokens to sell
+		<T as Config>::Currency::ensure_can_withdraw(
+			sold_asset_id.into(),
+			&sender,
+			sold_asset_amount.into(),
+			WithdrawReasons::all(),
+			// Does not fail due to earlier ensure
+			Default::default(),
+		)
+		.or(Err(Error::<T>::NotEnoughAssets))?;
+
+		let vault = Pallet::<T>::account_id();
+		let treasury_account: T::AccountId = Self::treasury_account_id();
+		let bnb_treasury_account: T::AccountId = Self::bnb_treasury_account_id();
+
+		// Transfer of fees, before tx can fail on min amount out
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&vault,
+			pool_fee_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&treasury_account,
+			treasury_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		<T as Config>::Currency::transfer(
+			sold_asset_id.into(),
+			&sender,
+			&bnb_treasury_account,
+			buy_and_burn_amount.into(),
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		// Add pool fee to pool
+		Pallet::<T>::set_reserves(
+			sold_asset_id,
+			input_reserve.saturating_add(pool_fee_amount),
+			bought_asset_id,
+			output_reserve,
+		)?;
+
+		// Ensure paid amount is less then maximum allowed price
+		if sold_asset_amount <= max_amount_in {
+			// Transfer sold token amount from user to vault and bought token amount from vault to user
+			<T as Config>::Currency::transfer(
+				sold_asset_id.into(),
+				&sender,
+				&vault,
+				(sold_asset_amount
+					.checked_sub(buy_and_burn_amount + treasury_amount + pool_fee_amount)
+					.ok_or_else(|| DispatchError::from(Error::<T>::SoldAmountTooLow))?)
+				.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			<T as Config>::Currency::transfer(
+				bought_asset_id.into(),
+				&vault,
+				&sender,
+				bought_asset_amount.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			// Apply changes in token pools, adding sold amount and removing bought amount
+			// Neither should fall to zero let alone underflow, due to how pool destruction works
+			// Won't overflow due to earlier ensure
+			let input_reserve_updated = input_reserve
+				.saturating_add(sold_asset_amount - treasury_amount - buy_and_burn_amount);
+			let output_reserve_updated = output_reserve.saturating_sub(bought_asset_amount);
+			Pallet::<T>::set_reserves(
+				sold_asset_id,
+				input_reserve_updated,
+				bought_asset_id,
+				output_reserve_updated,
+			)?;
+
+			log!(
+				info,
+				"buy_asset: ({:?}, {}, {}, {}, {}) -> {}",
+				sender,
+				sold_asset_id,
+				bought_asset_id,
+				bought_asset_amount,
+				max_amount_in,
+				sold_asset_amount
+			);
+
+			log!(
+				info,
+				"pool-state: [({}, {}) -> {}, ({}, {}) -> {}]",
+				sold_asset_id,
+				bought_asset_id,
+				input_reserve_updated,
+				bought_asset_id,
+				sold_asset_id,
+				output_reserve_updated
+			);
 
-	pub fn get_burn_amount_reserves(
-		first_asset_reserve: Balance,
-		second_asset_reserve: Balance,
-		liquidity_asset_id: TokenId,
-		liquidity_asset_amount: Balance,
-	) -> Result<(Balance, Balance), DispatchError> {
-		// Get token reserves and liquidity asset id
+			Pallet::<T>::deposit_event(Event::AssetsSwapped(
+				sender,
+				sold_asset_id,
+				sold_asset_amount,
+				bought_asset_id,
+				bought_asset_amount,
+			));
+		}
 
-		let total_liquidity_assets: Balance =
-			<T as Config>::Currency::total_issuance(liquidity_asset_id.into()).into();
+		Ok((
+			sold_asset_amount,
+			FeeSettlement { sold_asset_id, buy_and_burn_amount, treasury_amount, pool_fee },
+		))
+	}
 
-		// Calculate first and second token amount to be withdrawn
-		ensure!(!total_liquidity_assets.is_zero(), Error::<T>::DivisionByZero);
-		let first_asset_amount = multiply_by_rational_with_rounding(
-			first_asset_reserve,
-			liquidity_asset_amount,
-			total_liquidity_assets,
-			Rounding::Down,
-		)
-		.ok_or(Error::<T>::UnexpectedFailure)?;
-		let second_asset_amount = multiply_by_rational_with_rounding(
-			second_asset_reserve,
-			liquidity_asset_amount,
-			total_liquidity_assets,
-			Rounding::Down,
+	fn settle_fees(settlement: FeeSettlement) -> DispatchResult {
+		Self::settle_treasury_and_burn(
+			settlement.sold_asset_id,
+			settlement.buy_and_burn_amount,
+			settlement.treasury_amount,
+			&settlement.pool_fee,
 		)
-		.ok_or(Error::<T>::UnexpectedFailure)?;
-
-		Ok((first_asset_amount, second_asset_amount))
 	}
 
 	//TODO if pool contains key !
@@ -1539,6 +4427,7 @@ impl<T: Config> Pallet<T> {
 		sold_asset_id: TokenId,
 		burn_amount: Balance,
 		treasury_amount: Balance,
+		fee: &PoolFeeInfo,
 	) -> DispatchResult {
 		let vault = Self::account_id();
 		let mangata_id: TokenId = Self::native_token_id();
@@ -1568,14 +4457,29 @@ impl<T: Config> Pallet<T> {
 				Pallet::<T>::get_reserves(sold_asset_id, mangata_id)?;
 
 			// Calculating swapped mangata amount
-			let settle_amount_in_mangata = Self::calculate_sell_price_no_fee(
-				input_reserve,
-				output_reserve,
-				treasury_amount + burn_amount,
-			)?;
-			let treasury_amount_in_mangata = settle_amount_in_mangata *
-				T::TreasuryFeePercentage::get() /
-				(T::TreasuryFeePercentage::get() + T::BuyAndBurnFeePercentage::get());
+			// MAX: 3R
+			let settle_amount_in_mangata =
+				match Self::get_pool_kind(Self::get_liquidity_asset(sold_asset_id, mangata_id)?) {
+					PoolKind::ConstantProduct => Self::calculate_sell_price_no_fee(
+						input_reserve,
+						output_reserve,
+						treasury_amount + burn_amount,
+					)?,
+					PoolKind::StableSwap { amplification } => Self::calculate_stable_sell_price(
+						input_reserve,
+						output_reserve,
+						treasury_amount + burn_amount,
+						0,
+						amplification,
+					)?,
+				};
+			let treasury_amount_in_mangata = settle_amount_in_mangata
+				.checked_mul(fee.treasury_fee_percentage)
+				.and_then(|amount| {
+					amount
+						.checked_div(fee.treasury_fee_percentage + fee.buy_and_burn_fee_percentage)
+				})
+				.unwrap_or_default();
 
 			let burn_amount_in_mangata = settle_amount_in_mangata - treasury_amount_in_mangata;
 
@@ -1678,6 +4582,8 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			Error::<T>::PoolAlreadyExists,
 		);
 
+		Pallet::<T>::ensure_pool_not_locked(first_asset_id, second_asset_id)?;
+
 		// Ensure user has enough withdrawable tokens to create pool in amounts required
 
 		<T as Config>::Currency::ensure_can_withdraw(
@@ -1713,6 +4619,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			(first_asset_id, second_asset_id),
 			(first_asset_amount, second_asset_amount),
 		);
+		Pallet::<T>::reset_price_accumulator((first_asset_id, second_asset_id));
 
 		// Pools::insert((second_asset_id, first_asset_id), second_asset_amount);
 
@@ -1753,251 +4660,56 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			second_asset_amount,
 			liquidity_asset_id,
 			initial_liquidity
-		);
-
-		log!(
-			info,
-			"pool-state: [({}, {}) -> {}, ({}, {}) -> {}]",
-			first_asset_id,
-			second_asset_id,
-			first_asset_amount,
-			second_asset_id,
-			first_asset_id,
-			second_asset_amount
-		);
-		// This, will and should, never fail
-		Pallet::<T>::set_liquidity_asset_info(liquidity_asset_id, first_asset_id, second_asset_id)?;
-
-		Pallet::<T>::deposit_event(Event::PoolCreated(
-			sender,
-			first_asset_id,
-			first_asset_amount,
-			second_asset_id,
-			second_asset_amount,
-		));
-
-		Ok(())
-	}
-
-	fn sell_asset(
-		sender: T::AccountId,
-		sold_asset_id: Self::CurrencyId,
-		bought_asset_id: Self::CurrencyId,
-		sold_asset_amount: Self::Balance,
-		min_amount_out: Self::Balance,
-	) -> DispatchResult {
-		// Ensure not selling zero amount
-		ensure!(!sold_asset_amount.is_zero(), Error::<T>::ZeroAmount,);
-
-		ensure!(
-			!T::DisabledTokens::contains(&sold_asset_id) &&
-				!T::DisabledTokens::contains(&bought_asset_id),
-			Error::<T>::FunctionNotAvailableForThisToken
-		);
-
-		let buy_and_burn_amount = multiply_by_rational_with_rounding(
-			sold_asset_amount,
-			T::BuyAndBurnFeePercentage::get(),
-			10000,
-			Rounding::Down,
-		)
-		.ok_or(Error::<T>::UnexpectedFailure)? +
-			1;
-
-		let treasury_amount = multiply_by_rational_with_rounding(
-			sold_asset_amount,
-			T::TreasuryFeePercentage::get(),
-			10000,
-			Rounding::Down,
-		)
-		.ok_or(Error::<T>::UnexpectedFailure)? +
-			1;
-
-		let pool_fee_amount = multiply_by_rational_with_rounding(
-			sold_asset_amount,
-			T::PoolFeePercentage::get(),
-			10000,
-			Rounding::Down,
-		)
-		.ok_or(Error::<T>::UnexpectedFailure)? +
-			1;
-
-		// for future implementation of min fee if necessary
-		// let min_fee: u128 = 0;
-		// if buy_and_burn_amount + treasury_amount + pool_fee_amount < min_fee {
-		//     buy_and_burn_amount = min_fee * Self::total_fee() / T::BuyAndBurnFeePercentage::get();
-		//     treasury_amount = min_fee * Self::total_fee() / T::TreasuryFeePercentage::get();
-		//     pool_fee_amount = min_fee - buy_and_burn_amount - treasury_amount;
-		// }
-
-		// Get token reserves
-
-		// MAX: 2R
-		let (input_reserve, output_reserve) =
-			Pallet::<T>::get_reserves(sold_asset_id, bought_asset_id)?;
-
-		ensure!(input_reserve.checked_add(sold_asset_amount).is_some(), Error::<T>::MathOverflow);
-
-		// Calculate bought asset amount to be received by paying sold asset amount
-		let bought_asset_amount =
-			Pallet::<T>::calculate_sell_price(input_reserve, output_reserve, sold_asset_amount)?;
-
-		// Ensure user has enough tokens to sell
-		<T as Config>::Currency::ensure_can_withdraw(
-			sold_asset_id.into(),
-			&sender,
-			sold_asset_amount.into(),
-			WithdrawReasons::all(),
-			// Does not fail due to earlier ensure
-			Default::default(),
-		)
-		.or(Err(Error::<T>::NotEnoughAssets))?;
-
-		let vault = Pallet::<T>::account_id();
-		let treasury_account: T::AccountId = Self::treasury_account_id();
-		let bnb_treasury_account: T::AccountId = Self::bnb_treasury_account_id();
-
-		// Transfer of fees, before tx can fail on min amount out
-		<T as Config>::Currency::transfer(
-			sold_asset_id.into(),
-			&sender,
-			&vault,
-			pool_fee_amount.into(),
-			ExistenceRequirement::KeepAlive,
-		)?;
-
-		<T as Config>::Currency::transfer(
-			sold_asset_id.into(),
-			&sender,
-			&treasury_account,
-			treasury_amount.into(),
-			ExistenceRequirement::KeepAlive,
-		)?;
-
-		<T as Config>::Currency::transfer(
-			sold_asset_id.into(),
-			&sender,
-			&bnb_treasury_account,
-			buy_and_burn_amount.into(),
-			ExistenceRequirement::KeepAlive,
-		)?;
-
-		// Add pool fee to pool
-		// 2R 1W
-		Pallet::<T>::set_reserves(
-			sold_asset_id,
-			input_reserve.saturating_add(pool_fee_amount),
-			bought_asset_id,
-			output_reserve,
-		)?;
-
-		// Ensure bought token amount is higher then requested minimal amount
-		if bought_asset_amount >= min_amount_out {
-			// Transfer the rest of sold token amount from user to vault and bought token amount from vault to user
-			<T as Config>::Currency::transfer(
-				sold_asset_id.into(),
-				&sender,
-				&vault,
-				(sold_asset_amount
-					.checked_sub(buy_and_burn_amount + treasury_amount + pool_fee_amount)
-					.ok_or_else(|| DispatchError::from(Error::<T>::SoldAmountTooLow))?)
-				.into(),
-				ExistenceRequirement::KeepAlive,
-			)?;
-			<T as Config>::Currency::transfer(
-				bought_asset_id.into(),
-				&vault,
-				&sender,
-				bought_asset_amount.into(),
-				ExistenceRequirement::KeepAlive,
-			)?;
-
-			// Apply changes in token pools, adding sold amount and removing bought amount
-			// Neither should fall to zero let alone underflow, due to how pool destruction works
-			// Won't overflow due to earlier ensure
-			let input_reserve_updated = input_reserve
-				.saturating_add(sold_asset_amount - treasury_amount - buy_and_burn_amount);
-			let output_reserve_updated = output_reserve.saturating_sub(bought_asset_amount);
-
-			// MAX 2R 1W
-			Pallet::<T>::set_reserves(
-				sold_asset_id,
-				input_reserve_updated,
-				bought_asset_id,
-				output_reserve_updated,
-			)?;
-
-			log!(
-				info,
-				"sell_asset: ({:?}, {}, {}, {}, {}) -> {}",
-				sender,
-				sold_asset_id,
-				bought_asset_id,
-				sold_asset_amount,
-				min_amount_out,
-				bought_asset_amount
-			);
-
-			log!(
-				info,
-				"pool-state: [({}, {}) -> {}, ({}, {}) -> {}]",
-				sold_asset_id,
-				bought_asset_id,
-				input_reserve_updated,
-				bought_asset_id,
-				sold_asset_id,
-				output_reserve_updated
-			);
-
-			Pallet::<T>::deposit_event(Event::AssetsSwapped(
-				sender,
-				sold_asset_id,
-				sold_asset_amount,
-				bought_asset_id,
-				bought_asset_amount,
-			));
-		}
+		);
 
-		// Settle tokens which goes to treasury and for buy and burn purpose
-		Pallet::<T>::settle_treasury_and_burn(sold_asset_id, buy_and_burn_amount, treasury_amount)?;
+		log!(
+			info,
+			"pool-state: [({}, {}) -> {}, ({}, {}) -> {}]",
+			first_asset_id,
+			second_asset_id,
+			first_asset_amount,
+			second_asset_id,
+			first_asset_id,
+			second_asset_amount
+		);
+		// This, will and should, never fail
+		Pallet::<T>::set_liquidity_asset_info(liquidity_asset_id, first_asset_id, second_asset_id)?;
 
-		if bought_asset_amount < min_amount_out {
-			return Err(DispatchError::from(Error::<T>::InsufficientOutputAmount))
-		}
+		Pallet::<T>::deposit_event(Event::PoolCreated(
+			sender,
+			first_asset_id,
+			first_asset_amount,
+			second_asset_id,
+			second_asset_amount,
+		));
 
 		Ok(())
 	}
 
-	fn buy_asset(
+	fn sell_asset(
 		sender: T::AccountId,
 		sold_asset_id: Self::CurrencyId,
 		bought_asset_id: Self::CurrencyId,
-		bought_asset_amount: Self::Balance,
-		max_amount_in: Self::Balance,
+		sold_asset_amount: Self::Balance,
+		min_amount_out: Self::Balance,
 	) -> DispatchResult {
+		// Ensure not selling zero amount
+		ensure!(!sold_asset_amount.is_zero(), Error::<T>::ZeroAmount,);
+
 		ensure!(
 			!T::DisabledTokens::contains(&sold_asset_id) &&
 				!T::DisabledTokens::contains(&bought_asset_id),
 			Error::<T>::FunctionNotAvailableForThisToken
 		);
 
-		// Get token reserves
-		let (input_reserve, output_reserve) =
-			Pallet::<T>::get_reserves(sold_asset_id, bought_asset_id)?;
-
-		// Ensure there are enough tokens in reserves
-		ensure!(output_reserve > bought_asset_amount, Error::<T>::NotEnoughReserve,);
-
-		// Ensure not buying zero amount
-		ensure!(!bought_asset_amount.is_zero(), Error::<T>::ZeroAmount,);
-
-		// Calculate amount to be paid from bought amount
-		let sold_asset_amount =
-			Pallet::<T>::calculate_buy_price(input_reserve, output_reserve, bought_asset_amount)?;
+		// MAX: 2R
+		let liquidity_asset_id = Pallet::<T>::get_liquidity_asset(sold_asset_id, bought_asset_id)?;
+		// MAX: 1R
+		let pool_fee = Pallet::<T>::get_pool_fee(liquidity_asset_id);
 
 		let buy_and_burn_amount = multiply_by_rational_with_rounding(
 			sold_asset_amount,
-			T::BuyAndBurnFeePercentage::get(),
+			pool_fee.buy_and_burn_fee_percentage,
 			10000,
 			Rounding::Down,
 		)
@@ -2006,7 +4718,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 
 		let treasury_amount = multiply_by_rational_with_rounding(
 			sold_asset_amount,
-			T::TreasuryFeePercentage::get(),
+			pool_fee.treasury_fee_percentage,
 			10000,
 			Rounding::Down,
 		)
@@ -2015,7 +4727,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 
 		let pool_fee_amount = multiply_by_rational_with_rounding(
 			sold_asset_amount,
-			T::PoolFeePercentage::get(),
+			pool_fee.pool_fee_percentage,
 			10000,
 			Rounding::Down,
 		)
@@ -2030,8 +4742,24 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		//     pool_fee_amount = min_fee - buy_and_burn_amount - treasury_amount;
 		// }
 
+		// Get token reserves
+
+		// MAX: 2R
+		let (input_reserve, output_reserve) =
+			Pallet::<T>::get_reserves(sold_asset_id, bought_asset_id)?;
+
 		ensure!(input_reserve.checked_add(sold_asset_amount).is_some(), Error::<T>::MathOverflow);
 
+		// Calculate bought asset amount to be received by paying sold asset amount
+		// MAX: 1R
+		let bought_asset_amount = Pallet::<T>::calculate_sell_price_for_pool(
+			liquidity_asset_id,
+			input_reserve,
+			output_reserve,
+			sold_asset_amount,
+			pool_fee.total(),
+		)?;
+
 		// Ensure user has enough tokens to sell
 		<T as Config>::Currency::ensure_can_withdraw(
 			sold_asset_id.into(),
@@ -2073,6 +4801,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		)?;
 
 		// Add pool fee to pool
+		// 2R 1W
 		Pallet::<T>::set_reserves(
 			sold_asset_id,
 			input_reserve.saturating_add(pool_fee_amount),
@@ -2080,9 +4809,9 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			output_reserve,
 		)?;
 
-		// Ensure paid amount is less then maximum allowed price
-		if sold_asset_amount <= max_amount_in {
-			// Transfer sold token amount from user to vault and bought token amount from vault to user
+		// Ensure bought token amount is higher then requested minimal amount
+		if bought_asset_amount >= min_amount_out {
+			// Transfer the rest of sold token amount from user to vault and bought token amount from vault to user
 			<T as Config>::Currency::transfer(
 				sold_asset_id.into(),
 				&sender,
@@ -2107,6 +4836,8 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			let input_reserve_updated = input_reserve
 				.saturating_add(sold_asset_amount - treasury_amount - buy_and_burn_amount);
 			let output_reserve_updated = output_reserve.saturating_sub(bought_asset_amount);
+
+			// MAX 2R 1W
 			Pallet::<T>::set_reserves(
 				sold_asset_id,
 				input_reserve_updated,
@@ -2116,13 +4847,13 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 
 			log!(
 				info,
-				"buy_asset: ({:?}, {}, {}, {}, {}) -> {}",
+				"sell_asset: ({:?}, {}, {}, {}, {}) -> {}",
 				sender,
 				sold_asset_id,
 				bought_asset_id,
-				bought_asset_amount,
-				max_amount_in,
-				sold_asset_amount
+				sold_asset_amount,
+				min_amount_out,
+				bought_asset_amount
 			);
 
 			log!(
@@ -2144,8 +4875,38 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 				bought_asset_amount,
 			));
 		}
+
+		// Settle tokens which goes to treasury and for buy and burn purpose
+		Pallet::<T>::settle_treasury_and_burn(
+			sold_asset_id,
+			buy_and_burn_amount,
+			treasury_amount,
+			&pool_fee,
+		)?;
+
+		if bought_asset_amount < min_amount_out {
+			return Err(DispatchError::from(Error::<T>::InsufficientOutputAmount))
+		}
+
+		Ok(())
+	}
+
+	fn buy_asset(
+		sender: T::AccountId,
+		sold_asset_id: Self::CurrencyId,
+		bought_asset_id: Self::CurrencyId,
+		bought_asset_amount: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> DispatchResult {
+		let (sold_asset_amount, settlement) = Pallet::<T>::do_buy_asset(
+			sender,
+			sold_asset_id,
+			bought_asset_id,
+			bought_asset_amount,
+			max_amount_in,
+		)?;
 		// Settle tokens which goes to treasury and for buy and burn purpose
-		Pallet::<T>::settle_treasury_and_burn(sold_asset_id, buy_and_burn_amount, treasury_amount)?;
+		Pallet::<T>::settle_fees(settlement)?;
 
 		if sold_asset_amount > max_amount_in {
 			return Err(DispatchError::from(Error::<T>::InsufficientInputAmount))
@@ -2154,6 +4915,113 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	#[transactional]
+	fn multiswap_sell_asset(
+		sender: T::AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		sold_asset_amount: Self::Balance,
+		min_amount_out: Self::Balance,
+	) -> DispatchResult {
+		Pallet::<T>::ensure_valid_swap_path(&swap_token_list)?;
+
+		// Every hop sells everything bought in the previous one, the amount is recalculated
+		// right before each hop as settling fees of previous hops may alter following pools
+		let mut swap_amount = sold_asset_amount;
+		for hop in swap_token_list.windows(2) {
+			let bought_asset_amount =
+				Pallet::<T>::calculate_sell_price_id(hop[0], hop[1], swap_amount)?;
+
+			<Self as XykFunctionsTrait<T::AccountId>>::sell_asset(
+				sender.clone(),
+				hop[0],
+				hop[1],
+				swap_amount,
+				bought_asset_amount,
+			)?;
+
+			swap_amount = bought_asset_amount;
+		}
+
+		ensure!(swap_amount >= min_amount_out, Error::<T>::InsufficientOutputAmount);
+
+		log!(
+			info,
+			"multiswap_sell_asset: ({:?}, {:?}, {}, {}) -> {}",
+			sender,
+			swap_token_list,
+			sold_asset_amount,
+			min_amount_out,
+			swap_amount
+		);
+
+		Ok(())
+	}
+
+	#[transactional]
+	fn multiswap_buy_asset(
+		sender: T::AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		bought_asset_amount: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> DispatchResult {
+		Pallet::<T>::ensure_valid_swap_path(&swap_token_list)?;
+
+		// Required amounts are precalculated backwards from the last hop, so each pool
+		// must be visited only once for them to stay valid during execution
+		for (i, hop) in swap_token_list.windows(2).enumerate() {
+			ensure!(
+				!swap_token_list.windows(2).skip(i + 1).any(|other| {
+					(other[0] == hop[0] && other[1] == hop[1]) ||
+						(other[0] == hop[1] && other[1] == hop[0])
+				}),
+				Error::<T>::MultiswapBuyRepeatedPool
+			);
+		}
+
+		// swap_amounts[i] is the amount of swap_token_list[i] that goes in/out of the path
+		let mut swap_amounts: Vec<Balance> = sp_std::vec![Zero::zero(); swap_token_list.len()];
+		swap_amounts[swap_token_list.len() - 1] = bought_asset_amount;
+		for i in (0..swap_token_list.len() - 1).rev() {
+			swap_amounts[i] = Pallet::<T>::calculate_buy_price_id(
+				swap_token_list[i],
+				swap_token_list[i + 1],
+				swap_amounts[i + 1],
+			)?;
+		}
+
+		ensure!(swap_amounts[0] <= max_amount_in, Error::<T>::InsufficientInputAmount);
+
+		// Settling fees swaps them to mangata through pools that may be part of the path, so it is
+		// postponed until all hops are executed with precalculated amounts
+		let mut settlements: Vec<FeeSettlement> = Vec::with_capacity(swap_token_list.len() - 1);
+		for (i, hop) in swap_token_list.windows(2).enumerate() {
+			let (sold_asset_amount, settlement) = Pallet::<T>::do_buy_asset(
+				sender.clone(),
+				hop[0],
+				hop[1],
+				swap_amounts[i + 1],
+				swap_amounts[i],
+			)?;
+			ensure!(sold_asset_amount <= swap_amounts[i], Error::<T>::InsufficientInputAmount);
+			settlements.push(settlement);
+		}
+		for settlement in settlements {
+			Pallet::<T>::settle_fees(settlement)?;
+		}
+
+		log!(
+			info,
+			"multiswap_buy_asset: ({:?}, {:?}, {}, {}) -> {}",
+			sender,
+			swap_token_list,
+			bought_asset_amount,
+			max_amount_in,
+			swap_amounts[0]
+		);
+
+		Ok(())
+	}
+
 	fn mint_liquidity(
 		sender: T::AccountId,
 		first_asset_id: Self::CurrencyId,
@@ -2171,6 +5039,8 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			Error::<T>::NoSuchPool,
 		);
 
+		Pallet::<T>::ensure_pool_not_locked(first_asset_id, second_asset_id)?;
+
 		// TODO move ensure in get_liq_asset ?
 		// Get liquidity token id
 		let liquidity_asset_id = Pallet::<T>::get_liquidity_asset(first_asset_id, second_asset_id)?;
@@ -2332,6 +5202,9 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 
 		let liquidity_asset_id = Pallet::<T>::get_liquidity_asset(first_asset_id, second_asset_id)?;
 
+		// Checked upfront as burning all liquidity removes the pool without set_reserves
+		Pallet::<T>::ensure_pool_not_locked(first_asset_id, second_asset_id)?;
+
 		// First let's check how much we can actually burn
 		let max_instant_unreserve_amount =
 			T::ActivationReservesProvider::get_max_instant_unreserve_amount(
@@ -2449,6 +5322,11 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			LiquidityAssets::<T>::remove((first_asset_id, second_asset_id));
 			LiquidityAssets::<T>::remove((second_asset_id, first_asset_id));
 			LiquidityPools::<T>::remove(liquidity_asset_id);
+			PoolFees::<T>::remove(liquidity_asset_id);
+			PoolKinds::<T>::remove(liquidity_asset_id);
+			// observations are left to be overwritten, once pool is created again
+			PriceAccumulators::<T>::remove((first_asset_id, second_asset_id));
+			PriceAccumulators::<T>::remove((second_asset_id, first_asset_id));
 		} else {
 			// Apply changes in token pools, removing withdrawn amounts
 			// Cannot underflow due to earlier ensure
@@ -2509,7 +5387,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 
 		ensure!(mangata_amount <= claimable_rewards, Error::<T>::NotEnoughtRewardsEarned);
 
-		let rewards_info: RewardInfo = Self::get_rewards_info(user.clone(), liquidity_asset_id);
+		let rewards_info: RewardInfo = Self::get_rewards_info_v2(user.clone(), liquidity_asset_id)?;
 
 		let mut not_yet_claimed_rewards = rewards_info.rewards_not_yet_claimed;
 		let mut already_claimed_rewards = rewards_info.rewards_already_claimed;
@@ -2546,7 +5424,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			ExistenceRequirement::KeepAlive,
 		)?;
 
-		RewardsInfo::<T>::insert(user.clone(), liquidity_asset_id, rewards_info_new);
+		Pallet::<T>::insert_rewards_info(&user, liquidity_asset_id, rewards_info_new);
 
 		Pallet::<T>::deposit_event(Event::RewardsClaimed(user, liquidity_asset_id, mangata_amount));
 
@@ -2559,20 +5437,23 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 	) -> DispatchResult {
 		let mangata_id: TokenId = Self::native_token_id();
 
-		let rewards_info: RewardInfo = Self::get_rewards_info(user.clone(), liquidity_asset_id);
+		let rewards_info: RewardInfo = Self::get_rewards_info_v2(user.clone(), liquidity_asset_id)?;
 
-		let pool_rewards_ratio_current =
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
-				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+		let pool_rewards_ratio_current = Self::pool_rewards_ratio(liquidity_asset_id)?;
 
-		let current_rewards = Pallet::<T>::calculate_rewards_v2(
-			rewards_info.activated_amount,
-			liquidity_asset_id,
-			rewards_info.last_checkpoint,
-			rewards_info.pool_ratio_at_last_checkpoint,
-			rewards_info.missing_at_last_checkpoint,
-			pool_rewards_ratio_current,
-		)?;
+		// Checkpoint of user without activated liquidity may predate pool being promoted again
+		let current_rewards = if rewards_info.activated_amount == 0 {
+			0
+		} else {
+			Pallet::<T>::calculate_rewards_v2(
+				rewards_info.activated_amount,
+				liquidity_asset_id,
+				rewards_info.last_checkpoint,
+				rewards_info.pool_ratio_at_last_checkpoint,
+				rewards_info.missing_at_last_checkpoint,
+				pool_rewards_ratio_current,
+			)?
+		};
 
 		let rewards_not_yet_claimed = rewards_info.rewards_not_yet_claimed;
 		let rewards_already_claimed = rewards_info.rewards_already_claimed;
@@ -2600,7 +5481,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			ExistenceRequirement::KeepAlive,
 		)?;
 
-		RewardsInfo::<T>::insert(user.clone(), liquidity_asset_id, rewards_info_new.clone());
+		Pallet::<T>::insert_rewards_info(&user, liquidity_asset_id, rewards_info_new.clone());
 
 		Pallet::<T>::deposit_event(Event::RewardsClaimed(
 			user,
@@ -2617,6 +5498,16 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 			Error::<T>::PoolAlreadyPromoted,
 		);
 
+		// Rewards ratio of promoted pool starts from zero, checkpoints of activated liquidity
+		// taken against frozen ratio would not be valid anymore
+		if UnpromotedPoolsRewards::<T>::contains_key(liquidity_token_id) {
+			ensure!(
+				LiquidityMiningActivePoolV2::<T>::get(liquidity_token_id).is_zero(),
+				Error::<T>::LiquidityStillActivated,
+			);
+			UnpromotedPoolsRewards::<T>::remove(liquidity_token_id);
+		}
+
 		<T as Config>::PoolPromoteApi::promote_pool(liquidity_token_id);
 
 		Pallet::<T>::deposit_event(Event::PoolPromoted(liquidity_token_id));
@@ -2624,6 +5515,18 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	fn unpromote_pool(liquidity_token_id: TokenId) -> DispatchResult {
+		let pool_ratio = <T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_token_id)
+			.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
+
+		UnpromotedPoolsRewards::<T>::insert(liquidity_token_id, pool_ratio);
+		<T as Config>::PoolPromoteApi::unpromote_pool(liquidity_token_id);
+
+		Pallet::<T>::deposit_event(Event::PoolUnpromoted(liquidity_token_id));
+
+		Ok(())
+	}
+
 	fn activate_liquidity_v2(
 		user: T::AccountId,
 		liquidity_asset_id: Self::CurrencyId,
@@ -2662,10 +5565,7 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		liquidity_asset_id: Self::CurrencyId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		ensure!(
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id).is_some(),
-			Error::<T>::NotAPromotedPool
-		);
+		Self::pool_rewards_ratio(liquidity_asset_id)?;
 
 		let rewards_info: RewardInfo = Self::get_rewards_info(user.clone(), liquidity_asset_id);
 
@@ -2678,188 +5578,6 @@ impl<T: Config> XykFunctionsTrait<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
-	fn rewards_migrate_v1_to_v2(
-		user: AccountIdOf<T>,
-		liquidity_asset_id: TokenId,
-	) -> DispatchResult {
-		let mangata_id: TokenId = Self::native_token_id();
-
-		ensure!(
-			<T as Config>::PoolPromoteApi::get_pool_rewards(liquidity_asset_id).is_some(),
-			Error::<T>::NotAPromotedPool
-		);
-		//READING ALL NECESSARY STORAGE
-		let pool_ratio_current =
-			<T as Config>::PoolPromoteApi::get_pool_rewards_v2(liquidity_asset_id)
-				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?;
-		let available_rewards_for_pool: U256 = U256::from(
-			<T as Config>::PoolPromoteApi::get_pool_rewards(liquidity_asset_id)
-				.ok_or_else(|| DispatchError::from(Error::<T>::NotAPromotedPool))?,
-		);
-		let liquidity_assets_amount: Balance =
-			LiquidityMiningActiveUser::<T>::get((&user, &liquidity_asset_id));
-		let pool_activated_amount: Balance =
-			LiquidityMiningActivePool::<T>::get(&liquidity_asset_id);
-		let burned_not_claimed_rewards =
-			LiquidityMiningUserToBeClaimed::<T>::get((user.clone(), &liquidity_asset_id));
-		let already_claimed_rewards =
-			LiquidityMiningUserClaimed::<T>::get((user.clone(), &liquidity_asset_id));
-		let current_time: u32 = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>() /
-			T::RewardsDistributionPeriod::get();
-		let (
-			user_last_checkpoint,
-			user_cummulative_work_in_last_checkpoint,
-			user_missing_at_last_checkpoint,
-		) = LiquidityMiningUser::<T>::try_get((&user, &liquidity_asset_id))
-			.unwrap_or_else(|_| (current_time, U256::from(0), U256::from(0)));
-		let (
-			pool_last_checkpoint,
-			pool_cummulative_work_in_last_checkpoint,
-			pool_missing_at_last_checkpoint,
-		) = LiquidityMiningPool::<T>::try_get(&liquidity_asset_id)
-			.unwrap_or_else(|_| (current_time, U256::from(0), U256::from(0)));
-
-		//CALCULATE REWARDS
-		let time_passed_user = current_time - user_last_checkpoint;
-		let asymptote_u256_user: U256 = liquidity_assets_amount.into();
-		let cummulative_work_new_max_possible_user: U256 = asymptote_u256_user
-			.checked_mul(U256::from(time_passed_user))
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-		let base_user = user_missing_at_last_checkpoint
-			.checked_mul(U256::from(106))
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))? /
-			U256::from(6);
-		let q_pow_user = Self::calculate_q_pow(1.06, time_passed_user);
-		let cummulative_missing_new_user = base_user - base_user * REWARDS_PRECISION / q_pow_user;
-		let cummulative_work_new_user = cummulative_work_new_max_possible_user
-			.checked_sub(cummulative_missing_new_user)
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-		let work_user = user_cummulative_work_in_last_checkpoint + cummulative_work_new_user;
-		let time_passed_pool = current_time - pool_last_checkpoint;
-		let asymptote_u256_pool: U256 = pool_activated_amount.into();
-		let cummulative_work_new_max_possible_pool: U256 = asymptote_u256_pool
-			.checked_mul(U256::from(time_passed_pool))
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-		let base_pool = pool_missing_at_last_checkpoint
-			.checked_mul(U256::from(106))
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))? /
-			U256::from(6);
-		let q_pow_pool = Self::calculate_q_pow(1.06, time_passed_pool);
-		let cummulative_missing_new_pool = base_pool - base_pool * REWARDS_PRECISION / q_pow_pool;
-		let cummulative_work_new_pool = cummulative_work_new_max_possible_pool
-			.checked_sub(cummulative_missing_new_pool)
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-		let work_pool = pool_cummulative_work_in_last_checkpoint + cummulative_work_new_pool;
-
-		let mut current_rewards = Balance::try_from(0).unwrap();
-		if work_user != U256::from(0) && work_pool != U256::from(0) {
-			current_rewards = Balance::try_from(
-				available_rewards_for_pool
-					.checked_mul(work_user)
-					.ok_or_else(|| DispatchError::from(Error::<T>::NotEnoughtRewardsEarned))?
-					.checked_div(work_pool)
-					.ok_or_else(|| DispatchError::from(Error::<T>::DivisionByZero))?,
-			)
-			.map_err(|_| DispatchError::from(Error::<T>::NotEnoughtRewardsEarned))?;
-		}
-
-		let total_rewards = current_rewards
-			.checked_add(burned_not_claimed_rewards)
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?
-			.checked_sub(already_claimed_rewards)
-			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-
-		//TRANSFER ALL REWARDS TO USER
-		<T as Config>::Currency::transfer(
-			mangata_id.into(),
-			&<T as Config>::LiquidityMiningIssuanceVault::get(),
-			&user,
-			total_rewards.into(),
-			ExistenceRequirement::KeepAlive,
-		)?;
-
-		Pallet::<T>::deposit_event(Event::RewardsClaimed(
-			user.clone(),
-			liquidity_asset_id,
-			total_rewards,
-		));
-
-		// MODIFY REW1 POOL VALUES
-		let q_pow_pool = Self::calculate_q_pow(1.06, time_passed_pool);
-		let pool_missing_at_checkpoint: U256 =
-			pool_missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) / q_pow_pool;
-
-		let user_missing_at_checkpoint: U256 =
-			user_missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) / q_pow_user;
-
-		if pool_activated_amount != liquidity_assets_amount {
-			let pool_work_new = work_pool
-				.checked_sub(work_user)
-				.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-			let pool_missing_new = pool_missing_at_checkpoint
-				.checked_sub(user_missing_at_checkpoint)
-				.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
-			LiquidityMiningPool::<T>::insert(
-				&liquidity_asset_id,
-				(current_time, pool_work_new, pool_missing_new),
-			);
-
-			LiquidityMiningActivePool::<T>::try_mutate(liquidity_asset_id, |active_amount| {
-				if let Some(val) = active_amount.checked_sub(liquidity_assets_amount) {
-					*active_amount = val;
-					Ok(())
-				} else {
-					Err(())
-				}
-			})
-			.map_err(|_| DispatchError::from(Error::<T>::NotEnoughAssets))?;
-
-			<T as Config>::PoolPromoteApi::claim_pool_rewards(
-				liquidity_asset_id.into(),
-				total_rewards,
-			);
-		} else {
-			// IF LAST REMOVE POOL STORAGE
-			LiquidityMiningActivePool::<T>::remove(&liquidity_asset_id);
-			LiquidityMiningPool::<T>::remove(&liquidity_asset_id);
-			<T as Config>::PoolPromoteApi::claim_pool_rewards(
-				liquidity_asset_id.into(),
-				total_rewards,
-			);
-			// THERE WILL BE SOME REWARD LEFT
-		}
-
-		// REMOVE USER REW1 STORAGE
-		LiquidityMiningUser::<T>::remove((user.clone(), &liquidity_asset_id));
-		LiquidityMiningUserToBeClaimed::<T>::remove((user.clone(), &liquidity_asset_id));
-		LiquidityMiningActiveUser::<T>::remove((user.clone(), &liquidity_asset_id));
-		LiquidityMiningUserClaimed::<T>::remove((user.clone(), &liquidity_asset_id));
-
-		// ADD USER INFO TO REW2 STORAGE
-		let rewards_info_new: RewardInfo = RewardInfo {
-			activated_amount: liquidity_assets_amount,
-			rewards_not_yet_claimed: 0_u128,
-			rewards_already_claimed: 0_u128,
-			last_checkpoint: current_time,
-			pool_ratio_at_last_checkpoint: pool_ratio_current,
-			missing_at_last_checkpoint: user_missing_at_checkpoint,
-		};
-
-		LiquidityMiningActivePoolV2::<T>::try_mutate(liquidity_asset_id, |active_amount| {
-			if let Some(val) = active_amount.checked_add(liquidity_assets_amount) {
-				*active_amount = val;
-				Ok(())
-			} else {
-				Err(())
-			}
-		})
-		.map_err(|_| DispatchError::from(Error::<T>::LiquidityCheckpointMathError))?;
-
-		RewardsInfo::<T>::insert(user.clone(), liquidity_asset_id, rewards_info_new);
-
-		Ok(())
-	}
-
 	// This function has not been verified
 	fn get_tokens_required_for_minting(
 		liquidity_asset_id: Self::CurrencyId,
@@ -3095,6 +5813,16 @@ impl<T: Config> PoolCreateApi for Pallet<T> {
 	}
 }
 
+impl<T: Config> PoolPriceOracleTrait for Pallet<T> {
+	fn get_twap(
+		first_asset_id: TokenId,
+		second_asset_id: TokenId,
+		window: u32,
+	) -> Option<(Balance, Balance)> {
+		Pallet::<T>::get_twap(first_asset_id, second_asset_id, window).ok()
+	}
+}
+
 impl<T: Config> ActivedPoolQueryApi for Pallet<T> {
 	fn get_pool_activate_amount(liquidity_token_id: TokenId) -> Option<Balance> {
 		LiquidityMiningActivePoolV2::<T>::try_get(liquidity_token_id).ok()
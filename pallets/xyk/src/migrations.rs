@@ -0,0 +1,662 @@
+// Copyright (C) 2022 Mangata team
+
+use super::*;
+
+pub mod v1 {
+	//! Lazy migration of liquidity mining rewards v1 to rewards v2.
+	//!
+	//! Remaining v1 users are migrated in `on_idle` as long as the block has weight to spare,
+	//! users with activated liquidity first and then users with claimed rewards, which covers
+	//! users who only have rewards of burned liquidity left to claim.
+	//! Pending v1 rewards of user are paid out and its activated liquidity is carried over to
+	//! rewards v2 together with its progress on v1 rewards curve. If that fails, v1 storage of
+	//! user is left intact and the user is retried in a later block. Once there is no v1 user
+	//! left, the rest of v1 storage is cleared and storage version is bumped to 1.
+
+	use super::*;
+	use frame_support::{
+		storage::{with_transaction, TransactionOutcome},
+		storage_alias,
+		traits::GetStorageVersion,
+	};
+
+	#[cfg(feature = "try-runtime")]
+	use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+	#[storage_alias]
+	pub type LiquidityMiningUser<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_256,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		(u32, U256, U256),
+		ValueQuery,
+	>;
+
+	#[storage_alias]
+	pub type LiquidityMiningPool<T: Config> =
+		StorageMap<Pallet<T>, Blake2_256, TokenId, (u32, U256, U256), ValueQuery>;
+
+	#[storage_alias]
+	pub type LiquidityMiningUserToBeClaimed<T: Config> = StorageMap<
+		Pallet<T>,
+		Blake2_256,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		u128,
+		ValueQuery,
+	>;
+
+	#[storage_alias]
+	pub type LiquidityMiningActiveUser<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		u128,
+		ValueQuery,
+	>;
+
+	#[storage_alias]
+	pub type LiquidityMiningActivePool<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, TokenId, u128, ValueQuery>;
+
+	#[storage_alias]
+	pub type LiquidityMiningUserClaimed<T: Config> = StorageMap<
+		Pallet<T>,
+		Twox64Concat,
+		(<T as frame_system::Config>::AccountId, TokenId),
+		u128,
+		ValueQuery,
+	>;
+
+	/// Rewards v1 available for promoted pools, stored by issuance pallet
+	#[storage_alias]
+	pub type PromotedPoolsRewards =
+		StorageMap<Issuance, Twox64Concat, TokenId, Balance, ValueQuery>;
+
+	/// Raw storage key of v1 user visited last by `migrate_rewards_step`
+	#[storage_alias]
+	pub type RewardsV1MigrationCursor<T: Config> = StorageValue<Pallet<T>, Vec<u8>, OptionQuery>;
+
+	/// Migrates v1 users as long as `remaining_weight` allows, returns consumed weight
+	pub fn migrate_rewards_step<T: Config>(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut consumed_weight = db_weight.reads(1);
+		if consumed_weight.ref_time() > remaining_weight.ref_time() {
+			return Zero::zero()
+		}
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			return consumed_weight
+		}
+
+		let step_weight = <T as Config>::WeightInfo::rewards_v1_migration_step();
+		while consumed_weight.saturating_add(step_weight).ref_time() <= remaining_weight.ref_time()
+		{
+			match next_user::<T>() {
+				Some((user, liquidity_asset_id)) => {
+					// failed user is left in v1 storage and retried in the next pass
+					let _ = migrate_user::<T>(user, liquidity_asset_id);
+					consumed_weight = consumed_weight.saturating_add(step_weight);
+				},
+				None => {
+					RewardsV1MigrationCursor::<T>::kill();
+					consumed_weight = consumed_weight.saturating_add(db_weight.reads_writes(2, 1));
+					if LiquidityMiningActiveUser::<T>::iter_keys().next().is_some() ||
+						LiquidityMiningUserClaimed::<T>::iter_keys().next().is_some()
+					{
+						return consumed_weight
+					}
+					return consumed_weight.saturating_add(clear_storage::<T>(
+						remaining_weight.saturating_sub(consumed_weight),
+					))
+				},
+			}
+		}
+
+		consumed_weight
+	}
+
+	// Next v1 user after the cursor, users with activated liquidity are visited before users
+	// with claimed rewards
+	fn next_user<T: Config>() -> Option<(T::AccountId, TokenId)> {
+		let cursor = RewardsV1MigrationCursor::<T>::get();
+		let in_claimed = cursor.as_ref().map_or(false, |raw_key| {
+			!raw_key.starts_with(&LiquidityMiningActiveUser::<T>::final_prefix())
+		});
+
+		if !in_claimed {
+			let mut users = match cursor.clone() {
+				Some(raw_key) => LiquidityMiningActiveUser::<T>::iter_keys_from(raw_key),
+				None => LiquidityMiningActiveUser::<T>::iter_keys(),
+			};
+			if let Some(key) = users.next() {
+				RewardsV1MigrationCursor::<T>::put(LiquidityMiningActiveUser::<T>::hashed_key_for(
+					&key,
+				));
+				return Some(key)
+			}
+		}
+
+		let mut users = match cursor.filter(|_| in_claimed) {
+			Some(raw_key) => LiquidityMiningUserClaimed::<T>::iter_keys_from(raw_key),
+			None => LiquidityMiningUserClaimed::<T>::iter_keys(),
+		};
+		let key = users.next()?;
+		RewardsV1MigrationCursor::<T>::put(LiquidityMiningUserClaimed::<T>::hashed_key_for(&key));
+		Some(key)
+	}
+
+	/// Pays out v1 rewards of user and carries its activated liquidity over to rewards v2,
+	/// if that fails v1 storage of user is left intact so that it can be retried later
+	pub(crate) fn migrate_user<T: Config>(
+		user: T::AccountId,
+		liquidity_asset_id: TokenId,
+	) -> DispatchResult {
+		with_rollback(|| migrate_user_rewards::<T>(user.clone(), liquidity_asset_id)).map_err(|e| {
+			log!(
+				error,
+				"rewards v1 migration of {:?} in pool {} failed: {:?}, retrying later",
+				user,
+				liquidity_asset_id,
+				e
+			);
+			e
+		})
+	}
+
+	fn with_rollback(f: impl FnOnce() -> DispatchResult) -> DispatchResult {
+		with_transaction(|| match f() {
+			Ok(()) => TransactionOutcome::Commit(Ok(())),
+			Err(e) => TransactionOutcome::Rollback(Err(e)),
+		})
+	}
+
+	fn remove_user_storage<T: Config>(user: &T::AccountId, liquidity_asset_id: TokenId) {
+		LiquidityMiningUser::<T>::remove((user, liquidity_asset_id));
+		LiquidityMiningUserToBeClaimed::<T>::remove((user, liquidity_asset_id));
+		LiquidityMiningActiveUser::<T>::remove((user, liquidity_asset_id));
+		LiquidityMiningUserClaimed::<T>::remove((user, liquidity_asset_id));
+	}
+
+	fn migrate_user_rewards<T: Config>(
+		user: T::AccountId,
+		liquidity_asset_id: TokenId,
+	) -> DispatchResult {
+		let mangata_id: TokenId = Pallet::<T>::native_token_id();
+
+		//READING ALL NECESSARY STORAGE
+		let available_rewards_for_pool: U256 =
+			U256::from(PromotedPoolsRewards::get(liquidity_asset_id));
+		let liquidity_assets_amount: Balance =
+			LiquidityMiningActiveUser::<T>::get((&user, liquidity_asset_id));
+		let pool_activated_amount: Balance =
+			LiquidityMiningActivePool::<T>::get(liquidity_asset_id);
+		let burned_not_claimed_rewards =
+			LiquidityMiningUserToBeClaimed::<T>::get((&user, liquidity_asset_id));
+		let already_claimed_rewards =
+			LiquidityMiningUserClaimed::<T>::get((&user, liquidity_asset_id));
+		let current_time: u32 = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>() /
+			T::RewardsDistributionPeriod::get();
+		let (
+			user_last_checkpoint,
+			user_cummulative_work_in_last_checkpoint,
+			user_missing_at_last_checkpoint,
+		) = LiquidityMiningUser::<T>::try_get((&user, liquidity_asset_id))
+			.unwrap_or_else(|_| (current_time, U256::from(0), U256::from(0)));
+		let (
+			pool_last_checkpoint,
+			pool_cummulative_work_in_last_checkpoint,
+			pool_missing_at_last_checkpoint,
+		) = LiquidityMiningPool::<T>::try_get(liquidity_asset_id)
+			.unwrap_or_else(|_| (current_time, U256::from(0), U256::from(0)));
+
+		//CALCULATE REWARDS
+		let (work_user, q_pow_user) = calculate_work::<T>(
+			liquidity_assets_amount,
+			current_time,
+			user_last_checkpoint,
+			user_cummulative_work_in_last_checkpoint,
+			user_missing_at_last_checkpoint,
+		)?;
+		let (work_pool, q_pow_pool) = calculate_work::<T>(
+			pool_activated_amount,
+			current_time,
+			pool_last_checkpoint,
+			pool_cummulative_work_in_last_checkpoint,
+			pool_missing_at_last_checkpoint,
+		)?;
+
+		let mut current_rewards: Balance = 0;
+		if work_user != U256::from(0) && work_pool != U256::from(0) {
+			current_rewards = Balance::try_from(
+				available_rewards_for_pool
+					.checked_mul(work_user)
+					.ok_or_else(|| DispatchError::from(Error::<T>::NotEnoughtRewardsEarned))?
+					.checked_div(work_pool)
+					.ok_or_else(|| DispatchError::from(Error::<T>::DivisionByZero))?,
+			)
+			.map_err(|_| DispatchError::from(Error::<T>::NotEnoughtRewardsEarned))?;
+		}
+
+		let total_rewards = current_rewards
+			.checked_add(burned_not_claimed_rewards)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?
+			.checked_sub(already_claimed_rewards)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+
+		//TRANSFER ALL REWARDS TO USER
+		if !total_rewards.is_zero() {
+			<T as Config>::Currency::transfer(
+				mangata_id.into(),
+				&<T as Config>::LiquidityMiningIssuanceVault::get(),
+				&user,
+				total_rewards.into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Pallet::<T>::deposit_event(Event::RewardsClaimed(
+				user.clone(),
+				liquidity_asset_id,
+				total_rewards,
+			));
+		}
+
+		// MODIFY REW1 POOL VALUES
+		let pool_missing_at_checkpoint: U256 =
+			pool_missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) / q_pow_pool;
+
+		let user_missing_at_checkpoint: U256 =
+			user_missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) / q_pow_user;
+
+		if pool_activated_amount != liquidity_assets_amount {
+			let pool_work_new = work_pool
+				.checked_sub(work_user)
+				.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+			let pool_missing_new = pool_missing_at_checkpoint
+				.checked_sub(user_missing_at_checkpoint)
+				.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+			LiquidityMiningPool::<T>::insert(
+				liquidity_asset_id,
+				(current_time, pool_work_new, pool_missing_new),
+			);
+
+			LiquidityMiningActivePool::<T>::try_mutate(liquidity_asset_id, |active_amount| {
+				if let Some(val) = active_amount.checked_sub(liquidity_assets_amount) {
+					*active_amount = val;
+					Ok(())
+				} else {
+					Err(())
+				}
+			})
+			.map_err(|_| DispatchError::from(Error::<T>::NotEnoughAssets))?;
+		} else {
+			// IF LAST REMOVE POOL STORAGE
+			LiquidityMiningActivePool::<T>::remove(liquidity_asset_id);
+			LiquidityMiningPool::<T>::remove(liquidity_asset_id);
+			// THERE WILL BE SOME REWARD LEFT
+		}
+		PromotedPoolsRewards::mutate(liquidity_asset_id, |rewards| {
+			*rewards = rewards.saturating_sub(total_rewards)
+		});
+
+		// REMOVE USER REW1 STORAGE
+		remove_user_storage::<T>(&user, liquidity_asset_id);
+
+		// ADD USER INFO TO REW2 STORAGE
+		// users without activated liquidity are paid out even if pool is not promoted anymore
+		if !liquidity_assets_amount.is_zero() {
+			Pallet::<T>::checkpoint_activated_liquidity_v2(
+				user,
+				liquidity_asset_id,
+				liquidity_assets_amount,
+				user_missing_at_checkpoint,
+				Pallet::<T>::pool_rewards_ratio(liquidity_asset_id)?,
+			)?;
+		}
+
+		Ok(())
+	}
+
+	// Cumulative work of liquidity on v1 rewards curve at current time, returned together with
+	// q^time_passed
+	fn calculate_work<T: Config>(
+		liquidity_assets_amount: Balance,
+		current_time: u32,
+		last_checkpoint: u32,
+		cummulative_work_in_last_checkpoint: U256,
+		missing_at_last_checkpoint: U256,
+	) -> Result<(U256, U256), DispatchError> {
+		let time_passed = current_time
+			.checked_sub(last_checkpoint)
+			.ok_or_else(|| DispatchError::from(Error::<T>::PastTimeCalculation))?;
+		let cummulative_work_new_max_possible: U256 = U256::from(liquidity_assets_amount)
+			.checked_mul(U256::from(time_passed))
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+		let base = missing_at_last_checkpoint
+			.checked_mul(U256::from(106))
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))? /
+			U256::from(6);
+		let q_pow = U256::from(Pallet::<T>::calculate_q_pow(Q_V1, time_passed));
+		let cummulative_missing_new = base - base * U256::from(REWARDS_PRECISION) / q_pow;
+		let cummulative_work_new = cummulative_work_new_max_possible
+			.checked_sub(cummulative_missing_new)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+		let work = cummulative_work_in_last_checkpoint
+			.checked_add(cummulative_work_new)
+			.ok_or_else(|| DispatchError::from(Error::<T>::MathOverflow))?;
+
+		Ok((work, q_pow))
+	}
+
+	// Clears v1 storage left after all users are migrated, bumps storage version once done
+	fn clear_storage<T: Config>(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let key_weight = db_weight.reads_writes(1, 1).ref_time().max(1);
+		let mut limit: u32 = (remaining_weight.ref_time() / key_weight).saturated_into();
+		let mut consumed_weight: Weight = Zero::zero();
+
+		let clear_fns: [fn(u32, Option<&[u8]>) -> frame_support::sp_io::MultiRemovalResults; 5] = [
+			LiquidityMiningUser::<T>::clear,
+			LiquidityMiningUserToBeClaimed::<T>::clear,
+			LiquidityMiningPool::<T>::clear,
+			LiquidityMiningActivePool::<T>::clear,
+			PromotedPoolsRewards::clear,
+		];
+		for clear in clear_fns {
+			if limit == 0 {
+				return consumed_weight
+			}
+			let removal = clear(limit, None);
+			consumed_weight = consumed_weight.saturating_add(
+				db_weight.reads_writes(removal.loops.into(), removal.unique.into()),
+			);
+			if removal.maybe_cursor.is_some() {
+				return consumed_weight
+			}
+			limit = limit.saturating_sub(removal.loops);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log!(info, "rewards v1 migration finished");
+
+		consumed_weight.saturating_add(db_weight.writes(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn activated_per_pool<T: Config>() -> BTreeMap<TokenId, Balance> {
+		let mut activated = BTreeMap::new();
+		for ((_, liquidity_asset_id), amount) in LiquidityMiningActiveUser::<T>::iter() {
+			let pool_activated: &mut Balance = activated.entry(liquidity_asset_id).or_default();
+			*pool_activated = pool_activated.saturating_add(amount);
+		}
+		activated.retain(|_, amount| !amount.is_zero());
+		activated
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_upgrade<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			log!(info, "rewards v1 migration already finished");
+			return Ok(())
+		}
+
+		check_rewards_state::<T>()?;
+		log!(
+			info,
+			"rewards v1 migration pending for {} users with activated liquidity and {} with burned rewards to claim",
+			LiquidityMiningActiveUser::<T>::iter_keys().count(),
+			LiquidityMiningUserToBeClaimed::<T>::iter_values().filter(|rewards| !rewards.is_zero()).count()
+		);
+		Pallet::<T>::set_temp_storage(activated_per_pool::<T>(), "rewards_v1_activated");
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_upgrade<T: Config>() -> Result<(), &'static str> {
+		// users are migrated lazily after the upgrade, v1 state must be left intact by it
+		if let Some(activated) =
+			Pallet::<T>::get_temp_storage::<BTreeMap<TokenId, Balance>>("rewards_v1_activated")
+		{
+			ensure!(
+				activated == activated_per_pool::<T>(),
+				"rewards v1 activated liquidity changed during upgrade"
+			);
+		}
+
+		check_rewards_state::<T>()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn check_rewards_state<T: Config>() -> Result<(), &'static str> {
+		if Pallet::<T>::on_chain_storage_version() >= 1 {
+			ensure!(
+				LiquidityMiningActiveUser::<T>::iter_keys().next().is_none() &&
+					LiquidityMiningUserClaimed::<T>::iter_keys().next().is_none() &&
+					LiquidityMiningUserToBeClaimed::<T>::iter_values().next().is_none() &&
+					RewardsV1MigrationCursor::<T>::get().is_none(),
+				"rewards v1 users left after migration"
+			);
+			ensure!(
+				LiquidityMiningActivePool::<T>::iter_keys().next().is_none() &&
+					PromotedPoolsRewards::iter_keys().next().is_none(),
+				"rewards v1 pools left after migration"
+			);
+			return Ok(())
+		}
+
+		let mut pools_activated: BTreeMap<TokenId, Balance> =
+			LiquidityMiningActivePool::<T>::iter().collect();
+		pools_activated.retain(|_, amount| !amount.is_zero());
+		ensure!(
+			pools_activated == activated_per_pool::<T>(),
+			"rewards v1 activated liquidity of pools does not match their users"
+		);
+
+		// burned rewards are found only through users with activated liquidity or claimed
+		// rewards, the rest would be cleared together with v1 storage
+		let users: sp_std::collections::btree_set::BTreeSet<_> =
+			LiquidityMiningActiveUser::<T>::iter_keys()
+				.chain(LiquidityMiningUserClaimed::<T>::iter_keys())
+				.collect();
+		let reachable_to_be_claimed = users.into_iter().fold(0u128, |total, user| {
+			total.saturating_add(LiquidityMiningUserToBeClaimed::<T>::get(user))
+		});
+		let total_to_be_claimed = LiquidityMiningUserToBeClaimed::<T>::iter_values()
+			.fold(0u128, |total, rewards| total.saturating_add(rewards));
+		ensure!(
+			reachable_to_be_claimed == total_to_be_claimed,
+			"rewards v1 burned rewards of some users cannot be migrated"
+		);
+
+		Ok(())
+	}
+}
+
+pub mod v2 {
+	//! Index of accounts with rewards info by pool.
+	//!
+	//! Rewards info stored before the index is indexed in `on_idle` as long as the block has
+	//! weight to spare, once rewards v1 migration is finished. Once all rewards info is visited,
+	//! storage version is bumped to 2, reward curves cannot be changed until then.
+
+	use super::*;
+	use frame_support::{storage_alias, traits::GetStorageVersion};
+
+	/// Raw storage key of rewards info visited last by `index_rewards_accounts_step`
+	#[storage_alias]
+	pub type RewardsAccountsIndexCursor<T: Config> = StorageValue<Pallet<T>, Vec<u8>, OptionQuery>;
+
+	/// Indexes accounts with rewards info as long as `remaining_weight` allows, returns consumed
+	/// weight
+	pub fn index_rewards_accounts_step<T: Config>(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut consumed_weight = db_weight.reads(1);
+		if consumed_weight.ref_time() > remaining_weight.ref_time() {
+			return Zero::zero()
+		}
+		if Pallet::<T>::on_chain_storage_version() != 1 {
+			return consumed_weight
+		}
+
+		consumed_weight = consumed_weight.saturating_add(db_weight.reads(1));
+		let mut keys = match RewardsAccountsIndexCursor::<T>::get() {
+			Some(raw_key) => RewardsInfo::<T>::iter_keys_from(raw_key),
+			None => RewardsInfo::<T>::iter_keys(),
+		};
+		let entry_weight = db_weight.reads_writes(1, 1);
+		let mut last_visited = None;
+		while consumed_weight.saturating_add(entry_weight).ref_time() <= remaining_weight.ref_time()
+		{
+			consumed_weight = consumed_weight.saturating_add(entry_weight);
+			match keys.next() {
+				Some((user, liquidity_asset_id)) => {
+					RewardsInfoAccounts::<T>::insert(liquidity_asset_id, &user, ());
+					last_visited = Some((user, liquidity_asset_id));
+				},
+				None => {
+					RewardsAccountsIndexCursor::<T>::kill();
+					StorageVersion::new(2).put::<Pallet<T>>();
+					log!(info, "rewards accounts index finished");
+					return consumed_weight.saturating_add(db_weight.writes(2))
+				},
+			}
+		}
+
+		if let Some((user, liquidity_asset_id)) = last_visited {
+			RewardsAccountsIndexCursor::<T>::put(RewardsInfo::<T>::hashed_key_for(
+				user,
+				liquidity_asset_id,
+			));
+		}
+		consumed_weight.saturating_add(db_weight.writes(1))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn check_rewards_accounts<T: Config>() -> Result<(), &'static str> {
+		ensure!(
+			RewardsInfoAccounts::<T>::iter_keys().all(|(liquidity_asset_id, user)| {
+				RewardsInfo::<T>::contains_key(user, liquidity_asset_id)
+			}),
+			"rewards accounts index has account without rewards info"
+		);
+		if Pallet::<T>::on_chain_storage_version() >= 2 {
+			ensure!(
+				RewardsInfo::<T>::iter_keys().all(|(user, liquidity_asset_id)| {
+					RewardsInfoAccounts::<T>::contains_key(liquidity_asset_id, user)
+				}),
+				"rewards info missing in rewards accounts index"
+			);
+			ensure!(
+				RewardsAccountsIndexCursor::<T>::get().is_none(),
+				"rewards accounts index cursor left after index finished"
+			);
+		}
+		Ok(())
+	}
+}
+
+pub mod reward_curve {
+	//! Migration of rewards checkpoints to changed reward curve of a pool.
+	//!
+	//! Checkpoints taken before the change are checkpointed under previous curve at the time of
+	//! the change, either when used for the first time or in `on_idle` as long as the block has
+	//! weight to spare. Only rewards info of accounts indexed for the pool is visited, once all
+	//! of it is visited, pending migration of the pool is removed.
+
+	use super::*;
+
+	/// Migrates rewards info of pools with pending migration, one pool after another, as long as
+	/// `remaining_weight` allows, returns consumed weight
+	pub fn migrate_rewards_step<T: Config>(remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let mut consumed_weight: Weight = Zero::zero();
+		loop {
+			consumed_weight = consumed_weight.saturating_add(db_weight.reads(1));
+			if consumed_weight.ref_time() > remaining_weight.ref_time() {
+				return consumed_weight.saturating_sub(db_weight.reads(1))
+			}
+			let (liquidity_asset_id, migration) =
+				match PendingRewardCurveMigration::<T>::iter().next() {
+					Some(pending) => pending,
+					None => return consumed_weight,
+				};
+
+			let (pool_weight, finished) = migrate_pool_rewards::<T>(
+				liquidity_asset_id,
+				migration,
+				remaining_weight.saturating_sub(consumed_weight),
+			);
+			consumed_weight = consumed_weight.saturating_add(pool_weight);
+			if !finished {
+				return consumed_weight
+			}
+		}
+	}
+
+	// Migrates rewards info of accounts indexed for the pool as long as `remaining_weight`
+	// allows, returns consumed weight and whether migration of the pool is finished
+	fn migrate_pool_rewards<T: Config>(
+		liquidity_asset_id: TokenId,
+		mut migration: RewardCurveMigration<T::AccountId>,
+		remaining_weight: Weight,
+	) -> (Weight, bool) {
+		let db_weight = T::DbWeight::get();
+		let mut consumed_weight: Weight = Zero::zero();
+		let entry_weight = <T as Config>::WeightInfo::rewards_migrate_reward_curve();
+		let mut users = match migration.last_visited.clone() {
+			Some(user) => RewardsInfoAccounts::<T>::iter_key_prefix_from(
+				liquidity_asset_id,
+				RewardsInfoAccounts::<T>::hashed_key_for(liquidity_asset_id, user),
+			),
+			None => RewardsInfoAccounts::<T>::iter_key_prefix(liquidity_asset_id),
+		};
+		while consumed_weight.saturating_add(entry_weight).ref_time() <= remaining_weight.ref_time()
+		{
+			consumed_weight = consumed_weight.saturating_add(entry_weight);
+			match users.next() {
+				Some(user) => {
+					let rewards_info = RewardsInfo::<T>::get(&user, liquidity_asset_id);
+					migrate_rewards_info::<T>(liquidity_asset_id, &migration, &user, rewards_info);
+					migration.last_visited = Some(user);
+				},
+				None => {
+					PendingRewardCurveMigration::<T>::remove(liquidity_asset_id);
+					log!(info, "reward curve migration of pool {} finished", liquidity_asset_id);
+					return (consumed_weight.saturating_add(db_weight.writes(1)), true)
+				},
+			}
+		}
+
+		PendingRewardCurveMigration::<T>::insert(liquidity_asset_id, migration);
+		(consumed_weight.saturating_add(db_weight.writes(1)), false)
+	}
+
+	// Checkpoints rewards info under previous reward curve, if that fails the checkpoint is
+	// moved to the change of curve without rewards for the time before it so that migration
+	// can make progress
+	fn migrate_rewards_info<T: Config>(
+		liquidity_asset_id: TokenId,
+		migration: &RewardCurveMigration<T::AccountId>,
+		user: &T::AccountId,
+		rewards_info: RewardInfo,
+	) {
+		let migrated =
+			Pallet::<T>::rewards_info_under_current_curve(liquidity_asset_id, rewards_info.clone())
+				.unwrap_or_else(|e| {
+					log!(
+						error,
+						"reward curve migration of {:?} in pool {} failed: {:?}, rewards since last checkpoint are forfeited",
+						user,
+						liquidity_asset_id,
+						e
+					);
+					RewardInfo {
+						last_checkpoint: Pallet::<T>::get_reward_curve(liquidity_asset_id)
+							.start_time,
+						pool_ratio_at_last_checkpoint: migration.pool_ratio_at_change,
+						..rewards_info.clone()
+					}
+				});
+		if migrated != rewards_info {
+			RewardsInfo::<T>::insert(user, liquidity_asset_id, migrated);
+		}
+	}
+}
@@ -96,35 +96,25 @@ impl PoolPromoteApi for MockPromotedPoolApi {
 
 	fn unpromote_pool(liquidity_token_id: TokenId) -> bool {
 		let mut pools = PROMOTED_POOLS.lock().unwrap();
-		if pools.contains_key(&liquidity_token_id) {
-			false
-		} else {
-			pools.insert(liquidity_token_id, 0_u128.into());
-			true
-		}
+		pools.remove(&liquidity_token_id).is_some()
 	}
 
-	fn get_pool_rewards(liquidity_token_id: TokenId) -> Option<Balance> {
+	fn get_pool_rewards_v2(liquidity_token_id: TokenId) -> Option<sp_core::U256> {
 		let pools = PROMOTED_POOLS.lock().unwrap();
-		pools.get(&liquidity_token_id).map(|x| (*x).try_into().unwrap())
+		pools.get(&liquidity_token_id).map(|x| *x)
 	}
 
-	fn claim_pool_rewards(liquidity_token_id: TokenId, amount: Balance) -> bool {
-		let mut pools = PROMOTED_POOLS.lock().unwrap();
-		let rewards: Balance =
-			pools.get(&liquidity_token_id).map(|x| (*x).try_into().unwrap()).unwrap();
-		let new_rewards = U256::from(rewards - amount);
-		pools.insert(liquidity_token_id, new_rewards);
-		true
+	fn get_pool_weight(liquidity_token_id: TokenId) -> Option<u8> {
+		let pools = PROMOTED_POOLS.lock().unwrap();
+		pools.get(&liquidity_token_id).map(|_| pallet_issuance::DEFAULT_POOL_WEIGHT)
 	}
 
-	fn get_pool_rewards_v2(liquidity_token_id: TokenId) -> Option<sp_core::U256> {
-		let pools = PROMOTED_POOLS.lock().unwrap();
-		pools.get(&liquidity_token_id).map(|x| *x)
+	fn get_total_pools_weight() -> u32 {
+		PROMOTED_POOLS.lock().unwrap().len() as u32 * pallet_issuance::DEFAULT_POOL_WEIGHT as u32
 	}
 
-	fn len() -> usize {
-		PROMOTED_POOLS.lock().unwrap().len()
+	fn get_pool_rewards_history(_liquidity_token_id: TokenId) -> Vec<(u32, Balance)> {
+		Vec::new()
 	}
 
 	fn len_v2() -> usize {
@@ -293,19 +283,6 @@ impl Contains<(TokenId, TokenId)> for DummyBlacklistedPool {
 	}
 }
 
-pub struct RewardsMigrateAccountProvider<T: frame_system::Config>(PhantomData<T>);
-
-impl<T: frame_system::Config> Get<T::AccountId> for RewardsMigrateAccountProvider<T> {
-	fn get() -> T::AccountId {
-		let account32: sp_runtime::AccountId32 =
-			hex_literal::hex!["0e33df23356eb2e9e3baf0e8a5faae15bc70a6a5cce88f651a9faf6e8e937324"]
-				.into();
-		let mut init_account32 = sp_runtime::AccountId32::as_ref(&account32);
-		let init_account = T::AccountId::decode(&mut init_account32).unwrap();
-		init_account
-	}
-}
-
 pub struct MockAssetRegister;
 
 lazy_static::lazy_static! {
@@ -361,8 +338,8 @@ impl Config for Test {
 	type VestingProvider = Vesting;
 	type DisallowedPools = DummyBlacklistedPool;
 	type DisabledTokens = Nothing;
-	type RewardsMigrateAccount = RewardsMigrateAccountProvider<Self>;
 	type AssetMetadataMutation = MockAssetRegister;
+	type Call = Call;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -383,8 +360,8 @@ impl Config for Test {
 	type VestingProvider = Vesting;
 	type DisallowedPools = Nothing;
 	type DisabledTokens = Nothing;
-	type RewardsMigrateAccount = RewardsMigrateAccountProvider<Self>;
 	type AssetMetadataMutation = MockAssetRegister;
+	type Call = Call;
 }
 
 pub struct TokensActivationPassthrough<T: Config>(PhantomData<T>);
@@ -465,6 +442,10 @@ impl<T: Config> Pallet<T> {
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut ext: sp_io::TestExternalities =
 		system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
-	ext.execute_with(|| System::set_block_number(1));
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// storage version is set by genesis of the pallet on a new chain
+		StorageVersion::new(2).put::<XykStorage>();
+	});
 	ext
 }
@@ -274,21 +274,21 @@ fn liquidity_rewards_three_users_mint_W() {
 			.insert(4, U256::from(u128::MAX) * 100000 / 10000);
 		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 14704);
 
-		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(200);
 		MockPromotedPoolApi::instance()
 			.lock()
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 150000 / 10000);
-		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(240);
 		MockPromotedPoolApi::instance()
 			.lock()
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 163300 / 10000);
-		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(400);
 		MockPromotedPoolApi::instance()
@@ -335,21 +335,21 @@ fn liquidity_rewards_three_users_burn_W() {
 			.insert(4, U256::from(u128::MAX) * 100000 / 10000);
 		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 14704);
 
-		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(200);
 		MockPromotedPoolApi::instance()
 			.lock()
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 150000 / 10000);
-		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(240);
 		MockPromotedPoolApi::instance()
 			.lock()
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 163300 / 10000);
-		XykStorage::burn_liquidity(Origin::signed(4), 0, 1, 5000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(4), 0, 1, 5000, 0, 0).unwrap();
 
 		System::set_block_number(400);
 		MockPromotedPoolApi::instance()
@@ -512,14 +512,14 @@ fn liquidity_rewards_work_after_burn_W() {
 			.insert(4, U256::from(u128::MAX) * 100000 / 10000);
 		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 14704);
 
-		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(200);
 		MockPromotedPoolApi::instance()
 			.lock()
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 150000 / 10000);
-		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(240);
 		MockPromotedPoolApi::instance()
@@ -527,7 +527,7 @@ fn liquidity_rewards_work_after_burn_W() {
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * 163300 / 10000);
 
-		XykStorage::burn_liquidity(Origin::signed(4), 0, 1, 10000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(4), 0, 1, 10000, 0, 0).unwrap();
 
 		System::set_block_number(400);
 		MockPromotedPoolApi::instance()
@@ -537,7 +537,7 @@ fn liquidity_rewards_work_after_burn_W() {
 
 		assert_eq!(XykStorage::calculate_rewards_amount_v2(4, 4).unwrap(), 946);
 
-		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 20000, 20010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 0, 1, 20000, 20010, 0).unwrap();
 
 		System::set_block_number(500);
 		MockPromotedPoolApi::instance()
@@ -954,6 +954,7 @@ fn multi() {
 			4,
 			500000000000000000000000,
 			5000000000000000000000000,
+			0,
 		)
 		.unwrap();
 
@@ -968,7 +969,8 @@ fn multi() {
 		assert_eq!(XykStorage::balance(1, XykStorage::account_id()), 1500000000000000000000000); // amount of asset 1 in vault acc after creating pool
 		assert_eq!(XykStorage::balance(4, XykStorage::account_id()), 750000000000000000000001); // amount of asset 2 in vault acc after creating pool
 
-		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 225000000000000000000000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 225000000000000000000000, 0, 0)
+			.unwrap();
 
 		assert_eq!(
 			XykStorage::asset_pool((1, 4)),
@@ -981,7 +983,8 @@ fn multi() {
 		assert_eq!(XykStorage::balance(1, XykStorage::account_id()), 1200000000000000000000000); // amount of asset 1 in vault acc after creating pool
 		assert_eq!(XykStorage::balance(4, XykStorage::account_id()), 600000000000000000000001); // amount of asset 2 in vault acc after creating pool
 
-		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 225000000000000000000000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 225000000000000000000000, 0, 0)
+			.unwrap();
 
 		assert_eq!(
 			XykStorage::asset_pool((1, 4)),
@@ -1000,6 +1003,7 @@ fn multi() {
 			4,
 			1000000000000000000000000,
 			10000000000000000000000000,
+			0,
 		)
 		.unwrap();
 
@@ -1154,70 +1158,2023 @@ fn sell_W() {
 	});
 }
 
+#[test]
+fn sell_with_deadline_W() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(10);
+		XykStorage::sell_asset_with_deadline(Origin::signed(2), 1, 4, 20000000000000000000, 0, 10)
+			.unwrap();
+
+		assert_eq!(XykStorage::balance(1, 2), 940000000000000000000);
+		assert_eq!(XykStorage::balance(4, 2), 959959959959959959959);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (59979999999999999998, 40040040040040040041));
+	});
+}
+
+#[test]
+fn with_deadline_N_deadline_exceeded() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(11);
+
+		assert_err!(
+			XykStorage::sell_asset_with_deadline(
+				Origin::signed(2),
+				1,
+				4,
+				20000000000000000000,
+				0,
+				10
+			),
+			Error::<Test>::DeadlineExceeded,
+		);
+		assert_err!(
+			XykStorage::buy_asset_with_deadline(
+				Origin::signed(2),
+				1,
+				4,
+				10000000000000000000,
+				u128::MAX,
+				10
+			),
+			Error::<Test>::DeadlineExceeded,
+		);
+		assert_err!(
+			XykStorage::mint_liquidity_with_deadline(
+				Origin::signed(2),
+				1,
+				4,
+				20000000000000000000,
+				u128::MAX,
+				0,
+				10
+			),
+			Error::<Test>::DeadlineExceeded,
+		);
+		assert_err!(
+			XykStorage::burn_liquidity_with_deadline(
+				Origin::signed(2),
+				1,
+				4,
+				25000000000000000000,
+				0,
+				0,
+				10
+			),
+			Error::<Test>::DeadlineExceeded,
+		);
+
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40000000000000000000, 60000000000000000000));
+		assert_eq!(XykStorage::balance(1, 2), 960000000000000000000);
+		assert_eq!(XykStorage::balance(4, 2), 940000000000000000000);
+		assert_eq!(XykStorage::balance(5, 2), 50000000000000000000);
+	});
+}
+
 #[test]
 fn sell_W_other_way() {
 	new_test_ext().execute_with(|| {
-		initialize();
+		initialize();
+
+		XykStorage::sell_asset(Origin::signed(2), 4, 1, 30000000000000000000, 0).unwrap(); // selling 30000000000000000000 assetId 1 of pool 0 1
+
+		assert_eq!(XykStorage::balance(1, 2), 973306639973306639973); // amount of asset 0 in user acc after selling
+		assert_eq!(XykStorage::balance(4, 2), 910000000000000000000); // amount of asset 1 in user acc after buying
+															  // assert_eq!(XykStorage::asset_pool((1, 2)), 26684462240017795575); // amount of asset 0 in pool map
+															  // assert_eq!(XykStorage::asset_pool((1, 0)), 90000000000000000000); // amount of asset 1 in pool map
+		assert_eq!(XykStorage::asset_pool((1, 4)), (26693360026693360027, 89969999999999999998));
+		assert_eq!(XykStorage::balance(1, 2), 973306639973306639973); // amount of asset 0 on account 2
+		assert_eq!(XykStorage::balance(4, 2), 910000000000000000000); // amount of asset 1 on account 2
+		assert_eq!(XykStorage::balance(1, XykStorage::account_id()), 26693360026693360027); // amount of asset 0 in vault acc after creating pool
+		assert_eq!(XykStorage::balance(4, XykStorage::account_id()), 89969999999999999998);
+		// amount of asset 1 in vault acc after creating pool
+	});
+}
+
+#[test]
+fn sell_N_no_such_pool() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		assert_err!(
+			XykStorage::sell_asset(Origin::signed(2), 0, 10, 250000, 0),
+			Error::<Test>::NoSuchPool,
+		); // selling 250000 assetId 0 of pool 0 10 (only pool 0 1 exists)
+	});
+}
+#[test]
+fn sell_N_not_enough_selling_assset() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		assert_err!(
+			XykStorage::sell_asset(Origin::signed(2), 1, 4, 1000000000000000000000, 0),
+			Error::<Test>::NotEnoughAssets,
+		); // selling 1000000000000000000000 assetId 0 of pool 0 1 (user has only 960000000000000000000)
+	});
+}
+
+#[test]
+fn sell_N_insufficient_output_amount() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		assert_err!(
+			XykStorage::sell_asset(Origin::signed(2), 1, 4, 250000, 500000),
+			Error::<Test>::InsufficientOutputAmount,
+		); // selling 250000 assetId 0 of pool 0 1, by the formula user should get 166333 asset 1, but is requesting 500000
+	});
+}
+
+#[test]
+fn sell_N_zero_amount() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		assert_err!(
+			XykStorage::sell_asset(Origin::signed(2), 1, 4, 0, 500000),
+			Error::<Test>::ZeroAmount,
+		); // selling 0 assetId 0 of pool 0 1
+	});
+}
+
+#[test]
+fn multiswap_sell_W_same_as_sequential_sells() {
+	let (sequential_balances, sequential_pools) = new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let bought_amount = XykStorage::calculate_sell_price_id(4, 1, 50000000000000).unwrap();
+		XykStorage::sell_asset(Origin::signed(2), 4, 1, 50000000000000, 0).unwrap();
+		XykStorage::sell_asset(Origin::signed(2), 1, 0, bought_amount, 0).unwrap();
+
+		(
+			(XykStorage::balance(4, 2), XykStorage::balance(1, 2), XykStorage::balance(0, 2)),
+			(XykStorage::asset_pool((0, 1)), XykStorage::asset_pool((1, 4))),
+		)
+	});
+
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		XykStorage::multiswap_sell_asset(Origin::signed(2), vec![4, 1, 0], 50000000000000, 0)
+			.unwrap();
+
+		assert_eq!(
+			(XykStorage::balance(4, 2), XykStorage::balance(1, 2), XykStorage::balance(0, 2)),
+			sequential_balances
+		);
+		assert_eq!(
+			(XykStorage::asset_pool((0, 1)), XykStorage::asset_pool((1, 4))),
+			sequential_pools
+		);
+		// intermediate token is fully routed to the next hop
+		assert_eq!(XykStorage::balance(1, 2), 1000000000000000 - 200000000000000);
+	});
+}
+
+#[test]
+fn multiswap_sell_N_insufficient_output_amount_reverts_all_hops() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let expected_amount_out = XykStorage::calculate_sell_price_id(
+			1,
+			0,
+			XykStorage::calculate_sell_price_id(4, 1, 50000000000000).unwrap(),
+		)
+		.unwrap();
+
+		assert_err!(
+			XykStorage::multiswap_sell_asset(
+				Origin::signed(2),
+				vec![4, 1, 0],
+				50000000000000,
+				expected_amount_out + 1
+			),
+			Error::<Test>::InsufficientOutputAmount,
+		);
+
+		assert_eq!(XykStorage::balance(4, 2), 900000000000000);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (100000000000000, 100000000000000));
+		assert_eq!(XykStorage::asset_pool((0, 1)), (100000000000000, 100000000000000));
+	});
+}
+
+#[test]
+fn multiswap_sell_N_invalid_path() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+
+		assert_err!(
+			XykStorage::multiswap_sell_asset(Origin::signed(2), vec![4], 50000000000000, 0),
+			Error::<Test>::MultiswapPathTooShort,
+		);
+		assert_err!(
+			XykStorage::multiswap_sell_asset(
+				Origin::signed(2),
+				vec![4, 1, 1, 0],
+				50000000000000,
+				0
+			),
+			Error::<Test>::MultiswapSameTokenConsecutively,
+		);
+		assert_err!(
+			XykStorage::multiswap_sell_asset(Origin::signed(2), vec![4, 0], 50000000000000, 0),
+			Error::<Test>::NoSuchPool,
+		);
+		assert_err!(
+			XykStorage::multiswap_sell_asset(
+				Origin::signed(2),
+				vec![4, 1, 0, 1, 0, 1],
+				50000000000000,
+				0
+			),
+			Error::<Test>::MultiswapPathTooLong,
+		);
+	});
+}
+
+#[test]
+fn multiswap_buy_W() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let bought_amount = 10000000000000;
+		let intermediate_amount = XykStorage::calculate_buy_price_id(1, 0, bought_amount).unwrap();
+		let sold_amount = XykStorage::calculate_buy_price_id(4, 1, intermediate_amount).unwrap();
+
+		XykStorage::multiswap_buy_asset(
+			Origin::signed(2),
+			vec![4, 1, 0],
+			bought_amount,
+			sold_amount,
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::balance(4, 2), 900000000000000 - sold_amount);
+		assert_eq!(XykStorage::balance(1, 2), 800000000000000);
+		assert_eq!(XykStorage::balance(0, 2), 900000000000000 + bought_amount);
+	});
+}
+
+#[test]
+fn multiswap_buy_N_insufficient_input_amount() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let bought_amount = 10000000000000;
+		let intermediate_amount = XykStorage::calculate_buy_price_id(1, 0, bought_amount).unwrap();
+		let sold_amount = XykStorage::calculate_buy_price_id(4, 1, intermediate_amount).unwrap();
+
+		assert_err!(
+			XykStorage::multiswap_buy_asset(
+				Origin::signed(2),
+				vec![4, 1, 0],
+				bought_amount,
+				sold_amount - 1
+			),
+			Error::<Test>::InsufficientInputAmount,
+		);
+		assert_eq!(XykStorage::balance(4, 2), 900000000000000);
+		assert_eq!(XykStorage::balance(0, 2), 900000000000000);
+	});
+}
+
+#[test]
+fn multiswap_buy_N_same_pool_twice() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+
+		assert_err!(
+			XykStorage::multiswap_buy_asset(Origin::signed(2), vec![4, 1, 4], 1000, 1000000),
+			Error::<Test>::MultiswapBuyRepeatedPool,
+		);
+	});
+}
+
+#[test]
+fn multiswap_buy_W_path_crossing_mga_pool_used_for_fee_settlement() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		XykStorage::create_pool(Origin::signed(2), 4, 100000000000000, 0, 100000000000000).unwrap();
+		let bought_amount = 10000000000000;
+		let last_hop_amount = XykStorage::calculate_buy_price_id(0, 4, bought_amount).unwrap();
+		let intermediate_amount =
+			XykStorage::calculate_buy_price_id(1, 0, last_hop_amount).unwrap();
+		let sold_amount = XykStorage::calculate_buy_price_id(4, 1, intermediate_amount).unwrap();
+
+		// fees of the first hop are settled through pool (4,0) that is also the last hop
+		XykStorage::multiswap_buy_asset(
+			Origin::signed(2),
+			vec![4, 1, 0, 4],
+			bought_amount,
+			sold_amount,
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::balance(4, 2), 800000000000000 - sold_amount + bought_amount);
+		assert_eq!(XykStorage::balance(1, 2), 800000000000000);
+		assert_eq!(XykStorage::balance(0, 2), 800000000000000);
+	});
+}
+
+#[test]
+fn multiswap_sell_trait_fn_N_reverts_all_hops() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+
+		assert_err!(
+			<XykStorage as XykFunctionsTrait<AccountId>>::multiswap_sell_asset(
+				2,
+				vec![4, 1, 0],
+				50000000000000,
+				u128::MAX
+			),
+			Error::<Test>::InsufficientOutputAmount,
+		);
+		assert_eq!(XykStorage::balance(4, 2), 900000000000000);
+		assert_eq!(XykStorage::balance(1, 2), 800000000000000);
+		assert_eq!(XykStorage::balance(0, 2), 900000000000000);
+	});
+}
+
+#[test]
+fn get_best_sell_path_W_multihop() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let expected_amount = XykStorage::calculate_sell_price_id(
+			1,
+			0,
+			XykStorage::calculate_sell_price_id(4, 1, 50000000000000).unwrap(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			XykStorage::get_best_sell_path(4, 0, 50000000000000).unwrap(),
+			(vec![4, 1, 0], expected_amount)
+		);
+		assert_eq!(
+			XykStorage::calculate_sell_price_path(&[4, 1, 0], 50000000000000).unwrap(),
+			expected_amount
+		);
+	});
+}
+
+#[test]
+fn get_best_sell_path_W_prefers_higher_output() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		// shallow direct pool, routing through token 1 yields more
+		XykStorage::create_pool(Origin::signed(2), 0, 1000000, 4, 1000000).unwrap();
+		assert_eq!(XykStorage::get_best_sell_path(4, 0, 50000000000000).unwrap().0, vec![4, 1, 0]);
+	});
+
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		// direct pool as deep as the others, paying fee only once yields more
+		XykStorage::create_pool(Origin::signed(2), 0, 100000000000000, 4, 100000000000000).unwrap();
+		assert_eq!(
+			XykStorage::get_best_sell_path(4, 0, 50000000000000).unwrap(),
+			(vec![4, 0], XykStorage::calculate_sell_price_id(4, 0, 50000000000000).unwrap())
+		);
+	});
+}
+
+#[test]
+fn get_best_buy_path_W() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		let expected_amount = XykStorage::calculate_buy_price_id(
+			4,
+			1,
+			XykStorage::calculate_buy_price_id(1, 0, 10000000000000).unwrap(),
+		)
+		.unwrap();
+
+		assert_eq!(
+			XykStorage::get_best_buy_path(4, 0, 10000000000000).unwrap(),
+			(vec![4, 1, 0], expected_amount)
+		);
+
+		// quoted path and amount are exactly what multiswap charges
+		XykStorage::multiswap_buy_asset(
+			Origin::signed(2),
+			vec![4, 1, 0],
+			10000000000000,
+			expected_amount,
+		)
+		.unwrap();
+		assert_eq!(XykStorage::balance(4, 2), 900000000000000 - expected_amount);
+	});
+}
+
+#[test]
+fn get_best_path_N_no_path() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+
+		assert_err!(XykStorage::get_best_sell_path(4, 2, 1000), Error::<Test>::NoSuchPool);
+		assert_err!(XykStorage::get_best_buy_path(4, 2, 1000), Error::<Test>::NoSuchPool);
+		assert_err!(XykStorage::get_best_sell_path(4, 4, 1000), Error::<Test>::SameAsset);
+		// not enough reserve in any pool to buy requested amount
+		assert_err!(
+			XykStorage::get_best_buy_path(4, 0, 100000000000000),
+			Error::<Test>::NoSuchPool
+		);
+	});
+}
+
+#[test]
+fn set_pool_fee_W_sell_uses_pool_fee() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let pool_fee = PoolFeeInfo {
+			pool_fee_percentage: 100,
+			treasury_fee_percentage: 0,
+			buy_and_burn_fee_percentage: 0,
+		};
+		XykStorage::set_pool_fee(Origin::root(), 5, Some(pool_fee)).unwrap();
+		assert_eq!(XykStorage::pool_fees(5), Some(pool_fee));
+		assert_eq!(XykStorage::get_pool_fee(5), pool_fee);
+
+		assert_eq!(
+			XykStorage::calculate_sell_price_id(1, 4, 20000000000000000000).unwrap(),
+			19866220735785953177
+		);
+		// reserves based calculation keeps using default fee
+		assert_eq!(
+			XykStorage::calculate_sell_price(
+				40000000000000000000,
+				60000000000000000000,
+				20000000000000000000
+			)
+			.unwrap(),
+			19959959959959959959
+		);
+
+		XykStorage::sell_asset(Origin::signed(2), 1, 4, 20000000000000000000, 0).unwrap();
+
+		assert_eq!(XykStorage::balance(1, 2), 940000000000000000000);
+		assert_eq!(XykStorage::balance(4, 2), 959866220735785953177);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (59999999999999999998, 40133779264214046823));
+		let pool_fee_set_event =
+			crate::mock::Event::XykStorage(crate::Event::<Test>::PoolFeeSet(5, Some(pool_fee)));
+		assert!(System::events().iter().any(|record| record.event == pool_fee_set_event));
+	});
+}
+
+#[test]
+fn set_pool_fee_W_reset_to_default() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let pool_fee = PoolFeeInfo {
+			pool_fee_percentage: 100,
+			treasury_fee_percentage: 0,
+			buy_and_burn_fee_percentage: 0,
+		};
+		XykStorage::set_pool_fee(Origin::root(), 5, Some(pool_fee)).unwrap();
+		XykStorage::set_pool_fee(Origin::root(), 5, None).unwrap();
+		assert_eq!(XykStorage::pool_fees(5), None);
+
+		XykStorage::sell_asset(Origin::signed(2), 1, 4, 20000000000000000000, 0).unwrap();
+		assert_eq!(XykStorage::balance(4, 2), 959959959959959959959);
+	});
+}
+
+#[test]
+fn set_pool_fee_W_buy_uses_pool_fee() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let pool_fee = PoolFeeInfo {
+			pool_fee_percentage: 50,
+			treasury_fee_percentage: 10,
+			buy_and_burn_fee_percentage: 10,
+		};
+		XykStorage::set_pool_fee(Origin::root(), 5, Some(pool_fee)).unwrap();
+
+		let expected_sold_amount = XykStorage::calculate_buy_price_with_fee(
+			40000000000000000000,
+			60000000000000000000,
+			30000000000000000000,
+			70,
+		)
+		.unwrap();
+		assert_eq!(
+			XykStorage::calculate_buy_price_id(1, 4, 30000000000000000000).unwrap(),
+			expected_sold_amount
+		);
+		assert!(
+			expected_sold_amount >
+				XykStorage::calculate_buy_price(
+					40000000000000000000,
+					60000000000000000000,
+					30000000000000000000
+				)
+				.unwrap()
+		);
+
+		XykStorage::buy_asset(Origin::signed(2), 1, 4, 30000000000000000000, u128::MAX).unwrap();
+		assert_eq!(XykStorage::balance(1, 2), 960000000000000000000 - expected_sold_amount);
+		assert_eq!(XykStorage::balance(4, 2), 970000000000000000000);
+	});
+}
+
+#[test]
+fn set_pool_fee_N() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let pool_fee = PoolFeeInfo {
+			pool_fee_percentage: 5000,
+			treasury_fee_percentage: 2500,
+			buy_and_burn_fee_percentage: 2500,
+		};
+
+		assert_err!(
+			XykStorage::set_pool_fee(Origin::signed(2), 5, None),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_err!(XykStorage::set_pool_fee(Origin::root(), 6, None), Error::<Test>::NoSuchPool);
+		assert_err!(
+			XykStorage::set_pool_fee(Origin::root(), 5, Some(pool_fee)),
+			Error::<Test>::InvalidPoolFee
+		);
+	});
+}
+
+#[test]
+fn create_stable_pool_W() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+		XykStorage::create_stable_pool(
+			Origin::signed(2),
+			2,
+			100000000000000,
+			3,
+			100000000000000,
+			100,
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::asset_pool((2, 3)), (100000000000000, 100000000000000));
+		assert_eq!(XykStorage::get_pool_kind(8), PoolKind::StableSwap { amplification: 100 });
+		assert_eq!(XykStorage::get_pool_kind(7), PoolKind::ConstantProduct);
+		assert_eq!(XykStorage::balance(8, 2), 100000000000000);
+
+		let stable_pool_created_event =
+			crate::mock::Event::XykStorage(crate::Event::<Test>::StableSwapPoolCreated(8, 100));
+		assert!(System::events().iter().any(|record| record.event == stable_pool_created_event));
+
+		// burning whole pool removes its kind
+		XykStorage::burn_liquidity(Origin::signed(2), 2, 3, 100000000000000, 0, 0).unwrap();
+		assert_eq!(XykStorage::pool_kinds(8), None);
+	});
+}
+
+#[test]
+fn create_stable_pool_N_invalid_amplification() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		assert_err!(
+			XykStorage::create_stable_pool(
+				Origin::signed(2),
+				2,
+				100000000000000,
+				3,
+				100000000000000,
+				0
+			),
+			Error::<Test>::InvalidAmplification,
+		);
+		assert_err!(
+			XykStorage::create_stable_pool(
+				Origin::signed(2),
+				2,
+				100000000000000,
+				3,
+				100000000000000,
+				MAX_AMPLIFICATION + 1
+			),
+			Error::<Test>::InvalidAmplification,
+		);
+		assert!(XykStorage::liquidity_asset((2, 3)).is_none());
+	});
+}
+
+#[test]
+fn stable_pool_sell_W() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		XykStorage::create_stable_pool(
+			Origin::signed(2),
+			2,
+			100000000000000,
+			3,
+			100000000000000,
+			100,
+		)
+		.unwrap();
+
+		// constant product pool of same reserves would return 987158034397
+		assert_eq!(
+			XykStorage::calculate_sell_price(100000000000000, 100000000000000, 1000000000000)
+				.unwrap(),
+			987158034397
+		);
+		assert_eq!(XykStorage::calculate_sell_price_id(2, 3, 1000000000000).unwrap(), 996901583393);
+
+		XykStorage::sell_asset(Origin::signed(2), 2, 3, 1000000000000, 996901583393).unwrap();
+
+		assert_eq!(XykStorage::balance(2, 2), 899000000000000);
+		assert_eq!(XykStorage::balance(3, 2), 900996901583393);
+		assert_eq!(XykStorage::asset_pool((2, 3)), (100998999999998, 99003098416607));
+	});
+}
+
+#[test]
+fn stable_pool_buy_W() {
+	new_test_ext().execute_with(|| {
+		initialize_buy_and_burn();
+		XykStorage::create_stable_pool(
+			Origin::signed(2),
+			2,
+			100000000000000,
+			3,
+			100000000000000,
+			100,
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::calculate_buy_price_id(2, 3, 1000000000000).unwrap(), 1003108354479);
+		// selling the amount paid yields at least the amount bought
+		assert!(XykStorage::calculate_sell_price_id(2, 3, 1003108354479).unwrap() >= 1000000000000);
+
+		XykStorage::buy_asset(Origin::signed(2), 2, 3, 1000000000000, 1003108354479).unwrap();
+
+		assert_eq!(XykStorage::balance(2, 2), 900000000000000 - 1003108354479);
+		assert_eq!(XykStorage::balance(3, 2), 901000000000000);
+	});
+}
+
+#[test]
+fn get_twap_W_constant_price() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(11);
+
+		assert_eq!(
+			XykStorage::get_twap(1, 4, 10).unwrap(),
+			(1500000000000000000, 666666666666666666)
+		);
+		assert_eq!(
+			XykStorage::get_twap(4, 1, 10).unwrap(),
+			(666666666666666666, 1500000000000000000)
+		);
+		assert_eq!(
+			<XykStorage as PoolPriceOracleTrait>::get_twap(1, 4, 5),
+			Some((1500000000000000000, 666666666666666666))
+		);
+	});
+}
+
+#[test]
+fn get_twap_W_interpolates_between_observations() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(50);
+		XykStorage::sell_asset(Origin::signed(2), 1, 4, 20000000000000000000, 0).unwrap();
+		let (first_reserve, second_reserve) = XykStorage::asset_pool((1, 4));
+		let spot_price = second_reserve * 1000000000000000000 / first_reserve;
+
+		System::set_block_number(100);
+		// whole window is after the swap
+		assert_eq!(XykStorage::get_twap(1, 4, 20).unwrap().0, spot_price);
+		// window covers 49 blocks at initial price and 50 blocks at price after the swap
+		assert_eq!(
+			XykStorage::get_twap(1, 4, 99).unwrap().0,
+			(49 * 1500000000000000000 + 50 * spot_price) / 99
+		);
+	});
+}
+
+#[test]
+fn get_twap_W_single_block_manipulation_barely_moves_average() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(100);
+
+		// attacker dumps token 1, moving its spot price by more than half
+		XykStorage::sell_asset(Origin::signed(2), 1, 4, 40000000000000000000, 0).unwrap();
+		let (first_reserve, second_reserve) = XykStorage::asset_pool((1, 4));
+		assert!(second_reserve * 1000000000000000000 / first_reserve < 750000000000000000);
+
+		// prices set in current block are not part of the average yet
+		assert_eq!(XykStorage::get_twap(1, 4, 99).unwrap().0, 1500000000000000000);
+
+		// attacker sells back everything bought in the next block
+		System::set_block_number(101);
+		let bought_amount = XykStorage::balance(4, 2) - 940000000000000000000;
+		XykStorage::sell_asset(Origin::signed(2), 4, 1, bought_amount, 0).unwrap();
+
+		System::set_block_number(102);
+		let (twap, _) = XykStorage::get_twap(1, 4, 100).unwrap();
+		// single manipulated block out of hundred moves the average by less than 1%
+		assert!(twap < 1500000000000000000);
+		assert!(twap > 1485000000000000000);
+	});
+}
+
+#[test]
+fn get_twap_N() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		System::set_block_number(11);
+
+		assert_err!(XykStorage::get_twap(1, 4, 0), Error::<Test>::InvalidTwapWindow);
+		assert_err!(XykStorage::get_twap(1, 4, 11), Error::<Test>::NotEnoughPriceHistory);
+		assert_err!(XykStorage::get_twap(1, 2, 5), Error::<Test>::NoSuchPool);
+		assert_eq!(<XykStorage as PoolPriceOracleTrait>::get_twap(1, 4, 11), None);
+	});
+}
+
+#[test]
+#[serial]
+fn get_pools_returns_pools_ordered_by_liquidity_token_W() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+		XykStorage::promote_pool(Origin::root(), 7).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 7, 1000, None).unwrap();
+
+		assert_eq!(
+			XykStorage::get_pools(None, 10),
+			vec![
+				PoolInfo {
+					liquidity_token_id: 6,
+					first_asset_id: 0,
+					second_asset_id: 1,
+					first_asset_reserve: 100000000000000,
+					second_asset_reserve: 100000000000000,
+					liquidity_token_issuance: 100000000000000,
+					is_promoted: false,
+					activated_amount: 0,
+				},
+				PoolInfo {
+					liquidity_token_id: 7,
+					first_asset_id: 1,
+					second_asset_id: 4,
+					first_asset_reserve: 100000000000000,
+					second_asset_reserve: 100000000000000,
+					liquidity_token_issuance: 100000000000000,
+					is_promoted: true,
+					activated_amount: 1000,
+				},
+			]
+		);
+	});
+}
+
+#[test]
+fn get_pools_pagination_W() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+
+		let first_page = XykStorage::get_pools(None, 1);
+		assert_eq!(first_page.len(), 1);
+		assert_eq!(first_page[0].liquidity_token_id, 6);
+
+		let second_page = XykStorage::get_pools(Some(6), 1);
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(second_page[0].liquidity_token_id, 7);
+
+		assert!(XykStorage::get_pools(Some(7), 1).is_empty());
+		assert!(XykStorage::get_pools(None, 0).is_empty());
+
+		// start key does not have to be an existing pool
+		assert_eq!(XykStorage::get_pools(Some(2), 10).len(), 2);
+	});
+}
+
+#[test]
+#[serial]
+fn get_pools_rewards_stats_values_promoted_pools_in_native_currency_W() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+		XykStorage::promote_pool(Origin::root(), 6).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 6, 1000, None).unwrap();
+
+		// pool 7 is not promoted and has no rewards history
+		assert_eq!(
+			XykStorage::get_pools_rewards_stats(None, 10),
+			vec![PoolRewardsStats {
+				liquidity_token_id: 6,
+				rewards_history: vec![],
+				activated_amount: 1000,
+				reserves_valuation: 200000000000000,
+				activated_valuation: 2000,
+				pool_weight: 1,
+				total_pools_weight: 1,
+			}]
+		);
+
+		// pool not paired with native currency cannot be valuated
+		XykStorage::promote_pool(Origin::root(), 7).unwrap();
+		let stats = XykStorage::get_pools_rewards_stats(Some(6), 10);
+		assert_eq!(stats.len(), 1);
+		assert_eq!(stats[0].liquidity_token_id, 7);
+		assert_eq!(stats[0].reserves_valuation, 0);
+		assert_eq!(stats[0].pool_weight, 1);
+		assert_eq!(stats[0].total_pools_weight, 2);
+	});
+}
+
+#[test]
+fn get_pools_skips_removed_pools_W() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+
+		XykStorage::burn_liquidity(Origin::signed(2), 0, 1, 100000000000000, 0, 0).unwrap();
+
+		let pools = XykStorage::get_pools(None, 10);
+		assert_eq!(pools.len(), 1);
+		assert_eq!(pools[0].liquidity_token_id, 7);
+	});
+}
+
+#[test]
+#[serial]
+fn get_user_liquidity_positions_W() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		initialize();
+		XykStorage::promote_pool(Origin::root(), 5).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 5, 10000000000000000000, None)
+			.unwrap();
+
+		assert_eq!(
+			XykStorage::get_user_liquidity_positions(&2, None, 10).unwrap(),
+			vec![LiquidityPosition {
+				liquidity_token_id: 5,
+				first_asset_id: 1,
+				second_asset_id: 4,
+				free_amount: 40000000000000000000,
+				reserved_amount: 10000000000000000000,
+				activated_amount: 10000000000000000000,
+				first_asset_amount: 40000000000000000000,
+				second_asset_amount: 60000000000000000000,
+				claimable_rewards: 0,
+			}]
+		);
+	});
+}
+
+#[test]
+fn get_user_liquidity_positions_skips_pools_without_liquidity_W() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		assert_eq!(XykStorage::get_user_liquidity_positions(&3, None, 10).unwrap(), vec![]);
+
+		XykStorage::transfer(5, 2, 3, 5000000000000000000).unwrap();
+		assert_eq!(
+			XykStorage::get_user_liquidity_positions(&3, None, 10).unwrap(),
+			vec![LiquidityPosition {
+				liquidity_token_id: 5,
+				first_asset_id: 1,
+				second_asset_id: 4,
+				free_amount: 5000000000000000000,
+				reserved_amount: 0,
+				activated_amount: 0,
+				first_asset_amount: 4000000000000000000,
+				second_asset_amount: 6000000000000000000,
+				claimable_rewards: 0,
+			}]
+		);
+	});
+}
+
+#[test]
+fn get_user_liquidity_positions_pagination_counts_only_returned_positions_W() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+
+		// user 3 has no position in pool 6, which must not use up the page
+		XykStorage::transfer(7, 2, 3, 1000).unwrap();
+		let positions = XykStorage::get_user_liquidity_positions(&3, None, 1).unwrap();
+		assert_eq!(positions.len(), 1);
+		assert_eq!(positions[0].liquidity_token_id, 7);
+
+		let first_page = XykStorage::get_user_liquidity_positions(&2, None, 1).unwrap();
+		assert_eq!(first_page.len(), 1);
+		assert_eq!(first_page[0].liquidity_token_id, 6);
+
+		let second_page = XykStorage::get_user_liquidity_positions(&2, Some(6), 1).unwrap();
+		assert_eq!(second_page.len(), 1);
+		assert_eq!(second_page[0].liquidity_token_id, 7);
+
+		assert_eq!(XykStorage::get_user_liquidity_positions(&2, Some(7), 1).unwrap(), vec![]);
+	});
+}
+
+fn remark_call() -> Box<crate::mock::Call> {
+	Box::new(crate::mock::Call::System(frame_system::Call::remark { remark: vec![] }))
+}
+
+#[test]
+fn flash_swap_W() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		XykStorage::flash_swap(
+			Origin::signed(2),
+			1,
+			4,
+			1000000000000000000,
+			1000000000000000000,
+			remark_call(),
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::balance(4, 2), 941000000000000000000);
+		assert_eq!(XykStorage::balance(1, 2), 959319993879944919504);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40679326113935025414, 59000000000000000000));
+		assert_eq!(XykStorage::balance(1, XykStorage::treasury_account_id()), 340003060027541);
+		assert_eq!(XykStorage::balance(1, XykStorage::bnb_treasury_account_id()), 340003060027541);
+		assert_eq!(XykStorage::flash_swap_lock((1, 4)), None);
+
+		let flash_swap_event =
+			crate::mock::Event::XykStorage(crate::Event::<Test>::FlashSwapExecuted(
+				2,
+				1,
+				680006120055080496,
+				4,
+				1000000000000000000,
+			));
+		assert!(System::events().iter().any(|record| record.event == flash_swap_event));
+	});
+}
+
+#[test]
+fn flash_swap_N_callback_uses_locked_pool() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		let sell_call = Box::new(crate::mock::Call::XykStorage(crate::Call::sell_asset {
+			sold_asset_id: 4,
+			bought_asset_id: 1,
+			sold_asset_amount: 1000000000000000000,
+			min_amount_out: 0,
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				sell_call,
+			),
+			Error::<Test>::PoolLocked,
+		);
+
+		let flash_swap_call = Box::new(crate::mock::Call::XykStorage(crate::Call::flash_swap {
+			sold_asset_id: 4,
+			bought_asset_id: 1,
+			bought_asset_amount: 1000000000000000000,
+			max_amount_in: 1000000000000000000,
+			call: remark_call(),
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				flash_swap_call,
+			),
+			Error::<Test>::PoolLocked,
+		);
+
+		assert_eq!(XykStorage::balance(4, 2), 940000000000000000000);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40000000000000000000, 60000000000000000000));
+		assert_eq!(XykStorage::flash_swap_lock((1, 4)), None);
+	});
+}
+
+#[test]
+fn flash_swap_N_callback_mints_or_burns_locked_pool() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		let liquidity_asset_id = XykStorage::get_liquidity_asset(1, 4).unwrap();
+		let liquidity_amount = XykStorage::balance(liquidity_asset_id, 2);
+
+		let mint_call = Box::new(crate::mock::Call::XykStorage(crate::Call::mint_liquidity {
+			first_asset_id: 1,
+			second_asset_id: 4,
+			first_asset_amount: 1000000000000000000,
+			expected_second_asset_amount: 10000000000000000000,
+			min_liquidity_minted: 0,
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				mint_call,
+			),
+			Error::<Test>::PoolLocked,
+		);
+
+		// burning all liquidity removes the pool
+		let burn_call = Box::new(crate::mock::Call::XykStorage(crate::Call::burn_liquidity {
+			first_asset_id: 1,
+			second_asset_id: 4,
+			liquidity_asset_amount: liquidity_amount,
+			min_first_asset_amount: 0,
+			min_second_asset_amount: 0,
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				burn_call,
+			),
+			Error::<Test>::PoolLocked,
+		);
+
+		assert_eq!(XykStorage::balance(liquidity_asset_id, 2), liquidity_amount);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40000000000000000000, 60000000000000000000));
+		assert_eq!(XykStorage::flash_swap_lock((1, 4)), None);
+	});
+}
+
+#[test]
+fn flash_swap_N_callback_failed() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		let failing_call = Box::new(crate::mock::Call::XykStorage(crate::Call::sell_asset {
+			sold_asset_id: 2,
+			bought_asset_id: 3,
+			sold_asset_amount: 1000000000000000000,
+			min_amount_out: 0,
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				failing_call,
+			),
+			Error::<Test>::NoSuchPool,
+		);
+
+		assert_eq!(XykStorage::balance(4, 2), 940000000000000000000);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40000000000000000000, 60000000000000000000));
+	});
+}
+
+#[test]
+fn flash_swap_N_insufficient_input_amount() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				680006120055080495,
+				remark_call(),
+			),
+			Error::<Test>::InsufficientInputAmount,
+		);
+		assert_eq!(XykStorage::balance(4, 2), 940000000000000000000);
+	});
+}
+
+#[test]
+fn flash_swap_N_not_enough_assets_to_repay() {
+	new_test_ext().execute_with(|| {
+		initialize();
+
+		// whole balance of sold asset is moved away in callback
+		let transfer_call = Box::new(crate::mock::Call::Tokens(orml_tokens::Call::transfer_all {
+			dest: 3,
+			currency_id: 1,
+			keep_alive: false,
+		}));
+		assert_err!(
+			XykStorage::flash_swap(
+				Origin::signed(2),
+				1,
+				4,
+				1000000000000000000,
+				1000000000000000000,
+				transfer_call,
+			),
+			Error::<Test>::NotEnoughAssets,
+		);
+		assert_eq!(XykStorage::balance(1, 2), 960000000000000000000);
+	});
+}
+
+#[test]
+fn flash_swap_N_stable_pool() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		initialize_buy_and_burn();
+		XykStorage::create_stable_pool(
+			Origin::signed(2),
+			2,
+			100000000000000,
+			3,
+			100000000000000,
+			100,
+		)
+		.unwrap();
+
+		assert_err!(
+			XykStorage::flash_swap(Origin::signed(2), 2, 3, 1000, 10000, remark_call()),
+			Error::<Test>::FlashSwapNotSupported,
+		);
+	});
+}
+
+fn initialize_reward_schedules() -> TokenId {
+	MockPromotedPoolApi::instance().lock().unwrap().clear();
+	System::set_block_number(1);
+	let amount: u128 = 1_000_000_000_000;
+
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_pool(Origin::signed(2), 0, 10000, 1, 10000).unwrap();
+	XykStorage::promote_pool(Origin::root(), 4).unwrap();
+
+	// reward token
+	let reward_token = XykStorage::create_new_token(&3, amount);
+	XykStorage::set_reward_token_whitelisted(Origin::root(), reward_token, true).unwrap();
+	reward_token
+}
+
+#[test]
+#[serial]
+fn reward_schedule_rewards_split_by_activated_liquidity_W() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+		XykStorage::transfer(4, 2, 3, 2500).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 7500, None).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(3), 4, 2500, None).unwrap();
+
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 11).unwrap();
+		let schedule = XykStorage::reward_schedule(4, reward_token).unwrap();
+		assert_eq!(schedule.amount_per_block, 100);
+		assert_eq!(schedule.undistributed, 0);
+		assert_eq!(XykStorage::pool_reward_tokens(4).into_inner(), vec![reward_token]);
+		assert_eq!(
+			XykStorage::balance(reward_token, XykStorage::reward_schedules_account_id()),
+			1000
+		);
+
+		System::set_block_number(6);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(), 374);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(3, 4, reward_token).unwrap(), 124);
+		XykStorage::deactivate_liquidity_v2(Origin::signed(3), 4, 2500).unwrap();
+
+		// schedule ended in block 11, user 2 was the only one activated after block 6
+		System::set_block_number(20);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(), 874);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(3, 4, reward_token).unwrap(), 124);
+
+		let balance_before = XykStorage::balance(reward_token, 3);
+		XykStorage::claim_schedule_rewards(Origin::signed(2), 4, reward_token).unwrap();
+		XykStorage::claim_schedule_rewards(Origin::signed(3), 4, reward_token).unwrap();
+		assert_eq!(XykStorage::balance(reward_token, 2), 874);
+		assert_eq!(XykStorage::balance(reward_token, 3), balance_before + 124);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(), 0);
+
+		let claimed_event = crate::mock::Event::XykStorage(
+			crate::Event::<Test>::ScheduleRewardsClaimed(2, 4, reward_token, 874),
+		);
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+
+		assert_err!(
+			XykStorage::claim_schedule_rewards(Origin::signed(2), 4, reward_token),
+			Error::<Test>::NotEnoughtRewardsEarned,
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn reward_schedule_refund_undistributed_rewards_W() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+		let initial_balance = XykStorage::balance(reward_token, 3);
+
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1005, 11).unwrap();
+		assert_eq!(XykStorage::reward_schedule(4, reward_token).unwrap().undistributed, 5);
+
+		// nobody activated in blocks 1 - 5
+		System::set_block_number(5);
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
+		assert_err!(
+			XykStorage::refund_reward_schedule(Origin::signed(3), 4, reward_token),
+			Error::<Test>::RewardScheduleActive,
+		);
+
+		System::set_block_number(11);
+		assert_err!(
+			XykStorage::refund_reward_schedule(Origin::signed(2), 4, reward_token),
+			Error::<Test>::NotRewardScheduleSponsor,
+		);
+		XykStorage::refund_reward_schedule(Origin::signed(3), 4, reward_token).unwrap();
+		assert_eq!(XykStorage::balance(reward_token, 3), initial_balance - 1005 + 405);
+		assert_eq!(XykStorage::reward_schedule(4, reward_token).unwrap().undistributed, 0);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(), 599);
+
+		// new schedule of the same token continues from rewards of previous one
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 21).unwrap();
+		System::set_block_number(21);
+		assert_eq!(
+			XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(),
+			1599
+		);
+		assert_eq!(XykStorage::pool_reward_tokens(4).len(), 1);
+	});
+}
+
+#[test]
+#[serial]
+fn create_reward_schedule_N() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 5, reward_token, 1000, 11),
+			Error::<Test>::NotAPromotedPool,
+		);
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 0, 11),
+			Error::<Test>::ZeroAmount,
+		);
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 1),
+			Error::<Test>::InvalidRewardSchedule,
+		);
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 9, 11),
+			Error::<Test>::InvalidRewardSchedule,
+		);
+		assert_err!(
+			XykStorage::claim_schedule_rewards(Origin::signed(2), 4, reward_token),
+			Error::<Test>::NoSuchRewardSchedule,
+		);
+
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 11).unwrap();
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 21),
+			Error::<Test>::RewardScheduleActive,
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn create_reward_schedule_N_token_not_whitelisted() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+		let not_whitelisted_token = XykStorage::create_new_token(&3, 1_000_000_000_000);
+
+		assert_err!(
+			XykStorage::create_reward_schedule(
+				Origin::signed(3),
+				4,
+				not_whitelisted_token,
+				1000,
+				11
+			),
+			Error::<Test>::RewardTokenNotWhitelisted,
+		);
+		assert_err!(
+			XykStorage::set_reward_token_whitelisted(
+				Origin::signed(3),
+				not_whitelisted_token,
+				true
+			),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+
+		XykStorage::set_reward_token_whitelisted(Origin::root(), reward_token, false).unwrap();
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 11),
+			Error::<Test>::RewardTokenNotWhitelisted,
+		);
+
+		// native token is always allowed
+		XykStorage::transfer(0, 2, 3, 1000).unwrap();
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, 0, 1000, 11).unwrap();
+	});
+}
+
+#[test]
+#[serial]
+fn reward_schedule_ended_schedules_pruned_W() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+		let other_reward_token = XykStorage::create_new_token(&3, 1_000_000_000_000);
+		XykStorage::set_reward_token_whitelisted(Origin::root(), other_reward_token, true).unwrap();
+
+		XykStorage::transfer(4, 2, 3, 2500).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 5000, None).unwrap();
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 11).unwrap();
+
+		// ended schedule is pruned even though liquidity is activated
+		System::set_block_number(11);
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, other_reward_token, 1000, 21)
+			.unwrap();
+		assert_eq!(XykStorage::pool_reward_tokens(4).into_inner(), vec![other_reward_token]);
+		let rewards = XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap();
+		assert!(rewards > 0);
+
+		// liquidity activated after pruning earns nothing from pruned schedule
+		System::set_block_number(15);
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 2500, None).unwrap();
+		XykStorage::activate_liquidity_v2(Origin::signed(3), 4, 2500, None).unwrap();
+		System::set_block_number(25);
+		assert_eq!(
+			XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap(),
+			rewards
+		);
+		assert_eq!(XykStorage::calculate_schedule_rewards_amount(3, 4, reward_token).unwrap(), 0);
+
+		// new schedule of pruned token distributes only rewards of its own blocks
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 35).unwrap();
+		assert_eq!(XykStorage::pool_reward_tokens(4).into_inner(), vec![reward_token]);
+		System::set_block_number(35);
+		// user 2 holds 3/4 of activated liquidity
+		let rewards_after =
+			XykStorage::calculate_schedule_rewards_amount(2, 4, reward_token).unwrap();
+		assert!(rewards_after > rewards && rewards_after <= rewards + 750);
+		XykStorage::claim_schedule_rewards(Origin::signed(2), 4, reward_token).unwrap();
+		assert_eq!(XykStorage::balance(reward_token, 2), rewards_after);
+	});
+}
+
+#[test]
+#[serial]
+fn reward_schedule_ended_schedules_free_reward_token_slots_W() {
+	new_test_ext().execute_with(|| {
+		let reward_token = initialize_reward_schedules();
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
+
+		for _ in 0..MAX_REWARD_TOKENS_PER_POOL {
+			let token = XykStorage::create_new_token(&3, 1_000_000_000_000);
+			XykStorage::set_reward_token_whitelisted(Origin::root(), token, true).unwrap();
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, token, 1000, 11).unwrap();
+		}
+		assert_err!(
+			XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 11),
+			Error::<Test>::TooManyRewardTokens,
+		);
+
+		System::set_block_number(11);
+		XykStorage::create_reward_schedule(Origin::signed(3), 4, reward_token, 1000, 21).unwrap();
+		assert_eq!(XykStorage::pool_reward_tokens(4).into_inner(), vec![reward_token]);
+	});
+}
+
+fn initialize_unpromoted_pool() {
+	MockPromotedPoolApi::instance().lock().unwrap().clear();
+	System::set_block_number(1);
+	let amount: u128 = std::u128::MAX;
+
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::transfer(0, 2, <Test as Config>::LiquidityMiningIssuanceVault::get(), 10000000000)
+		.unwrap();
+
+	XykStorage::create_pool(Origin::signed(2), 0, 10000, 1, 10000).unwrap();
+	XykStorage::promote_pool(Origin::root(), 4).unwrap();
+	XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
+
+	System::set_block_number(10);
+	MockPromotedPoolApi::instance()
+		.lock()
+		.unwrap()
+		.insert(4, U256::from(u128::MAX * 1));
+	XykStorage::unpromote_pool(Origin::root(), 4).unwrap();
+}
+
+#[test]
+#[serial]
+fn unpromote_pool_freezes_rewards_W() {
+	new_test_ext().execute_with(|| {
+		initialize_unpromoted_pool();
+
+		assert_eq!(XykStorage::unpromoted_pool_rewards(4), Some(U256::from(u128::MAX)));
+		assert_eq!(MockPromotedPoolApi::get_pool_rewards_v2(4), None);
+		let unpromoted_event =
+			crate::mock::Event::XykStorage(crate::Event::<Test>::PoolUnpromoted(4));
+		assert!(System::events().iter().any(|record| record.event == unpromoted_event));
+
+		// rewards earned until unpromotion are still vesting, but pool ratio does not grow
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 291);
+		System::set_block_number(20);
+		let rewards = XykStorage::calculate_rewards_amount_v2(2, 4).unwrap();
+		assert!(rewards > 291 && rewards <= 10000);
+
+		assert_err!(
+			XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 1, None),
+			Error::<Test>::NotAPromotedPool,
+		);
+		assert_err!(XykStorage::unpromote_pool(Origin::root(), 4), Error::<Test>::NotAPromotedPool,);
+
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_v2(Origin::signed(2), 4).unwrap();
+		assert_eq!(XykStorage::balance(0, 2), balance_before + rewards);
+
+		XykStorage::deactivate_liquidity_v2(Origin::signed(2), 4, 10000).unwrap();
+		assert_eq!(XykStorage::get_rewards_info(2, 4).activated_amount, 0);
+		assert_eq!(XykStorage::liquidity_mining_active_pool_v2(4), 0);
+		assert_eq!(XykStorage::balance(4, 2), 10000);
+	});
+}
+
+#[test]
+#[serial]
+fn promote_unpromoted_pool_after_deactivation_W() {
+	new_test_ext().execute_with(|| {
+		initialize_unpromoted_pool();
+
+		assert_err!(
+			XykStorage::promote_pool(Origin::root(), 4),
+			Error::<Test>::LiquidityStillActivated,
+		);
+
+		XykStorage::deactivate_liquidity_v2(Origin::signed(2), 4, 10000).unwrap();
+		XykStorage::promote_pool(Origin::root(), 4).unwrap();
+		assert_eq!(XykStorage::unpromoted_pool_rewards(4), None);
+
+		// rewards earned before unpromotion stay claimable after pool ratio restarts from zero
+		let rewards = XykStorage::get_rewards_info(2, 4).rewards_not_yet_claimed;
+		assert!(rewards > 0);
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_v2(Origin::signed(2), 4).unwrap();
+		assert_eq!(XykStorage::balance(0, 2), balance_before + rewards);
+
+		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 0);
+	});
+}
+
+fn initialize_rewards_in_two_pools() {
+	MockPromotedPoolApi::instance().lock().unwrap().clear();
+	System::set_block_number(1);
+	let amount: u128 = std::u128::MAX;
+
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::transfer(0, 2, <Test as Config>::LiquidityMiningIssuanceVault::get(), 10000000000)
+		.unwrap();
+
+	XykStorage::create_pool(Origin::signed(2), 0, 10000, 1, 10000).unwrap();
+	XykStorage::create_pool(Origin::signed(2), 2, 10000, 3, 10000).unwrap();
+	XykStorage::promote_pool(Origin::root(), 4).unwrap();
+	XykStorage::promote_pool(Origin::root(), 5).unwrap();
+	XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
+	XykStorage::activate_liquidity_v2(Origin::signed(2), 5, 10000, None).unwrap();
+
+	System::set_block_number(10);
+	MockPromotedPoolApi::instance()
+		.lock()
+		.unwrap()
+		.insert(4, U256::from(u128::MAX * 1));
+	MockPromotedPoolApi::instance()
+		.lock()
+		.unwrap()
+		.insert(5, U256::from(u128::MAX * 1));
+}
+
+#[test]
+#[serial]
+fn claim_rewards_all_pools_W() {
+	new_test_ext().execute_with(|| {
+		initialize_rewards_in_two_pools();
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 291);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 291);
+
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 10).unwrap();
+
+		assert_eq!(XykStorage::balance(0, 2), balance_before + 582);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 0);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 0);
+
+		for event in [
+			crate::Event::<Test>::RewardsClaimed(2, 4, 291),
+			crate::Event::<Test>::RewardsClaimed(2, 5, 291),
+		] {
+			let event = crate::mock::Event::XykStorage(event);
+			assert!(System::events().iter().any(|record| record.event == event));
+		}
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			crate::mock::Event::XykStorage(crate::Event::<Test>::RewardsClaimedFromAllPools(
+				2,
+				582,
+				2,
+				_
+			))
+		)));
+
+		assert_err!(
+			XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 10),
+			Error::<Test>::NotEnoughtRewardsEarned,
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn claim_rewards_all_pools_limited_by_max_pools_W() {
+	new_test_ext().execute_with(|| {
+		initialize_rewards_in_two_pools();
+
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 1).unwrap();
+
+		assert_eq!(XykStorage::balance(0, 2), balance_before + 291);
+		assert_eq!(
+			XykStorage::calculate_rewards_amount_v2(2, 4).unwrap() +
+				XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(),
+			291
+		);
+
+		assert_err!(
+			XykStorage::claim_rewards_all_pools_v2(Origin::signed(3), None, 10),
+			Error::<Test>::NotEnoughtRewardsEarned,
+		);
+	});
+}
+
+fn last_visited_pool_of_claim_all() -> TokenId {
+	System::events()
+		.iter()
+		.rev()
+		.find_map(|record| match record.event {
+			crate::mock::Event::XykStorage(crate::Event::<Test>::RewardsClaimedFromAllPools(
+				_,
+				_,
+				_,
+				last_visited,
+			)) => Some(last_visited),
+			_ => None,
+		})
+		.unwrap()
+}
+
+#[test]
+#[serial]
+fn claim_rewards_all_pools_continues_after_start_key_W() {
+	new_test_ext().execute_with(|| {
+		initialize_rewards_in_two_pools();
+
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 1).unwrap();
+		let first_pool = last_visited_pool_of_claim_all();
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, first_pool).unwrap(), 0);
+
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), Some(first_pool), 1).unwrap();
+		let second_pool = last_visited_pool_of_claim_all();
+		assert_ne!(first_pool, second_pool);
+		assert_eq!(XykStorage::balance(0, 2), balance_before + 291);
+
+		assert_err!(
+			XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), Some(second_pool), 10),
+			Error::<Test>::NotEnoughtRewardsEarned,
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn claim_rewards_all_pools_empty_pools_do_not_count_towards_max_pools_W() {
+	new_test_ext().execute_with(|| {
+		initialize_rewards_in_two_pools();
+
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 1).unwrap();
+
+		// first pool in storage order has nothing to claim anymore and is skipped
+		let balance_before = XykStorage::balance(0, 2);
+		XykStorage::claim_rewards_all_pools_v2(Origin::signed(2), None, 1).unwrap();
+		assert_eq!(XykStorage::balance(0, 2), balance_before + 291);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 0);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 0);
+	});
+}
+
+fn initialize_compound_rewards() {
+	MockPromotedPoolApi::instance().lock().unwrap().clear();
+	System::set_block_number(1);
+	let amount: u128 = std::u128::MAX;
+
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::create_new_token(&2, amount);
+	XykStorage::transfer(
+		0,
+		2,
+		<Test as Config>::LiquidityMiningIssuanceVault::get(),
+		1_000_000_000_000,
+	)
+	.unwrap();
+
+	XykStorage::create_pool(Origin::signed(2), 0, 1_000_000_000_000, 1, 2_000_000_000_000).unwrap();
+	XykStorage::promote_pool(Origin::root(), 4).unwrap();
+	let liquidity_tokens_owned = XykStorage::balance(4, 2);
+	XykStorage::activate_liquidity_v2(Origin::signed(2), 4, liquidity_tokens_owned, None).unwrap();
+
+	System::set_block_number(10);
+	MockPromotedPoolApi::instance()
+		.lock()
+		.unwrap()
+		.insert(4, U256::from(u128::MAX * 1));
+}
+
+#[test]
+#[serial]
+fn compound_rewards_W() {
+	new_test_ext().execute_with(|| {
+		initialize_compound_rewards();
+
+		let rewards = XykStorage::calculate_rewards_amount_v2(2, 4).unwrap();
+		let activated_before = XykStorage::get_rewards_info(2, 4).activated_amount;
+		let native_before = XykStorage::balance(0, 2);
+		let other_before = XykStorage::balance(1, 2);
+		let liquidity_issuance_before = XykStorage::total_supply(4);
+		assert!(rewards > 0);
+
+		XykStorage::compound_rewards(Origin::signed(2), 4, 1, true).unwrap();
+
+		let minted = XykStorage::total_supply(4) - liquidity_issuance_before;
+		assert!(minted > 0);
+		assert_eq!(XykStorage::get_rewards_info(2, 4).activated_amount, activated_before + minted);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 0);
+
+		// only rounding leftovers of claimed rewards stay with the user
+		let native_leftover = XykStorage::balance(0, 2) - native_before;
+		let other_leftover = XykStorage::balance(1, 2) - other_before;
+		assert!(native_leftover * 100 < rewards);
+		assert!(other_leftover * 100 < rewards);
+
+		let compounded_event = crate::mock::Event::XykStorage(
+			crate::Event::<Test>::RewardsCompounded(2, 4, rewards, minted),
+		);
+		assert!(System::events().iter().any(|record| record.event == compounded_event));
+
+		assert_err!(
+			XykStorage::compound_rewards(Origin::signed(2), 4, 0, true),
+			Error::<Test>::NotEnoughtRewardsEarned,
+		);
+	});
+}
+
+#[test]
+#[serial]
+fn compound_rewards_N() {
+	new_test_ext().execute_with(|| {
+		initialize_compound_rewards();
+		let rewards = XykStorage::calculate_rewards_amount_v2(2, 4).unwrap();
+
+		assert_err!(
+			XykStorage::compound_rewards(Origin::signed(2), 4, u128::MAX, true),
+			Error::<Test>::InsufficientLiquidityMinted,
+		);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), rewards);
+
+		XykStorage::create_pool(Origin::signed(2), 2, 10000, 3, 10000).unwrap();
+		assert_err!(
+			XykStorage::compound_rewards(Origin::signed(2), 5, 0, false),
+			Error::<Test>::NotMangataLiquidityAsset,
+		);
+	});
+}
+
+// Reward curve as implemented with floating point arithmetic before, used as reference
+fn calculate_q_pow_f64(q: f64, pow: u32) -> u128 {
+	libm::floor(libm::pow(q, pow as f64) * REWARDS_PRECISION as f64) as u128
+}
+
+fn calculate_cumulative_work_max_ratio_f64(
+	liquidity_assets_amount: u128,
+	time_passed: u32,
+	missing_at_last_checkpoint: U256,
+) -> (U256, U256) {
+	if time_passed == 0 || liquidity_assets_amount == 0 {
+		return (U256::from(0), U256::from(1))
+	}
+	let max_possible = U256::from(liquidity_assets_amount) * U256::from(time_passed);
+	let base = missing_at_last_checkpoint * U256::from(103) / U256::from(3);
+	let q_pow = calculate_q_pow_f64(1.03, time_passed + 1);
+	let cummulative_missing_new =
+		base - base * U256::from(REWARDS_PRECISION) / q_pow - missing_at_last_checkpoint;
+	(max_possible - cummulative_missing_new, max_possible)
+}
+
+// Deterministic pseudo random numbers, so that property tests are reproducible
+fn xorshift(state: &mut u64) -> u64 {
+	*state ^= *state << 13;
+	*state ^= *state >> 7;
+	*state ^= *state << 17;
+	*state
+}
+
+// Fixed point results are exactly the same as floating point ones while both are precise enough,
+// i.e. for first 600 sessions with Q and first 350 sessions with Q_V1. After that floating point
+// loses precision and results may differ by less than 1 part in 10^11, fixed point results
+// being closer to exact values.
+#[test]
+fn calculate_q_pow_matches_floating_point_within_tolerance() {
+	for (q, q_f64, exact_below) in [(Q, 1.03, 600_u32), (Q_V1, 1.06, 350_u32)] {
+		let mut previous_q_pow = 0;
+		for pow in 0..5000_u32 {
+			let q_pow = XykStorage::calculate_q_pow(q, pow);
+			let q_pow_f64 = calculate_q_pow_f64(q_f64, pow);
+
+			if pow < exact_below {
+				assert_eq!(q_pow, q_pow_f64, "q: {}, pow: {}", q_f64, pow);
+			} else {
+				let difference = q_pow.max(q_pow_f64) - q_pow.min(q_pow_f64);
+				assert!(
+					difference <= q_pow.max(q_pow_f64) / 100_000_000_000,
+					"q: {}, pow: {}, fixed point: {}, floating point: {}",
+					q_f64,
+					pow,
+					q_pow,
+					q_pow_f64
+				);
+			}
+
+			assert!(q_pow >= previous_q_pow);
+			previous_q_pow = q_pow;
+		}
+
+		assert_eq!(XykStorage::calculate_q_pow(q, u32::MAX), u128::MAX);
+	}
+
+	assert_eq!(XykStorage::calculate_q_pow(Q, 0), 10000);
+	assert_eq!(XykStorage::calculate_q_pow(Q, 1), 10300);
+	assert_eq!(XykStorage::calculate_q_pow(Q, 2), 10609);
+}
+
+#[test]
+fn calculate_cumulative_work_max_ratio_matches_floating_point() {
+	let mut state = 0x2545_f491_4f6c_dd1d_u64;
+	for _ in 0..10000 {
+		let liquidity_assets_amount = ((xorshift(&mut state) as u128) << 16) + 1;
+		let missing_at_last_checkpoint =
+			U256::from(liquidity_assets_amount * (xorshift(&mut state) % 1000) as u128 / 1000);
+		let time_passed = (xorshift(&mut state) % 600) as u32;
+
+		assert_eq!(
+			XykStorage::calculate_cumulative_work_max_ratio(
+				liquidity_assets_amount,
+				time_passed,
+				missing_at_last_checkpoint,
+				Q
+			)
+			.unwrap(),
+			calculate_cumulative_work_max_ratio_f64(
+				liquidity_assets_amount,
+				time_passed,
+				missing_at_last_checkpoint
+			),
+			"amount: {}, time passed: {}, missing: {}",
+			liquidity_assets_amount,
+			time_passed,
+			missing_at_last_checkpoint
+		);
+		assert_eq!(
+			XykStorage::calculate_missing_at_checkpoint_v2(
+				time_passed,
+				missing_at_last_checkpoint,
+				Q
+			)
+			.unwrap(),
+			missing_at_last_checkpoint * U256::from(REWARDS_PRECISION) /
+				calculate_q_pow_f64(1.03, time_passed)
+		);
+	}
+}
+
+#[test]
+#[serial]
+fn set_reward_curve_keeps_checkpoints_W() {
+	new_test_ext().execute_with(|| {
+		initialize_rewards_in_two_pools();
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 291);
 
-		XykStorage::sell_asset(Origin::signed(2), 4, 1, 30000000000000000000, 0).unwrap(); // selling 30000000000000000000 assetId 1 of pool 0 1
+		let quotient = FixedU128::saturating_from_rational(110, 100);
+		XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5).unwrap();
+		assert_eq!(
+			XykStorage::pool_reward_curves(5),
+			Some(RewardCurve { quotient, distribution_period: 5, start_block: 11, start_time: 2 })
+		);
+		let curve_event =
+			crate::mock::Event::XykStorage(crate::Event::<Test>::RewardCurveSet(5, quotient, 5));
+		assert!(System::events().iter().any(|record| record.event == curve_event));
 
-		assert_eq!(XykStorage::balance(1, 2), 973306639973306639973); // amount of asset 0 in user acc after selling
-		assert_eq!(XykStorage::balance(4, 2), 910000000000000000000); // amount of asset 1 in user acc after buying
-															  // assert_eq!(XykStorage::asset_pool((1, 2)), 26684462240017795575); // amount of asset 0 in pool map
-															  // assert_eq!(XykStorage::asset_pool((1, 0)), 90000000000000000000); // amount of asset 1 in pool map
-		assert_eq!(XykStorage::asset_pool((1, 4)), (26693360026693360027, 89969999999999999998));
-		assert_eq!(XykStorage::balance(1, 2), 973306639973306639973); // amount of asset 0 on account 2
-		assert_eq!(XykStorage::balance(4, 2), 910000000000000000000); // amount of asset 1 on account 2
-		assert_eq!(XykStorage::balance(1, XykStorage::account_id()), 26693360026693360027); // amount of asset 0 in vault acc after creating pool
-		assert_eq!(XykStorage::balance(4, XykStorage::account_id()), 89969999999999999998);
-		// amount of asset 1 in vault acc after creating pool
+		// time of the pool continues after the previous curve
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 291);
+		assert_eq!(XykStorage::get_reward_curve(5).time_at(14), 2);
+		assert_eq!(XykStorage::get_reward_curve(5).time_at(15), 3);
+
+		// faster ramp up of pool 5, pool 4 uses default curve
+		System::set_block_number(30);
+		MockPromotedPoolApi::instance()
+			.lock()
+			.unwrap()
+			.insert(4, U256::from(u128::MAX) * U256::from(2));
+		MockPromotedPoolApi::instance()
+			.lock()
+			.unwrap()
+			.insert(5, U256::from(u128::MAX) * U256::from(2));
+		assert_eq!(XykStorage::get_reward_curve(4).time_at(30), 3);
+		assert_eq!(XykStorage::get_reward_curve(5).time_at(30), 6);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 4).unwrap(), 1144);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), 2597);
+
+		XykStorage::deactivate_liquidity_v2(Origin::signed(2), 5, 5000).unwrap();
+		assert_eq!(XykStorage::get_rewards_info(2, 5).last_checkpoint, 6);
+		XykStorage::claim_rewards_all_v2(Origin::signed(2), 5).unwrap();
 	});
 }
 
 #[test]
-fn sell_N_no_such_pool() {
+#[serial]
+fn set_reward_curve_migrates_checkpoints_under_previous_curve_W() {
 	new_test_ext().execute_with(|| {
-		initialize();
+		initialize_rewards_in_two_pools();
 
+		let quotient = FixedU128::saturating_from_rational(110, 100);
+		XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5).unwrap();
+		assert_eq!(
+			XykStorage::pending_reward_curve_migration(5),
+			Some(RewardCurveMigration {
+				previous_curve: RewardCurve {
+					quotient: Q,
+					distribution_period: 10,
+					start_block: 0,
+					start_time: 0,
+				},
+				pool_ratio_at_change: U256::from(u128::MAX),
+				last_visited: None,
+			})
+		);
 		assert_err!(
-			XykStorage::sell_asset(Origin::signed(2), 0, 10, 250000, 0),
-			Error::<Test>::NoSuchPool,
-		); // selling 250000 assetId 0 of pool 0 10 (only pool 0 1 exists)
+			XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5),
+			Error::<Test>::RewardCurveMigrationPending,
+		);
+
+		// not migrated checkpoint is checkpointed under previous curve on the fly
+		System::set_block_number(30);
+		MockPromotedPoolApi::instance()
+			.lock()
+			.unwrap()
+			.insert(5, U256::from(u128::MAX) * U256::from(2));
+		let rewards = XykStorage::calculate_rewards_amount_v2(2, 5).unwrap();
+		assert_eq!(rewards, 2597);
+		assert_eq!(XykStorage::get_rewards_info(2, 5).last_checkpoint, 0);
+
+		XykStorage::on_idle(30, Weight::from_ref_time(u64::MAX));
+		assert_eq!(XykStorage::pending_reward_curve_migration(5), None);
+		assert_eq!(
+			XykStorage::get_rewards_info(2, 5),
+			RewardInfo {
+				activated_amount: 10000,
+				rewards_not_yet_claimed: 291,
+				rewards_already_claimed: 0,
+				last_checkpoint: 2,
+				pool_ratio_at_last_checkpoint: U256::from(u128::MAX),
+				missing_at_last_checkpoint: U256::from(9708),
+			}
+		);
+		assert_eq!(XykStorage::calculate_rewards_amount_v2(2, 5).unwrap(), rewards);
+		// checkpoints of other pools are left intact
+		assert_eq!(XykStorage::get_rewards_info(2, 4).last_checkpoint, 0);
+
+		XykStorage::set_reward_curve(Origin::root(), 4, quotient, 5).unwrap();
 	});
 }
+
 #[test]
-fn sell_N_not_enough_selling_assset() {
+#[serial]
+fn set_reward_curve_of_pool_while_migration_of_other_pool_is_pending_W() {
 	new_test_ext().execute_with(|| {
-		initialize();
+		initialize_rewards_in_two_pools();
 
-		assert_err!(
-			XykStorage::sell_asset(Origin::signed(2), 1, 4, 1000000000000000000000, 0),
-			Error::<Test>::NotEnoughAssets,
-		); // selling 1000000000000000000000 assetId 0 of pool 0 1 (user has only 960000000000000000000)
+		let quotient = FixedU128::saturating_from_rational(110, 100);
+		XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5).unwrap();
+		XykStorage::set_reward_curve(Origin::root(), 4, quotient, 5).unwrap();
+		assert!(XykStorage::pending_reward_curve_migration(4).is_some());
+		assert!(XykStorage::pending_reward_curve_migration(5).is_some());
+
+		System::set_block_number(30);
+		XykStorage::on_idle(30, Weight::from_ref_time(u64::MAX));
+		assert_eq!(XykStorage::pending_reward_curve_migration(4), None);
+		assert_eq!(XykStorage::pending_reward_curve_migration(5), None);
+		assert_eq!(XykStorage::get_rewards_info(2, 4).last_checkpoint, 2);
+		assert_eq!(XykStorage::get_rewards_info(2, 5).last_checkpoint, 2);
 	});
 }
 
 #[test]
-fn sell_N_insufficient_output_amount() {
+#[serial]
+fn set_reward_curve_waits_for_rewards_accounts_index_W() {
 	new_test_ext().execute_with(|| {
-		initialize();
+		initialize_rewards_in_two_pools();
+
+		// rewards info stored before the index
+		RewardsInfoAccounts::<Test>::remove(4, 2);
+		RewardsInfoAccounts::<Test>::remove(5, 2);
+		StorageVersion::new(1).put::<XykStorage>();
 
+		let quotient = FixedU128::saturating_from_rational(110, 100);
 		assert_err!(
-			XykStorage::sell_asset(Origin::signed(2), 1, 4, 250000, 500000),
-			Error::<Test>::InsufficientOutputAmount,
-		); // selling 250000 assetId 0 of pool 0 1, by the formula user should get 166333 asset 1, but is requesting 500000
+			XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5),
+			Error::<Test>::RewardCurveMigrationPending,
+		);
+
+		XykStorage::on_idle(10, Weight::from_ref_time(u64::MAX));
+		assert_eq!(XykStorage::on_chain_storage_version(), 2);
+		assert_eq!(RewardsInfoAccounts::<Test>::get(4, 2), Some(()));
+		assert_eq!(RewardsInfoAccounts::<Test>::get(5, 2), Some(()));
+
+		XykStorage::set_reward_curve(Origin::root(), 5, quotient, 5).unwrap();
 	});
 }
 
 #[test]
-fn sell_N_zero_amount() {
+#[serial]
+fn set_reward_curve_N() {
 	new_test_ext().execute_with(|| {
-		initialize();
+		initialize_rewards_in_two_pools();
 
 		assert_err!(
-			XykStorage::sell_asset(Origin::signed(2), 1, 4, 0, 500000),
-			Error::<Test>::ZeroAmount,
-		); // selling 0 assetId 0 of pool 0 1
+			XykStorage::set_reward_curve(
+				Origin::signed(2),
+				4,
+				FixedU128::saturating_from_rational(110, 100),
+				5
+			),
+			sp_runtime::DispatchError::BadOrigin,
+		);
+		assert_err!(
+			XykStorage::set_reward_curve(
+				Origin::root(),
+				6,
+				FixedU128::saturating_from_rational(110, 100),
+				5
+			),
+			Error::<Test>::NotAPromotedPool,
+		);
+		for (quotient, distribution_period) in [
+			(FixedU128::saturating_from_rational(110, 100), 0),
+			(FixedU128::saturating_from_rational(100, 100), 5),
+			(FixedU128::saturating_from_rational(1005, 1000), 5),
+			(FixedU128::saturating_from_rational(201, 100), 5),
+		] {
+			assert_err!(
+				XykStorage::set_reward_curve(Origin::root(), 4, quotient, distribution_period),
+				Error::<Test>::InvalidRewardCurve,
+			);
+		}
+
+		XykStorage::set_reward_curve(
+			Origin::root(),
+			4,
+			FixedU128::saturating_from_rational(200, 100),
+			1,
+		)
+		.unwrap();
 	});
 }
 
@@ -1368,6 +3325,7 @@ fn mint_W() {
 			4,
 			20000000000000000000,
 			30000000000000000001,
+			0,
 		)
 		.unwrap();
 
@@ -1393,6 +3351,30 @@ fn mint_W() {
 	});
 }
 
+#[test]
+fn mint_N_insufficient_liquidity_minted() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		// minting with 20000000000000000000 assetId 1 yields 25000000000000000000 liquidity assets
+		assert_err!(
+			XykStorage::mint_liquidity(
+				Origin::signed(2),
+				1,
+				4,
+				20000000000000000000,
+				30000000000000000001,
+				25000000000000000001,
+			),
+			Error::<Test>::InsufficientLiquidityMinted,
+		);
+
+		assert_eq!(XykStorage::total_supply(5), 50000000000000000000);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (40000000000000000000, 60000000000000000000));
+		assert_eq!(XykStorage::balance(1, 2), 960000000000000000000);
+		assert_eq!(XykStorage::balance(4, 2), 940000000000000000000);
+	});
+}
+
 #[test]
 fn mint_W_other_way() {
 	new_test_ext().execute_with(|| {
@@ -1404,6 +3386,7 @@ fn mint_W_other_way() {
 			1,
 			30000000000000000000,
 			300000000000000000000,
+			0,
 		)
 		.unwrap();
 
@@ -1423,7 +3406,7 @@ fn mint_N_no_such_pool() {
 	new_test_ext().execute_with(|| {
 		initialize();
 		assert_err!(
-			XykStorage::mint_liquidity(Origin::signed(2), 0, 10, 250000, 250000),
+			XykStorage::mint_liquidity(Origin::signed(2), 0, 10, 250000, 250000, 0),
 			Error::<Test>::NoSuchPool,
 		); // minting pool 0 10 with 250000 assetId 0 (only pool 0 1 exists)
 	});
@@ -1439,7 +3422,8 @@ fn mint_N_not_enough_first_asset() {
 				1,
 				4,
 				1000000000000000000000,
-				10000000000000000000000
+				10000000000000000000000,
+				0
 			),
 			Error::<Test>::NotEnoughAssets,
 		); // minting pool 0 1 with 1000000000000000000000 assetId 0 (user has only 960000000000000000000)
@@ -1457,6 +3441,7 @@ fn mint_N_not_enough_second_asset() {
 				1,
 				1000000000000000000000,
 				10000000000000000000000,
+				0,
 			),
 			Error::<Test>::NotEnoughAssets,
 		); // minting pool 0 1 with 1000000000000000000000 assetId 1 (user has only 940000000000000000000)
@@ -1468,7 +3453,7 @@ fn min_N_zero_amount() {
 	new_test_ext().execute_with(|| {
 		initialize();
 		assert_err!(
-			XykStorage::mint_liquidity(Origin::signed(2), 1, 4, 0, 10),
+			XykStorage::mint_liquidity(Origin::signed(2), 1, 4, 0, 10, 0),
 			Error::<Test>::ZeroAmount,
 		); // minting pool 0 1 with 0 assetId 1
 	});
@@ -1479,7 +3464,7 @@ fn mint_N_second_asset_amount_exceeded_expectations() {
 	new_test_ext().execute_with(|| {
 		initialize();
 		assert_err!(
-			XykStorage::mint_liquidity(Origin::signed(2), 1, 4, 250000, 10),
+			XykStorage::mint_liquidity(Origin::signed(2), 1, 4, 250000, 10, 0),
 			Error::<Test>::SecondAssetAmountExceededExpectations,
 		); // minting pool 0 10 with 250000 assetId 0 (only pool 0 1 exists)
 	});
@@ -1489,7 +3474,7 @@ fn mint_N_second_asset_amount_exceeded_expectations() {
 fn burn_W() {
 	new_test_ext().execute_with(|| {
 		initialize();
-		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 25000000000000000000).unwrap(); // burning 20000000000000000000 asset 0 of pool 0 1
+		XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 25000000000000000000, 0, 0).unwrap(); // burning 20000000000000000000 asset 0 of pool 0 1
 
 		assert_eq!(XykStorage::balance(5, 2), 25000000000000000000); // amount of liquidity assets owned by user by creating pool and burning
 		assert_eq!(XykStorage::asset_pool((1, 4)), (20000000000000000000, 30000000000000000000));
@@ -1513,11 +3498,75 @@ fn burn_W() {
 	});
 }
 
+#[test]
+fn burn_N_insufficient_burned_assets_amount() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		// burning half of liquidity yields 20000000000000000000 asset 1 and 30000000000000000000 asset 4
+		assert_err!(
+			XykStorage::burn_liquidity(
+				Origin::signed(2),
+				1,
+				4,
+				25000000000000000000,
+				20000000000000000001,
+				0
+			),
+			Error::<Test>::InsufficientBurnedAssetsAmount,
+		);
+		assert_err!(
+			XykStorage::burn_liquidity(
+				Origin::signed(2),
+				1,
+				4,
+				25000000000000000000,
+				0,
+				30000000000000000001
+			),
+			Error::<Test>::InsufficientBurnedAssetsAmount,
+		);
+		assert_eq!(XykStorage::balance(5, 2), 50000000000000000000);
+
+		XykStorage::burn_liquidity(
+			Origin::signed(2),
+			1,
+			4,
+			25000000000000000000,
+			20000000000000000000,
+			30000000000000000000,
+		)
+		.unwrap();
+		assert_eq!(XykStorage::balance(5, 2), 25000000000000000000);
+	});
+}
+
+#[test]
+fn burn_with_deadline_W() {
+	new_test_ext().execute_with(|| {
+		initialize();
+		XykStorage::burn_liquidity_with_deadline(
+			Origin::signed(2),
+			1,
+			4,
+			25000000000000000000,
+			0,
+			0,
+			1,
+		)
+		.unwrap();
+
+		assert_eq!(XykStorage::balance(5, 2), 25000000000000000000);
+		assert_eq!(XykStorage::asset_pool((1, 4)), (20000000000000000000, 30000000000000000000));
+		assert_eq!(XykStorage::balance(1, 2), 980000000000000000000);
+		assert_eq!(XykStorage::balance(4, 2), 970000000000000000000);
+	});
+}
+
 #[test]
 fn burn_W_other_way() {
 	new_test_ext().execute_with(|| {
 		initialize();
-		XykStorage::burn_liquidity(Origin::signed(2), 4, 1, 25000000000000000000).unwrap(); // burning 30000000000000000000 asset 1 of pool 0 1
+		XykStorage::burn_liquidity(Origin::signed(2), 4, 1, 25000000000000000000, 0, 0).unwrap(); // burning 30000000000000000000 asset 1 of pool 0 1
 
 		assert_eq!(XykStorage::balance(5, 2), 25000000000000000000); // amount of liquidity assets owned by user by creating pool and burning
 		assert_eq!(XykStorage::asset_pool((1, 4)), (20000000000000000000, 30000000000000000000));
@@ -1535,7 +3584,7 @@ fn burn_N_not_enough_liquidity_asset() {
 		initialize();
 		// burning pool 0 1 with 500000000000000000000 liquidity asset amount (user has only 100000000000000000000 liquidity asset amount)
 		assert_err!(
-			XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 500000000000000000000,),
+			XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 500000000000000000000, 0, 0,),
 			Error::<Test>::NotEnoughAssets,
 		);
 	});
@@ -1547,7 +3596,7 @@ fn burn_N_no_such_pool() {
 		initialize();
 		// burning pool 0 10 with 250000 assetId 0 (only pool 0 1 exists)
 		assert_err!(
-			XykStorage::burn_liquidity(Origin::signed(2), 0, 10, 250000,),
+			XykStorage::burn_liquidity(Origin::signed(2), 0, 10, 250000, 0, 0,),
 			Error::<Test>::NoSuchPool,
 		);
 	});
@@ -1558,7 +3607,7 @@ fn burn_N_zero_amount() {
 	new_test_ext().execute_with(|| {
 		initialize();
 		assert_err!(
-			XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 0,),
+			XykStorage::burn_liquidity(Origin::signed(2), 1, 4, 0, 0, 0,),
 			Error::<Test>::ZeroAmount,
 		); // burning pool 0 1 with 0 assetId 1
 	});
@@ -1938,10 +3987,24 @@ fn rewards_rounding_during_often_mint() {
 			.insert(4, U256::from(u128::MAX) * U256::from(0));
 		XykStorage::transfer(0, 2, 3, 10000000000000000000000000).unwrap();
 		XykStorage::transfer(1, 2, 3, 10000000000000000000000000).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(2), 0, 1, 25000000000000000000000, 2000000000000)
-			.unwrap();
-		XykStorage::mint_liquidity(Origin::signed(3), 0, 1, 25000000000000000000000, 2000000000000)
-			.unwrap();
+		XykStorage::mint_liquidity(
+			Origin::signed(2),
+			0,
+			1,
+			25000000000000000000000,
+			2000000000000,
+			0,
+		)
+		.unwrap();
+		XykStorage::mint_liquidity(
+			Origin::signed(3),
+			0,
+			1,
+			25000000000000000000000,
+			2000000000000,
+			0,
+		)
+		.unwrap();
 
 		let mut non_minter_higher_rewards_counter = 0;
 		let mut higher_rewards_cumulative = 0;
@@ -1958,6 +4021,7 @@ fn rewards_rounding_during_often_mint() {
 					1,
 					34000000000000000000,
 					68000000000000000000,
+					0,
 				)
 				.unwrap();
 				log::info!("----------------------------");
@@ -2021,10 +4085,10 @@ fn rewards_storage_right_amounts_start1() {
 		XykStorage::transfer(1, 2, 6, 20010).unwrap();
 		XykStorage::transfer(2, 2, 6, 20010).unwrap();
 		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 10000, 10010).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(5), 1, 2, 10000, 10010).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(6), 1, 2, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010, 0).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 10000, 10010, 0).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(5), 1, 2, 10000, 10010, 0).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(6), 1, 2, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(100);
 		MockPromotedPoolApi::instance()
@@ -2081,7 +4145,7 @@ fn rewards_storage_right_amounts_start1() {
 
 		// usecase 6 burn some
 		user_balance_before = XykStorage::balance(0, 3);
-		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000, 0, 0).unwrap();
 		user_balance_after = XykStorage::balance(0, 3);
 		rewards_info = XykStorage::get_rewards_info(3, 4);
 
@@ -2092,7 +4156,7 @@ fn rewards_storage_right_amounts_start1() {
 
 		// usecase 7 mint some
 		user_balance_before = XykStorage::balance(0, 4);
-		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 5000, 5010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 5000, 5010, 0).unwrap();
 		user_balance_after = XykStorage::balance(0, 4);
 		rewards_info = XykStorage::get_rewards_info(4, 4);
 
@@ -2165,9 +4229,9 @@ fn rewards_storage_right_amounts_start2() {
 		XykStorage::transfer(1, 2, 6, 20010).unwrap();
 		XykStorage::transfer(2, 2, 6, 20010).unwrap();
 		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 10000, 10010).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(5), 1, 2, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010, 0).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 10000, 10010, 0).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(5), 1, 2, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(100);
 		MockPromotedPoolApi::instance()
@@ -2175,10 +4239,10 @@ fn rewards_storage_right_amounts_start2() {
 			.unwrap()
 			.insert(4, U256::from(u128::MAX) * U256::from(10));
 
-		XykStorage::burn_liquidity(Origin::signed(2), 1, 2, 5000).unwrap();
-		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000).unwrap();
-		XykStorage::burn_liquidity(Origin::signed(4), 1, 2, 5000).unwrap();
-		XykStorage::burn_liquidity(Origin::signed(5), 1, 2, 5000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(2), 1, 2, 5000, 0, 0).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000, 0, 0).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(4), 1, 2, 5000, 0, 0).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(5), 1, 2, 5000, 0, 0).unwrap();
 
 		System::set_block_number(200);
 		MockPromotedPoolApi::instance()
@@ -2219,7 +4283,7 @@ fn rewards_storage_right_amounts_start2() {
 
 		// usecase 9 burn some
 		user_balance_before = XykStorage::balance(0, 3);
-		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000).unwrap();
+		XykStorage::burn_liquidity(Origin::signed(3), 1, 2, 5000, 0, 0).unwrap();
 		user_balance_after = XykStorage::balance(0, 3);
 		rewards_info = XykStorage::get_rewards_info(3, 4);
 
@@ -2230,7 +4294,7 @@ fn rewards_storage_right_amounts_start2() {
 
 		// usecase 10 mint some
 		user_balance_before = XykStorage::balance(0, 4);
-		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 5000, 5010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(4), 1, 2, 5000, 5010, 0).unwrap();
 		user_balance_after = XykStorage::balance(0, 4);
 		rewards_info = XykStorage::get_rewards_info(4, 4);
 
@@ -2289,7 +4353,7 @@ fn rewards_storage_right_amounts_start3() {
 		XykStorage::transfer(2, 2, 4, 20010).unwrap();
 
 		XykStorage::activate_liquidity_v2(Origin::signed(2), 4, 10000, None).unwrap();
-		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010).unwrap();
+		XykStorage::mint_liquidity(Origin::signed(3), 1, 2, 10000, 10010, 0).unwrap();
 
 		System::set_block_number(100);
 		MockPromotedPoolApi::instance()
@@ -2333,6 +4397,30 @@ fn rewards_storage_right_amounts_start3() {
 	});
 }
 
+fn insert_rewards_v1_users() {
+	migrations::v1::PromotedPoolsRewards::insert(4, 200000);
+	migrations::v1::LiquidityMiningActiveUser::<Test>::insert((2, 4), 10000);
+	migrations::v1::LiquidityMiningActiveUser::<Test>::insert((3, 4), 10000);
+	migrations::v1::LiquidityMiningActivePool::<Test>::insert(4, 20000);
+	migrations::v1::LiquidityMiningUser::<Test>::insert(
+		(2, 4),
+		(0, U256::from(0), U256::from(10000)),
+	);
+	migrations::v1::LiquidityMiningUser::<Test>::insert(
+		(3, 4),
+		(10, U256::from(0), U256::from(10000)),
+	);
+	migrations::v1::LiquidityMiningPool::<Test>::insert(
+		4,
+		(10, U256::from(21985), U256::from(15584)),
+	);
+	migrations::v1::LiquidityMiningUserToBeClaimed::<Test>::insert((2, 4), 0);
+	migrations::v1::LiquidityMiningUserClaimed::<Test>::insert((2, 4), 0);
+	migrations::v1::LiquidityMiningUserToBeClaimed::<Test>::insert((3, 4), 0);
+	migrations::v1::LiquidityMiningUserClaimed::<Test>::insert((3, 4), 0);
+	StorageVersion::new(0).put::<XykStorage>();
+}
+
 #[test]
 #[serial]
 fn migration_work() {
@@ -2358,42 +4446,36 @@ fn migration_work() {
 
 		System::set_block_number(200);
 		MockPromotedPoolApi::instance().lock().unwrap().insert(4, U256::from(200000));
-
-		LiquidityMiningActiveUser::<Test>::insert((2, 4), 10000);
-		LiquidityMiningActiveUser::<Test>::insert((3, 4), 10000);
-		LiquidityMiningActivePool::<Test>::insert(4, 20000);
-		LiquidityMiningUser::<Test>::insert((2, 4), (0, U256::from(0), U256::from(10000)));
-		LiquidityMiningUser::<Test>::insert((3, 4), (10, U256::from(0), U256::from(10000)));
-		LiquidityMiningPool::<Test>::insert(4, (10, U256::from(21985), U256::from(15584)));
-		LiquidityMiningUserToBeClaimed::<Test>::insert((2, 4), 0);
-		LiquidityMiningUserClaimed::<Test>::insert((2, 4), 0);
-		LiquidityMiningUserToBeClaimed::<Test>::insert((3, 4), 0);
-		LiquidityMiningUserClaimed::<Test>::insert((3, 4), 0);
-
-		log::info!("{:?}", XykStorage::liquidity_mining_user((2, 4)));
-		log::info!("{:?}", XykStorage::liquidity_mining_user((3, 4)));
-		log::info!("{:?}", XykStorage::liquidity_mining_pool(4));
+		insert_rewards_v1_users();
 
 		assert_eq!(XykStorage::balance(0, 2), 0);
 		assert_eq!(XykStorage::balance(0, 3), 0);
 
-		<XykStorage as XykFunctionsTrait<AccountId>>::rewards_migrate_v1_to_v2(2, 4).unwrap();
-		log::info!("{:?}", MockPromotedPoolApi::get_pool_rewards(4));
-		<XykStorage as XykFunctionsTrait<AccountId>>::rewards_migrate_v1_to_v2(3, 4).unwrap();
-		log::info!("{:?}", MockPromotedPoolApi::get_pool_rewards(4)); // SHOULD BE AROUND 0
+		migrations::v1::migrate_user::<Test>(2, 4).unwrap();
+		migrations::v1::migrate_user::<Test>(3, 4).unwrap();
+		log::info!("{:?}", migrations::v1::PromotedPoolsRewards::get(4)); // SHOULD BE AROUND 0
 
 		assert_eq!(XykStorage::balance(0, 2), 156202);
 		assert_eq!(XykStorage::balance(0, 3), 43792);
-		assert_eq!(XykStorage::liquidity_mining_user((2, 4)), (0, U256::from(0), U256::from(0)));
-		assert_eq!(XykStorage::liquidity_mining_user((3, 4)), (0, U256::from(0), U256::from(0)));
-		assert_eq!(XykStorage::liquidity_mining_pool(4), (0, U256::from(0), U256::from(0)));
+		assert_eq!(
+			migrations::v1::LiquidityMiningUser::<Test>::get((2, 4)),
+			(0, U256::from(0), U256::from(0))
+		);
+		assert_eq!(
+			migrations::v1::LiquidityMiningUser::<Test>::get((3, 4)),
+			(0, U256::from(0), U256::from(0))
+		);
+		assert_eq!(
+			migrations::v1::LiquidityMiningPool::<Test>::get(4),
+			(0, U256::from(0), U256::from(0))
+		);
 
 		let mut rewards_info = XykStorage::get_rewards_info(2, 4);
 		assert_eq!(rewards_info.activated_amount, 10000);
 		assert_eq!(rewards_info.rewards_not_yet_claimed, 0);
 		assert_eq!(rewards_info.rewards_already_claimed, 0);
 		assert_eq!(rewards_info.last_checkpoint, 20);
-		assert_eq!(rewards_info.pool_ratio_at_last_checkpoint, U256::from(200000)); //these values will be from rew2, but reading pool_ratio_at_last_checkpoint works
+		assert_eq!(rewards_info.pool_ratio_at_last_checkpoint, U256::from(200000));
 		assert_eq!(rewards_info.missing_at_last_checkpoint, U256::from_dec_str("3118").unwrap());
 
 		rewards_info = XykStorage::get_rewards_info(3, 4);
@@ -2401,7 +4483,154 @@ fn migration_work() {
 		assert_eq!(rewards_info.rewards_not_yet_claimed, 0);
 		assert_eq!(rewards_info.rewards_already_claimed, 0);
 		assert_eq!(rewards_info.last_checkpoint, 20);
-		assert_eq!(rewards_info.pool_ratio_at_last_checkpoint, U256::from(43798)); //these values will be from rew2, but reading pool_ratio_at_last_checkpoint works
+		assert_eq!(rewards_info.pool_ratio_at_last_checkpoint, U256::from(200000));
 		assert_eq!(rewards_info.missing_at_last_checkpoint, U256::from_dec_str("5584").unwrap());
+
+		// all users are migrated, leftovers are cleared in next idle block
+		assert_eq!(XykStorage::on_chain_storage_version(), 0);
+		XykStorage::on_idle(201, Weight::from_ref_time(u64::MAX));
+		// rewards accounts are indexed right after in the same idle block
+		assert_eq!(XykStorage::on_chain_storage_version(), 2);
+		assert_eq!(migrations::v1::PromotedPoolsRewards::get(4), 0);
+	});
+}
+
+#[test]
+#[serial]
+fn migration_is_bounded_by_idle_weight() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		System::set_block_number(1);
+
+		let acc_id: u128 = 2;
+		let amount: u128 = 10000000000;
+
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::transfer(
+			0,
+			2,
+			<Test as Config>::LiquidityMiningIssuanceVault::get(),
+			10000000000,
+		)
+		.unwrap();
+
+		System::set_block_number(200);
+		MockPromotedPoolApi::instance().lock().unwrap().insert(4, U256::from(200000));
+		insert_rewards_v1_users();
+
+		let user_weight = <Test as Config>::WeightInfo::rewards_v1_migration_step();
+		let migrated_users = || {
+			[2, 3]
+				.iter()
+				.filter(|user| XykStorage::get_rewards_info(**user, 4).activated_amount != 0)
+				.count()
+		};
+
+		// not enough weight for a single user
+		assert_eq!(
+			XykStorage::on_idle(200, user_weight.saturating_sub(Weight::from_ref_time(1))),
+			Weight::from_ref_time(0)
+		);
+		assert_eq!(migrated_users(), 0);
+
+		assert_eq!(XykStorage::on_idle(200, user_weight), user_weight);
+		assert_eq!(migrated_users(), 1);
+		assert_eq!(XykStorage::on_chain_storage_version(), 0);
+
+		XykStorage::on_idle(201, user_weight);
+		assert_eq!(migrated_users(), 2);
+		assert_eq!(migrations::v1::LiquidityMiningActiveUser::<Test>::iter().count(), 0);
+		assert_eq!(migrations::v1::LiquidityMiningActivePool::<Test>::get(4), 0);
+		assert_eq!(XykStorage::on_chain_storage_version(), 0);
+
+		XykStorage::on_idle(202, user_weight);
+		assert_eq!(XykStorage::on_chain_storage_version(), 2);
+		assert!(XykStorage::balance(0, 2) > 0);
+		assert!(XykStorage::balance(0, 3) > 0);
+	});
+}
+
+#[test]
+#[serial]
+fn migration_pays_out_users_with_only_burned_rewards_to_claim() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		System::set_block_number(1);
+
+		let acc_id: u128 = 2;
+		let amount: u128 = 10000000000;
+
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::transfer(
+			0,
+			2,
+			<Test as Config>::LiquidityMiningIssuanceVault::get(),
+			10000000000,
+		)
+		.unwrap();
+
+		// pool 4 is not promoted in rewards v2, user 5 burned all its liquidity in rewards v1
+		System::set_block_number(200);
+		migrations::v1::PromotedPoolsRewards::insert(4, 200000);
+		migrations::v1::LiquidityMiningUserToBeClaimed::<Test>::insert((5, 4), 1234);
+		migrations::v1::LiquidityMiningUserClaimed::<Test>::insert((5, 4), 0);
+		StorageVersion::new(0).put::<XykStorage>();
+
+		XykStorage::on_idle(200, Weight::from_ref_time(u64::MAX));
+
+		assert_eq!(XykStorage::balance(0, 5), 1234);
+		assert_eq!(migrations::v1::LiquidityMiningUserToBeClaimed::<Test>::get((5, 4)), 0);
+		assert_eq!(XykStorage::get_rewards_info(5, 4).activated_amount, 0);
+		assert_eq!(XykStorage::on_chain_storage_version(), 2);
+	});
+}
+
+#[test]
+#[serial]
+fn migration_of_failed_user_is_retried_later() {
+	new_test_ext().execute_with(|| {
+		MockPromotedPoolApi::instance().lock().unwrap().clear();
+		System::set_block_number(1);
+
+		let acc_id: u128 = 2;
+		let amount: u128 = 10000000000;
+
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+		XykStorage::create_new_token(&acc_id, amount);
+
+		System::set_block_number(200);
+		MockPromotedPoolApi::instance().lock().unwrap().insert(4, U256::from(200000));
+		insert_rewards_v1_users();
+
+		// rewards vault is empty, v1 rewards cannot be paid out
+		let balance_before = XykStorage::balance(0, 2);
+		assert!(migrations::v1::migrate_user::<Test>(2, 4).is_err());
+		XykStorage::on_idle(200, Weight::from_ref_time(u64::MAX));
+
+		assert_eq!(XykStorage::balance(0, 2), balance_before);
+		assert_eq!(migrations::v1::LiquidityMiningActiveUser::<Test>::get((2, 4)), 10000);
+		assert_eq!(migrations::v1::LiquidityMiningActiveUser::<Test>::get((3, 4)), 10000);
+		assert_eq!(migrations::v1::LiquidityMiningActivePool::<Test>::get(4), 20000);
+		assert_eq!(XykStorage::get_rewards_info(2, 4).activated_amount, 0);
+		assert_eq!(XykStorage::on_chain_storage_version(), 0);
+
+		XykStorage::transfer(
+			0,
+			2,
+			<Test as Config>::LiquidityMiningIssuanceVault::get(),
+			10000000000,
+		)
+		.unwrap();
+		XykStorage::on_idle(201, Weight::from_ref_time(u64::MAX));
+
+		assert!(XykStorage::balance(0, 3) > 0);
+		assert_eq!(XykStorage::get_rewards_info(2, 4).activated_amount, 10000);
+		assert_eq!(XykStorage::get_rewards_info(3, 4).activated_amount, 10000);
+		assert_eq!(XykStorage::on_chain_storage_version(), 2);
 	});
 }
@@ -17,7 +17,21 @@ pub trait WeightInfo {
 	fn claim_rewards_all_v2() -> Weight;
 	fn activate_liquidity_v2() -> Weight;
 	fn deactivate_liquidity_v2() -> Weight;
-	fn rewards_migrate_v1_to_v2() -> Weight;
+	fn rewards_v1_migration_step() -> Weight;
+	fn multiswap_sell_asset(x: u32, ) -> Weight;
+	fn multiswap_buy_asset(x: u32, ) -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn create_stable_pool() -> Weight;
+	fn flash_swap() -> Weight;
+	fn create_reward_schedule() -> Weight;
+	fn claim_schedule_rewards() -> Weight;
+	fn refund_reward_schedule() -> Weight;
+	fn unpromote_pool() -> Weight;
+	fn claim_rewards_all_pools_v2(x: u32, ) -> Weight;
+	fn compound_rewards() -> Weight;
+	fn set_reward_curve() -> Weight;
+	fn set_reward_token_whitelisted() -> Weight;
+	fn rewards_migrate_reward_curve() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -39,18 +53,18 @@ impl WeightInfo for () {
 	}
 	fn mint_liquidity() -> Weight {
 		Weight::from_ref_time(270_706_000)
-			.saturating_add(RocksDbWeight::get().reads(14 as u64))
-			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+			.saturating_add(RocksDbWeight::get().reads(31 as u64))
+			.saturating_add(RocksDbWeight::get().writes(27 as u64))
 	}
 	fn mint_liquidity_using_vesting_native_tokens() -> Weight {
 		Weight::from_ref_time(378_541_000)
-			.saturating_add(RocksDbWeight::get().reads(18 as u64))
-			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+			.saturating_add(RocksDbWeight::get().reads(35 as u64))
+			.saturating_add(RocksDbWeight::get().writes(31 as u64))
 	}
 	fn burn_liquidity() -> Weight {
 		Weight::from_ref_time(260_718_000)
-			.saturating_add(RocksDbWeight::get().reads(14 as u64))
-			.saturating_add(RocksDbWeight::get().writes(17 as u64))
+			.saturating_add(RocksDbWeight::get().reads(31 as u64))
+			.saturating_add(RocksDbWeight::get().writes(33 as u64))
 	}
 	fn promote_pool() -> Weight {
 		Weight::from_ref_time(36_108_000)
@@ -75,21 +89,94 @@ impl WeightInfo for () {
 	//TODO retest
 	fn activate_liquidity_v2() -> Weight {
 		Weight::from_ref_time(119_779_000)
-			.saturating_add(RocksDbWeight::get().reads(6 as u64))
-			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads(23 as u64))
+			.saturating_add(RocksDbWeight::get().writes(21 as u64))
 	}
 	
 	//TODO retest
 	fn deactivate_liquidity_v2() -> Weight {
 		Weight::from_ref_time(133_607_000)
-			.saturating_add(RocksDbWeight::get().reads(7 as u64))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(24 as u64))
+			.saturating_add(RocksDbWeight::get().writes(23 as u64))
 	}
 
 	//TODO retest
-	fn rewards_migrate_v1_to_v2() -> Weight {
+	fn rewards_v1_migration_step() -> Weight {
 		Weight::from_ref_time(133_607_000)
-			.saturating_add(RocksDbWeight::get().reads(7 as u64))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn multiswap_sell_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(151_597_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn multiswap_buy_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(158_939_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn set_pool_fee() -> Weight {
+		(Weight::from_ref_time(24_160_000))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn create_stable_pool() -> Weight {
+		(Weight::from_ref_time(245_380_000))
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	fn flash_swap() -> Weight {
+		(Weight::from_ref_time(174_520_000))
+			.saturating_add(RocksDbWeight::get().reads(17 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+	}
+	fn create_reward_schedule() -> Weight {
+		(Weight::from_ref_time(98_360_000))
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(14 as u64))
+	}
+	fn claim_schedule_rewards() -> Weight {
+		(Weight::from_ref_time(71_240_000))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn refund_reward_schedule() -> Weight {
+		(Weight::from_ref_time(58_910_000))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn unpromote_pool() -> Weight {
+		(Weight::from_ref_time(31_420_000))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn claim_rewards_all_pools_v2(x: u32, ) -> Weight {
+		(Weight::from_ref_time(12_400_000))
+			.saturating_add((Weight::from_ref_time(88_120_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((9 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
+	}
+	fn compound_rewards() -> Weight {
+		(Weight::from_ref_time(312_650_000))
+			.saturating_add(RocksDbWeight::get().reads(44 as u64))
+			.saturating_add(RocksDbWeight::get().writes(34 as u64))
+	}
+	fn set_reward_curve() -> Weight {
+		(Weight::from_ref_time(21_160_000))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_reward_token_whitelisted() -> Weight {
+		(Weight::from_ref_time(21_480_000))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn rewards_migrate_reward_curve() -> Weight {
+		(Weight::from_ref_time(38_420_000))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 }
@@ -4,7 +4,7 @@ use frame_support::pallet_prelude::*;
 use mangata_types::{Balance, TokenId};
 use mp_multipurpose_liquidity::{ActivateKind, BondKind};
 use sp_runtime::traits::{AtLeast32BitUnsigned, MaybeDisplay};
-use sp_std::fmt::Debug;
+use sp_std::{fmt::Debug, vec::Vec};
 
 pub trait StakingReservesProviderTrait {
 	type AccountId: Parameter
@@ -106,6 +106,20 @@ pub trait XykFunctionsTrait<AccountId> {
 		max_amount_in: Self::Balance,
 	) -> DispatchResult;
 
+	fn multiswap_sell_asset(
+		sender: AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		sold_asset_amount: Self::Balance,
+		min_amount_out: Self::Balance,
+	) -> DispatchResult;
+
+	fn multiswap_buy_asset(
+		sender: AccountId,
+		swap_token_list: Vec<Self::CurrencyId>,
+		bought_asset_amount: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> DispatchResult;
+
 	fn mint_liquidity(
 		sender: AccountId,
 		first_asset_id: Self::CurrencyId,
@@ -140,6 +154,8 @@ pub trait XykFunctionsTrait<AccountId> {
 
 	fn promote_pool(liquidity_token_id: TokenId) -> DispatchResult;
 
+	fn unpromote_pool(liquidity_token_id: TokenId) -> DispatchResult;
+
 	fn activate_liquidity_v2(
 		sender: AccountId,
 		liquidity_token_id: Self::CurrencyId,
@@ -153,10 +169,16 @@ pub trait XykFunctionsTrait<AccountId> {
 		amount: Self::Balance,
 	) -> DispatchResult;
 
-	fn rewards_migrate_v1_to_v2(
-		account: AccountId,
-		liquidity_token_id: Self::CurrencyId,
-	) -> DispatchResult;
-
 	fn is_liquidity_token(liquidity_asset_id: TokenId) -> bool;
 }
+
+pub trait PoolPriceOracleTrait {
+	/// Time weighted average prices of `first_asset_id` in `second_asset_id` and vice versa over
+	/// last `window` blocks, scaled by 10^18. `None` if there is no such pool or not enough
+	/// price history.
+	fn get_twap(
+		first_asset_id: TokenId,
+		second_asset_id: TokenId,
+		window: u32,
+	) -> Option<(Balance, Balance)>;
+}
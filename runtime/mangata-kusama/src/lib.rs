@@ -74,8 +74,12 @@ pub use pallet_issuance::{IssuanceInfo, PoolPromoteApi};
 pub use pallet_sudo_origin;
 pub use pallet_xyk;
 // XCM Imports
+use issuance_runtime_api::RpcRoundIssuance;
 use pallet_xyk::AssetMetadataMutationTrait;
-use xyk_runtime_api::{RpcAmountsResult, XYKRpcResult};
+use xyk_runtime_api::{
+	RpcAmountsResult, RpcLiquidityPosition, RpcPoolInfo, RpcPoolRewardsStats, RpcRoundRewards,
+	RpcSwapPathResult, RpcTwapResult, XYKRpcResult,
+};
 
 // Make the WASM binary available.
 #[cfg(feature = "std")]
@@ -155,10 +159,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("mangata-parachain"),
 	impl_name: create_runtime_str!("mangata-parachain"),
 	authoring_version: 10,
-	spec_version: 10,
+	spec_version: 11,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 10,
+	transaction_version: 11,
 	state_version: 0,
 };
 
@@ -441,18 +445,6 @@ impl Contains<TokenId> for TestTokensFilter {
 	}
 }
 
-pub struct RewardsMigrationAccountProvider<T: frame_system::Config>(PhantomData<T>);
-impl<T: frame_system::Config> Get<T::AccountId> for RewardsMigrationAccountProvider<T> {
-	fn get() -> T::AccountId {
-		let account32: sp_runtime::AccountId32 =
-			hex_literal::hex!["0e33df23356eb2e9e3baf0e8a5faae15bc70a6a5cce88f651a9faf6e8e937324"]
-				.into();
-		let mut init_account32 = sp_runtime::AccountId32::as_ref(&account32);
-		let init_account = T::AccountId::decode(&mut init_account32).unwrap();
-		init_account
-	}
-}
-
 pub struct AssetMetadataMutation;
 impl AssetMetadataMutationTrait for AssetMetadataMutation {
 	fn set_asset_info(
@@ -494,11 +486,13 @@ impl pallet_xyk::Config for Runtime {
 	type DisabledTokens = TestTokensFilter;
 	type AssetMetadataMutation = AssetMetadataMutation;
 	type WeightInfo = weights::pallet_xyk_weights::ModuleWeight<Runtime>;
-	type RewardsMigrateAccount = RewardsMigrationAccountProvider<Self>;
+	type Call = Call;
 }
 
 parameter_types! {
 	pub const BootstrapUpdateBuffer: BlockNumber = 300;
+	pub const MaxActiveBootstraps: u32 = 10;
+	pub const BootstrapInitializeWeightBudget: Weight = Weight::from_ref_time(MAXIMUM_BLOCK_WEIGHT / 10);
 }
 
 impl pallet_bootstrap::BootstrapBenchmarkingConfig for Runtime {}
@@ -507,6 +501,8 @@ impl pallet_bootstrap::Config for Runtime {
 	type Event = Event;
 	type PoolCreateApi = Xyk;
 	type BootstrapUpdateBuffer = BootstrapUpdateBuffer;
+	type MaxActiveBootstraps = MaxActiveBootstraps;
+	type InitializeWeightBudget = BootstrapInitializeWeightBudget;
 	type Currency = orml_tokens::MultiTokenCurrencyAdapter<Runtime>;
 	type VestingProvider = Vesting;
 	type TreasuryPalletId = TreasuryPalletId;
@@ -1298,6 +1294,144 @@ impl_runtime_apis! {
 				},
 			}
 		}
+
+		fn get_best_sell_path(
+			sold_token_id: TokenId,
+			bought_token_id: TokenId,
+			sell_amount: Balance,
+		) -> RpcSwapPathResult<Balance, TokenId> {
+			match Xyk::get_best_sell_path(sold_token_id, bought_token_id, sell_amount){
+				Ok((path, amount)) => RpcSwapPathResult{ path, amount },
+				Err(e) => {
+						log::warn!(target:"xyk", "rpc 'XYK::get_best_sell_path' error: '{:?}', returning default value instead", e);
+						Default::default()
+				},
+			}
+		}
+
+		fn get_best_buy_path(
+			sold_token_id: TokenId,
+			bought_token_id: TokenId,
+			buy_amount: Balance,
+		) -> RpcSwapPathResult<Balance, TokenId> {
+			match Xyk::get_best_buy_path(sold_token_id, bought_token_id, buy_amount){
+				Ok((path, amount)) => RpcSwapPathResult{ path, amount },
+				Err(e) => {
+						log::warn!(target:"xyk", "rpc 'XYK::get_best_buy_path' error: '{:?}', returning default value instead", e);
+						Default::default()
+				},
+			}
+		}
+
+		fn get_twap(
+			first_asset_id: TokenId,
+			second_asset_id: TokenId,
+			window: u32,
+		) -> RpcTwapResult<Balance> {
+			match Xyk::get_twap(first_asset_id, second_asset_id, window){
+				Ok((first_asset_price, second_asset_price)) => RpcTwapResult{ first_asset_price, second_asset_price },
+				Err(e) => {
+						log::warn!(target:"xyk", "rpc 'XYK::get_twap' error: '{:?}', returning default value instead", e);
+						Default::default()
+				},
+			}
+		}
+
+		fn get_pools(
+			start_after: Option<TokenId>,
+			limit: u32,
+		) -> Vec<RpcPoolInfo<Balance, TokenId>> {
+			Xyk::get_pools(start_after, limit)
+				.into_iter()
+				.map(|pool| RpcPoolInfo {
+					liquidity_token_id: pool.liquidity_token_id,
+					first_asset_id: pool.first_asset_id,
+					second_asset_id: pool.second_asset_id,
+					first_asset_reserve: pool.first_asset_reserve,
+					second_asset_reserve: pool.second_asset_reserve,
+					liquidity_token_issuance: pool.liquidity_token_issuance,
+					is_promoted: pool.is_promoted,
+					activated_amount: pool.activated_amount,
+				})
+				.collect()
+		}
+
+		fn get_user_portfolio(
+			user: AccountId,
+			start_after: Option<TokenId>,
+			limit: u32,
+		) -> Vec<RpcLiquidityPosition<Balance, TokenId>> {
+			match Xyk::get_user_liquidity_positions(&user, start_after, limit){
+				Ok(positions) => positions
+					.into_iter()
+					.map(|position| {
+						let reserve_status = MultiPurposeLiquidity::get_reserve_status(
+							&user,
+							position.liquidity_token_id,
+						);
+						RpcLiquidityPosition {
+							liquidity_token_id: position.liquidity_token_id,
+							first_asset_id: position.first_asset_id,
+							second_asset_id: position.second_asset_id,
+							free_amount: position.free_amount,
+							reserved_amount: position.reserved_amount,
+							activated_amount: position.activated_amount,
+							first_asset_amount: position.first_asset_amount,
+							second_asset_amount: position.second_asset_amount,
+							staked_unactivated_reserves: reserve_status.staked_unactivated_reserves,
+							activated_unstaked_reserves: reserve_status.activated_unstaked_reserves,
+							staked_and_activated_reserves: reserve_status
+								.staked_and_activated_reserves,
+							unspent_reserves: reserve_status.unspent_reserves,
+							relock_amount: reserve_status.relock_amount,
+							claimable_rewards: position.claimable_rewards,
+						}
+					})
+					.collect(),
+				Err(e) => {
+						log::warn!(target:"xyk", "rpc 'XYK::get_user_portfolio' error: '{:?}', returning default value instead", e);
+						Default::default()
+				},
+			}
+		}
+
+		fn get_pools_rewards_stats(
+			start_after: Option<TokenId>,
+			limit: u32,
+		) -> Vec<RpcPoolRewardsStats<Balance, TokenId>> {
+			Xyk::get_pools_rewards_stats(start_after, limit)
+				.into_iter()
+				.map(|stats| RpcPoolRewardsStats {
+					liquidity_token_id: stats.liquidity_token_id,
+					rewards_history: stats
+						.rewards_history
+						.into_iter()
+						.map(|(round, rewards)| RpcRoundRewards { round, rewards })
+						.collect(),
+					activated_amount: stats.activated_amount,
+					reserves_valuation: stats.reserves_valuation,
+					activated_valuation: stats.activated_valuation,
+					pool_weight: stats.pool_weight,
+					total_pools_weight: stats.total_pools_weight,
+				})
+				.collect()
+		}
+	}
+
+	impl issuance_runtime_api::IssuanceApi<Block, Balance> for Runtime {
+		fn get_issuance_projection(
+			from_round: u32,
+			rounds: u32,
+		) -> Vec<RpcRoundIssuance<Balance>> {
+			Issuance::project_issuance(from_round, rounds)
+				.into_iter()
+				.map(|(round, liquidity_mining_issuance, staking_issuance)| RpcRoundIssuance {
+					round,
+					liquidity_mining_issuance,
+					staking_issuance,
+				})
+				.collect()
+		}
 	}
 
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
@@ -60,6 +60,9 @@ pub trait WeightInfo {
 	fn provision_vested() -> Weight;
 	fn claim_and_activate_liquidity_tokens() -> Weight;
 	fn finalize() -> Weight;
+	fn finish_bootstrap() -> Weight;
+	fn withdraw_provisions() -> Weight;
+	fn lock_up_provisions() -> Weight;
 }
 
 /// Weights for pallet_bootstrap using the Mangata node and recommended hardware.
@@ -71,10 +74,11 @@ impl<T: frame_system::Config> pallet_bootstrap::WeightInfo for ModuleWeight<T> {
 	// Storage: Bootstrap BootstrapSchedule (r:0 w:1)
 	// Storage: Bootstrap PromoteBootstrapPool (r:0 w:1)
 	// Storage: Bootstrap ActivePair (r:0 w:1)
+	// Storage: Bootstrap MinValuations (r:0 w:1)
 	fn schedule_bootstrap() -> Weight {
 		(Weight::from_ref_time(26_692_000 ))
 			.saturating_add(T::DbWeight::get().reads(4  as u64))
-			.saturating_add(T::DbWeight::get().writes(3  as u64))
+			.saturating_add(T::DbWeight::get().writes(4  as u64))
 	}
 	// Storage: Bootstrap ActivePair (r:1 w:0)
 	// Storage: Bootstrap Phase (r:1 w:0)
@@ -83,11 +87,15 @@ impl<T: frame_system::Config> pallet_bootstrap::WeightInfo for ModuleWeight<T> {
 	// Storage: Tokens Accounts (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Bootstrap Provisions (r:1 w:1)
+	// Storage: Bootstrap VestedProvisions (r:1 w:0)
+	// Storage: Bootstrap WhitelistAllowances (r:1 w:0)
+	// Storage: Bootstrap ProvisionCaps (r:1 w:0)
 	// Storage: Bootstrap Valuations (r:1 w:1)
+	// Storage: Bootstrap LockedUpAccounts (r:1 w:0)
 	// Storage: Bootstrap ProvisionAccounts (r:0 w:1)
 	fn provision() -> Weight {
 		(Weight::from_ref_time(80_812_000 ))
-			.saturating_add(T::DbWeight::get().reads(9  as u64))
+			.saturating_add(T::DbWeight::get().reads(13  as u64))
 			.saturating_add(T::DbWeight::get().writes(6  as u64))
 	}
 	// Storage: Vesting Vesting (r:1 w:1)
@@ -99,11 +107,15 @@ impl<T: frame_system::Config> pallet_bootstrap::WeightInfo for ModuleWeight<T> {
 	// Storage: Bootstrap BootstrapSchedule (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Bootstrap VestedProvisions (r:1 w:1)
+	// Storage: Bootstrap Provisions (r:1 w:0)
+	// Storage: Bootstrap WhitelistAllowances (r:1 w:0)
+	// Storage: Bootstrap ProvisionCaps (r:1 w:0)
 	// Storage: Bootstrap Valuations (r:1 w:1)
+	// Storage: Bootstrap LockedUpAccounts (r:1 w:0)
 	// Storage: Bootstrap ProvisionAccounts (r:0 w:1)
 	fn provision_vested() -> Weight {
 		(Weight::from_ref_time(111_492_000 ))
-			.saturating_add(T::DbWeight::get().reads(11  as u64))
+			.saturating_add(T::DbWeight::get().reads(15  as u64))
 			.saturating_add(T::DbWeight::get().writes(8  as u64))
 	}
 	// Storage: Bootstrap Phase (r:1 w:0)
@@ -123,10 +135,13 @@ impl<T: frame_system::Config> pallet_bootstrap::WeightInfo for ModuleWeight<T> {
 	// Storage: Xyk LiquidityMiningActiveUser (r:1 w:1)
 	// Storage: Xyk LiquidityMiningPool (r:1 w:1)
 	// Storage: Xyk LiquidityMiningActivePool (r:1 w:1)
+	// Storage: Bootstrap LockedUpAccounts (r:1 w:0)
+	// Storage: Bootstrap LockupBonusValuations (r:1 w:0)
+	// Storage: Bootstrap LockupBonusProvisions (r:2 w:0)
 	// Storage: Bootstrap ProvisionAccounts (r:0 w:1)
 	fn claim_and_activate_liquidity_tokens() -> Weight {
 		(Weight::from_ref_time(253_032_000 ))
-			.saturating_add(T::DbWeight::get().reads(21  as u64))
+			.saturating_add(T::DbWeight::get().reads(25  as u64))
 			.saturating_add(T::DbWeight::get().writes(12  as u64))
 	}
 	// Storage: Bootstrap Phase (r:1 w:1)
@@ -146,6 +161,53 @@ impl<T: frame_system::Config> pallet_bootstrap::WeightInfo for ModuleWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6  as u64))
 			.saturating_add(T::DbWeight::get().writes(13  as u64))
 	}
+	// Storage: Bootstrap BootstrapSchedule (r:1 w:0)
+	// Storage: Bootstrap Phase (r:1 w:1)
+	// Storage: Bootstrap ActivePair (r:1 w:0)
+	// Storage: Bootstrap Valuations (r:1 w:0)
+	// Storage: Bootstrap MinValuations (r:1 w:0)
+	// Storage: Xyk Pools (r:2 w:2)
+	// Storage: Tokens NextCurrencyId (r:1 w:1)
+	// Storage: Tokens Accounts (r:5 w:5)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: AssetRegistry Metadata (r:2 w:1)
+	// Storage: Xyk LiquidityAssets (r:0 w:1)
+	// Storage: Xyk LiquidityPools (r:0 w:1)
+	// Storage: Bootstrap PromoteBootstrapPool (r:1 w:0)
+	// Storage: Issuance PromotedPoolsRewardsV2 (r:1 w:0)
+	// Storage: Bootstrap MintedLiquidity (r:0 w:1)
+	fn finish_bootstrap() -> Weight {
+		(Weight::from_ref_time(95_640_000 ))
+			.saturating_add(T::DbWeight::get().reads(18  as u64))
+			.saturating_add(T::DbWeight::get().writes(14  as u64))
+	}
+	// Storage: Bootstrap Phase (r:1 w:0)
+	// Storage: Bootstrap ProvisionAccounts (r:1 w:1)
+	// Storage: Bootstrap ActivePair (r:1 w:0)
+	// Storage: Bootstrap Provisions (r:2 w:2)
+	// Storage: Bootstrap VestedProvisions (r:2 w:2)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Vesting Vesting (r:0 w:1)
+	// Storage: Tokens Locks (r:0 w:1)
+	fn withdraw_provisions() -> Weight {
+		(Weight::from_ref_time(104_310_000 ))
+			.saturating_add(T::DbWeight::get().reads(9  as u64))
+			.saturating_add(T::DbWeight::get().writes(9  as u64))
+	}
+	// Storage: Bootstrap LockupBonus (r:1 w:0)
+	// Storage: Bootstrap Phase (r:1 w:0)
+	// Storage: Bootstrap BootstrapSchedule (r:1 w:0)
+	// Storage: Bootstrap LockedUpAccounts (r:1 w:1)
+	// Storage: Bootstrap ActivePair (r:1 w:0)
+	// Storage: Bootstrap Provisions (r:2 w:0)
+	// Storage: Bootstrap VestedProvisions (r:2 w:0)
+	// Storage: Bootstrap LockupBonusProvisions (r:2 w:2)
+	// Storage: Bootstrap LockupBonusValuations (r:2 w:2)
+	fn lock_up_provisions() -> Weight {
+		(Weight::from_ref_time(52_110_000 ))
+			.saturating_add(T::DbWeight::get().reads(13  as u64))
+			.saturating_add(T::DbWeight::get().writes(5  as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -153,21 +215,21 @@ impl WeightInfo for () {
 	fn schedule_bootstrap() -> Weight {
 		(Weight::from_ref_time(26_692_000 ))
 			.saturating_add(RocksDbWeight::get().reads(4  as u64))
-			.saturating_add(RocksDbWeight::get().writes(3  as u64))
+			.saturating_add(RocksDbWeight::get().writes(4  as u64))
 	}
 	fn provision() -> Weight {
 		(Weight::from_ref_time(80_812_000 ))
-			.saturating_add(RocksDbWeight::get().reads(9  as u64))
+			.saturating_add(RocksDbWeight::get().reads(13  as u64))
 			.saturating_add(RocksDbWeight::get().writes(6  as u64))
 	}
 	fn provision_vested() -> Weight {
 		(Weight::from_ref_time(111_492_000 ))
-			.saturating_add(RocksDbWeight::get().reads(11  as u64))
+			.saturating_add(RocksDbWeight::get().reads(15  as u64))
 			.saturating_add(RocksDbWeight::get().writes(8  as u64))
 	}
 	fn claim_and_activate_liquidity_tokens() -> Weight {
 		(Weight::from_ref_time(253_032_000 ))
-			.saturating_add(RocksDbWeight::get().reads(21  as u64))
+			.saturating_add(RocksDbWeight::get().reads(25  as u64))
 			.saturating_add(RocksDbWeight::get().writes(12  as u64))
 	}
 	fn finalize() -> Weight {
@@ -175,4 +237,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6  as u64))
 			.saturating_add(RocksDbWeight::get().writes(13  as u64))
 	}
+	fn finish_bootstrap() -> Weight {
+		(Weight::from_ref_time(95_640_000 ))
+			.saturating_add(RocksDbWeight::get().reads(18  as u64))
+			.saturating_add(RocksDbWeight::get().writes(14  as u64))
+	}
+	fn withdraw_provisions() -> Weight {
+		(Weight::from_ref_time(104_310_000 ))
+			.saturating_add(RocksDbWeight::get().reads(9  as u64))
+			.saturating_add(RocksDbWeight::get().writes(9  as u64))
+	}
+	fn lock_up_provisions() -> Weight {
+		(Weight::from_ref_time(52_110_000 ))
+			.saturating_add(RocksDbWeight::get().reads(13  as u64))
+			.saturating_add(RocksDbWeight::get().writes(5  as u64))
+	}
 }
@@ -58,6 +58,8 @@ pub trait WeightInfo {
 	fn init_issuance_config() -> Weight;
 	fn finalize_tge() -> Weight;
 	fn execute_tge(x: u32, ) -> Weight;
+	fn set_pool_weight() -> Weight;
+	fn set_issuance_schedule(x: u32, ) -> Weight;
 }
 
 /// Weights for pallet_issuance using the Mangata node and recommended hardware.
@@ -93,6 +95,17 @@ impl<T: frame_system::Config> pallet_issuance::WeightInfo for ModuleWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(2 as u64))
 			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
 	}
+	fn set_pool_weight() -> Weight {
+		(Weight::from_ref_time(19_530_000))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_issuance_schedule(x: u32, ) -> Weight {
+		(Weight::from_ref_time(21_402_000))
+			.saturating_add((Weight::from_ref_time(96_000)).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -116,4 +129,15 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
 	}
+	fn set_pool_weight() -> Weight {
+		(Weight::from_ref_time(19_530_000))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_issuance_schedule(x: u32, ) -> Weight {
+		(Weight::from_ref_time(21_402_000))
+			.saturating_add((Weight::from_ref_time(96_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
 }
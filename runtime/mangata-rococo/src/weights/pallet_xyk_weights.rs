@@ -66,7 +66,21 @@ pub trait WeightInfo {
 	fn claim_rewards_all_v2() -> Weight;
 	fn activate_liquidity_v2() -> Weight;
 	fn deactivate_liquidity_v2() -> Weight;
-	fn rewards_migrate_v1_to_v2() -> Weight;
+	fn rewards_v1_migration_step() -> Weight;
+	fn multiswap_sell_asset(x: u32, ) -> Weight;
+	fn multiswap_buy_asset(x: u32, ) -> Weight;
+	fn set_pool_fee() -> Weight;
+	fn create_stable_pool() -> Weight;
+	fn flash_swap() -> Weight;
+	fn create_reward_schedule() -> Weight;
+	fn claim_schedule_rewards() -> Weight;
+	fn refund_reward_schedule() -> Weight;
+	fn unpromote_pool() -> Weight;
+	fn claim_rewards_all_pools_v2(x: u32, ) -> Weight;
+	fn compound_rewards() -> Weight;
+	fn set_reward_curve() -> Weight;
+	fn set_reward_token_whitelisted() -> Weight;
+	fn rewards_migrate_reward_curve() -> Weight;
 }
 
 /// Weights for pallet_xyk using the Mangata node and recommended hardware.
@@ -113,10 +127,13 @@ impl<T: frame_system::Config> pallet_xyk::WeightInfo for ModuleWeight<T> {
 	// Storage: Xyk LiquidityMiningPool (r:1 w:1)
 	// Storage: Xyk LiquidityMiningActivePool (r:1 w:1)
 	// Storage: MultiPurposeLiquidity ReserveStatus (r:1 w:1)
+	// Storage: Xyk PoolRewardTokens (r:1 w:0)
+	// Storage: Xyk RewardSchedules (r:8 w:8)
+	// Storage: Xyk ScheduleRewardsInfo (r:8 w:8)
 	fn mint_liquidity() -> Weight {
 		(Weight::from_ref_time(169_416_000))
-			.saturating_add(T::DbWeight::get().reads(15 as u64))
-			.saturating_add(T::DbWeight::get().writes(12 as u64))
+			.saturating_add(T::DbWeight::get().reads(32 as u64))
+			.saturating_add(T::DbWeight::get().writes(28 as u64))
 	}
 	// Storage: Xyk LiquidityAssets (r:1 w:0)
 	// Storage: Issuance PromotedPoolsRewards (r:1 w:0)
@@ -126,10 +143,13 @@ impl<T: frame_system::Config> pallet_xyk::WeightInfo for ModuleWeight<T> {
 	// Storage: Xyk Pools (r:1 w:1)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Storage: Tokens NextCurrencyId (r:1 w:0)
+	// Storage: Xyk PoolRewardTokens (r:1 w:0)
+	// Storage: Xyk RewardSchedules (r:8 w:8)
+	// Storage: Xyk ScheduleRewardsInfo (r:8 w:8)
 	fn mint_liquidity_using_vesting_native_tokens() -> Weight {
 		(Weight::from_ref_time(198_407_000))
-			.saturating_add(T::DbWeight::get().reads(14 as u64))
-			.saturating_add(T::DbWeight::get().writes(11 as u64))
+			.saturating_add(T::DbWeight::get().reads(31 as u64))
+			.saturating_add(T::DbWeight::get().writes(27 as u64))
 	}
 	// Storage: Xyk LiquidityAssets (r:1 w:2)
 	// Storage: Issuance PromotedPoolsRewards (r:1 w:1)
@@ -144,10 +164,13 @@ impl<T: frame_system::Config> pallet_xyk::WeightInfo for ModuleWeight<T> {
 	// Storage: Xyk LiquidityMiningUserClaimed (r:1 w:1)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Storage: Xyk LiquidityPools (r:0 w:1)
+	// Storage: Xyk PoolRewardTokens (r:1 w:0)
+	// Storage: Xyk RewardSchedules (r:8 w:8)
+	// Storage: Xyk ScheduleRewardsInfo (r:8 w:8)
 	fn burn_liquidity() -> Weight {
 		(Weight::from_ref_time(175_567_000))
-			.saturating_add(T::DbWeight::get().reads(16 as u64))
-			.saturating_add(T::DbWeight::get().writes(19 as u64))
+			.saturating_add(T::DbWeight::get().reads(33 as u64))
+			.saturating_add(T::DbWeight::get().writes(35 as u64))
 	}
 	// Storage: Issuance PromotedPoolsRewards (r:1 w:0)
 	// Storage: Xyk LiquidityMiningUser (r:1 w:0)
@@ -185,8 +208,8 @@ impl<T: frame_system::Config> pallet_xyk::WeightInfo for ModuleWeight<T> {
 	}
 	fn activate_liquidity_v2() -> Weight {
 		(Weight::from_ref_time(70_204_000))
-			.saturating_add(RocksDbWeight::get().reads(6 as u64))
-			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads(23 as u64))
+			.saturating_add(RocksDbWeight::get().writes(21 as u64))
 	}
 	// Storage: Issuance PromotedPoolsRewards (r:1 w:1)
 	// Storage: Xyk LiquidityMiningActiveUser (r:1 w:1)
@@ -196,17 +219,93 @@ impl<T: frame_system::Config> pallet_xyk::WeightInfo for ModuleWeight<T> {
 	// Storage: Xyk LiquidityMiningUserToBeClaimed (r:1 w:1)
 	// Storage: Xyk LiquidityMiningUserClaimed (r:1 w:1)
 	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Xyk PoolRewardTokens (r:1 w:0)
+	// Storage: Xyk RewardSchedules (r:8 w:8)
+	// Storage: Xyk ScheduleRewardsInfo (r:8 w:8)
 	fn deactivate_liquidity_v2() -> Weight {
 		(Weight::from_ref_time(84_598_000))
-			.saturating_add(T::DbWeight::get().reads(8 as u64))
-			.saturating_add(T::DbWeight::get().writes(8 as u64))
+			.saturating_add(T::DbWeight::get().reads(25 as u64))
+			.saturating_add(T::DbWeight::get().writes(24 as u64))
 	}
 //TODO retest
-fn rewards_migrate_v1_to_v2() -> Weight {
+fn rewards_v1_migration_step() -> Weight {
 	(Weight::from_ref_time(133_607_000))
-		.saturating_add(RocksDbWeight::get().reads(7 as u64))
-		.saturating_add(RocksDbWeight::get().writes(7 as u64))
+		.saturating_add(RocksDbWeight::get().reads(9 as u64))
+		.saturating_add(RocksDbWeight::get().writes(8 as u64))
 }
+	fn multiswap_sell_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(151_597_000)).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn multiswap_buy_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(158_939_000)).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn set_pool_fee() -> Weight {
+		(Weight::from_ref_time(24_160_000))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn create_stable_pool() -> Weight {
+		(Weight::from_ref_time(245_380_000))
+			.saturating_add(T::DbWeight::get().reads(13 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	fn flash_swap() -> Weight {
+		(Weight::from_ref_time(174_520_000))
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
+	}
+	fn create_reward_schedule() -> Weight {
+		(Weight::from_ref_time(98_360_000))
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+	}
+	fn claim_schedule_rewards() -> Weight {
+		(Weight::from_ref_time(71_240_000))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn refund_reward_schedule() -> Weight {
+		(Weight::from_ref_time(58_910_000))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn unpromote_pool() -> Weight {
+		(Weight::from_ref_time(31_420_000))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn claim_rewards_all_pools_v2(x: u32, ) -> Weight {
+		(Weight::from_ref_time(12_400_000))
+			.saturating_add((Weight::from_ref_time(88_120_000)).saturating_mul(x as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().reads((9 as u64).saturating_mul(x as u64)))
+			.saturating_add(T::DbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
+	}
+	fn compound_rewards() -> Weight {
+		(Weight::from_ref_time(312_650_000))
+			.saturating_add(T::DbWeight::get().reads(44 as u64))
+			.saturating_add(T::DbWeight::get().writes(34 as u64))
+	}
+	fn set_reward_curve() -> Weight {
+		(Weight::from_ref_time(21_160_000))
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_reward_token_whitelisted() -> Weight {
+		(Weight::from_ref_time(21_480_000))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn rewards_migrate_reward_curve() -> Weight {
+		(Weight::from_ref_time(38_420_000))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -228,18 +327,18 @@ impl WeightInfo for () {
 	}
 	fn mint_liquidity() -> Weight {
 		(Weight::from_ref_time(169_416_000))
-			.saturating_add(RocksDbWeight::get().reads(15 as u64))
-			.saturating_add(RocksDbWeight::get().writes(12 as u64))
+			.saturating_add(RocksDbWeight::get().reads(32 as u64))
+			.saturating_add(RocksDbWeight::get().writes(28 as u64))
 	}
 	fn mint_liquidity_using_vesting_native_tokens() -> Weight {
 		(Weight::from_ref_time(198_407_000))
-			.saturating_add(RocksDbWeight::get().reads(14 as u64))
-			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+			.saturating_add(RocksDbWeight::get().reads(31 as u64))
+			.saturating_add(RocksDbWeight::get().writes(27 as u64))
 	}
 	fn burn_liquidity() -> Weight {
 		(Weight::from_ref_time(175_567_000))
-			.saturating_add(RocksDbWeight::get().reads(16 as u64))
-			.saturating_add(RocksDbWeight::get().writes(19 as u64))
+			.saturating_add(RocksDbWeight::get().reads(33 as u64))
+			.saturating_add(RocksDbWeight::get().writes(35 as u64))
 	}
 	fn promote_pool() -> Weight {
 		(Weight::from_ref_time(21_528_000))
@@ -258,18 +357,91 @@ impl WeightInfo for () {
 	}
 	fn activate_liquidity_v2() -> Weight {
 		(Weight::from_ref_time(70_204_000))
-			.saturating_add(RocksDbWeight::get().reads(6 as u64))
-			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+			.saturating_add(RocksDbWeight::get().reads(23 as u64))
+			.saturating_add(RocksDbWeight::get().writes(21 as u64))
 	}
 	fn deactivate_liquidity_v2() -> Weight {
 		(Weight::from_ref_time(84_598_000))
-			.saturating_add(RocksDbWeight::get().reads(8 as u64))
-			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+			.saturating_add(RocksDbWeight::get().reads(25 as u64))
+			.saturating_add(RocksDbWeight::get().writes(24 as u64))
 	}
 	//TODO retest
-	fn rewards_migrate_v1_to_v2() -> Weight {
+	fn rewards_v1_migration_step() -> Weight {
 		(Weight::from_ref_time(133_607_000))
-			.saturating_add(RocksDbWeight::get().reads(7 as u64))
-			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn multiswap_sell_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(151_597_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn multiswap_buy_asset(x: u32, ) -> Weight {
+		(Weight::from_ref_time(20_000_000))
+			.saturating_add((Weight::from_ref_time(158_939_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads((6 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(x as u64)))
+	}
+	fn set_pool_fee() -> Weight {
+		(Weight::from_ref_time(24_160_000))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn create_stable_pool() -> Weight {
+		(Weight::from_ref_time(245_380_000))
+			.saturating_add(RocksDbWeight::get().reads(13 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	fn flash_swap() -> Weight {
+		(Weight::from_ref_time(174_520_000))
+			.saturating_add(RocksDbWeight::get().reads(17 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
+	}
+	fn create_reward_schedule() -> Weight {
+		(Weight::from_ref_time(98_360_000))
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(14 as u64))
+	}
+	fn claim_schedule_rewards() -> Weight {
+		(Weight::from_ref_time(71_240_000))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn refund_reward_schedule() -> Weight {
+		(Weight::from_ref_time(58_910_000))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn unpromote_pool() -> Weight {
+		(Weight::from_ref_time(31_420_000))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn claim_rewards_all_pools_v2(x: u32, ) -> Weight {
+		(Weight::from_ref_time(12_400_000))
+			.saturating_add((Weight::from_ref_time(88_120_000)).saturating_mul(x as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().reads((9 as u64).saturating_mul(x as u64)))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(x as u64)))
+	}
+	fn compound_rewards() -> Weight {
+		(Weight::from_ref_time(312_650_000))
+			.saturating_add(RocksDbWeight::get().reads(44 as u64))
+			.saturating_add(RocksDbWeight::get().writes(34 as u64))
+	}
+	fn set_reward_curve() -> Weight {
+		(Weight::from_ref_time(21_160_000))
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_reward_token_whitelisted() -> Weight {
+		(Weight::from_ref_time(21_480_000))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn rewards_migrate_reward_curve() -> Weight {
+		(Weight::from_ref_time(38_420_000))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 }